@@ -85,6 +85,86 @@ fn bench_insert_edge(c: &mut Criterion) {
     }
 }
 
+fn bench_bulk_load(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bulk_load");
+    group.sample_size(10);
+    for n in [1000, 100000] {
+        let edges = (0..n)
+            .map(|_| {
+                let triple = Triple {
+                    sub: Ulid::new(),
+                    pred: Ulid::new(),
+                    obj: Ulid::new(),
+                };
+                (triple, [0u8; 32])
+            })
+            .collect::<Vec<_>>();
+
+        group.throughput(criterion::Throughput::Elements(n));
+
+        group.bench_function(BenchmarkId::new("MemTripleStore/insert_edge", n), |b| {
+            b.iter(|| {
+                let mut mem: MemTripleStore<Ulid, [u8; 32], [u8; 32]> =
+                    MemTripleStore::new(UlidIdGenerator::new());
+                for (triple, props) in edges.iter().cloned() {
+                    mem.insert_edge(triple, props).unwrap();
+                }
+                black_box(mem)
+            })
+        });
+
+        group.bench_function(BenchmarkId::new("MemTripleStore/insert_edges", n), |b| {
+            b.iter(|| {
+                let mut mem: MemTripleStore<Ulid, [u8; 32], [u8; 32]> =
+                    MemTripleStore::new(UlidIdGenerator::new());
+                mem.insert_edges(edges.iter().cloned()).unwrap();
+                black_box(mem)
+            })
+        });
+
+        group.bench_function(BenchmarkId::new("MemTripleStore/bulk_load", n), |b| {
+            b.iter(|| {
+                let mut mem: MemTripleStore<Ulid, [u8; 32], [u8; 32]> =
+                    MemTripleStore::new(UlidIdGenerator::new());
+                mem.bulk_load(edges.iter().cloned(), true).unwrap();
+                black_box(mem)
+            })
+        });
+
+        group.bench_function(BenchmarkId::new("SledTripleStore/insert_edge", n), |b| {
+            b.iter(|| {
+                let tempdir = tempdir::TempDir::new("sled").unwrap();
+                let db = sled::open(tempdir.path()).unwrap();
+                let mut sled: SledTripleStore<Ulid, [u8; 32], [u8; 32]> =
+                    SledTripleStore::new(&db, UlidIdGenerator::new()).unwrap();
+                for (triple, props) in edges.iter().cloned() {
+                    sled.insert_edge(triple, props).unwrap();
+                }
+            })
+        });
+
+        group.bench_function(BenchmarkId::new("SledTripleStore/insert_edges", n), |b| {
+            b.iter(|| {
+                let tempdir = tempdir::TempDir::new("sled").unwrap();
+                let db = sled::open(tempdir.path()).unwrap();
+                let mut sled: SledTripleStore<Ulid, [u8; 32], [u8; 32]> =
+                    SledTripleStore::new(&db, UlidIdGenerator::new()).unwrap();
+                sled.insert_edges(edges.iter().cloned()).unwrap();
+            })
+        });
+
+        group.bench_function(BenchmarkId::new("SledTripleStore/bulk_load", n), |b| {
+            b.iter(|| {
+                let tempdir = tempdir::TempDir::new("sled").unwrap();
+                let db = sled::open(tempdir.path()).unwrap();
+                let mut sled: SledTripleStore<Ulid, [u8; 32], [u8; 32]> =
+                    SledTripleStore::new(&db, UlidIdGenerator::new()).unwrap();
+                sled.bulk_load(edges.iter().cloned(), true).unwrap();
+            })
+        });
+    }
+}
+
 fn bench_query_node(c: &mut Criterion) {
     let mut group = c.benchmark_group("query_node");
     for n in [1000, 100000, 1000000] {
@@ -157,6 +237,7 @@ criterion_group!(
     benches,
     bench_insert_node,
     bench_insert_edge,
+    bench_bulk_load,
     bench_query_node,
     bench_query_edge
 );
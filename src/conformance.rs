@@ -2,33 +2,39 @@ use serde::{Deserialize, Serialize};
 
 use crate::traits::Mergeable;
 
+pub mod bulk_load;
+pub mod count;
 pub mod extend;
+pub mod get;
+pub mod index;
 pub mod insert;
 pub mod iter;
 pub mod merge;
+pub mod paginate;
+pub mod path;
+pub mod pattern;
+pub mod quad;
 pub mod query;
 pub mod remove;
 pub mod set;
+pub mod stats;
+pub mod subscribe;
+pub mod transaction;
+pub mod verify;
 
-#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize, Default)]
 pub(crate) struct TestMergeable {
     a: Option<String>,
     b: Option<String>,
 }
 
-impl Default for TestMergeable {
-    fn default() -> Self {
-        TestMergeable { a: None, b: None }
-    }
-}
-
 impl Mergeable for TestMergeable {
     fn merge(&mut self, other: Self) {
-        other.a.map(|a| {
+        if let Some(a) = other.a {
             self.a = Some(a);
-        });
-        other.b.map(|b| {
+        }
+        if let Some(b) = other.b {
             self.b = Some(b);
-        });
+        }
     }
 }
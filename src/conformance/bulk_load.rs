@@ -0,0 +1,160 @@
+use std::collections::HashSet;
+
+use ulid::Ulid;
+
+use crate::{prelude::*, traits::IdType, EdgeOrder, Triple};
+
+fn triple<Id: IdType + From<Ulid>>(sub: u128, pred: u128, obj: u128) -> Triple<Id> {
+    Triple {
+        sub: Ulid(sub).into(),
+        pred: Ulid(pred).into(),
+        obj: Ulid(obj).into(),
+    }
+}
+
+fn collect<T: std::hash::Hash + Eq, E: std::fmt::Debug>(
+    iter: impl Iterator<Item = Result<T, E>>,
+) -> HashSet<T> {
+    iter.map(|r| r.expect("ok")).collect()
+}
+
+fn edges<Id: IdType, T: TripleStore<Id, String, String>>(
+    db: &T,
+    order: EdgeOrder,
+) -> Vec<(Triple<Id>, String)> {
+    db.iter_edges(order).map(|r| r.expect("ok")).collect()
+}
+
+pub(crate) fn test_bulk_load<
+    Id: IdType + From<Ulid>,
+    T: TripleStore<Id, String, String> + TripleStoreBulkLoad<Id, String, String>,
+>(
+    mut db: T,
+) {
+    let id = |n: u128| Id::from(Ulid(n));
+
+    db.insert_node(id(1), "old".to_string()).expect("ok");
+    db.insert_edge(triple(1, 10, 2), "old".to_string())
+        .expect("ok");
+
+    db.insert_nodes([
+        (id(2), "b".to_string()),
+        (id(1), "a".to_string()),
+        (id(3), "c".to_string()),
+        (id(2), "b again".to_string()),
+    ])
+    .expect("ok");
+    db.insert_edges([
+        (triple(3, 10, 1), "c->a".to_string()),
+        (triple(1, 10, 2), "a->b".to_string()),
+        (triple(2, 11, 3), "b->c".to_string()),
+        (triple(3, 10, 1), "c->a again".to_string()),
+    ])
+    .expect("ok");
+
+    // The last props given win, over both the store and earlier items.
+    assert_eq!(
+        db.iter_vertices()
+            .map(|r| r.expect("ok"))
+            .collect::<Vec<_>>(),
+        [
+            (id(1), "a".to_string()),
+            (id(2), "b again".to_string()),
+            (id(3), "c".to_string())
+        ]
+    );
+    let expected = [
+        (triple(1, 10, 2), "a->b".to_string()),
+        (triple(2, 11, 3), "b->c".to_string()),
+        (triple(3, 10, 1), "c->a again".to_string()),
+    ];
+    assert_eq!(edges(&db, EdgeOrder::SPO), expected);
+
+    // Every ordering agrees.
+    for order in [EdgeOrder::POS, EdgeOrder::OSP] {
+        assert_eq!(
+            edges(&db, order).into_iter().collect::<HashSet<_>>(),
+            expected.clone().into_iter().collect::<HashSet<_>>()
+        );
+    }
+
+    // The results match inserting one at a time, including removal.
+    db.remove_node(id(1)).expect("ok");
+    assert_eq!(
+        edges(&db, EdgeOrder::SPO),
+        [(triple(2, 11, 3), "b->c".to_string())]
+    );
+
+    // Nothing to load is fine.
+    db.insert_edges([]).expect("ok");
+    db.insert_nodes([]).expect("ok");
+    assert_eq!(db.iter_vertices().count(), 2);
+}
+
+pub(crate) fn test_bulk_load_no_duplicates<
+    Id: IdType + From<Ulid>,
+    T: TripleStore<Id, String, String> + TripleStoreBulkLoad<Id, String, String>,
+>(
+    mut db: T,
+) {
+    let mut spo = (0..100)
+        .map(|n| (n % 7, 100 + n % 3, n))
+        .collect::<Vec<_>>();
+    spo.sort();
+    let expected = spo
+        .into_iter()
+        .map(|(sub, pred, obj)| (triple(sub, pred, obj), format!("{obj}")))
+        .collect::<Vec<_>>();
+
+    db.bulk_load(expected.clone().into_iter().rev(), true)
+        .expect("ok");
+
+    assert_eq!(edges(&db, EdgeOrder::SPO), expected);
+    for order in [EdgeOrder::POS, EdgeOrder::OSP] {
+        assert_eq!(
+            edges(&db, order).into_iter().collect::<HashSet<_>>(),
+            expected.clone().into_iter().collect::<HashSet<_>>()
+        );
+    }
+}
+
+pub(crate) fn test_bulk_load_indexed<
+    Id: IdType + From<Ulid>,
+    T: TripleStore<Id, String, String>
+        + TripleStoreBulkLoad<Id, String, String>
+        + TripleStoreIndex<Id, String, String>,
+>(
+    mut db: T,
+) {
+    let id = |n: u128| Id::from(Ulid(n));
+
+    db.register_node_index("props", |p: &String| vec![p.clone()])
+        .expect("ok");
+    db.register_edge_index("props", |p: &String| vec![p.clone()])
+        .expect("ok");
+
+    db.insert_node(id(1), "x".to_string()).expect("ok");
+    db.insert_edge(triple(1, 10, 2), "x".to_string())
+        .expect("ok");
+
+    db.insert_nodes([(id(1), "y".to_string()), (id(2), "x".to_string())])
+        .expect("ok");
+    db.insert_edges([
+        (triple(1, 10, 2), "y".to_string()),
+        (triple(2, 10, 1), "x".to_string()),
+    ])
+    .expect("ok");
+
+    let x = "x".to_string();
+    let y = "y".to_string();
+    assert_eq!(collect(db.lookup_nodes("props", &x)), [id(2)].into());
+    assert_eq!(collect(db.lookup_nodes("props", &y)), [id(1)].into());
+    assert_eq!(
+        collect(db.lookup_edges("props", &x)),
+        [triple(2, 10, 1)].into()
+    );
+    assert_eq!(
+        collect(db.lookup_edges("props", &y)),
+        [triple(1, 10, 2)].into()
+    );
+}
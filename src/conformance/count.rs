@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use ulid::Ulid;
+
+use crate::{prelude::*, traits::IdType, Query, Triple};
+
+fn triple<Id: IdType + From<Ulid>>(sub: u128, pred: u128, obj: u128) -> Triple<Id> {
+    Triple {
+        sub: Ulid(sub).into(),
+        pred: Ulid(pred).into(),
+        obj: Ulid(obj).into(),
+    }
+}
+
+fn build_graph<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(db: &mut T) {
+    for n in 1..=4 {
+        db.insert_node(Ulid(n).into(), format!("node {}", n))
+            .expect("ok");
+    }
+    for (sub, pred, obj) in [
+        (1, 10, 2),
+        (1, 10, 3),
+        (2, 10, 3),
+        (3, 10, 3),
+        (1, 11, 4),
+        (2, 11, 4),
+        (4, 12, 1),
+    ] {
+        db.insert_edge(triple(sub, pred, obj), "".to_string())
+            .expect("ok");
+    }
+}
+
+pub(crate) fn test_count<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(mut db: T) {
+    let id = |n: u128| Id::from(Ulid(n));
+
+    assert_eq!(db.len_nodes().expect("ok"), 0);
+    assert_eq!(db.len_edges().expect("ok"), 0);
+    assert_eq!(db.out_degree(&id(1)).expect("ok"), 0);
+
+    build_graph(&mut db);
+    assert_eq!(db.len_nodes().expect("ok"), 4);
+    assert_eq!(db.len_edges().expect("ok"), 7);
+
+    assert_eq!(db.out_degree(&id(1)).expect("ok"), 3);
+    assert_eq!(db.out_degree(&id(3)).expect("ok"), 1);
+    assert_eq!(db.in_degree(&id(3)).expect("ok"), 3);
+    assert_eq!(db.in_degree(&id(5)).expect("ok"), 0);
+
+    // Every query counts the rows `run` would return.
+    let queries = [
+        Query::NodeProps([id(1), id(4), id(5)].into()),
+        Query::SPO([(id(1), id(10), id(2)), (id(1), id(10), id(4))].into()),
+        Query::S([id(1), id(2)].into()),
+        Query::P([id(10)].into()),
+        Query::O([id(3), id(4)].into()),
+        Query::SP([(id(1), id(10)), (id(2), id(11))].into()),
+        Query::SO([(id(1), id(4)), (id(3), id(3))].into()),
+        Query::PO([(id(10), id(3)), (id(12), id(2))].into()),
+    ];
+    let expected = [2, 1, 5, 4, 5, 3, 2, 3];
+    for (query, expected) in queries.into_iter().zip(expected) {
+        let result = db.run(query.clone()).expect("ok");
+        let run_count = match query {
+            Query::NodeProps(_) => result.len_nodes().expect("ok"),
+            _ => result.len_edges().expect("ok"),
+        };
+        assert_eq!(run_count, expected, "{:?}", query);
+        assert_eq!(
+            db.count(query.clone()).expect("ok"),
+            expected,
+            "{:?}",
+            query
+        );
+    }
+
+    db.remove_node(id(3)).expect("ok");
+    assert_eq!(db.len_nodes().expect("ok"), 3);
+    assert_eq!(db.len_edges().expect("ok"), 4);
+    assert_eq!(db.out_degree(&id(1)).expect("ok"), 2);
+    assert_eq!(db.count(Query::P([id(10)].into())).expect("ok"), 1);
+}
+
+pub(crate) fn test_count_aggregates<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(
+    mut db: T,
+) {
+    let id = |n: u128| Id::from(Ulid(n));
+
+    assert_eq!(db.count_by_predicate().expect("ok"), HashMap::new());
+    assert_eq!(db.count_by_object(None).expect("ok"), HashMap::new());
+
+    build_graph(&mut db);
+    assert_eq!(
+        db.count_by_predicate().expect("ok"),
+        [(id(10), 4), (id(11), 2), (id(12), 1)].into()
+    );
+    assert_eq!(
+        db.count_by_object(None).expect("ok"),
+        [(id(1), 1), (id(2), 1), (id(3), 3), (id(4), 2)].into()
+    );
+    assert_eq!(
+        db.count_by_object(Some(id(10))).expect("ok"),
+        [(id(2), 1), (id(3), 3)].into()
+    );
+    assert_eq!(
+        db.count_by_object(Some(id(13))).expect("ok"),
+        HashMap::new()
+    );
+
+    db.remove_edge(triple(4, 12, 1)).expect("ok");
+    db.remove_edge(triple(1, 10, 2)).expect("ok");
+    assert_eq!(
+        db.count_by_predicate().expect("ok"),
+        [(id(10), 3), (id(11), 2)].into()
+    );
+    assert_eq!(
+        db.count_by_object(None).expect("ok"),
+        [(id(3), 3), (id(4), 2)].into()
+    );
+}
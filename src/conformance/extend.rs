@@ -1,4 +1,4 @@
-use crate::{prelude::*, Triple};
+use crate::{prelude::*, traits::IdType, Triple};
 use ulid::Ulid;
 
 struct Config {
@@ -42,44 +42,53 @@ impl Default for Config {
     }
 }
 
-fn setup_left<T: TripleStore<Ulid, String, String>>(config: &Config, left: &mut T) {
+fn setup_left<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(
+    config: &Config,
+    left: &mut T,
+) {
     // Construct the left graph to be (1, "a") -("1")-> (2, "b")
-    left.insert_node(config.node_1, config.node_props_1.clone())
+    left.insert_node(Id::from(config.node_1), config.node_props_1.clone())
         .expect("success");
-    left.insert_node(config.node_2, config.node_props_2.clone())
+    left.insert_node(Id::from(config.node_2), config.node_props_2.clone())
         .expect("success");
     left.insert_edge(
         Triple {
-            sub: config.node_1,
-            pred: config.edge_1,
-            obj: config.node_2,
+            sub: Id::from(config.node_1),
+            pred: Id::from(config.edge_1),
+            obj: Id::from(config.node_2),
         },
         config.edge_props_1.clone(),
     )
     .expect("success");
 }
 
-fn setup_right<T: TripleStore<Ulid, String, String>>(config: &Config, right: &mut T) {
+fn setup_right<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(
+    config: &Config,
+    right: &mut T,
+) {
     // Construct the right graph to be (3, "c") -("2")-> (1, "d")
     right
-        .insert_node(config.node_3, config.node_props_3.clone())
+        .insert_node(Id::from(config.node_3), config.node_props_3.clone())
         .expect("success");
     right
-        .insert_node(config.node_4, config.node_props_4.clone())
+        .insert_node(Id::from(config.node_4), config.node_props_4.clone())
         .expect("success");
     right
         .insert_edge(
             Triple {
-                sub: config.node_3,
-                pred: config.edge_1,
-                obj: config.node_4,
+                sub: Id::from(config.node_3),
+                pred: Id::from(config.edge_1),
+                obj: Id::from(config.node_4),
             },
             config.edge_props_2.clone(),
         )
         .expect("success");
 }
 
-pub(crate) fn test_extend<T: TripleStore<Ulid, String, String>>(mut left: T, mut right: T) {
+pub(crate) fn test_extend<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(
+    mut left: T,
+    mut right: T,
+) {
     let config = Config::default();
     setup_left(&config, &mut left);
     setup_right(&config, &mut right);
@@ -93,9 +102,9 @@ pub(crate) fn test_extend<T: TripleStore<Ulid, String, String>>(mut left: T, mut
         .map(|i| i.expect("success"))
         .collect::<Vec<_>>();
     assert_eq!(node_data.len(), 3);
-    assert!(node_data.contains(&(config.node_1, config.node_props_4)));
-    assert!(node_data.contains(&(config.node_2, config.node_props_2)));
-    assert!(node_data.contains(&(config.node_3, config.node_props_3)));
+    assert!(node_data.contains(&(Id::from(config.node_1), config.node_props_4)));
+    assert!(node_data.contains(&(Id::from(config.node_2), config.node_props_2)));
+    assert!(node_data.contains(&(Id::from(config.node_3), config.node_props_3)));
 
     let edge_data = left
         .iter_edges(crate::EdgeOrder::SPO)
@@ -104,17 +113,17 @@ pub(crate) fn test_extend<T: TripleStore<Ulid, String, String>>(mut left: T, mut
     assert_eq!(edge_data.len(), 2);
     assert!(edge_data.contains(&(
         Triple {
-            sub: config.node_3,
-            pred: config.edge_1,
-            obj: config.node_1
+            sub: Id::from(config.node_3),
+            pred: Id::from(config.edge_1),
+            obj: Id::from(config.node_1)
         },
         config.edge_props_2
     )));
     assert!(edge_data.contains(&(
         Triple {
-            sub: config.node_1,
-            pred: config.edge_1,
-            obj: config.node_2
+            sub: Id::from(config.node_1),
+            pred: Id::from(config.edge_1),
+            obj: Id::from(config.node_2)
         },
         config.edge_props_1
     )));
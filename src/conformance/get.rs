@@ -0,0 +1,72 @@
+use ulid::Ulid;
+
+use crate::{prelude::*, traits::IdType, Triple};
+
+pub(crate) fn test_get_node<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(
+    mut db: T,
+) {
+    let node_1 = Id::from(Ulid(1));
+    let node_2 = Id::from(Ulid(2));
+    let node_3 = Id::from(Ulid(3));
+
+    db.insert_node(node_1.clone(), "a".to_string()).expect("ok");
+    db.insert_node(node_2.clone(), "b".to_string()).expect("ok");
+
+    assert_eq!(db.get_node(&node_1).expect("ok"), Some("a".to_string()));
+    assert_eq!(db.get_node(&node_2).expect("ok"), Some("b".to_string()));
+    assert_eq!(db.get_node(&node_3).expect("ok"), None);
+
+    assert!(db.contains_node(&node_1).expect("ok"));
+    assert!(db.contains_node(&node_2).expect("ok"));
+    assert!(!db.contains_node(&node_3).expect("ok"));
+
+    db.remove_node(&node_1).expect("ok");
+    assert_eq!(db.get_node(&node_1).expect("ok"), None);
+    assert!(!db.contains_node(&node_1).expect("ok"));
+}
+
+pub(crate) fn test_get_edge<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(
+    mut db: T,
+) {
+    let node_1 = Id::from(Ulid(1));
+    let node_2 = Id::from(Ulid(2));
+    let edge_1 = Id::from(Ulid(10));
+    let edge_2 = Id::from(Ulid(11));
+
+    let triple_1 = Triple {
+        sub: node_1.clone(),
+        pred: edge_1.clone(),
+        obj: node_2.clone(),
+    };
+    let triple_2 = Triple {
+        sub: node_2.clone(),
+        pred: edge_1.clone(),
+        obj: node_1.clone(),
+    };
+    let missing = Triple {
+        sub: node_1.clone(),
+        pred: edge_2.clone(),
+        obj: node_2.clone(),
+    };
+
+    db.insert_edge(triple_1.clone(), "a".to_string())
+        .expect("ok");
+    db.insert_edge(triple_2.clone(), "b".to_string())
+        .expect("ok");
+
+    assert_eq!(db.get_edge(&triple_1).expect("ok"), Some("a".to_string()));
+    assert_eq!(db.get_edge(&triple_2).expect("ok"), Some("b".to_string()));
+    assert_eq!(db.get_edge(&missing).expect("ok"), None);
+
+    assert!(db.contains_edge(&triple_1).expect("ok"));
+    assert!(db.contains_edge(&triple_2).expect("ok"));
+    assert!(!db.contains_edge(&missing).expect("ok"));
+
+    // Edge ids are not vertices.
+    assert!(!db.contains_node(&edge_1).expect("ok"));
+
+    db.remove_edge(triple_1.clone()).expect("ok");
+    assert_eq!(db.get_edge(&triple_1).expect("ok"), None);
+    assert!(!db.contains_edge(&triple_1).expect("ok"));
+    assert!(db.contains_edge(&triple_2).expect("ok"));
+}
@@ -0,0 +1,271 @@
+use std::collections::HashSet;
+
+use ulid::Ulid;
+
+use crate::{prelude::*, traits::IdType, Triple};
+
+use super::TestMergeable;
+
+fn domain(props: &str) -> Vec<String> {
+    props.split('@').skip(1).map(|s| s.to_string()).collect()
+}
+
+fn user(props: &str) -> Vec<String> {
+    props.split('@').take(1).map(|s| s.to_string()).collect()
+}
+
+fn tags(props: &str) -> Vec<String> {
+    props.split(',').map(|s| s.to_string()).collect()
+}
+
+fn collect<T: std::hash::Hash + Eq, E: std::fmt::Debug>(
+    iter: impl Iterator<Item = Result<T, E>>,
+) -> HashSet<T> {
+    iter.map(|r| r.expect("ok")).collect()
+}
+
+pub(crate) fn test_node_index<
+    Id: IdType + From<Ulid>,
+    T: TripleStore<Id, String, String> + TripleStoreIndex<Id, String, String>,
+>(
+    mut db: T,
+) {
+    let node_1 = Id::from(Ulid(1));
+    let node_2 = Id::from(Ulid(2));
+    let node_3 = Id::from(Ulid(3));
+    let node_4 = Id::from(Ulid(4));
+
+    let x = "x".to_string();
+    let y = "y".to_string();
+
+    // Existing nodes are indexed on registration.
+    db.insert_node(node_1.clone(), "alice@x".into())
+        .expect("ok");
+    db.insert_node(node_2.clone(), "bob@y".into()).expect("ok");
+    db.insert_node(node_3.clone(), "carol@x".into())
+        .expect("ok");
+    db.register_node_index("domain", |p: &String| domain(p))
+        .expect("ok");
+    db.register_node_index("user", |p: &String| user(p))
+        .expect("ok");
+
+    assert_eq!(
+        collect(db.lookup_nodes("domain", &x)),
+        [node_1.clone(), node_3.clone()].into()
+    );
+    assert_eq!(
+        collect(db.lookup_nodes("domain", &y)),
+        [node_2.clone()].into()
+    );
+    assert_eq!(
+        collect(db.lookup_nodes("domain", &"z".to_string())),
+        [].into()
+    );
+
+    // Inserts and updates are reflected in the index.
+    db.insert_node(node_4.clone(), "dave@y".into()).expect("ok");
+    db.insert_node(node_1.clone(), "alice@y".into())
+        .expect("ok");
+    assert_eq!(
+        collect(db.lookup_nodes("domain", &x)),
+        [node_3.clone()].into()
+    );
+    assert_eq!(
+        collect(db.lookup_nodes("domain", &y)),
+        [node_1.clone(), node_2.clone(), node_4.clone()].into()
+    );
+
+    // Removals are reflected in the index.
+    db.remove_node(&node_3).expect("ok");
+    assert_eq!(collect(db.lookup_nodes("domain", &x)), [].into());
+
+    // Range queries return nodes in key order.
+    assert_eq!(
+        db.range_nodes("user", "b".to_string().."d".to_string())
+            .map(|r| r.expect("ok"))
+            .collect::<Vec<_>>(),
+        vec![node_2.clone()]
+    );
+    assert_eq!(
+        db.range_nodes("user", "alice".to_string()..)
+            .map(|r| r.expect("ok"))
+            .collect::<Vec<_>>(),
+        [node_1.clone(), node_2.clone(), node_4.clone()]
+    );
+    assert_eq!(
+        db.range_nodes("user", ..="bob".to_string())
+            .map(|r| r.expect("ok"))
+            .collect::<Vec<_>>(),
+        [node_1.clone(), node_2.clone()]
+    );
+    assert_eq!(
+        db.range_nodes(
+            "user",
+            (
+                std::ops::Bound::Excluded("alice".to_string()),
+                std::ops::Bound::Excluded("dave".to_string())
+            )
+        )
+        .map(|r| r.expect("ok"))
+        .collect::<Vec<_>>(),
+        vec![node_2.clone()]
+    );
+    assert_eq!(
+        db.range_nodes("user", "d".to_string().."b".to_string())
+            .count(),
+        0
+    );
+
+    // Unknown indexes produce an error.
+    assert!(db.lookup_nodes("missing", &x).any(|r| r.is_err()));
+    assert!(db.lookup_edges("domain", &x).any(|r| r.is_err()));
+}
+
+pub(crate) fn test_edge_index<
+    Id: IdType + From<Ulid>,
+    T: TripleStore<Id, String, String> + TripleStoreIndex<Id, String, String>,
+>(
+    mut db: T,
+) {
+    let node_1 = Id::from(Ulid(1));
+    let node_2 = Id::from(Ulid(2));
+    let node_3 = Id::from(Ulid(3));
+    let edge = Id::from(Ulid(10));
+
+    let triple = |sub: &Id, obj: &Id| Triple {
+        sub: sub.clone(),
+        pred: edge.clone(),
+        obj: obj.clone(),
+    };
+
+    let red = "red".to_string();
+    let blue = "blue".to_string();
+
+    db.insert_edge(triple(&node_1, &node_2), "red,blue".into())
+        .expect("ok");
+    db.register_edge_index("tags", |p: &String| tags(p))
+        .expect("ok");
+    db.insert_edge(triple(&node_2, &node_3), "red".into())
+        .expect("ok");
+    db.insert_edge(triple(&node_3, &node_1), "green".into())
+        .expect("ok");
+
+    // Each key produced by the extractor is indexed.
+    assert_eq!(
+        collect(db.lookup_edges("tags", &red)),
+        [triple(&node_1, &node_2), triple(&node_2, &node_3)].into()
+    );
+    assert_eq!(
+        collect(db.lookup_edges("tags", &blue)),
+        [triple(&node_1, &node_2)].into()
+    );
+
+    // Replacing edge props replaces the keys.
+    db.insert_edge(triple(&node_1, &node_2), "blue".into())
+        .expect("ok");
+    assert_eq!(
+        collect(db.lookup_edges("tags", &red)),
+        [triple(&node_2, &node_3)].into()
+    );
+    assert_eq!(
+        collect(db.lookup_edges("tags", &blue)),
+        [triple(&node_1, &node_2)].into()
+    );
+
+    // Range queries.
+    assert_eq!(
+        db.range_edges("tags", "a".to_string().."h".to_string())
+            .map(|r| r.expect("ok"))
+            .collect::<Vec<_>>(),
+        [triple(&node_1, &node_2), triple(&node_3, &node_1)]
+    );
+
+    // Removing an edge, or a node with edges, removes their keys.
+    db.remove_edge(triple(&node_1, &node_2)).expect("ok");
+    assert_eq!(collect(db.lookup_edges("tags", &blue)), [].into());
+
+    db.remove_node(&node_3).expect("ok");
+    assert_eq!(collect(db.range_edges::<String>("tags", ..)), [].into());
+}
+
+pub(crate) fn test_index_merge<
+    Id: IdType + From<Ulid>,
+    T: TripleStore<Id, TestMergeable, TestMergeable>
+        + TripleStoreMerge<Id, TestMergeable, TestMergeable>
+        + TripleStoreIndex<Id, TestMergeable, TestMergeable>,
+>(
+    mut left: T,
+    mut right: T,
+) {
+    let node_1 = Id::from(Ulid(1));
+    let node_2 = Id::from(Ulid(2));
+    let edge = Id::from(Ulid(10));
+    let triple = Triple {
+        sub: node_1.clone(),
+        pred: edge.clone(),
+        obj: node_2.clone(),
+    };
+
+    let props = |a: Option<&str>, b: Option<&str>| TestMergeable {
+        a: a.map(|s| s.to_string()),
+        b: b.map(|s| s.to_string()),
+    };
+    let key = |props: &TestMergeable| props.a.iter().cloned().collect::<Vec<_>>();
+
+    left.register_node_index("a", key).expect("ok");
+    left.register_edge_index("a", key).expect("ok");
+
+    left.insert_node(node_1.clone(), props(Some("x"), None))
+        .expect("ok");
+    left.insert_edge(triple.clone(), props(Some("x"), None))
+        .expect("ok");
+
+    // Merging props which do not touch the key keeps the entry.
+    left.merge_node(node_1.clone(), props(None, Some("b")))
+        .expect("ok");
+    left.merge_edge(triple.clone(), props(None, Some("b")))
+        .expect("ok");
+    assert_eq!(
+        collect(left.lookup_nodes("a", &"x".to_string())),
+        [node_1.clone()].into()
+    );
+    assert_eq!(
+        collect(left.lookup_edges("a", &"x".to_string())),
+        [triple.clone()].into()
+    );
+
+    // Merging a new key moves the entry.
+    left.merge_node(node_1.clone(), props(Some("y"), None))
+        .expect("ok");
+    left.merge_edge(triple.clone(), props(Some("y"), None))
+        .expect("ok");
+    assert_eq!(collect(left.lookup_nodes("a", &"x".to_string())), [].into());
+    assert_eq!(collect(left.lookup_edges("a", &"x".to_string())), [].into());
+    assert_eq!(
+        collect(left.lookup_nodes("a", &"y".to_string())),
+        [node_1.clone()].into()
+    );
+    assert_eq!(
+        collect(left.lookup_edges("a", &"y".to_string())),
+        [triple.clone()].into()
+    );
+
+    // Extending replaces props and so the keys.
+    right
+        .insert_node(node_1.clone(), props(Some("z"), None))
+        .expect("ok");
+    right
+        .insert_node(node_2.clone(), props(Some("z"), None))
+        .expect("ok");
+    right
+        .insert_edge(triple.clone(), props(None, None))
+        .expect("ok");
+    left.extend(right).expect("ok");
+
+    assert_eq!(collect(left.lookup_nodes("a", &"y".to_string())), [].into());
+    assert_eq!(
+        collect(left.lookup_nodes("a", &"z".to_string())),
+        [node_1.clone(), node_2.clone()].into()
+    );
+    assert_eq!(collect(left.range_edges::<String>("a", ..)), [].into());
+}
@@ -1,6 +1,6 @@
 use ulid::Ulid;
 
-use crate::{prelude::*, EdgeOrder, Triple};
+use crate::{prelude::*, traits::IdType, EdgeOrder, Triple};
 
 struct Config {
     node_1: Ulid,
@@ -54,16 +54,18 @@ impl Default for Config {
     }
 }
 
-pub(crate) fn test_insert_node<T: TripleStore<Ulid, String, String>>(mut db: T) {
+pub(crate) fn test_insert_node<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(
+    mut db: T,
+) {
     let config = Config::default();
 
-    db.insert_node(config.node_1, config.node_data_1.clone())
+    db.insert_node(Id::from(config.node_1), config.node_data_1.clone())
         .expect("Insert should succeed");
-    db.insert_node(config.node_2, config.node_data_2.clone())
+    db.insert_node(Id::from(config.node_2), config.node_data_2.clone())
         .expect("Insert should succeed");
-    db.insert_node(config.node_3, config.node_data_3.clone())
+    db.insert_node(Id::from(config.node_3), config.node_data_3.clone())
         .expect("Insert should succeed");
-    db.insert_node(config.node_4, config.node_data_4.clone())
+    db.insert_node(Id::from(config.node_4), config.node_data_4.clone())
         .expect("Insert should succeed");
 
     let (nodes, edges) = db.iter_nodes(EdgeOrder::SPO);
@@ -71,68 +73,70 @@ pub(crate) fn test_insert_node<T: TripleStore<Ulid, String, String>>(mut db: T)
     assert_eq!(
         nodes.map(|e| e.expect("ok")).collect::<Vec<_>>(),
         [
-            (config.node_1, config.node_data_1.clone()),
-            (config.node_2, config.node_data_2.clone()),
-            (config.node_3, config.node_data_3.clone()),
-            (config.node_4, config.node_data_4.clone()),
+            (Id::from(config.node_1), config.node_data_1.clone()),
+            (Id::from(config.node_2), config.node_data_2.clone()),
+            (Id::from(config.node_3), config.node_data_3.clone()),
+            (Id::from(config.node_4), config.node_data_4.clone()),
         ]
         .to_vec()
     );
     assert_eq!(edges.collect::<Vec<_>>().len(), 0);
 
     // Update one of the entries by replacement.
-    db.insert_node(config.node_4, config.node_data_5.clone())
+    db.insert_node(Id::from(config.node_4), config.node_data_5.clone())
         .expect("Insert should succeed");
 
     let (nodes, edges) = db.iter_nodes(EdgeOrder::SPO);
     assert_eq!(
         nodes.map(|e| e.expect("ok")).collect::<Vec<_>>(),
         [
-            (config.node_1, config.node_data_1),
-            (config.node_2, config.node_data_2),
-            (config.node_3, config.node_data_3),
-            (config.node_4, config.node_data_5),
+            (Id::from(config.node_1), config.node_data_1),
+            (Id::from(config.node_2), config.node_data_2),
+            (Id::from(config.node_3), config.node_data_3),
+            (Id::from(config.node_4), config.node_data_5),
         ]
         .to_vec()
     );
     assert_eq!(edges.collect::<Vec<_>>().len(), 0);
 }
 
-pub(crate) fn test_insert_edge<T: TripleStore<Ulid, String, String>>(mut db: T) {
+pub(crate) fn test_insert_edge<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(
+    mut db: T,
+) {
     let config = Config::default();
 
     for (node, props) in [
-        (config.node_1, config.node_data_1.clone()),
-        (config.node_2, config.node_data_2.clone()),
-        (config.node_3, config.node_data_3.clone()),
-        (config.node_4, config.node_data_4.clone()),
+        (Id::from(config.node_1), config.node_data_1.clone()),
+        (Id::from(config.node_2), config.node_data_2.clone()),
+        (Id::from(config.node_3), config.node_data_3.clone()),
+        (Id::from(config.node_4), config.node_data_4.clone()),
     ] {
         db.insert_node(node, props).expect("insert should succeed");
     }
 
     db.insert_edge(
         Triple {
-            sub: config.node_1,
-            pred: config.edge_1,
-            obj: config.node_2,
+            sub: Id::from(config.node_1),
+            pred: Id::from(config.edge_1),
+            obj: Id::from(config.node_2),
         },
         config.edge_data_1.clone(),
     )
     .expect("insert edge should succeed");
     db.insert_edge(
         Triple {
-            sub: config.node_2,
-            pred: config.edge_2,
-            obj: config.node_3,
+            sub: Id::from(config.node_2),
+            pred: Id::from(config.edge_2),
+            obj: Id::from(config.node_3),
         },
         config.edge_data_2.clone(),
     )
     .expect("insert edge should succeed");
     db.insert_edge(
         Triple {
-            sub: config.node_3,
-            pred: config.edge_3,
-            obj: config.node_4,
+            sub: Id::from(config.node_3),
+            pred: Id::from(config.edge_3),
+            obj: Id::from(config.node_4),
         },
         config.edge_data_3.clone(),
     )
@@ -141,9 +145,9 @@ pub(crate) fn test_insert_edge<T: TripleStore<Ulid, String, String>>(mut db: T)
     // Update one of the edges
     db.insert_edge(
         Triple {
-            sub: config.node_3,
-            pred: config.edge_3,
-            obj: config.node_4,
+            sub: Id::from(config.node_3),
+            pred: Id::from(config.edge_3),
+            obj: Id::from(config.node_4),
         },
         config.edge_data_4.clone(),
     )
@@ -153,10 +157,10 @@ pub(crate) fn test_insert_edge<T: TripleStore<Ulid, String, String>>(mut db: T)
     assert_eq!(
         nodes.map(|e| e.expect("ok")).collect::<Vec<_>>(),
         [
-            (config.node_1, config.node_data_1.clone()),
-            (config.node_2, config.node_data_2.clone()),
-            (config.node_3, config.node_data_3.clone()),
-            (config.node_4, config.node_data_4.clone()),
+            (Id::from(config.node_1), config.node_data_1.clone()),
+            (Id::from(config.node_2), config.node_data_2.clone()),
+            (Id::from(config.node_3), config.node_data_3.clone()),
+            (Id::from(config.node_4), config.node_data_4.clone()),
         ]
         .to_vec()
     );
@@ -165,25 +169,25 @@ pub(crate) fn test_insert_edge<T: TripleStore<Ulid, String, String>>(mut db: T)
         [
             (
                 Triple {
-                    sub: config.node_1,
-                    pred: config.edge_1,
-                    obj: config.node_2,
+                    sub: Id::from(config.node_1),
+                    pred: Id::from(config.edge_1),
+                    obj: Id::from(config.node_2),
                 },
                 config.edge_data_1.clone()
             ),
             (
                 Triple {
-                    sub: config.node_2,
-                    pred: config.edge_2,
-                    obj: config.node_3,
+                    sub: Id::from(config.node_2),
+                    pred: Id::from(config.edge_2),
+                    obj: Id::from(config.node_3),
                 },
                 config.edge_data_2.clone(),
             ),
             (
                 Triple {
-                    sub: config.node_3,
-                    pred: config.edge_3,
-                    obj: config.node_4,
+                    sub: Id::from(config.node_3),
+                    pred: Id::from(config.edge_3),
+                    obj: Id::from(config.node_4),
                 },
                 config.edge_data_4.clone(),
             )
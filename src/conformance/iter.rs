@@ -1,6 +1,6 @@
 use ulid::Ulid;
 
-use crate::{prelude::*, PropsTriple, Triple};
+use crate::{prelude::*, traits::IdType, PropsTriple, Triple};
 
 #[derive(Clone)]
 struct Config {
@@ -52,21 +52,24 @@ impl Default for Config {
     }
 }
 
-fn build_graph<T: TripleStore<Ulid, String, String>>(db: &mut T, config: Config) {
-    db.insert_node(config.node_1, config.node_props_1)
+fn build_graph<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(
+    db: &mut T,
+    config: Config,
+) {
+    db.insert_node(Id::from(config.node_1), config.node_props_1)
         .expect("success");
-    db.insert_node(config.node_2, config.node_props_2)
+    db.insert_node(Id::from(config.node_2), config.node_props_2)
         .expect("success");
-    db.insert_node(config.node_3, config.node_props_3)
+    db.insert_node(Id::from(config.node_3), config.node_props_3)
         .expect("success");
-    db.insert_node(config.node_4, config.node_props_4)
+    db.insert_node(Id::from(config.node_4), config.node_props_4)
         .expect("success");
 
     db.insert_edge(
         Triple {
-            sub: config.node_1,
-            pred: config.edge_1,
-            obj: config.node_2,
+            sub: Id::from(config.node_1),
+            pred: Id::from(config.edge_1),
+            obj: Id::from(config.node_2),
         },
         config.edge_props_1,
     )
@@ -74,9 +77,9 @@ fn build_graph<T: TripleStore<Ulid, String, String>>(db: &mut T, config: Config)
 
     db.insert_edge(
         Triple {
-            sub: config.node_2,
-            pred: config.edge_2,
-            obj: config.node_3,
+            sub: Id::from(config.node_2),
+            pred: Id::from(config.edge_2),
+            obj: Id::from(config.node_3),
         },
         config.edge_props_2,
     )
@@ -84,16 +87,18 @@ fn build_graph<T: TripleStore<Ulid, String, String>>(db: &mut T, config: Config)
 
     db.insert_edge(
         Triple {
-            sub: config.node_3,
-            pred: config.edge_3,
-            obj: config.node_4,
+            sub: Id::from(config.node_3),
+            pred: Id::from(config.edge_3),
+            obj: Id::from(config.node_4),
         },
         config.edge_props_3,
     )
     .expect("success");
 }
 
-pub(crate) fn test_iter_spo<T: TripleStore<Ulid, String, String>>(mut db: T) {
+pub(crate) fn test_iter_spo<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(
+    mut db: T,
+) {
     let config = Config::default();
 
     build_graph(&mut db, config.clone());
@@ -103,26 +108,28 @@ pub(crate) fn test_iter_spo<T: TripleStore<Ulid, String, String>>(mut db: T) {
             .collect::<Vec<_>>(),
         [
             PropsTriple {
-                sub: (config.node_1, config.node_props_1.clone()),
-                pred: (config.edge_1, config.edge_props_1.clone()),
-                obj: (config.node_2, config.node_props_2.clone())
+                sub: (Id::from(config.node_1), config.node_props_1.clone()),
+                pred: (Id::from(config.edge_1), config.edge_props_1.clone()),
+                obj: (Id::from(config.node_2), config.node_props_2.clone())
             },
             PropsTriple {
-                sub: (config.node_2, config.node_props_2.clone()),
-                pred: (config.edge_2, config.edge_props_2.clone()),
-                obj: (config.node_3, config.node_props_3.clone())
+                sub: (Id::from(config.node_2), config.node_props_2.clone()),
+                pred: (Id::from(config.edge_2), config.edge_props_2.clone()),
+                obj: (Id::from(config.node_3), config.node_props_3.clone())
             },
             PropsTriple {
-                sub: (config.node_3, config.node_props_3.clone()),
-                pred: (config.edge_3, config.edge_props_3.clone()),
-                obj: (config.node_4, config.node_props_4.clone())
+                sub: (Id::from(config.node_3), config.node_props_3.clone()),
+                pred: (Id::from(config.edge_3), config.edge_props_3.clone()),
+                obj: (Id::from(config.node_4), config.node_props_4.clone())
             },
         ]
         .to_vec()
     );
 }
 
-pub(crate) fn test_iter_pos<T: TripleStore<Ulid, String, String>>(mut db: T) {
+pub(crate) fn test_iter_pos<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(
+    mut db: T,
+) {
     let config = Config::default();
 
     build_graph(&mut db, config.clone());
@@ -132,26 +139,28 @@ pub(crate) fn test_iter_pos<T: TripleStore<Ulid, String, String>>(mut db: T) {
             .collect::<Vec<_>>(),
         [
             PropsTriple {
-                sub: (config.node_1, config.node_props_1.clone()),
-                pred: (config.edge_1, config.edge_props_1.clone()),
-                obj: (config.node_2, config.node_props_2.clone())
+                sub: (Id::from(config.node_1), config.node_props_1.clone()),
+                pred: (Id::from(config.edge_1), config.edge_props_1.clone()),
+                obj: (Id::from(config.node_2), config.node_props_2.clone())
             },
             PropsTriple {
-                sub: (config.node_3, config.node_props_3.clone()),
-                pred: (config.edge_3, config.edge_props_3.clone()),
-                obj: (config.node_4, config.node_props_4.clone())
+                sub: (Id::from(config.node_3), config.node_props_3.clone()),
+                pred: (Id::from(config.edge_3), config.edge_props_3.clone()),
+                obj: (Id::from(config.node_4), config.node_props_4.clone())
             },
             PropsTriple {
-                sub: (config.node_2, config.node_props_2.clone()),
-                pred: (config.edge_2, config.edge_props_2.clone()),
-                obj: (config.node_3, config.node_props_3.clone())
+                sub: (Id::from(config.node_2), config.node_props_2.clone()),
+                pred: (Id::from(config.edge_2), config.edge_props_2.clone()),
+                obj: (Id::from(config.node_3), config.node_props_3.clone())
             },
         ]
         .to_vec()
     );
 }
 
-pub(crate) fn test_iter_osp<T: TripleStore<Ulid, String, String>>(mut db: T) {
+pub(crate) fn test_iter_osp<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(
+    mut db: T,
+) {
     let config = Config::default();
 
     build_graph(&mut db, config.clone());
@@ -161,26 +170,28 @@ pub(crate) fn test_iter_osp<T: TripleStore<Ulid, String, String>>(mut db: T) {
             .collect::<Vec<_>>(),
         [
             PropsTriple {
-                sub: (config.node_3, config.node_props_3.clone()),
-                pred: (config.edge_3, config.edge_props_3.clone()),
-                obj: (config.node_4, config.node_props_4.clone())
+                sub: (Id::from(config.node_3), config.node_props_3.clone()),
+                pred: (Id::from(config.edge_3), config.edge_props_3.clone()),
+                obj: (Id::from(config.node_4), config.node_props_4.clone())
             },
             PropsTriple {
-                sub: (config.node_1, config.node_props_1.clone()),
-                pred: (config.edge_1, config.edge_props_1.clone()),
-                obj: (config.node_2, config.node_props_2.clone())
+                sub: (Id::from(config.node_1), config.node_props_1.clone()),
+                pred: (Id::from(config.edge_1), config.edge_props_1.clone()),
+                obj: (Id::from(config.node_2), config.node_props_2.clone())
             },
             PropsTriple {
-                sub: (config.node_2, config.node_props_2.clone()),
-                pred: (config.edge_2, config.edge_props_2.clone()),
-                obj: (config.node_3, config.node_props_3.clone())
+                sub: (Id::from(config.node_2), config.node_props_2.clone()),
+                pred: (Id::from(config.edge_2), config.edge_props_2.clone()),
+                obj: (Id::from(config.node_3), config.node_props_3.clone())
             },
         ]
         .to_vec()
     );
 }
 
-pub(crate) fn test_iter_edge_spo<T: TripleStore<Ulid, String, String>>(mut db: T) {
+pub(crate) fn test_iter_edge_spo<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(
+    mut db: T,
+) {
     let config = Config::default();
 
     build_graph(&mut db, config.clone());
@@ -191,25 +202,25 @@ pub(crate) fn test_iter_edge_spo<T: TripleStore<Ulid, String, String>>(mut db: T
         [
             (
                 Triple {
-                    sub: config.node_1.clone(),
-                    pred: config.edge_1.clone(),
-                    obj: config.node_2.clone(),
+                    sub: Id::from(config.node_1),
+                    pred: Id::from(config.edge_1),
+                    obj: Id::from(config.node_2),
                 },
                 config.edge_props_1
             ),
             (
                 Triple {
-                    sub: config.node_2.clone(),
-                    pred: config.edge_2.clone(),
-                    obj: config.node_3.clone(),
+                    sub: Id::from(config.node_2),
+                    pred: Id::from(config.edge_2),
+                    obj: Id::from(config.node_3),
                 },
                 config.edge_props_2
             ),
             (
                 Triple {
-                    sub: config.node_3.clone(),
-                    pred: config.edge_3.clone(),
-                    obj: config.node_4.clone(),
+                    sub: Id::from(config.node_3),
+                    pred: Id::from(config.edge_3),
+                    obj: Id::from(config.node_4),
                 },
                 config.edge_props_3
             )
@@ -218,7 +229,9 @@ pub(crate) fn test_iter_edge_spo<T: TripleStore<Ulid, String, String>>(mut db: T
     );
 }
 
-pub(crate) fn test_iter_edge_pos<T: TripleStore<Ulid, String, String>>(mut db: T) {
+pub(crate) fn test_iter_edge_pos<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(
+    mut db: T,
+) {
     let config = Config::default();
 
     build_graph(&mut db, config.clone());
@@ -229,25 +242,25 @@ pub(crate) fn test_iter_edge_pos<T: TripleStore<Ulid, String, String>>(mut db: T
         [
             (
                 Triple {
-                    sub: config.node_1.clone(),
-                    pred: config.edge_1.clone(),
-                    obj: config.node_2.clone(),
+                    sub: Id::from(config.node_1),
+                    pred: Id::from(config.edge_1),
+                    obj: Id::from(config.node_2),
                 },
                 config.edge_props_1
             ),
             (
                 Triple {
-                    sub: config.node_3.clone(),
-                    pred: config.edge_3.clone(),
-                    obj: config.node_4.clone(),
+                    sub: Id::from(config.node_3),
+                    pred: Id::from(config.edge_3),
+                    obj: Id::from(config.node_4),
                 },
                 config.edge_props_3
             ),
             (
                 Triple {
-                    sub: config.node_2.clone(),
-                    pred: config.edge_2.clone(),
-                    obj: config.node_3.clone(),
+                    sub: Id::from(config.node_2),
+                    pred: Id::from(config.edge_2),
+                    obj: Id::from(config.node_3),
                 },
                 config.edge_props_2
             ),
@@ -256,7 +269,9 @@ pub(crate) fn test_iter_edge_pos<T: TripleStore<Ulid, String, String>>(mut db: T
     );
 }
 
-pub(crate) fn test_iter_edge_osp<T: TripleStore<Ulid, String, String>>(mut db: T) {
+pub(crate) fn test_iter_edge_osp<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(
+    mut db: T,
+) {
     let config = Config::default();
 
     build_graph(&mut db, config.clone());
@@ -267,25 +282,25 @@ pub(crate) fn test_iter_edge_osp<T: TripleStore<Ulid, String, String>>(mut db: T
         [
             (
                 Triple {
-                    sub: config.node_3.clone(),
-                    pred: config.edge_3.clone(),
-                    obj: config.node_4.clone(),
+                    sub: Id::from(config.node_3),
+                    pred: Id::from(config.edge_3),
+                    obj: Id::from(config.node_4),
                 },
                 config.edge_props_3
             ),
             (
                 Triple {
-                    sub: config.node_1.clone(),
-                    pred: config.edge_1.clone(),
-                    obj: config.node_2.clone(),
+                    sub: Id::from(config.node_1),
+                    pred: Id::from(config.edge_1),
+                    obj: Id::from(config.node_2),
                 },
                 config.edge_props_1
             ),
             (
                 Triple {
-                    sub: config.node_2.clone(),
-                    pred: config.edge_2.clone(),
-                    obj: config.node_3.clone(),
+                    sub: Id::from(config.node_2),
+                    pred: Id::from(config.edge_2),
+                    obj: Id::from(config.node_3),
                 },
                 config.edge_props_2
             ),
@@ -294,23 +309,27 @@ pub(crate) fn test_iter_edge_osp<T: TripleStore<Ulid, String, String>>(mut db: T
     );
 }
 
-pub(crate) fn test_iter_node<T: TripleStore<Ulid, String, String>>(mut db: T) {
+pub(crate) fn test_iter_node<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(
+    mut db: T,
+) {
     let config = Config::default();
 
     build_graph(&mut db, config.clone());
     assert_eq!(
         db.iter_vertices().map(|r| r.unwrap()).collect::<Vec<_>>(),
         [
-            (config.node_1, config.node_props_1),
-            (config.node_4, config.node_props_4),
-            (config.node_2, config.node_props_2),
-            (config.node_3, config.node_props_3),
+            (Id::from(config.node_1), config.node_props_1),
+            (Id::from(config.node_4), config.node_props_4),
+            (Id::from(config.node_2), config.node_props_2),
+            (Id::from(config.node_3), config.node_props_3),
         ]
         .to_vec()
     );
 }
 
-pub(crate) fn test_into_iter_spo<T: TripleStore<Ulid, String, String>>(mut db: T) {
+pub(crate) fn test_into_iter_spo<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(
+    mut db: T,
+) {
     let config = Config::default();
 
     build_graph(&mut db, config.clone());
@@ -320,26 +339,28 @@ pub(crate) fn test_into_iter_spo<T: TripleStore<Ulid, String, String>>(mut db: T
             .collect::<Vec<_>>(),
         [
             PropsTriple {
-                sub: (config.node_1, config.node_props_1.clone()),
-                pred: (config.edge_1, config.edge_props_1.clone()),
-                obj: (config.node_2, config.node_props_2.clone())
+                sub: (Id::from(config.node_1), config.node_props_1.clone()),
+                pred: (Id::from(config.edge_1), config.edge_props_1.clone()),
+                obj: (Id::from(config.node_2), config.node_props_2.clone())
             },
             PropsTriple {
-                sub: (config.node_2, config.node_props_2.clone()),
-                pred: (config.edge_2, config.edge_props_2.clone()),
-                obj: (config.node_3, config.node_props_3.clone())
+                sub: (Id::from(config.node_2), config.node_props_2.clone()),
+                pred: (Id::from(config.edge_2), config.edge_props_2.clone()),
+                obj: (Id::from(config.node_3), config.node_props_3.clone())
             },
             PropsTriple {
-                sub: (config.node_3, config.node_props_3.clone()),
-                pred: (config.edge_3, config.edge_props_3.clone()),
-                obj: (config.node_4, config.node_props_4.clone())
+                sub: (Id::from(config.node_3), config.node_props_3.clone()),
+                pred: (Id::from(config.edge_3), config.edge_props_3.clone()),
+                obj: (Id::from(config.node_4), config.node_props_4.clone())
             },
         ]
         .to_vec()
     );
 }
 
-pub(crate) fn test_into_iter_pos<T: TripleStore<Ulid, String, String>>(mut db: T) {
+pub(crate) fn test_into_iter_pos<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(
+    mut db: T,
+) {
     let config = Config::default();
 
     build_graph(&mut db, config.clone());
@@ -349,26 +370,28 @@ pub(crate) fn test_into_iter_pos<T: TripleStore<Ulid, String, String>>(mut db: T
             .collect::<Vec<_>>(),
         [
             PropsTriple {
-                sub: (config.node_1, config.node_props_1.clone()),
-                pred: (config.edge_1, config.edge_props_1.clone()),
-                obj: (config.node_2, config.node_props_2.clone())
+                sub: (Id::from(config.node_1), config.node_props_1.clone()),
+                pred: (Id::from(config.edge_1), config.edge_props_1.clone()),
+                obj: (Id::from(config.node_2), config.node_props_2.clone())
             },
             PropsTriple {
-                sub: (config.node_3, config.node_props_3.clone()),
-                pred: (config.edge_3, config.edge_props_3.clone()),
-                obj: (config.node_4, config.node_props_4.clone())
+                sub: (Id::from(config.node_3), config.node_props_3.clone()),
+                pred: (Id::from(config.edge_3), config.edge_props_3.clone()),
+                obj: (Id::from(config.node_4), config.node_props_4.clone())
             },
             PropsTriple {
-                sub: (config.node_2, config.node_props_2.clone()),
-                pred: (config.edge_2, config.edge_props_2.clone()),
-                obj: (config.node_3, config.node_props_3.clone())
+                sub: (Id::from(config.node_2), config.node_props_2.clone()),
+                pred: (Id::from(config.edge_2), config.edge_props_2.clone()),
+                obj: (Id::from(config.node_3), config.node_props_3.clone())
             },
         ]
         .to_vec()
     );
 }
 
-pub(crate) fn test_into_iter_osp<T: TripleStore<Ulid, String, String>>(mut db: T) {
+pub(crate) fn test_into_iter_osp<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(
+    mut db: T,
+) {
     let config = Config::default();
 
     build_graph(&mut db, config.clone());
@@ -378,26 +401,31 @@ pub(crate) fn test_into_iter_osp<T: TripleStore<Ulid, String, String>>(mut db: T
             .collect::<Vec<_>>(),
         [
             PropsTriple {
-                sub: (config.node_3, config.node_props_3.clone()),
-                pred: (config.edge_3, config.edge_props_3.clone()),
-                obj: (config.node_4, config.node_props_4.clone())
+                sub: (Id::from(config.node_3), config.node_props_3.clone()),
+                pred: (Id::from(config.edge_3), config.edge_props_3.clone()),
+                obj: (Id::from(config.node_4), config.node_props_4.clone())
             },
             PropsTriple {
-                sub: (config.node_1, config.node_props_1.clone()),
-                pred: (config.edge_1, config.edge_props_1.clone()),
-                obj: (config.node_2, config.node_props_2.clone())
+                sub: (Id::from(config.node_1), config.node_props_1.clone()),
+                pred: (Id::from(config.edge_1), config.edge_props_1.clone()),
+                obj: (Id::from(config.node_2), config.node_props_2.clone())
             },
             PropsTriple {
-                sub: (config.node_2, config.node_props_2.clone()),
-                pred: (config.edge_2, config.edge_props_2.clone()),
-                obj: (config.node_3, config.node_props_3.clone())
+                sub: (Id::from(config.node_2), config.node_props_2.clone()),
+                pred: (Id::from(config.edge_2), config.edge_props_2.clone()),
+                obj: (Id::from(config.node_3), config.node_props_3.clone())
             },
         ]
         .to_vec()
     );
 }
 
-pub(crate) fn test_into_iter_edge_spo<T: TripleStore<Ulid, String, String>>(mut db: T) {
+pub(crate) fn test_into_iter_edge_spo<
+    Id: IdType + From<Ulid>,
+    T: TripleStore<Id, String, String>,
+>(
+    mut db: T,
+) {
     let config = Config::default();
 
     build_graph(&mut db, config.clone());
@@ -408,25 +436,25 @@ pub(crate) fn test_into_iter_edge_spo<T: TripleStore<Ulid, String, String>>(mut
         [
             (
                 Triple {
-                    sub: config.node_1.clone(),
-                    pred: config.edge_1.clone(),
-                    obj: config.node_2.clone(),
+                    sub: Id::from(config.node_1),
+                    pred: Id::from(config.edge_1),
+                    obj: Id::from(config.node_2),
                 },
                 config.edge_props_1
             ),
             (
                 Triple {
-                    sub: config.node_2.clone(),
-                    pred: config.edge_2.clone(),
-                    obj: config.node_3.clone(),
+                    sub: Id::from(config.node_2),
+                    pred: Id::from(config.edge_2),
+                    obj: Id::from(config.node_3),
                 },
                 config.edge_props_2
             ),
             (
                 Triple {
-                    sub: config.node_3.clone(),
-                    pred: config.edge_3.clone(),
-                    obj: config.node_4.clone(),
+                    sub: Id::from(config.node_3),
+                    pred: Id::from(config.edge_3),
+                    obj: Id::from(config.node_4),
                 },
                 config.edge_props_3
             )
@@ -435,7 +463,12 @@ pub(crate) fn test_into_iter_edge_spo<T: TripleStore<Ulid, String, String>>(mut
     );
 }
 
-pub(crate) fn test_into_iter_edge_pos<T: TripleStore<Ulid, String, String>>(mut db: T) {
+pub(crate) fn test_into_iter_edge_pos<
+    Id: IdType + From<Ulid>,
+    T: TripleStore<Id, String, String>,
+>(
+    mut db: T,
+) {
     let config = Config::default();
 
     build_graph(&mut db, config.clone());
@@ -446,25 +479,25 @@ pub(crate) fn test_into_iter_edge_pos<T: TripleStore<Ulid, String, String>>(mut
         [
             (
                 Triple {
-                    sub: config.node_1.clone(),
-                    pred: config.edge_1.clone(),
-                    obj: config.node_2.clone(),
+                    sub: Id::from(config.node_1),
+                    pred: Id::from(config.edge_1),
+                    obj: Id::from(config.node_2),
                 },
                 config.edge_props_1
             ),
             (
                 Triple {
-                    sub: config.node_3.clone(),
-                    pred: config.edge_3.clone(),
-                    obj: config.node_4.clone(),
+                    sub: Id::from(config.node_3),
+                    pred: Id::from(config.edge_3),
+                    obj: Id::from(config.node_4),
                 },
                 config.edge_props_3
             ),
             (
                 Triple {
-                    sub: config.node_2.clone(),
-                    pred: config.edge_2.clone(),
-                    obj: config.node_3.clone(),
+                    sub: Id::from(config.node_2),
+                    pred: Id::from(config.edge_2),
+                    obj: Id::from(config.node_3),
                 },
                 config.edge_props_2
             ),
@@ -473,7 +506,12 @@ pub(crate) fn test_into_iter_edge_pos<T: TripleStore<Ulid, String, String>>(mut
     );
 }
 
-pub(crate) fn test_into_iter_edge_osp<T: TripleStore<Ulid, String, String>>(mut db: T) {
+pub(crate) fn test_into_iter_edge_osp<
+    Id: IdType + From<Ulid>,
+    T: TripleStore<Id, String, String>,
+>(
+    mut db: T,
+) {
     let config = Config::default();
 
     build_graph(&mut db, config.clone());
@@ -484,25 +522,25 @@ pub(crate) fn test_into_iter_edge_osp<T: TripleStore<Ulid, String, String>>(mut
         [
             (
                 Triple {
-                    sub: config.node_3.clone(),
-                    pred: config.edge_3.clone(),
-                    obj: config.node_4.clone(),
+                    sub: Id::from(config.node_3),
+                    pred: Id::from(config.edge_3),
+                    obj: Id::from(config.node_4),
                 },
                 config.edge_props_3
             ),
             (
                 Triple {
-                    sub: config.node_1.clone(),
-                    pred: config.edge_1.clone(),
-                    obj: config.node_2.clone(),
+                    sub: Id::from(config.node_1),
+                    pred: Id::from(config.edge_1),
+                    obj: Id::from(config.node_2),
                 },
                 config.edge_props_1
             ),
             (
                 Triple {
-                    sub: config.node_2.clone(),
-                    pred: config.edge_2.clone(),
-                    obj: config.node_3.clone(),
+                    sub: Id::from(config.node_2),
+                    pred: Id::from(config.edge_2),
+                    obj: Id::from(config.node_3),
                 },
                 config.edge_props_2
             ),
@@ -511,7 +549,9 @@ pub(crate) fn test_into_iter_edge_osp<T: TripleStore<Ulid, String, String>>(mut
     );
 }
 
-pub(crate) fn test_into_iter_node<T: TripleStore<Ulid, String, String>>(mut db: T) {
+pub(crate) fn test_into_iter_node<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(
+    mut db: T,
+) {
     let config = Config::default();
 
     build_graph(&mut db, config.clone());
@@ -520,10 +560,10 @@ pub(crate) fn test_into_iter_node<T: TripleStore<Ulid, String, String>>(mut db:
             .map(|r| r.unwrap())
             .collect::<Vec<_>>(),
         [
-            (config.node_1, config.node_props_1),
-            (config.node_4, config.node_props_4),
-            (config.node_2, config.node_props_2),
-            (config.node_3, config.node_props_3),
+            (Id::from(config.node_1), config.node_props_1),
+            (Id::from(config.node_4), config.node_props_4),
+            (Id::from(config.node_2), config.node_props_2),
+            (Id::from(config.node_3), config.node_props_3),
         ]
         .to_vec()
     );
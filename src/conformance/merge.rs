@@ -1,6 +1,6 @@
 use ulid::Ulid;
 
-use crate::{prelude::*, Triple};
+use crate::{prelude::*, traits::IdType, Triple};
 
 use super::TestMergeable;
 
@@ -47,44 +47,45 @@ impl Default for Config {
 }
 
 fn build_graph<
-    T: TripleStore<Ulid, TestMergeable, TestMergeable>
-        + TripleStoreMerge<Ulid, TestMergeable, TestMergeable>,
+    Id: IdType + From<Ulid>,
+    T: TripleStore<Id, TestMergeable, TestMergeable>
+        + TripleStoreMerge<Id, TestMergeable, TestMergeable>,
 >(
     mut db: T,
     config: Config,
 ) -> T {
-    db.insert_node(config.node_1, config.node_1_props)
+    db.insert_node(Id::from(config.node_1), config.node_1_props)
         .expect("success");
-    db.insert_node(config.node_2, config.node_2_props)
+    db.insert_node(Id::from(config.node_2), config.node_2_props)
         .expect("success");
-    db.insert_node(config.node_3, config.node_3_props)
+    db.insert_node(Id::from(config.node_3), config.node_3_props)
         .expect("success");
-    db.insert_node(config.node_4, config.node_4_props)
+    db.insert_node(Id::from(config.node_4), config.node_4_props)
         .expect("success");
 
     db.insert_edge(
         Triple {
-            sub: config.node_1,
-            pred: config.edge_1,
-            obj: config.node_3,
+            sub: Id::from(config.node_1),
+            pred: Id::from(config.edge_1),
+            obj: Id::from(config.node_3),
         },
         config.edge_1_props,
     )
     .expect("success");
     db.insert_edge(
         Triple {
-            sub: config.node_1,
-            pred: config.edge_2,
-            obj: config.node_3,
+            sub: Id::from(config.node_1),
+            pred: Id::from(config.edge_2),
+            obj: Id::from(config.node_3),
         },
         config.edge_2_props,
     )
     .expect("success");
     db.insert_edge(
         Triple {
-            sub: config.node_3,
-            pred: config.edge_3,
-            obj: config.node_4,
+            sub: Id::from(config.node_3),
+            pred: Id::from(config.edge_3),
+            obj: Id::from(config.node_4),
         },
         config.edge_3_props,
     )
@@ -94,8 +95,9 @@ fn build_graph<
 }
 
 pub(crate) fn test_merge<
-    T: TripleStore<Ulid, TestMergeable, TestMergeable>
-        + TripleStoreMerge<Ulid, TestMergeable, TestMergeable>
+    Id: IdType + From<Ulid>,
+    T: TripleStore<Id, TestMergeable, TestMergeable>
+        + TripleStoreMerge<Id, TestMergeable, TestMergeable>
         + std::fmt::Debug,
 >(
     mut make_db: impl FnMut() -> T,
@@ -180,8 +182,9 @@ pub(crate) fn test_merge<
 }
 
 pub(crate) fn test_merge_node<
-    T: TripleStore<Ulid, TestMergeable, TestMergeable>
-        + TripleStoreMerge<Ulid, TestMergeable, TestMergeable>
+    Id: IdType + From<Ulid>,
+    T: TripleStore<Id, TestMergeable, TestMergeable>
+        + TripleStoreMerge<Id, TestMergeable, TestMergeable>
         + std::fmt::Debug,
 >(
     mut make_db: impl FnMut() -> T,
@@ -201,7 +204,7 @@ pub(crate) fn test_merge_node<
 
     graph_1
         .merge_node(
-            initial_graph.node_1,
+            Id::from(initial_graph.node_1),
             TestMergeable {
                 a: Some("baz".into()),
                 b: None,
@@ -211,7 +214,7 @@ pub(crate) fn test_merge_node<
 
     graph_1
         .merge_node(
-            initial_graph.node_2,
+            Id::from(initial_graph.node_2),
             TestMergeable {
                 a: Some("baz".into()),
                 b: None,
@@ -238,8 +241,9 @@ pub(crate) fn test_merge_node<
 }
 
 pub(crate) fn test_merge_edge<
-    T: TripleStore<Ulid, TestMergeable, TestMergeable>
-        + TripleStoreMerge<Ulid, TestMergeable, TestMergeable>
+    Id: IdType + From<Ulid>,
+    T: TripleStore<Id, TestMergeable, TestMergeable>
+        + TripleStoreMerge<Id, TestMergeable, TestMergeable>
         + std::fmt::Debug,
 >(
     mut make_db: impl FnMut() -> T,
@@ -253,20 +257,20 @@ pub(crate) fn test_merge_edge<
     // Setup the Initial Graph
     let make_graph = |mut graph: T| {
         graph
-            .insert_node(node_1, TestMergeable::default())
+            .insert_node(Id::from(node_1), TestMergeable::default())
             .expect("ok");
         graph
-            .insert_node(node_2, TestMergeable::default())
+            .insert_node(Id::from(node_2), TestMergeable::default())
             .expect("ok");
         graph
-            .insert_node(node_3, TestMergeable::default())
+            .insert_node(Id::from(node_3), TestMergeable::default())
             .expect("ok");
         graph
             .insert_edge(
                 Triple {
-                    sub: node_1,
-                    pred: edge,
-                    obj: node_2,
+                    sub: Id::from(node_1),
+                    pred: Id::from(edge),
+                    obj: Id::from(node_2),
                 },
                 TestMergeable {
                     a: Some("foo".into()),
@@ -284,9 +288,9 @@ pub(crate) fn test_merge_edge<
     expected_graph
         .insert_edge(
             Triple {
-                sub: node_1,
-                pred: edge,
-                obj: node_2,
+                sub: Id::from(node_1),
+                pred: Id::from(edge),
+                obj: Id::from(node_2),
             },
             TestMergeable {
                 a: Some("foo".into()),
@@ -297,9 +301,9 @@ pub(crate) fn test_merge_edge<
     expected_graph
         .insert_edge(
             Triple {
-                sub: node_2,
-                pred: edge,
-                obj: node_3,
+                sub: Id::from(node_2),
+                pred: Id::from(edge),
+                obj: Id::from(node_3),
             },
             TestMergeable {
                 a: Some("baz".into()),
@@ -312,9 +316,9 @@ pub(crate) fn test_merge_edge<
     graph
         .merge_edge(
             Triple {
-                sub: node_1,
-                pred: edge,
-                obj: node_2,
+                sub: Id::from(node_1),
+                pred: Id::from(edge),
+                obj: Id::from(node_2),
             },
             TestMergeable {
                 a: None,
@@ -325,9 +329,9 @@ pub(crate) fn test_merge_edge<
     graph
         .merge_edge(
             Triple {
-                sub: node_2,
-                pred: edge,
-                obj: node_3,
+                sub: Id::from(node_2),
+                pred: Id::from(edge),
+                obj: Id::from(node_3),
             },
             TestMergeable {
                 a: Some("baz".into()),
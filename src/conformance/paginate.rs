@@ -0,0 +1,239 @@
+use std::collections::HashSet;
+
+use ulid::Ulid;
+
+use crate::{prelude::*, traits::IdType, Cursor, EdgeOrder, Page, Query, Triple};
+
+fn triple<Id: IdType + From<Ulid>>(sub: u128, pred: u128, obj: u128) -> Triple<Id> {
+    Triple {
+        sub: Ulid(sub).into(),
+        pred: Ulid(pred).into(),
+        obj: Ulid(obj).into(),
+    }
+}
+
+/// Follow cursors until the last page, checking that no page is larger than `limit`.
+fn collect_pages<T, E: std::fmt::Debug>(
+    limit: usize,
+    mut page: impl FnMut(Option<&Cursor>) -> Result<Page<T>, E>,
+) -> Vec<T> {
+    let mut items = Vec::new();
+    let mut cursor = None;
+    loop {
+        let next = page(cursor.as_ref()).expect("ok");
+        assert!(next.items.len() <= limit);
+        items.extend(next.items);
+        match next.next {
+            Some(next) => cursor = Some(next),
+            None => return items,
+        }
+    }
+}
+
+pub(crate) fn test_page_vertices<
+    Id: IdType + From<Ulid>,
+    T: TripleStore<Id, String, String> + TripleStorePaginate<Id, String, String>,
+>(
+    mut db: T,
+) {
+    let id = |n: u128| Id::from(Ulid(n));
+
+    assert_eq!(db.page_vertices(None, 3).expect("ok"), Page::default());
+
+    for n in 1..=7 {
+        db.insert_node(id(n * 10), format!("{}", n)).expect("ok");
+    }
+    let expected = db
+        .iter_vertices()
+        .map(|r| r.expect("ok"))
+        .collect::<Vec<_>>();
+    for limit in [1, 3, 7, 10] {
+        assert_eq!(
+            collect_pages(limit, |after| db.page_vertices(after, limit)),
+            expected
+        );
+    }
+
+    // A page which ends exactly at the last row has no cursor.
+    let page = db.page_vertices(None, 7).expect("ok");
+    assert_eq!(page.items.len(), 7);
+    assert_eq!(page.next, None);
+
+    // A limit of zero gives an empty page rather than a cursor which would never advance.
+    assert_eq!(db.page_vertices(None, 0).expect("ok"), Page::default());
+
+    // Resuming is unaffected by rows inserted before the cursor or removed after it.
+    let first = db.page_vertices(None, 3).expect("ok");
+    assert_eq!(first.items, expected[..3]);
+    db.insert_node(id(5), "before".to_string()).expect("ok");
+    db.insert_node(id(35), "after".to_string()).expect("ok");
+    db.remove_node(id(40)).expect("ok");
+    let second = db.page_vertices(first.next.as_ref(), 2).expect("ok");
+    assert_eq!(
+        second.items,
+        [(id(35), "after".to_string()), (id(50), "5".to_string())]
+    );
+
+    // Cursors round trip through bytes, and cannot be used on another table.
+    let cursor = Cursor::from_bytes(second.next.expect("more rows").as_bytes());
+    assert_eq!(
+        db.page_vertices(Some(&cursor), 10).expect("ok").items,
+        [(id(60), "6".to_string()), (id(70), "7".to_string())]
+    );
+    assert!(db.page_edges(EdgeOrder::SPO, Some(&cursor), 10).is_err());
+    assert!(db
+        .page_vertices(Some(&Cursor::from_bytes(vec![0, 1, 2])), 10)
+        .is_err());
+}
+
+pub(crate) fn test_page_edges<
+    Id: IdType + From<Ulid>,
+    T: TripleStore<Id, String, String> + TripleStorePaginate<Id, String, String>,
+>(
+    mut db: T,
+) {
+    for (sub, pred, obj) in [
+        (1, 10, 2),
+        (1, 10, 3),
+        (2, 10, 3),
+        (3, 11, 1),
+        (2, 11, 1),
+        (1, 12, 1),
+    ] {
+        db.insert_edge(triple(sub, pred, obj), format!("{}-{}-{}", sub, pred, obj))
+            .expect("ok");
+    }
+
+    for order in [EdgeOrder::SPO, EdgeOrder::POS, EdgeOrder::OSP] {
+        let expected = db
+            .iter_edges(order.clone())
+            .map(|r| r.expect("ok"))
+            .collect::<Vec<_>>();
+        for limit in [1, 2, 6, 10] {
+            assert_eq!(
+                collect_pages(limit, |after| db.page_edges(order.clone(), after, limit)),
+                expected,
+                "{:?}",
+                order
+            );
+        }
+    }
+
+    // Resuming is unaffected by rows inserted before the cursor, or by removing the row at the cursor.
+    let first = db.page_edges(EdgeOrder::SPO, None, 2).expect("ok");
+    assert_eq!(
+        first
+            .items
+            .iter()
+            .map(|(triple, _)| triple.clone())
+            .collect::<Vec<_>>(),
+        [triple(1, 10, 2), triple(1, 10, 3)]
+    );
+    db.insert_edge(triple(1, 9, 9), "".to_string()).expect("ok");
+    db.remove_edge(triple(1, 10, 3)).expect("ok");
+    assert_eq!(
+        db.page_edges(EdgeOrder::SPO, first.next.as_ref(), 10)
+            .expect("ok")
+            .items
+            .into_iter()
+            .map(|(triple, _)| triple)
+            .collect::<Vec<_>>(),
+        [
+            triple(1, 12, 1),
+            triple(2, 10, 3),
+            triple(2, 11, 1),
+            triple(3, 11, 1)
+        ]
+    );
+
+    // Cursors are tied to one table.
+    assert!(db
+        .page_edges(EdgeOrder::POS, first.next.as_ref(), 10)
+        .is_err());
+}
+
+pub(crate) fn test_page_query<
+    Id: IdType + From<Ulid>,
+    T: TripleStore<Id, String, String> + TripleStorePaginate<Id, String, String>,
+>(
+    mut db: T,
+) {
+    let id = |n: u128| Id::from(Ulid(n));
+
+    for n in 1..=5 {
+        db.insert_node(id(n), format!("{}", n)).expect("ok");
+    }
+    for sub in 1..=4 {
+        for obj in 1..=4 {
+            db.insert_edge(
+                triple(sub, 10 + (sub + obj) % 2, obj),
+                format!("{}-{}", sub, obj),
+            )
+            .expect("ok");
+        }
+    }
+
+    let queries = [
+        Query::NodeProps([id(4), id(2), id(9), id(1)].into()),
+        Query::SPO(
+            [
+                (id(1), id(10), id(1)),
+                (id(1), id(10), id(2)),
+                (id(3), id(11), id(2)),
+            ]
+            .into(),
+        ),
+        Query::S([id(3), id(1)].into()),
+        Query::P([id(11)].into()),
+        Query::O([id(4), id(2), id(9)].into()),
+        Query::SP([(id(2), id(10)), (id(1), id(11))].into()),
+        Query::SO([(id(4), id(1)), (id(1), id(4)), (id(2), id(2))].into()),
+        Query::PO([(id(10), id(3)), (id(11), id(1))].into()),
+    ];
+
+    for query in queries {
+        let (nodes, edges) = db.iter_query(query.clone());
+        let expected_nodes = nodes.map(|r| r.expect("ok")).collect::<HashSet<_>>();
+        let expected_edges = edges.map(|r| r.expect("ok")).collect::<HashSet<_>>();
+
+        for limit in [1, 2, 100] {
+            let nodes = collect_pages(limit, |after| {
+                db.page_query(query.clone(), after, limit)
+                    .map(|(nodes, _)| nodes)
+            });
+            let edges = collect_pages(limit, |after| {
+                db.page_query(query.clone(), after, limit)
+                    .map(|(_, edges)| edges)
+            });
+
+            // Every row is returned exactly once.
+            assert_eq!(nodes.len(), expected_nodes.len(), "{:?}", query);
+            assert_eq!(nodes.into_iter().collect::<HashSet<_>>(), expected_nodes);
+            assert_eq!(edges.len(), expected_edges.len(), "{:?}", query);
+            assert_eq!(edges.into_iter().collect::<HashSet<_>>(), expected_edges);
+        }
+    }
+
+    // Rows come in key order, so a cursor stays valid while the store changes.
+    let query = Query::S([id(1), id(2)].into());
+    let (_, first) = db.page_query(query.clone(), None, 3).expect("ok");
+    assert_eq!(
+        first
+            .items
+            .iter()
+            .map(|(t, _)| t.clone())
+            .collect::<Vec<_>>(),
+        [triple(1, 10, 1), triple(1, 10, 3), triple(1, 11, 2)]
+    );
+    db.insert_edge(triple(1, 10, 5), "".to_string())
+        .expect("ok");
+    let (_, second) = db.page_query(query, first.next.as_ref(), 2).expect("ok");
+    assert_eq!(
+        second
+            .items
+            .iter()
+            .map(|(t, _)| t.clone())
+            .collect::<Vec<_>>(),
+        [triple(1, 11, 4), triple(2, 10, 2)]
+    );
+}
@@ -0,0 +1,164 @@
+use std::collections::HashSet;
+
+use ulid::Ulid;
+
+use crate::{path::PropertyPath, prelude::*, traits::IdType, Triple};
+
+// a -part_of-> b -part_of-> c -part_of-> a  (a cycle)
+// c -part_of-> d
+// e -owns-> a
+fn build_graph<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(mut db: T) -> T {
+    let part_of = Ulid(10);
+    let owns = Ulid(11);
+    for (sub, pred, obj) in [
+        (Ulid(1), part_of, Ulid(2)),
+        (Ulid(2), part_of, Ulid(3)),
+        (Ulid(3), part_of, Ulid(1)),
+        (Ulid(3), part_of, Ulid(4)),
+        (Ulid(5), owns, Ulid(1)),
+    ] {
+        db.insert_edge(
+            Triple {
+                sub: sub.into(),
+                pred: pred.into(),
+                obj: obj.into(),
+            },
+            "".to_string(),
+        )
+        .expect("ok");
+    }
+    db
+}
+
+fn pairs<Id: IdType + From<Ulid>>(items: &[(u128, u128)]) -> HashSet<(Id, Id)> {
+    items
+        .iter()
+        .map(|(sub, obj)| (Ulid(*sub).into(), Ulid(*obj).into()))
+        .collect()
+}
+
+pub(crate) fn test_run_path<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(db: T) {
+    let db = build_graph(db);
+    let id = |n: u128| Id::from(Ulid(n));
+    let part_of = id(10);
+    let owns = id(11);
+
+    // A single predicate is the same as matching triples.
+    assert_eq!(
+        db.run_path(Some(id(3)), &PropertyPath::pred(part_of.clone()), None)
+            .expect("ok"),
+        pairs(&[(3, 1), (3, 4)])
+    );
+
+    // Transitive closure terminates on the cycle, and includes the start only because it is on the cycle.
+    let plus = query! { path { [part_of.clone()]+ } };
+    assert_eq!(
+        db.run_path(Some(id(1)), &plus, None).expect("ok"),
+        pairs(&[(1, 1), (1, 2), (1, 3), (1, 4)])
+    );
+    assert_eq!(
+        db.run_path(Some(id(4)), &plus, None).expect("ok"),
+        pairs(&[])
+    );
+    assert_eq!(
+        db.run_path(None, &plus, Some(id(4))).expect("ok"),
+        pairs(&[(1, 4), (2, 4), (3, 4)])
+    );
+    assert_eq!(
+        db.run_path(Some(id(2)), &plus, Some(id(4))).expect("ok"),
+        pairs(&[(2, 4)])
+    );
+    assert_eq!(
+        db.run_path(Some(id(4)), &plus, Some(id(2))).expect("ok"),
+        pairs(&[])
+    );
+    assert_eq!(
+        db.run_path(None, &plus, None).expect("ok"),
+        pairs(&[
+            (1, 1),
+            (1, 2),
+            (1, 3),
+            (1, 4),
+            (2, 1),
+            (2, 2),
+            (2, 3),
+            (2, 4),
+            (3, 1),
+            (3, 2),
+            (3, 3),
+            (3, 4),
+        ])
+    );
+
+    // Zero length paths connect a node to itself.
+    let star = query! { path { [part_of.clone()]* } };
+    assert_eq!(
+        db.run_path(Some(id(4)), &star, None).expect("ok"),
+        pairs(&[(4, 4)])
+    );
+    assert_eq!(
+        db.run_path(None, &star, Some(id(5))).expect("ok"),
+        pairs(&[(5, 5)])
+    );
+    assert_eq!(
+        db.run_path(None, &star, None)
+            .expect("ok")
+            .into_iter()
+            .filter(|(sub, obj)| sub == obj)
+            .count(),
+        5
+    );
+    assert_eq!(
+        db.run_path(Some(id(1)), &query! { path { [part_of.clone()]? } }, None)
+            .expect("ok"),
+        pairs(&[(1, 1), (1, 2)])
+    );
+
+    // Inverse, sequence and alternatives.
+    assert_eq!(
+        db.run_path(Some(id(1)), &query! { path { ^[part_of.clone()] } }, None)
+            .expect("ok"),
+        pairs(&[(1, 3)])
+    );
+    assert_eq!(
+        db.run_path(
+            Some(id(5)),
+            &query! { path { [owns.clone()] / [part_of.clone()] / [part_of.clone()] } },
+            None
+        )
+        .expect("ok"),
+        pairs(&[(5, 3)])
+    );
+    assert_eq!(
+        db.run_path(
+            None,
+            &query! { path { [owns.clone()] / [part_of.clone()]* } },
+            Some(id(4))
+        )
+        .expect("ok"),
+        pairs(&[(5, 4)])
+    );
+    assert_eq!(
+        db.run_path(
+            None,
+            &query! { path { [owns.clone()] / [part_of.clone()] } },
+            None
+        )
+        .expect("ok"),
+        pairs(&[(5, 2)])
+    );
+    assert_eq!(
+        db.run_path(
+            Some(id(1)),
+            &query! { path { ^([owns.clone()] | [part_of.clone()]) } },
+            None
+        )
+        .expect("ok"),
+        pairs(&[(1, 3), (1, 5)])
+    );
+    assert_eq!(
+        db.run_path(None, &query! { path { ^[owns.clone()] } }, None)
+            .expect("ok"),
+        pairs(&[(1, 5)])
+    );
+}
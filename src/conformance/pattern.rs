@@ -0,0 +1,201 @@
+use std::collections::HashSet;
+
+use ulid::Ulid;
+
+use crate::{
+    pattern::{Bindings, Pattern, Term},
+    prelude::*,
+    traits::IdType,
+    Triple,
+};
+
+#[derive(Clone)]
+struct Config {
+    alice: Ulid,
+    bob: Ulid,
+    carol: Ulid,
+    acme: Ulid,
+    initech: Ulid,
+    knows: Ulid,
+    works_at: Ulid,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        let mut gen = ulid::Generator::new();
+        Self {
+            alice: gen.generate().unwrap(),
+            bob: gen.generate().unwrap(),
+            carol: gen.generate().unwrap(),
+            acme: gen.generate().unwrap(),
+            initech: gen.generate().unwrap(),
+            knows: gen.generate().unwrap(),
+            works_at: gen.generate().unwrap(),
+        }
+    }
+}
+
+impl Config {
+    fn triples<Id: IdType + From<Ulid>>(&self) -> Vec<Triple<Id>> {
+        [
+            (self.alice, self.knows, self.bob),
+            (self.alice, self.knows, self.carol),
+            (self.bob, self.knows, self.carol),
+            (self.carol, self.knows, self.carol),
+            (self.bob, self.works_at, self.acme),
+            (self.carol, self.works_at, self.acme),
+            (self.alice, self.works_at, self.initech),
+        ]
+        .into_iter()
+        .map(|(sub, pred, obj)| Triple {
+            sub: sub.into(),
+            pred: pred.into(),
+            obj: obj.into(),
+        })
+        .collect()
+    }
+}
+
+fn build_graph<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(
+    mut db: T,
+    config: &Config,
+) -> T {
+    for triple in config.triples::<Id>() {
+        db.insert_edge(triple, "".to_string()).expect("ok");
+    }
+    db
+}
+
+fn bindings<Id: IdType + From<Ulid>>(items: &[(&str, Ulid)]) -> Bindings<Id> {
+    items
+        .iter()
+        .map(|(name, id)| (name.to_string(), Id::from(*id)))
+        .collect()
+}
+
+fn assert_solutions<Id: IdType>(actual: Vec<Bindings<Id>>, expected: Vec<Bindings<Id>>) {
+    assert_eq!(actual.len(), expected.len(), "{:?}", actual);
+    for solution in expected {
+        assert!(
+            actual.contains(&solution),
+            "{:?} not in {:?}",
+            solution,
+            actual
+        );
+    }
+}
+
+pub(crate) fn test_match_triples<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(
+    db: T,
+) {
+    let config = Config::default();
+    let db = build_graph(db, &config);
+    let all = config.triples::<Id>();
+
+    let id = |u: Ulid| Some(Id::from(u));
+    let lookups = [
+        (id(config.alice), id(config.knows), id(config.bob)),
+        (id(config.alice), id(config.knows), id(config.acme)),
+        (id(config.alice), id(config.knows), None),
+        (id(config.carol), None, id(config.carol)),
+        (id(config.bob), None, None),
+        (None, id(config.works_at), id(config.acme)),
+        (None, id(config.works_at), None),
+        (None, None, id(config.carol)),
+        (None, None, None),
+    ];
+
+    for (sub, pred, obj) in lookups {
+        let expected = all
+            .iter()
+            .filter(|t| {
+                sub.as_ref().is_none_or(|s| *s == t.sub)
+                    && pred.as_ref().is_none_or(|p| *p == t.pred)
+                    && obj.as_ref().is_none_or(|o| *o == t.obj)
+            })
+            .cloned()
+            .collect::<HashSet<_>>();
+
+        let actual = db
+            .match_triples(sub.clone(), pred.clone(), obj.clone())
+            .map(|r| r.expect("ok"))
+            .collect::<Vec<_>>();
+
+        assert_eq!(actual.len(), expected.len());
+        assert_eq!(actual.into_iter().collect::<HashSet<_>>(), expected);
+    }
+}
+
+pub(crate) fn test_run_pattern<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(db: T) {
+    let config = Config::default();
+    let db = build_graph(db, &config);
+
+    let knows = Id::from(config.knows);
+    let works_at = Id::from(config.works_at);
+    let acme = Id::from(config.acme);
+
+    // An empty pattern has a single empty solution.
+    assert_solutions(
+        db.run_pattern(&Pattern::new()).expect("ok"),
+        vec![Bindings::new()],
+    );
+
+    // Who knows someone that works at acme?
+    assert_solutions(
+        db.run_pattern(&query! { pattern { ?x -[knows.clone()]-> ?y . ?y -[works_at.clone()]-> [acme.clone()] } })
+            .expect("ok"),
+        vec![
+            bindings(&[("x", config.alice), ("y", config.bob)]),
+            bindings(&[("x", config.alice), ("y", config.carol)]),
+            bindings(&[("x", config.bob), ("y", config.carol)]),
+            bindings(&[("x", config.carol), ("y", config.carol)]),
+        ],
+    );
+
+    // A repeated variable within a triple only matches self loops.
+    assert_solutions(
+        db.run_pattern(&query! { pattern { ?x -[knows.clone()]-> ?x } })
+            .expect("ok"),
+        vec![bindings(&[("x", config.carol)])],
+    );
+
+    // Variables may appear as predicates.
+    assert_solutions(
+        db.run_pattern(&Pattern::new().with(
+            Id::from(config.alice),
+            Term::var("p"),
+            Id::from(config.initech),
+        ))
+        .expect("ok"),
+        vec![bindings(&[("p", config.works_at)])],
+    );
+
+    // Colleagues who know each other.
+    assert_solutions(
+        db.run_pattern(&query! { pattern {
+            ?x -[knows.clone()]-> ?y .
+            ?x -[works_at.clone()]-> ?company .
+            ?y -[works_at.clone()]-> ?company
+        } })
+        .expect("ok"),
+        vec![
+            bindings(&[
+                ("x", config.bob),
+                ("y", config.carol),
+                ("company", config.acme),
+            ]),
+            bindings(&[
+                ("x", config.carol),
+                ("y", config.carol),
+                ("company", config.acme),
+            ]),
+        ],
+    );
+
+    // Nothing matches.
+    assert_solutions(
+        db.run_pattern(&query! { pattern { [acme.clone()] -?p-> ?x . ?x -[knows.clone()]-> ?y } })
+            .expect("ok"),
+        vec![],
+    );
+}
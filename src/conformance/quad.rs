@@ -0,0 +1,246 @@
+use std::collections::HashSet;
+
+use ulid::Ulid;
+
+use crate::{prelude::*, traits::IdType, Quad, QuadOrder, Query, Triple};
+
+fn triple<Id: IdType + From<Ulid>>(sub: u128, pred: u128, obj: u128) -> Triple<Id> {
+    Triple {
+        sub: Ulid(sub).into(),
+        pred: Ulid(pred).into(),
+        obj: Ulid(obj).into(),
+    }
+}
+
+fn quad<Id: IdType + From<Ulid>>(sub: u128, pred: u128, obj: u128, graph: u128) -> Quad<Id> {
+    Quad::new(triple(sub, pred, obj), Ulid(graph).into())
+}
+
+fn quads<Id: IdType, T: QuadStore<Id, String, String>>(
+    db: &T,
+    order: QuadOrder,
+) -> Vec<(Quad<Id>, String)> {
+    db.iter_quads(order).map(|r| r.expect("ok")).collect()
+}
+
+fn query<Id: IdType + From<Ulid>, T: QuadStore<Id, String, String>>(
+    db: &T,
+    graphs: &[u128],
+    query: Query<Id>,
+) -> HashSet<Quad<Id>> {
+    db.iter_query_graphs(graphs.iter().map(|graph| Ulid(*graph).into()), query)
+        .map(|r| r.expect("ok").0)
+        .collect()
+}
+
+pub(crate) fn test_quads<Id: IdType + From<Ulid>, T: QuadStore<Id, String, String>>(mut db: T) {
+    let id = |n: u128| Id::from(Ulid(n));
+
+    db.insert_node(id(1), "a".to_string()).expect("ok");
+    db.insert_edge(triple(1, 10, 2), "default".to_string())
+        .expect("ok");
+    db.insert_quad(quad(1, 10, 2, 100), "first".to_string())
+        .expect("ok");
+    db.insert_quad(quad(2, 11, 1, 100), "second".to_string())
+        .expect("ok");
+    db.insert_quad(quad(1, 10, 2, 200), "other".to_string())
+        .expect("ok");
+
+    // Inserting again replaces the props.
+    db.insert_quad(quad(1, 10, 2, 100), "first again".to_string())
+        .expect("ok");
+
+    // Named graphs are kept apart from each other and from the default graph.
+    assert_eq!(
+        db.get_quad(&quad(1, 10, 2, 100)).expect("ok"),
+        Some("first again".to_string())
+    );
+    assert_eq!(
+        db.get_quad(&quad(1, 10, 2, 200)).expect("ok"),
+        Some("other".to_string())
+    );
+    assert_eq!(db.get_quad(&quad(1, 10, 2, 300)).expect("ok"), None);
+    assert_eq!(
+        db.get_edge(&triple(1, 10, 2)).expect("ok"),
+        Some("default".to_string())
+    );
+    assert_eq!(db.iter_edges(Default::default()).count(), 1);
+
+    assert_eq!(
+        db.iter_graphs().map(|r| r.expect("ok")).collect::<Vec<_>>(),
+        [id(100), id(200)]
+    );
+
+    // Each ordering groups by graph first.
+    assert_eq!(
+        quads(&db, QuadOrder::GSPO),
+        [
+            (quad(1, 10, 2, 100), "first again".to_string()),
+            (quad(2, 11, 1, 100), "second".to_string()),
+            (quad(1, 10, 2, 200), "other".to_string()),
+        ]
+    );
+    assert_eq!(
+        quads(&db, QuadOrder::GPOS),
+        [
+            (quad(1, 10, 2, 100), "first again".to_string()),
+            (quad(2, 11, 1, 100), "second".to_string()),
+            (quad(1, 10, 2, 200), "other".to_string()),
+        ]
+    );
+    assert_eq!(
+        quads(&db, QuadOrder::GOSP),
+        [
+            (quad(2, 11, 1, 100), "second".to_string()),
+            (quad(1, 10, 2, 100), "first again".to_string()),
+            (quad(1, 10, 2, 200), "other".to_string()),
+        ]
+    );
+
+    // Removing a quad only affects its own graph.
+    db.remove_quad(quad(1, 10, 2, 200)).expect("ok");
+    db.remove_quad(quad(1, 10, 2, 300)).expect("ok");
+    assert_eq!(db.get_quad(&quad(1, 10, 2, 200)).expect("ok"), None);
+    assert_eq!(
+        db.iter_graphs().map(|r| r.expect("ok")).collect::<Vec<_>>(),
+        [id(100)]
+    );
+    assert_eq!(
+        db.get_edge(&triple(1, 10, 2)).expect("ok"),
+        Some("default".to_string())
+    );
+
+    // Removing a vertex removes its edges from every graph.
+    db.insert_quad(quad(3, 10, 3, 200), "loop".to_string())
+        .expect("ok");
+    db.remove_node(id(1)).expect("ok");
+    assert_eq!(
+        quads(&db, QuadOrder::GSPO),
+        [(quad(3, 10, 3, 200), "loop".to_string())]
+    );
+    assert_eq!(db.iter_edges(Default::default()).count(), 0);
+
+    db.remove_node(id(3)).expect("ok");
+    assert_eq!(quads(&db, QuadOrder::GSPO), []);
+    assert_eq!(db.iter_graphs().count(), 0);
+}
+
+pub(crate) fn test_query_graphs<Id: IdType + From<Ulid>, T: QuadStore<Id, String, String>>(
+    mut db: T,
+) {
+    let id = |n: u128| Id::from(Ulid(n));
+
+    for graph in [100, 200, 300] {
+        db.insert_quad(quad(1, 10, 2, graph), format!("{graph}"))
+            .expect("ok");
+        db.insert_quad(quad(2, 11, 3, graph), format!("{graph}"))
+            .expect("ok");
+    }
+    db.insert_quad(quad(1, 11, 3, 100), "100".to_string())
+        .expect("ok");
+    db.insert_edge(triple(1, 10, 2), "default".to_string())
+        .expect("ok");
+
+    assert_eq!(
+        query(&db, &[100], Query::S([id(1)].into())),
+        [quad(1, 10, 2, 100), quad(1, 11, 3, 100)].into()
+    );
+    assert_eq!(
+        query(&db, &[100, 200], Query::P([id(10)].into())),
+        [quad(1, 10, 2, 100), quad(1, 10, 2, 200)].into()
+    );
+    assert_eq!(
+        query(&db, &[300], Query::O([id(3)].into())),
+        [quad(2, 11, 3, 300)].into()
+    );
+    assert_eq!(
+        query(&db, &[100, 300], Query::SP([(id(1), id(11))].into())),
+        [quad(1, 11, 3, 100)].into()
+    );
+    assert_eq!(
+        query(&db, &[200], Query::PO([(id(11), id(3))].into())),
+        [quad(2, 11, 3, 200)].into()
+    );
+    assert_eq!(
+        query(&db, &[100, 200], Query::SO([(id(1), id(3))].into())),
+        [quad(1, 11, 3, 100)].into()
+    );
+    assert_eq!(
+        query(
+            &db,
+            &[100, 200, 300],
+            Query::SPO([(id(2), id(11), id(3)), (id(1), id(11), id(3))].into())
+        ),
+        [
+            quad(2, 11, 3, 100),
+            quad(2, 11, 3, 200),
+            quad(2, 11, 3, 300),
+            quad(1, 11, 3, 100)
+        ]
+        .into()
+    );
+
+    // Repeated and unknown graphs are fine, and vertices are not scoped to graphs.
+    assert_eq!(
+        query(&db, &[200, 200, 400], Query::S([id(1)].into())),
+        [quad(1, 10, 2, 200)].into()
+    );
+    assert_eq!(query(&db, &[], Query::S([id(1)].into())), HashSet::new());
+    assert_eq!(
+        query(&db, &[100], Query::NodeProps([id(1)].into())),
+        HashSet::new()
+    );
+
+    // Results carry their props.
+    assert_eq!(
+        db.iter_query_graphs([id(300)], Query::P([id(10)].into()))
+            .map(|r| r.expect("ok"))
+            .collect::<Vec<_>>(),
+        [(quad(1, 10, 2, 300), "300".to_string())]
+    );
+}
+
+pub(crate) fn test_drop_graph<Id: IdType + From<Ulid>, T: QuadStore<Id, String, String>>(
+    mut db: T,
+) {
+    let id = |n: u128| Id::from(Ulid(n));
+
+    db.insert_node(id(1), "a".to_string()).expect("ok");
+    db.insert_edge(triple(1, 10, 2), "default".to_string())
+        .expect("ok");
+    for graph in [100, 200, 300] {
+        db.insert_quad(quad(1, 10, 2, graph), format!("{graph}"))
+            .expect("ok");
+        db.insert_quad(quad(2, 10, 1, graph), format!("{graph}"))
+            .expect("ok");
+    }
+
+    db.drop_graph(&id(200)).expect("ok");
+    assert_eq!(
+        db.iter_graphs().map(|r| r.expect("ok")).collect::<Vec<_>>(),
+        [id(100), id(300)]
+    );
+    assert_eq!(query(&db, &[200], Query::S([id(1)].into())), HashSet::new());
+    assert_eq!(query(&db, &[100, 300], Query::P([id(10)].into())).len(), 4);
+
+    // Vertices and the default graph are untouched, and dropping again does nothing.
+    db.drop_graph(&id(200)).expect("ok");
+    assert_eq!(db.get_node(&id(1)).expect("ok"), Some("a".to_string()));
+    assert_eq!(
+        db.get_edge(&triple(1, 10, 2)).expect("ok"),
+        Some("default".to_string())
+    );
+
+    // A dropped graph can be filled again.
+    db.insert_quad(quad(1, 10, 2, 200), "again".to_string())
+        .expect("ok");
+    assert_eq!(
+        db.get_quad(&quad(1, 10, 2, 200)).expect("ok"),
+        Some("again".to_string())
+    );
+
+    db.drop_graph(&id(100)).expect("ok");
+    db.drop_graph(&id(200)).expect("ok");
+    db.drop_graph(&id(300)).expect("ok");
+    assert_eq!(db.iter_quads(QuadOrder::GSPO).count(), 0);
+}
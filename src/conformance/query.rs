@@ -2,7 +2,7 @@ use std::collections::HashSet;
 
 use ulid::Ulid;
 
-use crate::{prelude::*, Query, Triple};
+use crate::{prelude::*, traits::IdType, PropsFilter, Query, Triple};
 
 #[derive(Clone)]
 struct Config {
@@ -62,13 +62,16 @@ impl Default for Config {
     }
 }
 
-fn build_graph<T: TripleStore<Ulid, String, String>>(mut db: T, config: Config) -> T {
+fn build_graph<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(
+    mut db: T,
+    config: Config,
+) -> T {
     for (node, props) in [
-        (config.node_0, config.node_props_0),
-        (config.node_1, config.node_props_1),
-        (config.node_2, config.node_props_2),
-        (config.node_3, config.node_props_3),
-        (config.node_4, config.node_props_4),
+        (Id::from(config.node_0), config.node_props_0),
+        (Id::from(config.node_1), config.node_props_1),
+        (Id::from(config.node_2), config.node_props_2),
+        (Id::from(config.node_3), config.node_props_3),
+        (Id::from(config.node_4), config.node_props_4),
     ] {
         db.insert_node(node, props).expect("success");
     }
@@ -76,41 +79,41 @@ fn build_graph<T: TripleStore<Ulid, String, String>>(mut db: T, config: Config)
     for (triple, props) in [
         (
             Triple {
-                sub: config.node_0,
-                pred: config.edge_1,
-                obj: config.node_1,
+                sub: Id::from(config.node_0),
+                pred: Id::from(config.edge_1),
+                obj: Id::from(config.node_1),
             },
             config.edge_0_1_props,
         ),
         (
             Triple {
-                sub: config.node_0,
-                pred: config.edge_2,
-                obj: config.node_2,
+                sub: Id::from(config.node_0),
+                pred: Id::from(config.edge_2),
+                obj: Id::from(config.node_2),
             },
             config.edge_0_2_props,
         ),
         (
             Triple {
-                sub: config.node_1,
-                pred: config.edge_1,
-                obj: config.node_2,
+                sub: Id::from(config.node_1),
+                pred: Id::from(config.edge_1),
+                obj: Id::from(config.node_2),
             },
             config.edge_props_1,
         ),
         (
             Triple {
-                sub: config.node_2,
-                pred: config.edge_2,
-                obj: config.node_4,
+                sub: Id::from(config.node_2),
+                pred: Id::from(config.edge_2),
+                obj: Id::from(config.node_4),
             },
             config.edge_props_2,
         ),
         (
             Triple {
-                sub: config.node_3,
-                pred: config.edge_2,
-                obj: config.node_4,
+                sub: Id::from(config.node_3),
+                pred: Id::from(config.edge_2),
+                obj: Id::from(config.node_4),
             },
             config.edge_props_3,
         ),
@@ -121,13 +124,17 @@ fn build_graph<T: TripleStore<Ulid, String, String>>(mut db: T, config: Config)
     db
 }
 
-pub(crate) fn test_query_node_props<T: TripleStore<Ulid, String, String>>(db: T) {
+pub(crate) fn test_query_node_props<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(
+    db: T,
+) {
     let config = Config::default();
 
     let graph = build_graph(db, config.clone());
 
     let query = graph
-        .run(Query::NodeProps([config.node_1, config.node_2].into()))
+        .run(Query::NodeProps(
+            [Id::from(config.node_1), Id::from(config.node_2)].into(),
+        ))
         .expect("ok");
 
     assert_eq!(
@@ -136,8 +143,8 @@ pub(crate) fn test_query_node_props<T: TripleStore<Ulid, String, String>>(db: T)
             .map(|r| r.expect("ok"))
             .collect::<HashSet<_>>(),
         [
-            (config.node_1, config.node_props_1),
-            (config.node_2, config.node_props_2)
+            (Id::from(config.node_1), config.node_props_1),
+            (Id::from(config.node_2), config.node_props_2)
         ]
         .into()
     );
@@ -150,7 +157,9 @@ pub(crate) fn test_query_node_props<T: TripleStore<Ulid, String, String>>(db: T)
     );
 }
 
-pub(crate) fn test_query_edge_props<T: TripleStore<Ulid, String, String>>(db: T) {
+pub(crate) fn test_query_edge_props<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(
+    db: T,
+) {
     let config = Config::default();
 
     let graph = build_graph(db, config.clone());
@@ -158,8 +167,16 @@ pub(crate) fn test_query_edge_props<T: TripleStore<Ulid, String, String>>(db: T)
     let query = graph
         .run(Query::SPO(
             [
-                (config.node_1, config.edge_1, config.node_2),
-                (config.node_2, config.edge_2, config.node_4),
+                (
+                    Id::from(config.node_1),
+                    Id::from(config.edge_1),
+                    Id::from(config.node_2),
+                ),
+                (
+                    Id::from(config.node_2),
+                    Id::from(config.edge_2),
+                    Id::from(config.node_4),
+                ),
             ]
             .into(),
         ))
@@ -173,17 +190,17 @@ pub(crate) fn test_query_edge_props<T: TripleStore<Ulid, String, String>>(db: T)
         [
             (
                 Triple {
-                    sub: config.node_1,
-                    pred: config.edge_1,
-                    obj: config.node_2,
+                    sub: Id::from(config.node_1),
+                    pred: Id::from(config.edge_1),
+                    obj: Id::from(config.node_2),
                 },
                 config.edge_props_1
             ),
             (
                 Triple {
-                    sub: config.node_2,
-                    pred: config.edge_2,
-                    obj: config.node_4,
+                    sub: Id::from(config.node_2),
+                    pred: Id::from(config.edge_2),
+                    obj: Id::from(config.node_4),
                 },
                 config.edge_props_2
             )
@@ -193,13 +210,15 @@ pub(crate) fn test_query_edge_props<T: TripleStore<Ulid, String, String>>(db: T)
     assert_eq!(query.iter_vertices().collect::<Vec<_>>().len(), 0);
 }
 
-pub(crate) fn test_query_s<T: TripleStore<Ulid, String, String>>(db: T) {
+pub(crate) fn test_query_s<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(db: T) {
     let config = Config::default();
 
     let graph = build_graph(db, config.clone());
 
     let query = graph
-        .run(Query::S([config.node_1, config.node_3].into()))
+        .run(Query::S(
+            [Id::from(config.node_1), Id::from(config.node_3)].into(),
+        ))
         .expect("ok");
 
     assert_eq!(
@@ -210,17 +229,17 @@ pub(crate) fn test_query_s<T: TripleStore<Ulid, String, String>>(db: T) {
         [
             (
                 Triple {
-                    sub: config.node_1,
-                    pred: config.edge_1,
-                    obj: config.node_2,
+                    sub: Id::from(config.node_1),
+                    pred: Id::from(config.edge_1),
+                    obj: Id::from(config.node_2),
                 },
                 config.edge_props_1
             ),
             (
                 Triple {
-                    sub: config.node_3,
-                    pred: config.edge_2,
-                    obj: config.node_4,
+                    sub: Id::from(config.node_3),
+                    pred: Id::from(config.edge_2),
+                    obj: Id::from(config.node_4),
                 },
                 config.edge_props_3
             )
@@ -231,13 +250,15 @@ pub(crate) fn test_query_s<T: TripleStore<Ulid, String, String>>(db: T) {
     assert_eq!(query.iter_vertices().collect::<Vec<_>>().len(), 0);
 }
 
-pub(crate) fn test_query_sp<T: TripleStore<Ulid, String, String>>(db: T) {
+pub(crate) fn test_query_sp<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(db: T) {
     let config = Config::default();
 
     let graph = build_graph(db, config.clone());
 
     let query = graph
-        .run(Query::SP([(config.node_0, config.edge_1)].into()))
+        .run(Query::SP(
+            [(Id::from(config.node_0), Id::from(config.edge_1))].into(),
+        ))
         .expect("ok");
 
     assert_eq!(
@@ -247,9 +268,9 @@ pub(crate) fn test_query_sp<T: TripleStore<Ulid, String, String>>(db: T) {
             .collect::<HashSet<_>>(),
         [(
             Triple {
-                sub: config.node_0,
-                pred: config.edge_1,
-                obj: config.node_1,
+                sub: Id::from(config.node_0),
+                pred: Id::from(config.edge_1),
+                obj: Id::from(config.node_1),
             },
             config.edge_0_1_props
         ),]
@@ -259,12 +280,14 @@ pub(crate) fn test_query_sp<T: TripleStore<Ulid, String, String>>(db: T) {
     assert_eq!(query.iter_vertices().collect::<Vec<_>>().len(), 0);
 }
 
-pub(crate) fn test_query_p<T: TripleStore<Ulid, String, String>>(db: T) {
+pub(crate) fn test_query_p<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(db: T) {
     let config = Config::default();
 
     let graph = build_graph(db, config.clone());
 
-    let query = graph.run(Query::P([config.edge_1].into())).expect("ok");
+    let query = graph
+        .run(Query::P([Id::from(config.edge_1)].into()))
+        .expect("ok");
 
     assert_eq!(
         query
@@ -274,17 +297,17 @@ pub(crate) fn test_query_p<T: TripleStore<Ulid, String, String>>(db: T) {
         [
             (
                 Triple {
-                    sub: config.node_0,
-                    pred: config.edge_1,
-                    obj: config.node_1,
+                    sub: Id::from(config.node_0),
+                    pred: Id::from(config.edge_1),
+                    obj: Id::from(config.node_1),
                 },
                 config.edge_0_1_props,
             ),
             (
                 Triple {
-                    sub: config.node_1,
-                    pred: config.edge_1,
-                    obj: config.node_2,
+                    sub: Id::from(config.node_1),
+                    pred: Id::from(config.edge_1),
+                    obj: Id::from(config.node_2),
                 },
                 config.edge_props_1,
             )
@@ -295,13 +318,15 @@ pub(crate) fn test_query_p<T: TripleStore<Ulid, String, String>>(db: T) {
     assert_eq!(query.iter_vertices().collect::<Vec<_>>().len(), 0);
 }
 
-pub(crate) fn test_query_po<T: TripleStore<Ulid, String, String>>(db: T) {
+pub(crate) fn test_query_po<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(db: T) {
     let config = Config::default();
 
     let graph = build_graph(db, config.clone());
 
     let query = graph
-        .run(Query::PO([(config.edge_1, config.node_2)].into()))
+        .run(Query::PO(
+            [(Id::from(config.edge_1), Id::from(config.node_2))].into(),
+        ))
         .expect("ok");
 
     assert_eq!(
@@ -311,9 +336,9 @@ pub(crate) fn test_query_po<T: TripleStore<Ulid, String, String>>(db: T) {
             .collect::<HashSet<_>>(),
         [(
             Triple {
-                sub: config.node_1,
-                pred: config.edge_1,
-                obj: config.node_2,
+                sub: Id::from(config.node_1),
+                pred: Id::from(config.edge_1),
+                obj: Id::from(config.node_2),
             },
             config.edge_props_1,
         )]
@@ -323,12 +348,14 @@ pub(crate) fn test_query_po<T: TripleStore<Ulid, String, String>>(db: T) {
     assert_eq!(query.iter_vertices().collect::<Vec<_>>().len(), 0);
 }
 
-pub(crate) fn test_query_o<T: TripleStore<Ulid, String, String>>(db: T) {
+pub(crate) fn test_query_o<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(db: T) {
     let config = Config::default();
 
     let graph = build_graph(db, config.clone());
 
-    let query = graph.run(Query::O([config.node_4].into())).expect("ok");
+    let query = graph
+        .run(Query::O([Id::from(config.node_4)].into()))
+        .expect("ok");
 
     assert_eq!(
         query
@@ -338,17 +365,17 @@ pub(crate) fn test_query_o<T: TripleStore<Ulid, String, String>>(db: T) {
         [
             (
                 Triple {
-                    sub: config.node_2,
-                    pred: config.edge_2,
-                    obj: config.node_4,
+                    sub: Id::from(config.node_2),
+                    pred: Id::from(config.edge_2),
+                    obj: Id::from(config.node_4),
                 },
                 config.edge_props_2,
             ),
             (
                 Triple {
-                    sub: config.node_3,
-                    pred: config.edge_2,
-                    obj: config.node_4,
+                    sub: Id::from(config.node_3),
+                    pred: Id::from(config.edge_2),
+                    obj: Id::from(config.node_4),
                 },
                 config.edge_props_3,
             )
@@ -359,13 +386,15 @@ pub(crate) fn test_query_o<T: TripleStore<Ulid, String, String>>(db: T) {
     assert_eq!(query.iter_vertices().collect::<Vec<_>>().len(), 0);
 }
 
-pub(crate) fn test_query_os<T: TripleStore<Ulid, String, String>>(db: T) {
+pub(crate) fn test_query_os<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(db: T) {
     let config = Config::default();
 
     let graph = build_graph(db, config.clone());
 
     let query = graph
-        .run(Query::SO([(config.node_2, config.node_4)].into()))
+        .run(Query::SO(
+            [(Id::from(config.node_2), Id::from(config.node_4))].into(),
+        ))
         .expect("ok");
 
     assert_eq!(
@@ -375,9 +404,9 @@ pub(crate) fn test_query_os<T: TripleStore<Ulid, String, String>>(db: T) {
             .collect::<HashSet<_>>(),
         [(
             Triple {
-                sub: config.node_2,
-                pred: config.edge_2,
-                obj: config.node_4,
+                sub: Id::from(config.node_2),
+                pred: Id::from(config.edge_2),
+                obj: Id::from(config.node_4),
             },
             config.edge_props_2,
         )]
@@ -386,3 +415,165 @@ pub(crate) fn test_query_os<T: TripleStore<Ulid, String, String>>(db: T) {
 
     assert_eq!(query.iter_vertices().collect::<Vec<_>>().len(), 0);
 }
+
+pub(crate) fn test_iter_query<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(db: T) {
+    let config = Config::default();
+
+    let graph = build_graph(db, config.clone());
+
+    let node = |u: Ulid| Id::from(u);
+    let queries = [
+        Query::NodeProps([node(config.node_0), node(config.node_3)].into()),
+        Query::SPO(
+            [
+                (
+                    node(config.node_0),
+                    node(config.edge_1),
+                    node(config.node_1),
+                ),
+                (
+                    node(config.node_0),
+                    node(config.edge_1),
+                    node(config.node_4),
+                ),
+            ]
+            .into(),
+        ),
+        Query::S([node(config.node_1), node(config.node_3)].into()),
+        Query::SP([(node(config.node_0), node(config.edge_2))].into()),
+        Query::SO([(node(config.node_0), node(config.node_1))].into()),
+        Query::P([node(config.edge_2)].into()),
+        Query::PO([(node(config.edge_2), node(config.node_4))].into()),
+        Query::O([node(config.node_2), node(config.node_4)].into()),
+    ];
+
+    for query in queries {
+        let node_query = matches!(query, Query::NodeProps(_));
+        let expected = graph.run(query.clone()).expect("ok");
+
+        let (nodes, edges) = graph.iter_query(query);
+        let nodes = nodes.map(|r| r.expect("ok")).collect::<HashSet<_>>();
+        let edges = edges.map(|r| r.expect("ok")).collect::<HashSet<_>>();
+
+        assert_eq!(
+            nodes,
+            expected
+                .iter_vertices()
+                .map(|r| r.expect("ok"))
+                .collect::<HashSet<_>>()
+        );
+        assert_eq!(
+            edges,
+            expected
+                .iter_edges(crate::EdgeOrder::SPO)
+                .map(|r| r.expect("ok"))
+                .collect::<HashSet<_>>()
+        );
+
+        // Only one of the two iterators applies to each query.
+        if node_query {
+            assert_eq!(nodes.len(), 2);
+            assert!(edges.is_empty());
+        } else {
+            assert!(nodes.is_empty());
+            assert!(!edges.is_empty());
+        }
+    }
+}
+
+pub(crate) fn test_query_filtered<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(
+    db: T,
+) {
+    let config = Config::default();
+
+    let graph = build_graph(db, config.clone());
+
+    let node = |u: Ulid| Id::from(u);
+
+    // Node props filter.
+    let keep_node = |_: &Id, props: &String| props == "a" || props == "c";
+    let filter = PropsFilter::new().nodes(&keep_node);
+    let query = Query::NodeProps(
+        [
+            node(config.node_0),
+            node(config.node_1),
+            node(config.node_2),
+            node(config.node_3),
+        ]
+        .into(),
+    );
+    let expected = [
+        (node(config.node_0), config.node_props_0.clone()),
+        (node(config.node_2), config.node_props_2.clone()),
+    ]
+    .into_iter()
+    .collect::<HashSet<_>>();
+
+    let (nodes, edges) = graph.iter_query_filtered(query.clone(), filter);
+    assert_eq!(
+        nodes.map(|r| r.expect("ok")).collect::<HashSet<_>>(),
+        expected
+    );
+    assert_eq!(edges.count(), 0);
+
+    let result = graph.run_filtered(query, filter).expect("ok");
+    assert_eq!(
+        result
+            .iter_vertices()
+            .map(|r| r.expect("ok"))
+            .collect::<HashSet<_>>(),
+        expected
+    );
+
+    // Edge props filter.
+    let keep_edge = |_: &Triple<Id>, props: &String| props.ends_with("_g");
+    let filter = PropsFilter::new().edges(&keep_edge);
+    let query = Query::P([node(config.edge_2)].into());
+    let expected = [
+        (
+            Triple {
+                sub: node(config.node_2),
+                pred: node(config.edge_2),
+                obj: node(config.node_4),
+            },
+            config.edge_props_2.clone(),
+        ),
+        (
+            Triple {
+                sub: node(config.node_3),
+                pred: node(config.edge_2),
+                obj: node(config.node_4),
+            },
+            config.edge_props_3.clone(),
+        ),
+    ]
+    .into_iter()
+    .collect::<HashSet<_>>();
+
+    let (nodes, edges) = graph.iter_query_filtered(query.clone(), filter);
+    assert_eq!(nodes.count(), 0);
+    assert_eq!(
+        edges.map(|r| r.expect("ok")).collect::<HashSet<_>>(),
+        expected
+    );
+
+    let result = graph.run_filtered(query, filter).expect("ok");
+    assert_eq!(
+        result
+            .iter_edges(crate::EdgeOrder::SPO)
+            .map(|r| r.expect("ok"))
+            .collect::<HashSet<_>>(),
+        expected
+    );
+
+    // Edge filters may also look at the triple, and node filters do not apply to edge queries.
+    let sub = node(config.node_3);
+    let keep_edge = |triple: &Triple<Id>, _: &String| triple.sub == sub;
+    let keep_node = |_: &Id, _: &String| false;
+    let filter = PropsFilter::new().nodes(&keep_node).edges(&keep_edge);
+    let (_, edges) = graph.iter_query_filtered(Query::O([node(config.node_4)].into()), filter);
+    assert_eq!(
+        edges.map(|r| r.expect("ok").1).collect::<Vec<_>>(),
+        [config.edge_props_3]
+    );
+}
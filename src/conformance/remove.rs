@@ -1,6 +1,6 @@
 use ulid::Ulid;
 
-use crate::{prelude::*, EdgeOrder, PropsTriple, Triple};
+use crate::{prelude::*, traits::IdType, EdgeOrder, PropsTriple, Triple};
 
 struct Config {
     node_1: Ulid,
@@ -48,12 +48,15 @@ impl Default for Config {
     }
 }
 
-fn populate_graph<T: TripleStore<Ulid, String, String>>(config: &Config, db: &mut T) {
+fn populate_graph<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(
+    config: &Config,
+    db: &mut T,
+) {
     for (node, props) in [
-        (config.node_1, config.node_data_1.clone()),
-        (config.node_2, config.node_data_2.clone()),
-        (config.node_3, config.node_data_3.clone()),
-        (config.node_4, config.node_data_4.clone()),
+        (Id::from(config.node_1), config.node_data_1.clone()),
+        (Id::from(config.node_2), config.node_data_2.clone()),
+        (Id::from(config.node_3), config.node_data_3.clone()),
+        (Id::from(config.node_4), config.node_data_4.clone()),
     ] {
         db.insert_node(node, props).expect("insert should succeed");
     }
@@ -61,25 +64,25 @@ fn populate_graph<T: TripleStore<Ulid, String, String>>(config: &Config, db: &mu
     for (triple, props) in [
         (
             Triple {
-                sub: config.node_1,
-                pred: config.edge_1,
-                obj: config.node_2,
+                sub: Id::from(config.node_1),
+                pred: Id::from(config.edge_1),
+                obj: Id::from(config.node_2),
             },
             config.edge_data_1.clone(),
         ),
         (
             Triple {
-                sub: config.node_2,
-                pred: config.edge_2,
-                obj: config.node_3,
+                sub: Id::from(config.node_2),
+                pred: Id::from(config.edge_2),
+                obj: Id::from(config.node_3),
             },
             config.edge_data_2.clone(),
         ),
         (
             Triple {
-                sub: config.node_3,
-                pred: config.edge_3,
-                obj: config.node_4,
+                sub: Id::from(config.node_3),
+                pred: Id::from(config.edge_3),
+                obj: Id::from(config.node_4),
             },
             config.edge_data_3.clone(),
         ),
@@ -89,20 +92,22 @@ fn populate_graph<T: TripleStore<Ulid, String, String>>(config: &Config, db: &mu
     }
 }
 
-pub(crate) fn test_remove_node<T: TripleStore<Ulid, String, String>>(mut db: T) {
+pub(crate) fn test_remove_node<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(
+    mut db: T,
+) {
     let config = Config::default();
     populate_graph(&config, &mut db);
 
-    db.remove_node(config.node_1).expect("ok");
-    db.remove_node(config.node_4).expect("ok");
+    db.remove_node(Id::from(config.node_1)).expect("ok");
+    db.remove_node(Id::from(config.node_4)).expect("ok");
 
     assert_eq!(
         db.iter_vertices()
             .map(|r| r.expect("ok"))
             .collect::<Vec<_>>(),
         [
-            (config.node_2, config.node_data_2.clone()),
-            (config.node_3, config.node_data_3.clone()),
+            (Id::from(config.node_2), config.node_data_2.clone()),
+            (Id::from(config.node_3), config.node_data_3.clone()),
         ]
         .to_vec()
     );
@@ -113,9 +118,9 @@ pub(crate) fn test_remove_node<T: TripleStore<Ulid, String, String>>(mut db: T)
             .collect::<Vec<_>>(),
         [(
             Triple {
-                sub: config.node_2,
-                pred: config.edge_2,
-                obj: config.node_3,
+                sub: Id::from(config.node_2),
+                pred: Id::from(config.edge_2),
+                obj: Id::from(config.node_3),
             },
             config.edge_data_2.clone(),
         ),]
@@ -127,29 +132,31 @@ pub(crate) fn test_remove_node<T: TripleStore<Ulid, String, String>>(mut db: T)
             .map(|r| r.expect("ok"))
             .collect::<Vec<_>>(),
         [PropsTriple {
-            sub: (config.node_2, config.node_data_2),
-            pred: (config.edge_2, config.edge_data_2),
-            obj: (config.node_3, config.node_data_3)
+            sub: (Id::from(config.node_2), config.node_data_2),
+            pred: (Id::from(config.edge_2), config.edge_data_2),
+            obj: (Id::from(config.node_3), config.node_data_3)
         }]
         .to_vec()
     );
 }
 
-pub(crate) fn test_remove_edge<T: TripleStore<Ulid, String, String>>(mut db: T) {
+pub(crate) fn test_remove_edge<Id: IdType + From<Ulid>, T: TripleStore<Id, String, String>>(
+    mut db: T,
+) {
     let config = Config::default();
     populate_graph(&config, &mut db);
 
     db.remove_edge(Triple {
-        sub: config.node_1,
-        pred: config.edge_1,
-        obj: config.node_2,
+        sub: Id::from(config.node_1),
+        pred: Id::from(config.edge_1),
+        obj: Id::from(config.node_2),
     })
     .expect("ok");
 
     db.remove_edge(Triple {
-        sub: config.node_3,
-        pred: config.edge_3,
-        obj: config.node_4,
+        sub: Id::from(config.node_3),
+        pred: Id::from(config.edge_3),
+        obj: Id::from(config.node_4),
     })
     .expect("ok");
 
@@ -158,10 +165,10 @@ pub(crate) fn test_remove_edge<T: TripleStore<Ulid, String, String>>(mut db: T)
             .map(|r| r.expect("ok"))
             .collect::<Vec<_>>(),
         [
-            (config.node_1, config.node_data_1.clone()),
-            (config.node_2, config.node_data_2.clone()),
-            (config.node_3, config.node_data_3.clone()),
-            (config.node_4, config.node_data_4.clone()),
+            (Id::from(config.node_1), config.node_data_1.clone()),
+            (Id::from(config.node_2), config.node_data_2.clone()),
+            (Id::from(config.node_3), config.node_data_3.clone()),
+            (Id::from(config.node_4), config.node_data_4.clone()),
         ]
         .to_vec()
     );
@@ -172,9 +179,9 @@ pub(crate) fn test_remove_edge<T: TripleStore<Ulid, String, String>>(mut db: T)
             .collect::<Vec<_>>(),
         [(
             Triple {
-                sub: config.node_2,
-                pred: config.edge_2,
-                obj: config.node_3,
+                sub: Id::from(config.node_2),
+                pred: Id::from(config.edge_2),
+                obj: Id::from(config.node_3),
             },
             config.edge_data_2.clone(),
         )]
@@ -186,9 +193,9 @@ pub(crate) fn test_remove_edge<T: TripleStore<Ulid, String, String>>(mut db: T)
             .map(|r| r.expect("ok"))
             .collect::<Vec<_>>(),
         [PropsTriple {
-            sub: (config.node_2, config.node_data_2),
-            pred: (config.edge_2, config.edge_data_2),
-            obj: (config.node_3, config.node_data_3)
+            sub: (Id::from(config.node_2), config.node_data_2),
+            pred: (Id::from(config.edge_2), config.edge_data_2),
+            obj: (Id::from(config.node_3), config.node_data_3)
         }]
         .to_vec()
     );
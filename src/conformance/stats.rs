@@ -0,0 +1,235 @@
+use std::collections::{HashMap, HashSet};
+
+use ulid::Ulid;
+
+use crate::{
+    pattern::{Pattern, Term, TriplePattern},
+    prelude::*,
+    traits::{IdType, PredicateStats, Statistics},
+    EdgeOrder, Triple,
+};
+
+fn triple<Id: IdType + From<Ulid>>(sub: u128, pred: u128, obj: u128) -> Triple<Id> {
+    Triple {
+        sub: Ulid(sub).into(),
+        pred: Ulid(pred).into(),
+        obj: Ulid(obj).into(),
+    }
+}
+
+/// Count the statistics of `db` from scratch.
+fn recount<Id: IdType, T: TripleStore<Id, (), ()>>(db: &T) -> Statistics<Id> {
+    let mut stats = Statistics {
+        nodes: db.iter_vertices().count() as u64,
+        ..Default::default()
+    };
+
+    let mut subjects: HashMap<Id, HashSet<Id>> = HashMap::new();
+    let mut objects: HashMap<Id, HashSet<Id>> = HashMap::new();
+    for r in db.iter_edges(EdgeOrder::SPO) {
+        let (triple, _) = r.expect("ok");
+        stats.edges += 1;
+        stats
+            .predicates
+            .entry(triple.pred.clone())
+            .or_default()
+            .edges += 1;
+        subjects
+            .entry(triple.pred.clone())
+            .or_default()
+            .insert(triple.sub);
+        objects.entry(triple.pred).or_default().insert(triple.obj);
+    }
+    for (pred, predicate) in stats.predicates.iter_mut() {
+        predicate.subjects = subjects[pred].len() as u64;
+        predicate.objects = objects[pred].len() as u64;
+    }
+    stats
+}
+
+fn assert_statistics<Id: IdType, T: TripleStore<Id, (), ()>>(db: &T) {
+    assert_eq!(db.statistics().expect("ok"), recount(db));
+}
+
+pub(crate) fn test_statistics<
+    Id: IdType + From<Ulid>,
+    T: TripleStore<Id, (), ()> + TripleStoreMerge<Id, (), ()>,
+>(
+    mut db: T,
+) {
+    let id = |n: u128| Id::from(Ulid(n));
+    let stats = |edges, subjects, objects| PredicateStats {
+        edges,
+        subjects,
+        objects,
+    };
+
+    assert_eq!(db.statistics().expect("ok"), Statistics::default());
+
+    for n in 1..=3 {
+        db.insert_node(id(n), ()).expect("ok");
+    }
+    db.insert_edge(triple(1, 10, 2), ()).expect("ok");
+    db.insert_edge(triple(1, 10, 3), ()).expect("ok");
+    db.insert_edge(triple(2, 10, 3), ()).expect("ok");
+    db.insert_edge(triple(1, 11, 2), ()).expect("ok");
+
+    let statistics = db.statistics().expect("ok");
+    assert_eq!(statistics.nodes, 3);
+    assert_eq!(statistics.edges, 4);
+    assert_eq!(statistics.predicate(&id(10)), stats(3, 2, 2));
+    assert_eq!(statistics.predicate(&id(11)), stats(1, 1, 1));
+    assert_eq!(statistics.predicate(&id(12)), stats(0, 0, 0));
+    assert_statistics(&db);
+
+    // Overwriting existing nodes and edges changes nothing.
+    db.insert_node(id(1), ()).expect("ok");
+    db.insert_edge(triple(1, 10, 2), ()).expect("ok");
+    assert_eq!(db.statistics().expect("ok"), statistics);
+
+    // Merging counts new nodes and edges only.
+    db.merge_node(id(1), ()).expect("ok");
+    db.merge_node(id(4), ()).expect("ok");
+    db.merge_edge(triple(1, 11, 2), ()).expect("ok");
+    db.merge_edge(triple(2, 11, 3), ()).expect("ok");
+    let statistics = db.statistics().expect("ok");
+    assert_eq!(statistics.nodes, 4);
+    assert_eq!(statistics.predicate(&id(11)), stats(2, 2, 2));
+    assert_statistics(&db);
+
+    // A subject or object is only gone when its last edge with the predicate is.
+    db.remove_edge(triple(1, 10, 3)).expect("ok");
+    db.remove_edge(triple(3, 10, 1)).expect("ok");
+    assert_eq!(
+        db.statistics().expect("ok").predicate(&id(10)),
+        stats(2, 2, 2)
+    );
+    assert_statistics(&db);
+
+    // Removing a node removes its edges.
+    db.remove_node(id(3)).expect("ok");
+    let statistics = db.statistics().expect("ok");
+    assert_eq!(statistics.nodes, 3);
+    assert_eq!(statistics.edges, 2);
+    assert_eq!(statistics.predicate(&id(10)), stats(1, 1, 1));
+    assert_eq!(statistics.predicate(&id(11)), stats(1, 1, 1));
+    assert_statistics(&db);
+
+    // Predicates without edges are dropped.
+    db.remove_edge(triple(1, 11, 2)).expect("ok");
+    assert_eq!(
+        db.statistics()
+            .expect("ok")
+            .predicates
+            .keys()
+            .collect::<Vec<_>>(),
+        [&id(10)]
+    );
+    assert_statistics(&db);
+}
+
+pub(crate) fn test_statistics_bulk<
+    Id: IdType + From<Ulid>,
+    T: TripleStore<Id, (), ()> + TripleStoreBulkLoad<Id, (), ()> + TripleStoreTransaction<Id, (), ()>,
+>(
+    mut db: T,
+) {
+    let id = |n: u128| Id::from(Ulid(n));
+
+    db.insert_node(id(1), ()).expect("ok");
+    db.insert_edge(triple(1, 10, 2), ()).expect("ok");
+
+    db.insert_nodes((1..=5).map(|n| (id(n), ()))).expect("ok");
+    db.insert_edges([
+        (triple(1, 10, 2), ()),
+        (triple(1, 10, 3), ()),
+        (triple(2, 10, 3), ()),
+        (triple(1, 10, 3), ()),
+        (triple(4, 11, 5), ()),
+    ])
+    .expect("ok");
+    let statistics = db.statistics().expect("ok");
+    assert_eq!(statistics.nodes, 5);
+    assert_eq!(statistics.edges, 4);
+    assert_statistics(&db);
+
+    db.bulk_load([(triple(5, 11, 4), ()), (triple(5, 12, 1), ())], true)
+        .expect("ok");
+    assert_eq!(db.statistics().expect("ok").edges, 6);
+    assert_statistics(&db);
+
+    db.transaction(|tx| {
+        tx.insert_node(id(6), ())?;
+        tx.insert_edge(triple(6, 10, 1), ())?;
+        tx.insert_edge(triple(1, 10, 2), ())?;
+        tx.remove_node(id(5))?;
+        tx.remove_edge(triple(2, 10, 3))?;
+        Ok(())
+    })
+    .expect("ok");
+    let statistics = db.statistics().expect("ok");
+    assert_eq!(statistics.nodes, 5);
+    assert_eq!(statistics.edges, 3);
+    assert_eq!(
+        statistics.predicate(&id(10)),
+        PredicateStats {
+            edges: 3,
+            subjects: 2,
+            objects: 3
+        }
+    );
+    assert_statistics(&db);
+}
+
+pub(crate) fn test_explain<Id: IdType + From<Ulid>, T: TripleStore<Id, (), ()>>(mut db: T) {
+    let id = |n: u128| Id::from(Ulid(n));
+    let (knows, likes) = (id(10), id(11));
+
+    // Everyone knows everyone, but only a few things are liked.
+    for sub in 1..=5 {
+        for obj in 1..=5 {
+            db.insert_edge(triple(sub, 10, obj), ()).expect("ok");
+        }
+    }
+    db.insert_edge(triple(1, 11, 100), ()).expect("ok");
+    db.insert_edge(triple(2, 11, 100), ()).expect("ok");
+
+    let pattern = Pattern::new()
+        .with(Term::var("x"), knows.clone(), Term::var("y"))
+        .with(Term::var("y"), likes.clone(), Term::var("z"));
+    let knows_pattern = TriplePattern::new(Term::var("x"), knows.clone(), Term::var("y"));
+    let likes_pattern = TriplePattern::new(Term::var("y"), likes.clone(), Term::var("z"));
+
+    // The rarer predicate is looked up first, then joined on its subject.
+    let plan = db.explain(&pattern).expect("ok");
+    assert_eq!(
+        plan.steps
+            .iter()
+            .map(|step| (step.pattern.clone(), step.order.clone(), step.estimate))
+            .collect::<Vec<_>>(),
+        [
+            (likes_pattern.clone(), EdgeOrder::POS, 2.0),
+            (knows_pattern.clone(), EdgeOrder::POS, 5.0),
+        ]
+    );
+    assert_eq!(plan.steps[1].rows, 10.0);
+    assert_eq!(db.run_pattern(&pattern).expect("ok").len(), 10);
+
+    // Once the other predicate is rarer the order flips.
+    for sub in 1..=5 {
+        for obj in 100..=120 {
+            db.insert_edge(triple(sub, 11, obj), ()).expect("ok");
+        }
+    }
+    let plan = db.explain(&pattern).expect("ok");
+    assert_eq!(
+        plan.steps
+            .iter()
+            .map(|step| (step.pattern.clone(), step.order.clone()))
+            .collect::<Vec<_>>(),
+        [
+            (knows_pattern, EdgeOrder::POS),
+            (likes_pattern, EdgeOrder::SPO),
+        ]
+    );
+}
@@ -0,0 +1,495 @@
+use ulid::Ulid;
+
+use crate::{
+    prelude::*,
+    traits::{Event, EventFilter, IdType, MergedProps, Property, Subscription},
+    Quad, Triple,
+};
+
+use super::TestMergeable;
+
+fn triple<Id: IdType + From<Ulid>>(sub: u128, pred: u128, obj: u128) -> Triple<Id> {
+    Triple {
+        sub: Ulid(sub).into(),
+        pred: Ulid(pred).into(),
+        obj: Ulid(obj).into(),
+    }
+}
+
+fn quad<Id: IdType + From<Ulid>>(sub: u128, pred: u128, obj: u128, graph: u128) -> Quad<Id> {
+    Quad::new(triple(sub, pred, obj), Ulid(graph).into())
+}
+
+fn drain<Id: IdType, NodeProps: Property, EdgeProps: Property>(
+    subscription: &Subscription<Id, NodeProps, EdgeProps>,
+) -> Vec<Event<Id, NodeProps, EdgeProps>> {
+    subscription.try_iter().collect()
+}
+
+/// Check that `events` holds the `expected` events in some order.
+fn assert_unordered<Id: IdType, NodeProps: Property, EdgeProps: Property>(
+    events: Vec<Event<Id, NodeProps, EdgeProps>>,
+    expected: &[Event<Id, NodeProps, EdgeProps>],
+) {
+    assert_eq!(events.len(), expected.len(), "{events:?}");
+    for event in expected {
+        assert!(events.contains(event), "{event:?} missing from {events:?}");
+    }
+}
+
+fn mergeable(a: Option<&str>, b: Option<&str>) -> TestMergeable {
+    TestMergeable {
+        a: a.map(str::to_string),
+        b: b.map(str::to_string),
+    }
+}
+
+pub(crate) fn test_subscribe<
+    Id: IdType + From<Ulid>,
+    T: TripleStore<Id, String, String> + TripleStoreSubscribe<Id, String, String>,
+>(
+    mut db: T,
+) {
+    let id = |n: u128| Id::from(Ulid(n));
+
+    // Changes made before subscribing are not seen.
+    db.insert_node(id(1), "a".to_string()).expect("ok");
+    let subscription = db.subscribe(EventFilter::new());
+    assert_eq!(drain(&subscription), []);
+
+    db.insert_node(id(2), "b".to_string()).expect("ok");
+    db.insert_edge(triple(1, 10, 2), "a->b".to_string())
+        .expect("ok");
+    db.insert_edge(triple(2, 10, 1), "b->a".to_string())
+        .expect("ok");
+    db.insert_edge(triple(2, 11, 2), "b->b".to_string())
+        .expect("ok");
+    assert_eq!(
+        drain(&subscription),
+        [
+            Event::NodeInserted {
+                node: id(2),
+                props: "b".to_string()
+            },
+            Event::EdgeInserted {
+                triple: triple(1, 10, 2),
+                props: "a->b".to_string()
+            },
+            Event::EdgeInserted {
+                triple: triple(2, 10, 1),
+                props: "b->a".to_string()
+            },
+            Event::EdgeInserted {
+                triple: triple(2, 11, 2),
+                props: "b->b".to_string()
+            },
+        ]
+    );
+
+    // Replacing props is reported as an insert.
+    db.insert_node(id(2), "b again".to_string()).expect("ok");
+    assert_eq!(
+        drain(&subscription),
+        [Event::NodeInserted {
+            node: id(2),
+            props: "b again".to_string()
+        }]
+    );
+
+    // Removing a node removes each of its edges first, including self loops only once.
+    db.remove_node(id(2)).expect("ok");
+    let events = drain(&subscription);
+    assert_eq!(events.last(), Some(&Event::NodeRemoved { node: id(2) }));
+    assert_unordered(
+        events,
+        &[
+            Event::EdgeRemoved {
+                triple: triple(1, 10, 2),
+            },
+            Event::EdgeRemoved {
+                triple: triple(2, 10, 1),
+            },
+            Event::EdgeRemoved {
+                triple: triple(2, 11, 2),
+            },
+            Event::NodeRemoved { node: id(2) },
+        ],
+    );
+
+    // Removing what is not there publishes nothing.
+    db.remove_edge(triple(1, 10, 2)).expect("ok");
+    db.remove_node(id(2)).expect("ok");
+    assert_eq!(drain(&subscription), []);
+
+    db.insert_edge(triple(1, 10, 3), "a->c".to_string())
+        .expect("ok");
+    db.remove_edge(triple(1, 10, 3)).expect("ok");
+    assert_eq!(
+        drain(&subscription),
+        [
+            Event::EdgeInserted {
+                triple: triple(1, 10, 3),
+                props: "a->c".to_string()
+            },
+            Event::EdgeRemoved {
+                triple: triple(1, 10, 3)
+            },
+        ]
+    );
+}
+
+pub(crate) fn test_subscribe_merge<
+    Id: IdType + From<Ulid>,
+    T: TripleStore<Id, TestMergeable, TestMergeable>
+        + TripleStoreMerge<Id, TestMergeable, TestMergeable>
+        + TripleStoreTransaction<Id, TestMergeable, TestMergeable>
+        + TripleStoreSubscribe<Id, TestMergeable, TestMergeable>,
+>(
+    mut db: T,
+) {
+    let id = |n: u128| Id::from(Ulid(n));
+    let subscription = db.subscribe(EventFilter::new());
+
+    // Merging into something missing inserts it.
+    db.merge_node(id(1), mergeable(Some("a"), None))
+        .expect("ok");
+    db.merge_edge(triple(1, 10, 2), mergeable(Some("a"), None))
+        .expect("ok");
+
+    // Merging into something present reports the props after merging.
+    db.merge_node(id(1), mergeable(None, Some("b")))
+        .expect("ok");
+    db.merge_edge(triple(1, 10, 2), mergeable(Some("c"), Some("b")))
+        .expect("ok");
+
+    assert_eq!(
+        drain(&subscription),
+        [
+            Event::NodeInserted {
+                node: id(1),
+                props: mergeable(Some("a"), None)
+            },
+            Event::EdgeInserted {
+                triple: triple(1, 10, 2),
+                props: mergeable(Some("a"), None)
+            },
+            Event::PropsMerged(MergedProps::Node {
+                node: id(1),
+                props: mergeable(Some("a"), Some("b"))
+            }),
+            Event::PropsMerged(MergedProps::Edge {
+                triple: triple(1, 10, 2),
+                props: mergeable(Some("c"), Some("b"))
+            }),
+        ]
+    );
+
+    // Transactions report merges the same way.
+    db.transaction(|tx| {
+        tx.merge_node(id(1), mergeable(Some("d"), None))?;
+        tx.merge_node(id(2), mergeable(Some("e"), None))?;
+        tx.merge_edge(triple(1, 10, 2), mergeable(None, Some("f")))?;
+        Ok(())
+    })
+    .expect("ok");
+    assert_unordered(
+        drain(&subscription),
+        &[
+            Event::PropsMerged(MergedProps::Node {
+                node: id(1),
+                props: mergeable(Some("d"), Some("b")),
+            }),
+            Event::NodeInserted {
+                node: id(2),
+                props: mergeable(Some("e"), None),
+            },
+            Event::PropsMerged(MergedProps::Edge {
+                triple: triple(1, 10, 2),
+                props: mergeable(Some("c"), Some("f")),
+            }),
+        ],
+    );
+}
+
+pub(crate) fn test_subscribe_bulk<
+    Id: IdType + From<Ulid>,
+    T: TripleStore<Id, String, String>
+        + TripleStoreBulkLoad<Id, String, String>
+        + TripleStoreTransaction<Id, String, String>
+        + TripleStoreSubscribe<Id, String, String>,
+>(
+    mut db: T,
+) {
+    let id = |n: u128| Id::from(Ulid(n));
+    let subscription = db.subscribe(EventFilter::new());
+
+    // Bulk loads publish the props which won.
+    db.insert_nodes([
+        (id(1), "a".to_string()),
+        (id(2), "b".to_string()),
+        (id(1), "a again".to_string()),
+    ])
+    .expect("ok");
+    db.insert_edges([
+        (triple(1, 10, 2), "a->b".to_string()),
+        (triple(2, 10, 1), "b->a".to_string()),
+    ])
+    .expect("ok");
+    assert_unordered(
+        drain(&subscription),
+        &[
+            Event::NodeInserted {
+                node: id(1),
+                props: "a again".to_string(),
+            },
+            Event::NodeInserted {
+                node: id(2),
+                props: "b".to_string(),
+            },
+            Event::EdgeInserted {
+                triple: triple(1, 10, 2),
+                props: "a->b".to_string(),
+            },
+            Event::EdgeInserted {
+                triple: triple(2, 10, 1),
+                props: "b->a".to_string(),
+            },
+        ],
+    );
+
+    // Transactions publish once applied.
+    db.transaction(|tx| {
+        tx.insert_node(id(3), "c".to_string())?;
+        tx.insert_edge(triple(2, 10, 3), "b->c".to_string())?;
+        tx.remove_edge(triple(2, 10, 1))?;
+        Ok(())
+    })
+    .expect("ok");
+    assert_unordered(
+        drain(&subscription),
+        &[
+            Event::NodeInserted {
+                node: id(3),
+                props: "c".to_string(),
+            },
+            Event::EdgeInserted {
+                triple: triple(2, 10, 3),
+                props: "b->c".to_string(),
+            },
+            Event::EdgeRemoved {
+                triple: triple(2, 10, 1),
+            },
+        ],
+    );
+}
+
+pub(crate) fn test_subscribe_extend<
+    Id: IdType + From<Ulid>,
+    T: TripleStore<Id, String, String>
+        + TripleStoreExtend<Id, String, String>
+        + TripleStoreSubscribe<Id, String, String>,
+>(
+    mut left: T,
+    mut right: T,
+) {
+    let id = |n: u128| Id::from(Ulid(n));
+    let subscription = left.subscribe(EventFilter::new());
+
+    right.insert_node(id(1), "a".to_string()).expect("ok");
+    right
+        .insert_edge(triple(1, 10, 2), "a->b".to_string())
+        .expect("ok");
+    left.extend(right).expect("ok");
+
+    assert_eq!(
+        drain(&subscription),
+        [
+            Event::NodeInserted {
+                node: id(1),
+                props: "a".to_string()
+            },
+            Event::EdgeInserted {
+                triple: triple(1, 10, 2),
+                props: "a->b".to_string()
+            },
+        ]
+    );
+}
+
+pub(crate) fn test_subscribe_filter<
+    Id: IdType + From<Ulid>,
+    T: TripleStore<Id, String, String> + TripleStoreSubscribe<Id, String, String>,
+>(
+    mut db: T,
+) {
+    let id = |n: u128| Id::from(Ulid(n));
+
+    let by_subject = db.subscribe(EventFilter::new().subject(id(1)));
+    let by_predicate = db.subscribe(EventFilter::new().predicate(id(10)));
+    let by_both = db.subscribe(EventFilter::new().subject(id(2)).predicate(id(10)));
+    let dropped = db.subscribe(EventFilter::new());
+    drop(dropped);
+
+    db.insert_node(id(1), "a".to_string()).expect("ok");
+    db.insert_node(id(2), "b".to_string()).expect("ok");
+    db.insert_edge(triple(1, 10, 2), "a->b".to_string())
+        .expect("ok");
+    db.insert_edge(triple(1, 11, 2), "a-x->b".to_string())
+        .expect("ok");
+    db.insert_edge(triple(2, 10, 1), "b->a".to_string())
+        .expect("ok");
+
+    assert_eq!(
+        drain(&by_subject),
+        [
+            Event::NodeInserted {
+                node: id(1),
+                props: "a".to_string()
+            },
+            Event::EdgeInserted {
+                triple: triple(1, 10, 2),
+                props: "a->b".to_string()
+            },
+            Event::EdgeInserted {
+                triple: triple(1, 11, 2),
+                props: "a-x->b".to_string()
+            },
+        ]
+    );
+    assert_eq!(
+        drain(&by_predicate),
+        [
+            Event::EdgeInserted {
+                triple: triple(1, 10, 2),
+                props: "a->b".to_string()
+            },
+            Event::EdgeInserted {
+                triple: triple(2, 10, 1),
+                props: "b->a".to_string()
+            },
+        ]
+    );
+    assert_eq!(
+        drain(&by_both),
+        [Event::EdgeInserted {
+            triple: triple(2, 10, 1),
+            props: "b->a".to_string()
+        }]
+    );
+
+    // Iterating blocks for the next event, which has already been published here.
+    let mut by_subject = by_subject;
+    db.remove_edge(triple(1, 11, 2)).expect("ok");
+    assert_eq!(
+        by_subject.next(),
+        Some(Event::EdgeRemoved {
+            triple: triple(1, 11, 2)
+        })
+    );
+
+    // Subscriptions end once the store is gone.
+    drop(db);
+    assert_eq!(by_subject.next(), None);
+}
+
+pub(crate) fn test_subscribe_quads<
+    Id: IdType + From<Ulid>,
+    T: QuadStore<Id, String, String> + TripleStoreSubscribe<Id, String, String>,
+>(
+    mut db: T,
+) {
+    let id = |n: u128| Id::from(Ulid(n));
+
+    let subscription = db.subscribe(EventFilter::new());
+    let by_predicate = db.subscribe(EventFilter::new().predicate(id(11)));
+
+    db.insert_quad(quad(1, 10, 2, 100), "a->b".to_string())
+        .expect("ok");
+    db.insert_quad(quad(1, 11, 2, 100), "a-x->b".to_string())
+        .expect("ok");
+    db.insert_quad(quad(1, 10, 2, 100), "a->b again".to_string())
+        .expect("ok");
+    db.remove_quad(quad(1, 11, 2, 100)).expect("ok");
+    assert_eq!(
+        drain(&subscription),
+        [
+            Event::QuadInserted {
+                quad: quad(1, 10, 2, 100),
+                props: "a->b".to_string()
+            },
+            Event::QuadInserted {
+                quad: quad(1, 11, 2, 100),
+                props: "a-x->b".to_string()
+            },
+            Event::QuadInserted {
+                quad: quad(1, 10, 2, 100),
+                props: "a->b again".to_string()
+            },
+            Event::QuadRemoved {
+                quad: quad(1, 11, 2, 100)
+            },
+        ]
+    );
+    assert_eq!(
+        drain(&by_predicate),
+        [
+            Event::QuadInserted {
+                quad: quad(1, 11, 2, 100),
+                props: "a-x->b".to_string()
+            },
+            Event::QuadRemoved {
+                quad: quad(1, 11, 2, 100)
+            },
+        ]
+    );
+
+    // Dropping a graph removes each of its edges, and removing what is not there publishes nothing.
+    db.insert_quad(quad(2, 10, 3, 100), "b->c".to_string())
+        .expect("ok");
+    drain(&subscription);
+    db.drop_graph(&id(100)).expect("ok");
+    assert_unordered(
+        drain(&subscription),
+        &[
+            Event::QuadRemoved {
+                quad: quad(1, 10, 2, 100),
+            },
+            Event::QuadRemoved {
+                quad: quad(2, 10, 3, 100),
+            },
+        ],
+    );
+    db.drop_graph(&id(100)).expect("ok");
+    db.remove_quad(quad(1, 10, 2, 100)).expect("ok");
+    assert_eq!(drain(&subscription), []);
+
+    // Removing a node removes its edges in every named graph before the node itself, self loops only once.
+    db.insert_node(id(2), "b".to_string()).expect("ok");
+    db.insert_quad(quad(1, 10, 2, 100), "a->b".to_string())
+        .expect("ok");
+    db.insert_quad(quad(2, 10, 3, 200), "b->c".to_string())
+        .expect("ok");
+    db.insert_quad(quad(2, 10, 2, 200), "b->b".to_string())
+        .expect("ok");
+    db.insert_quad(quad(1, 10, 3, 200), "a->c".to_string())
+        .expect("ok");
+    drain(&subscription);
+    db.remove_node(id(2)).expect("ok");
+    let events = drain(&subscription);
+    assert_eq!(events.last(), Some(&Event::NodeRemoved { node: id(2) }));
+    assert_unordered(
+        events,
+        &[
+            Event::QuadRemoved {
+                quad: quad(1, 10, 2, 100),
+            },
+            Event::QuadRemoved {
+                quad: quad(2, 10, 3, 200),
+            },
+            Event::QuadRemoved {
+                quad: quad(2, 10, 2, 200),
+            },
+            Event::NodeRemoved { node: id(2) },
+        ],
+    );
+}
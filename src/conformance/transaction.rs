@@ -0,0 +1,191 @@
+use ulid::Ulid;
+
+use crate::{
+    prelude::*,
+    traits::{IdType, Property},
+    EdgeOrder, Quad, QuadOrder, Triple,
+};
+
+use super::TestMergeable;
+
+fn triple<Id: IdType + From<Ulid>>(sub: u128, pred: u128, obj: u128) -> Triple<Id> {
+    Triple {
+        sub: Ulid(sub).into(),
+        pred: Ulid(pred).into(),
+        obj: Ulid(obj).into(),
+    }
+}
+
+type Contents<Id, NodeProps, EdgeProps> = (Vec<(Id, NodeProps)>, Vec<(Triple<Id>, EdgeProps)>);
+
+fn contents<Id: IdType, NodeProps: Property, EdgeProps: Property, T>(
+    db: &T,
+) -> Contents<Id, NodeProps, EdgeProps>
+where
+    T: TripleStore<Id, NodeProps, EdgeProps>,
+{
+    (
+        db.iter_nodes(EdgeOrder::SPO)
+            .0
+            .map(|r| r.expect("ok"))
+            .collect(),
+        db.iter_edges(EdgeOrder::SPO)
+            .map(|r| r.expect("ok"))
+            .collect(),
+    )
+}
+
+pub(crate) fn test_transaction_commit<
+    Id: IdType + From<Ulid>,
+    T: TripleStore<Id, String, String> + TripleStoreTransaction<Id, String, String>,
+>(
+    mut db: T,
+) {
+    let id = |n: u128| Id::from(Ulid(n));
+
+    db.insert_node(id(1), "a".to_string()).expect("ok");
+    db.insert_node(id(2), "b".to_string()).expect("ok");
+    db.insert_edge(triple(1, 10, 2), "a->b".to_string())
+        .expect("ok");
+
+    let result = db
+        .transaction(|tx| {
+            tx.insert_node(id(3), "c".to_string())?;
+            tx.insert_edge(triple(2, 10, 3), "b->c".to_string())?;
+            tx.insert_edge(triple(3, 10, 1), "c->a".to_string())?;
+
+            // Later operations see the effects of earlier ones.
+            tx.insert_edge(triple(2, 10, 3), "b->c again".to_string())?;
+            tx.insert_edge(triple(3, 11, 1), "c-x->a".to_string())?;
+            tx.remove_edge(triple(3, 11, 1))?;
+
+            // Removing a node removes its edges, including those added in the transaction.
+            tx.remove_node(id(1))?;
+            assert_eq!(tx.len(), 7);
+            Ok(42)
+        })
+        .expect("ok");
+    assert_eq!(result, 42);
+
+    assert_eq!(
+        contents(&db),
+        (
+            vec![(id(2), "b".to_string()), (id(3), "c".to_string())],
+            vec![(triple(2, 10, 3), "b->c again".to_string())]
+        )
+    );
+
+    // An empty transaction does nothing.
+    db.transaction(|tx| {
+        assert!(tx.is_empty());
+        Ok(())
+    })
+    .expect("ok");
+    assert_eq!(db.iter_vertices().count(), 2);
+}
+
+pub(crate) fn test_transaction_rollback<
+    Id: IdType + From<Ulid>,
+    T: TripleStore<Id, String, String> + TripleStoreTransaction<Id, String, String>,
+>(
+    mut db: T,
+    error: T::Error,
+) {
+    let id = |n: u128| Id::from(Ulid(n));
+
+    db.insert_node(id(1), "a".to_string()).expect("ok");
+    db.insert_node(id(2), "b".to_string()).expect("ok");
+    db.insert_edge(triple(1, 10, 2), "a->b".to_string())
+        .expect("ok");
+    let before = contents(&db);
+
+    assert!(db
+        .transaction(|tx| {
+            tx.insert_node(id(3), "c".to_string())?;
+            tx.insert_edge(triple(2, 10, 3), "b->c".to_string())?;
+            tx.remove_node(id(1))?;
+            Err::<(), _>(error)
+        })
+        .is_err());
+
+    assert_eq!(contents(&db), before);
+}
+
+pub(crate) fn test_transaction_merge<
+    Id: IdType + From<Ulid>,
+    T: TripleStore<Id, TestMergeable, TestMergeable>
+        + TripleStoreTransaction<Id, TestMergeable, TestMergeable>,
+>(
+    mut db: T,
+) {
+    let id = |n: u128| Id::from(Ulid(n));
+    let props = |a: Option<&str>, b: Option<&str>| TestMergeable {
+        a: a.map(str::to_string),
+        b: b.map(str::to_string),
+    };
+
+    db.insert_node(id(1), props(Some("a"), None)).expect("ok");
+    db.insert_edge(triple(1, 10, 2), props(None, Some("b")))
+        .expect("ok");
+
+    db.transaction(|tx| {
+        tx.merge_node(id(1), props(None, Some("b")))?;
+        tx.merge_node(id(2), props(Some("new"), None))?;
+        tx.merge_node(id(2), props(None, Some("merged")))?;
+        tx.merge_edge(triple(1, 10, 2), props(Some("a"), None))?;
+        Ok(())
+    })
+    .expect("ok");
+
+    assert_eq!(
+        contents(&db),
+        (
+            vec![
+                (id(1), props(Some("a"), Some("b"))),
+                (id(2), props(Some("new"), Some("merged")))
+            ],
+            vec![(triple(1, 10, 2), props(Some("a"), Some("b")))]
+        )
+    );
+}
+
+pub(crate) fn test_transaction_remove_node_quads<
+    Id: IdType + From<Ulid>,
+    T: QuadStore<Id, String, String> + TripleStoreTransaction<Id, String, String>,
+>(
+    mut db: T,
+) {
+    let id = |n: u128| Id::from(Ulid(n));
+    let quad = |sub, obj, graph| Quad::new(triple(sub, 10, obj), id(graph));
+
+    db.insert_node(id(1), "a".to_string()).expect("ok");
+    db.insert_node(id(2), "b".to_string()).expect("ok");
+    db.insert_edge(triple(1, 10, 2), "a->b".to_string())
+        .expect("ok");
+    for (sub, obj, graph) in [(1, 2, 100), (2, 2, 200), (2, 3, 200), (1, 3, 100)] {
+        db.insert_quad(quad(sub, obj, graph), format!("{sub}->{obj}"))
+            .expect("ok");
+    }
+
+    // Removing a node in a transaction also removes its edges in every named graph, as remove_node does.
+    db.transaction(|tx| {
+        tx.remove_node(id(2))?;
+        tx.insert_edge(triple(1, 10, 3), "a->c".to_string())?;
+        Ok(())
+    })
+    .expect("ok");
+
+    assert_eq!(
+        contents(&db),
+        (
+            vec![(id(1), "a".to_string())],
+            vec![(triple(1, 10, 3), "a->c".to_string())]
+        )
+    );
+    assert_eq!(
+        db.iter_quads(QuadOrder::GSPO)
+            .map(|r| r.expect("ok"))
+            .collect::<Vec<_>>(),
+        [(quad(1, 3, 100), "1->3".to_string())]
+    );
+}
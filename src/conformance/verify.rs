@@ -0,0 +1,69 @@
+use ulid::Ulid;
+
+use crate::{prelude::*, traits::IdType, Quad, Triple};
+
+fn triple<Id: IdType + From<Ulid>>(sub: u128, pred: u128, obj: u128) -> Triple<Id> {
+    Triple {
+        sub: Ulid(sub).into(),
+        pred: Ulid(pred).into(),
+        obj: Ulid(obj).into(),
+    }
+}
+
+pub(crate) fn test_verify_consistent<
+    Id: IdType + From<Ulid>,
+    T: TripleStore<Id, String, String>
+        + QuadStore<Id, String, String>
+        + TripleStoreVerify<Id, String, String>,
+>(
+    mut db: T,
+) {
+    let id = |n: u128| Id::from(Ulid(n));
+    assert!(db.verify().expect("ok").is_consistent());
+
+    db.insert_node(id(1), "a".to_string()).expect("ok");
+    db.insert_node(id(2), "b".to_string()).expect("ok");
+    db.insert_edge(triple(1, 10, 2), "a->b".to_string())
+        .expect("ok");
+    db.insert_edge(triple(2, 10, 3), "b->c".to_string())
+        .expect("ok");
+    db.insert_edge(triple(3, 11, 1), "c->a".to_string())
+        .expect("ok");
+    db.insert_quad(Quad::new(triple(1, 10, 2), id(20)), "a->b in g".to_string())
+        .expect("ok");
+    db.insert_quad(Quad::new(triple(1, 11, 3), id(20)), "a->c in g".to_string())
+        .expect("ok");
+    db.insert_quad(Quad::new(triple(2, 10, 1), id(21)), "b->a in h".to_string())
+        .expect("ok");
+
+    // Overwriting and removing edges and nodes leaves nothing behind.
+    db.insert_edge(triple(1, 10, 2), "a->b again".to_string())
+        .expect("ok");
+    db.remove_edge(triple(2, 10, 3)).expect("ok");
+    db.insert_quad(
+        Quad::new(triple(1, 10, 2), id(20)),
+        "a->b in g again".to_string(),
+    )
+    .expect("ok");
+    db.remove_quad(Quad::new(triple(2, 10, 1), id(21)))
+        .expect("ok");
+    db.remove_node(id(3)).expect("ok");
+
+    let report = db.verify().expect("ok");
+    assert!(report.is_consistent(), "{:?}", report);
+    assert_eq!(report.edges, 1);
+    assert_eq!(report.quads, 1);
+
+    // Repairing a consistent store changes nothing.
+    assert_eq!(db.repair().expect("ok"), report);
+    assert_eq!(
+        db.get_edge(&triple(1, 10, 2)).expect("ok"),
+        Some("a->b again".to_string())
+    );
+    assert_eq!(
+        db.get_quad(&Quad::new(triple(1, 10, 2), id(20)))
+            .expect("ok"),
+        Some("a->b in g again".to_string())
+    );
+    assert_eq!(db.statistics().expect("ok").edges, 1);
+}
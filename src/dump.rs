@@ -0,0 +1,1037 @@
+//! Exporting and importing a whole store in a portable binary dump format.
+//!
+//! A dump can be written from any [TripleStoreIter] and read into any [TripleStoreInsert], so it can be used to back
+//! up a store, to move data between [MemTripleStore][crate::MemTripleStore] and
+//! [SledTripleStore][crate::SledTripleStore], or to carry it across versions of this crate. The edges of named graphs
+//! are only included by [write_dataset] and read back by [read_dataset], which work with any [QuadStore]. Dumps are
+//! written and read as streams, so stores larger than memory can be dumped.
+//!
+//! Reading checks the whole dump before inserting anything, then goes back to its start to insert the records, so it
+//! needs a reader which can [Seek], such as a file. A dump which is corrupt, truncated or cannot be decoded leaves the
+//! store as it was.
+//!
+//! # Format
+//! All integers are big-endian.
+//!
+//! * A header: the magic bytes `STSDUMP\0`, the format version as a `u16`, the size of an id in bytes as a `u8`, the
+//!   [name of the id type][ConcreteIdType::NAME] and the name of the [Codec] used for props, each as a `u8` length
+//!   followed by UTF-8.
+//! * A record for each vertex with props: the byte `1`, the id, and the props as a `u64` length followed by the
+//!   encoded props.
+//! * A record for each edge: the byte `2`, the subject, predicate and object ids, and the props as for a vertex.
+//! * A record for each edge in a named graph: the byte `3`, the graph, subject, predicate and object ids, and the props
+//!   as for a vertex.
+//! * A trailer: the byte `0`, the number of vertices, of edges and of edges in named graphs as `u64`s, and the CRC-32
+//!   of everything before it.
+//!
+//! Ids are written with [to_be_bytes][ConcreteIdType::to_be_bytes].
+//!
+//! # Example
+//! ```
+//! # use ulid::Ulid;
+//! # use simple_triplestore::{dump, prelude::*, MemTripleStore, SledTripleStore, Triple, UlidIdGenerator};
+//! let mut db = MemTripleStore::new(UlidIdGenerator::new());
+//! db.insert_node(Ulid(1), "alice".to_string()).expect("ok");
+//! db.insert_edge(Triple { sub: Ulid(1), pred: Ulid(10), obj: Ulid(2) }, 42u32).expect("ok");
+//!
+//! let mut backup = Vec::new();
+//! dump::write(&db, &mut backup)?;
+//!
+//! let temp_dir = tempdir::TempDir::new("sled")?;
+//! let sled_db = sled::open(temp_dir.path())?;
+//! let mut restored = SledTripleStore::<Ulid, String, u32>::new(&sled_db, UlidIdGenerator::new()).expect("ok");
+//! let summary = dump::read(std::io::Cursor::new(backup), &mut restored)?;
+//! assert_eq!((summary.nodes, summary.edges), (1, 1));
+//! assert_eq!(restored.get_node(&Ulid(1)).expect("ok"), Some("alice".to_string()));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    prelude::*,
+    traits::{ConcreteIdType, Property},
+    EdgeOrder, Quad, QuadOrder, Triple,
+};
+
+const MAGIC: &[u8; 8] = b"STSDUMP\0";
+
+/// The version of the format which is written. Dumps of other versions are rejected.
+const VERSION: u16 = 1;
+
+const END_RECORD: u8 = 0;
+const NODE_RECORD: u8 = 1;
+const EDGE_RECORD: u8 = 2;
+const QUAD_RECORD: u8 = 3;
+
+/// How many vertices or edges are inserted at a time when reading a dump.
+const BATCH_SIZE: usize = 10_000;
+
+/// The error type of a [Codec].
+pub type CodecError = Box<dyn std::error::Error + Send + Sync>;
+
+/// An encoding for the props in a dump.
+///
+/// Its name is recorded in the header, and a dump can only be read with a codec of the same name.
+pub trait Codec {
+    /// The name to record in the header.
+    fn name(&self) -> &str;
+
+    fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, CodecError>;
+
+    fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, CodecError>;
+}
+
+/// Encodes props with [bincode], as [SledTripleStore][crate::SledTripleStore] stores them. The default [Codec].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Bincode;
+
+impl Codec for Bincode {
+    fn name(&self) -> &str {
+        "bincode"
+    }
+
+    fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, CodecError> {
+        Ok(bincode::serialize(value)?)
+    }
+
+    fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, CodecError> {
+        Ok(bincode::deserialize(bytes)?)
+    }
+}
+
+/// The number of records in a dump.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Summary {
+    /// The number of vertices with props.
+    pub nodes: u64,
+
+    /// The number of edges.
+    pub edges: u64,
+
+    /// The number of edges in named graphs.
+    pub quads: u64,
+}
+
+/// Errors which can occur while writing a dump.
+#[derive(Debug)]
+pub enum WriteError<StoreError> {
+    /// The underlying writer failed.
+    Io(std::io::Error),
+
+    /// Some props could not be encoded.
+    Codec(CodecError),
+
+    /// The triplestore returned an error.
+    Store(StoreError),
+}
+
+impl<StoreError: std::fmt::Debug> std::fmt::Display for WriteError<StoreError> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WriteError::Io(e) => f.write_fmt(format_args!("io error: {}", e)),
+            WriteError::Codec(e) => f.write_fmt(format_args!("codec error: {}", e)),
+            WriteError::Store(e) => f.write_fmt(format_args!("store error: {:?}", e)),
+        }
+    }
+}
+
+impl<StoreError: std::fmt::Debug> std::error::Error for WriteError<StoreError> {}
+
+impl<StoreError> From<std::io::Error> for WriteError<StoreError> {
+    fn from(e: std::io::Error) -> Self {
+        WriteError::Io(e)
+    }
+}
+
+/// Errors which can occur while reading a dump.
+#[derive(Debug)]
+pub enum ReadError<StoreError> {
+    /// The underlying reader failed, or the dump ended early.
+    Io(std::io::Error),
+
+    /// The input does not start with the magic bytes of a dump.
+    NotADump,
+
+    /// The dump was written in a version of the format which is not supported.
+    UnsupportedVersion(u16),
+
+    /// The dump was written from a store with ids of a different size.
+    IdSizeMismatch { expected: usize, found: usize },
+
+    /// The dump was written from a store with a different type of id.
+    IdTypeMismatch { expected: String, found: String },
+
+    /// The dump was written with a different [Codec].
+    CodecMismatch { expected: String, found: String },
+
+    /// The dump is malformed.
+    Corrupt(String),
+
+    /// The checksum in the trailer does not match the contents of the dump.
+    ChecksumMismatch,
+
+    /// The dump has edges in named graphs, which can only be read by [read_dataset].
+    NamedGraphs,
+
+    /// Some props could not be decoded.
+    Codec(CodecError),
+
+    /// The triplestore returned an error.
+    Store(StoreError),
+}
+
+impl<StoreError: std::fmt::Debug> std::fmt::Display for ReadError<StoreError> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReadError::Io(e) => f.write_fmt(format_args!("io error: {}", e)),
+            ReadError::NotADump => f.write_str("not a dump"),
+            ReadError::UnsupportedVersion(version) => {
+                f.write_fmt(format_args!("unsupported dump version {}", version))
+            }
+            ReadError::IdSizeMismatch { expected, found } => f.write_fmt(format_args!(
+                "expected ids of {} bytes, found {}",
+                expected, found
+            )),
+            ReadError::IdTypeMismatch { expected, found } => f.write_fmt(format_args!(
+                "expected ids of type {}, found {}",
+                expected, found
+            )),
+            ReadError::CodecMismatch { expected, found } => f.write_fmt(format_args!(
+                "expected props encoded with {}, found {}",
+                expected, found
+            )),
+            ReadError::Corrupt(message) => f.write_fmt(format_args!("corrupt dump: {}", message)),
+            ReadError::ChecksumMismatch => f.write_str("checksum mismatch"),
+            ReadError::NamedGraphs => f.write_str("dump has edges in named graphs"),
+            ReadError::Codec(e) => f.write_fmt(format_args!("codec error: {}", e)),
+            ReadError::Store(e) => f.write_fmt(format_args!("store error: {:?}", e)),
+        }
+    }
+}
+
+impl<StoreError: std::fmt::Debug> std::error::Error for ReadError<StoreError> {}
+
+impl<StoreError> From<std::io::Error> for ReadError<StoreError> {
+    fn from(e: std::io::Error) -> Self {
+        ReadError::Io(e)
+    }
+}
+
+/// The lookup table for the CRC-32 used by zlib and PNG.
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// A reader or writer which keeps the CRC-32 of everything passed through it.
+struct Checksummed<T> {
+    inner: T,
+    crc: u32,
+}
+
+impl<T> Checksummed<T> {
+    fn new(inner: T) -> Self {
+        Self { inner, crc: !0 }
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.crc = CRC_TABLE[((self.crc ^ *byte as u32) & 0xFF) as usize] ^ (self.crc >> 8);
+        }
+    }
+
+    fn checksum(&self) -> u32 {
+        !self.crc
+    }
+}
+
+impl<W: Write> Write for Checksummed<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.update(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<R: Read> Read for Checksummed<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.update(&buf[..read]);
+        Ok(read)
+    }
+}
+
+fn id_size<Id: ConcreteIdType>() -> usize {
+    std::mem::size_of::<Id::ByteArrayType>()
+}
+
+/// Write every vertex and edge in `db` to `writer` as a dump, encoding props with [Bincode].
+pub fn write<
+    Id: ConcreteIdType,
+    NodeProps: Property + Serialize,
+    EdgeProps: Property + Serialize,
+    T: TripleStoreIter<Id, NodeProps, EdgeProps>,
+>(
+    db: &T,
+    writer: impl Write,
+) -> Result<Summary, WriteError<T::Error>> {
+    write_with(db, writer, Bincode)
+}
+
+/// Write every vertex and edge in `db` to `writer` as a dump, encoding props with `codec`.
+///
+/// Vertices are written in id order and edges in [EdgeOrder::SPO] order. The output is buffered.
+pub fn write_with<
+    Id: ConcreteIdType,
+    NodeProps: Property + Serialize,
+    EdgeProps: Property + Serialize,
+    T: TripleStoreIter<Id, NodeProps, EdgeProps>,
+>(
+    db: &T,
+    writer: impl Write,
+    codec: impl Codec,
+) -> Result<Summary, WriteError<T::Error>> {
+    write_records(db, std::iter::empty(), writer, codec)
+}
+
+/// Write every vertex and edge in `db`, including those in named graphs, to `writer` as a dump, encoding props with
+/// [Bincode].
+pub fn write_dataset<
+    Id: ConcreteIdType,
+    NodeProps: Property + Serialize,
+    EdgeProps: Property + Serialize,
+    T: QuadStore<Id, NodeProps, EdgeProps>,
+>(
+    db: &T,
+    writer: impl Write,
+) -> Result<Summary, WriteError<T::Error>> {
+    write_dataset_with(db, writer, Bincode)
+}
+
+/// Write every vertex and edge in `db`, including those in named graphs, to `writer` as a dump, encoding props with
+/// `codec`.
+///
+/// As [write_with], followed by the edges in named graphs in [QuadOrder::GSPO] order.
+pub fn write_dataset_with<
+    Id: ConcreteIdType,
+    NodeProps: Property + Serialize,
+    EdgeProps: Property + Serialize,
+    T: QuadStore<Id, NodeProps, EdgeProps>,
+>(
+    db: &T,
+    writer: impl Write,
+    codec: impl Codec,
+) -> Result<Summary, WriteError<T::Error>> {
+    write_records(db, db.iter_quads(QuadOrder::GSPO), writer, codec)
+}
+
+/// Write the vertices and edges of `db`, followed by `quads`.
+fn write_records<
+    Id: ConcreteIdType,
+    NodeProps: Property + Serialize,
+    EdgeProps: Property + Serialize,
+    T: TripleStoreIter<Id, NodeProps, EdgeProps>,
+>(
+    db: &T,
+    quads: impl Iterator<Item = Result<(Quad<Id>, EdgeProps), T::Error>>,
+    writer: impl Write,
+    codec: impl Codec,
+) -> Result<Summary, WriteError<T::Error>> {
+    let mut output = Checksummed::new(BufWriter::new(writer));
+    let id_name = Id::NAME.as_bytes();
+    let id_name_len = u8::try_from(id_name.len()).map_err(|_| {
+        WriteError::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("id type name {:?} is too long", Id::NAME),
+        ))
+    })?;
+    let codec_name = codec.name().as_bytes();
+    let codec_name_len = u8::try_from(codec_name.len()).map_err(|_| {
+        WriteError::Codec(format!("codec name {:?} is too long", codec.name()).into())
+    })?;
+    output.write_all(MAGIC)?;
+    output.write_all(&VERSION.to_be_bytes())?;
+    output.write_all(&[id_size::<Id>() as u8, id_name_len])?;
+    output.write_all(id_name)?;
+    output.write_all(&[codec_name_len])?;
+    output.write_all(codec_name)?;
+
+    let mut summary = Summary::default();
+    for r in db.iter_vertices() {
+        let (id, props) = r.map_err(WriteError::Store)?;
+        output.write_all(&[NODE_RECORD])?;
+        output.write_all(id.to_be_bytes().as_ref())?;
+        write_props(
+            &mut output,
+            codec.encode(&props).map_err(WriteError::Codec)?,
+        )?;
+        summary.nodes += 1;
+    }
+    for r in db.iter_edges(EdgeOrder::SPO) {
+        let (triple, props) = r.map_err(WriteError::Store)?;
+        output.write_all(&[EDGE_RECORD])?;
+        for id in [triple.sub, triple.pred, triple.obj] {
+            output.write_all(id.to_be_bytes().as_ref())?;
+        }
+        write_props(
+            &mut output,
+            codec.encode(&props).map_err(WriteError::Codec)?,
+        )?;
+        summary.edges += 1;
+    }
+    for r in quads {
+        let (quad, props) = r.map_err(WriteError::Store)?;
+        output.write_all(&[QUAD_RECORD])?;
+        for id in [quad.graph, quad.sub, quad.pred, quad.obj] {
+            output.write_all(id.to_be_bytes().as_ref())?;
+        }
+        write_props(
+            &mut output,
+            codec.encode(&props).map_err(WriteError::Codec)?,
+        )?;
+        summary.quads += 1;
+    }
+
+    output.write_all(&[END_RECORD])?;
+    output.write_all(&summary.nodes.to_be_bytes())?;
+    output.write_all(&summary.edges.to_be_bytes())?;
+    output.write_all(&summary.quads.to_be_bytes())?;
+    let checksum = output.checksum();
+    output.inner.write_all(&checksum.to_be_bytes())?;
+    output.inner.flush()?;
+    Ok(summary)
+}
+
+fn write_props(output: &mut impl Write, props: Vec<u8>) -> std::io::Result<()> {
+    output.write_all(&(props.len() as u64).to_be_bytes())?;
+    output.write_all(&props)
+}
+
+/// A record of a dump, with its ids and props still encoded.
+enum Record {
+    Node(Vec<u8>, Vec<u8>),
+    Edge([Vec<u8>; 3], Vec<u8>),
+    Quad([Vec<u8>; 4], Vec<u8>),
+}
+
+/// Reads the records of a dump, checking the trailer once they run out.
+struct RecordReader<R: Read> {
+    input: Checksummed<BufReader<R>>,
+    id_size: usize,
+    summary: Summary,
+}
+
+impl<R: Read> RecordReader<R> {
+    /// Read the header, which must be for ids of the size and type named in `id_type`, if given, and props encoded with
+    /// `codec`, if given.
+    fn new<E>(
+        reader: R,
+        id_type: Option<(usize, &str)>,
+        codec: Option<&str>,
+    ) -> Result<Self, ReadError<E>> {
+        let mut input = Checksummed::new(BufReader::new(reader));
+        let mut magic = [0; MAGIC.len()];
+        input.read_exact(&mut magic).map_err(|e| match e.kind() {
+            std::io::ErrorKind::UnexpectedEof => ReadError::NotADump,
+            _ => ReadError::Io(e),
+        })?;
+        if &magic != MAGIC {
+            return Err(ReadError::NotADump);
+        }
+
+        let version = u16::from_be_bytes(read_array(&mut input)?);
+        if version != VERSION {
+            return Err(ReadError::UnsupportedVersion(version));
+        }
+
+        let [found_id_size] = read_array(&mut input)?;
+        let found_id_size = found_id_size as usize;
+        if let Some((id_size, _)) = id_type.filter(|(id_size, _)| *id_size != found_id_size) {
+            return Err(ReadError::IdSizeMismatch {
+                expected: id_size,
+                found: found_id_size,
+            });
+        }
+
+        let id_name = read_name(&mut input)?;
+        if let Some((_, expected)) = id_type.filter(|(_, expected)| *expected != id_name) {
+            return Err(ReadError::IdTypeMismatch {
+                expected: expected.to_string(),
+                found: id_name,
+            });
+        }
+
+        let codec_name = read_name(&mut input)?;
+        if let Some(codec) = codec.filter(|codec| *codec != codec_name) {
+            return Err(ReadError::CodecMismatch {
+                expected: codec.to_string(),
+                found: codec_name,
+            });
+        }
+
+        Ok(Self {
+            input,
+            id_size: found_id_size,
+            summary: Summary::default(),
+        })
+    }
+
+    fn next_record<E>(&mut self) -> Result<Option<Record>, ReadError<E>> {
+        let [tag] = read_array(&mut self.input)?;
+        match tag {
+            NODE_RECORD => {
+                let id = self.read_bytes(self.id_size as u64)?;
+                let props = self.read_props()?;
+                self.summary.nodes += 1;
+                Ok(Some(Record::Node(id, props)))
+            }
+            EDGE_RECORD => {
+                let ids = [
+                    self.read_bytes(self.id_size as u64)?,
+                    self.read_bytes(self.id_size as u64)?,
+                    self.read_bytes(self.id_size as u64)?,
+                ];
+                let props = self.read_props()?;
+                self.summary.edges += 1;
+                Ok(Some(Record::Edge(ids, props)))
+            }
+            QUAD_RECORD => {
+                let ids = [
+                    self.read_bytes(self.id_size as u64)?,
+                    self.read_bytes(self.id_size as u64)?,
+                    self.read_bytes(self.id_size as u64)?,
+                    self.read_bytes(self.id_size as u64)?,
+                ];
+                let props = self.read_props()?;
+                self.summary.quads += 1;
+                Ok(Some(Record::Quad(ids, props)))
+            }
+            END_RECORD => {
+                self.finish()?;
+                Ok(None)
+            }
+            tag => Err(ReadError::Corrupt(format!("unknown record type {}", tag))),
+        }
+    }
+
+    /// Check the trailer against the records which were read.
+    fn finish<E>(&mut self) -> Result<(), ReadError<E>> {
+        let trailer = Summary {
+            nodes: u64::from_be_bytes(read_array(&mut self.input)?),
+            edges: u64::from_be_bytes(read_array(&mut self.input)?),
+            quads: u64::from_be_bytes(read_array(&mut self.input)?),
+        };
+        let checksum = self.input.checksum();
+        if u32::from_be_bytes(read_array(&mut self.input.inner)?) != checksum {
+            return Err(ReadError::ChecksumMismatch);
+        }
+        if trailer != self.summary {
+            return Err(ReadError::Corrupt(format!(
+                "expected {:?}, found {:?}",
+                trailer, self.summary
+            )));
+        }
+        if self.input.inner.read(&mut [0])? != 0 {
+            return Err(ReadError::Corrupt("data after the trailer".to_string()));
+        }
+        Ok(())
+    }
+
+    fn read_bytes<E>(&mut self, len: u64) -> Result<Vec<u8>, ReadError<E>> {
+        // A corrupt length must not allocate up front, so the buffer grows as the data arrives.
+        let mut bytes = Vec::new();
+        (&mut self.input).take(len).read_to_end(&mut bytes)?;
+        if bytes.len() as u64 != len {
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+        }
+        Ok(bytes)
+    }
+
+    fn read_props<E>(&mut self) -> Result<Vec<u8>, ReadError<E>> {
+        let len = u64::from_be_bytes(read_array(&mut self.input)?);
+        self.read_bytes(len)
+    }
+}
+
+fn read_array<const N: usize>(input: &mut impl Read) -> std::io::Result<[u8; N]> {
+    let mut bytes = [0; N];
+    input.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Read a name from the header, as a `u8` length followed by UTF-8.
+fn read_name(input: &mut impl Read) -> std::io::Result<String> {
+    let [len] = read_array(input)?;
+    let mut name = vec![0; len as usize];
+    input.read_exact(&mut name)?;
+    Ok(String::from_utf8_lossy(&name).into_owned())
+}
+
+/// A record of a dump, with its ids and props decoded.
+enum Item<Id: ConcreteIdType, NodeProps, EdgeProps> {
+    Node(Id, NodeProps),
+    Edge(Triple<Id>, EdgeProps),
+    Quad(Quad<Id>, EdgeProps),
+}
+
+fn decode_item<
+    Id: ConcreteIdType,
+    NodeProps: DeserializeOwned,
+    EdgeProps: DeserializeOwned,
+    StoreError,
+>(
+    record: Record,
+    codec: &impl Codec,
+) -> Result<Item<Id, NodeProps, EdgeProps>, ReadError<StoreError>> {
+    let decode_id = |bytes: &[u8]| {
+        Id::try_from_be_bytes(bytes).ok_or_else(|| ReadError::Corrupt("invalid id".to_string()))
+    };
+    Ok(match record {
+        Record::Node(id, props) => Item::Node(
+            decode_id(&id)?,
+            codec.decode(&props).map_err(ReadError::Codec)?,
+        ),
+        Record::Edge([sub, pred, obj], props) => Item::Edge(
+            Triple {
+                sub: decode_id(&sub)?,
+                pred: decode_id(&pred)?,
+                obj: decode_id(&obj)?,
+            },
+            codec.decode(&props).map_err(ReadError::Codec)?,
+        ),
+        Record::Quad([graph, sub, pred, obj], props) => Item::Quad(
+            Quad {
+                sub: decode_id(&sub)?,
+                pred: decode_id(&pred)?,
+                obj: decode_id(&obj)?,
+                graph: decode_id(&graph)?,
+            },
+            codec.decode(&props).map_err(ReadError::Codec)?,
+        ),
+    })
+}
+
+/// Read the dump in `reader` twice: first to check all of it, decoding every record, then from the start again to
+/// pass each record to `load`. Edges in named graphs are rejected unless `quads` is set.
+fn read_records<
+    Id: ConcreteIdType,
+    NodeProps: DeserializeOwned,
+    EdgeProps: DeserializeOwned,
+    StoreError,
+>(
+    mut reader: impl Read + Seek,
+    codec: &impl Codec,
+    quads: bool,
+    mut load: impl FnMut(Item<Id, NodeProps, EdgeProps>) -> Result<(), StoreError>,
+) -> Result<Summary, ReadError<StoreError>> {
+    let id_type = Some((id_size::<Id>(), Id::NAME));
+    let start = reader.stream_position()?;
+
+    let mut records = RecordReader::new(&mut reader, id_type, Some(codec.name()))?;
+    while let Some(record) = records.next_record()? {
+        if !quads && matches!(record, Record::Quad(..)) {
+            return Err(ReadError::NamedGraphs);
+        }
+        decode_item::<Id, NodeProps, EdgeProps, _>(record, codec)?;
+    }
+    let summary = records.summary;
+
+    reader.seek(SeekFrom::Start(start))?;
+    let mut records = RecordReader::new(&mut reader, id_type, Some(codec.name()))?;
+    while let Some(record) = records.next_record()? {
+        load(decode_item(record, codec)?).map_err(ReadError::Store)?;
+    }
+    Ok(summary)
+}
+
+/// Read a dump from `reader` into `db`, decoding props with [Bincode].
+pub fn read<
+    Id: ConcreteIdType,
+    NodeProps: Property + DeserializeOwned,
+    EdgeProps: Property + DeserializeOwned,
+    T: TripleStoreInsert<Id, NodeProps, EdgeProps>,
+>(
+    reader: impl Read + Seek,
+    db: &mut T,
+) -> Result<Summary, ReadError<T::Error>> {
+    read_with(reader, db, Bincode)
+}
+
+/// Read a dump from `reader` into `db`, decoding props with `codec`.
+///
+/// The whole dump is checked and decoded before anything is inserted, so a dump which is corrupt, truncated, cannot be
+/// decoded or has edges in named graphs is rejected with the store left as it was. Only an error from the store itself
+/// can leave part of the dump inserted. The input is buffered.
+pub fn read_with<
+    Id: ConcreteIdType,
+    NodeProps: Property + DeserializeOwned,
+    EdgeProps: Property + DeserializeOwned,
+    T: TripleStoreInsert<Id, NodeProps, EdgeProps>,
+>(
+    reader: impl Read + Seek,
+    db: &mut T,
+    codec: impl Codec,
+) -> Result<Summary, ReadError<T::Error>> {
+    read_records(reader, &codec, false, |item| match item {
+        Item::Node(id, props) => db.insert_node(id, props),
+        Item::Edge(triple, props) => db.insert_edge(triple, props),
+        Item::Quad(..) => unreachable!("edges in named graphs are rejected while checking"),
+    })
+}
+
+/// Read a dump from `reader` into `db`, including the edges in named graphs, decoding props with [Bincode].
+pub fn read_dataset<
+    Id: ConcreteIdType,
+    NodeProps: Property + DeserializeOwned,
+    EdgeProps: Property + DeserializeOwned,
+    T: QuadStore<Id, NodeProps, EdgeProps> + TripleStoreBulkLoad<Id, NodeProps, EdgeProps>,
+>(
+    reader: impl Read + Seek,
+    db: &mut T,
+) -> Result<Summary, ReadError<T::Error>> {
+    read_dataset_with(reader, db, Bincode)
+}
+
+/// Read a dump from `reader` into `db`, including the edges in named graphs, decoding props with `codec`.
+///
+/// As [read_with], except that vertices and edges are [bulk loaded][TripleStoreBulkLoad] in batches.
+pub fn read_dataset_with<
+    Id: ConcreteIdType,
+    NodeProps: Property + DeserializeOwned,
+    EdgeProps: Property + DeserializeOwned,
+    T: QuadStore<Id, NodeProps, EdgeProps> + TripleStoreBulkLoad<Id, NodeProps, EdgeProps>,
+>(
+    reader: impl Read + Seek,
+    db: &mut T,
+    codec: impl Codec,
+) -> Result<Summary, ReadError<T::Error>> {
+    let mut nodes = Vec::new();
+    let mut edges = Vec::new();
+    let summary = read_records(reader, &codec, true, |item| match item {
+        Item::Node(id, props) => {
+            nodes.push((id, props));
+            if nodes.len() == BATCH_SIZE {
+                db.insert_nodes(nodes.drain(..))?;
+            }
+            Ok(())
+        }
+        Item::Edge(triple, props) => {
+            edges.push((triple, props));
+            if edges.len() == BATCH_SIZE {
+                db.insert_edges(edges.drain(..))?;
+            }
+            Ok(())
+        }
+        Item::Quad(quad, props) => db.insert_quad(quad, props),
+    })?;
+    db.insert_nodes(nodes).map_err(ReadError::Store)?;
+    db.insert_edges(edges).map_err(ReadError::Store)?;
+    Ok(summary)
+}
+
+/// Check that the dump in `reader` is complete and matches its checksum, without decoding any props.
+pub fn check(reader: impl Read) -> Result<Summary, ReadError<std::convert::Infallible>> {
+    let mut records = RecordReader::new(reader, None, None)?;
+    while records.next_record()?.is_some() {}
+    Ok(records.summary)
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use ulid::Ulid;
+
+    use crate::{prelude::*, MemTripleStore, Quad, Triple, UlidIdGenerator};
+
+    use super::{Codec, CodecError, ReadError, Summary};
+
+    fn triple(sub: u128, pred: u128, obj: u128) -> Triple<Ulid> {
+        Triple {
+            sub: Ulid(sub),
+            pred: Ulid(pred),
+            obj: Ulid(obj),
+        }
+    }
+
+    fn populated() -> MemTripleStore<Ulid, String, Vec<u32>> {
+        let mut db = MemTripleStore::new(UlidIdGenerator::new());
+        db.insert_node(Ulid(1), "a".to_string()).expect("ok");
+        db.insert_node(Ulid(2), "b".to_string()).expect("ok");
+        db.insert_edge(triple(1, 10, 2), vec![1, 2]).expect("ok");
+        db.insert_edge(triple(2, 10, 3), vec![]).expect("ok");
+        db.insert_edge(triple(3, 11, 1), vec![3]).expect("ok");
+        db.insert_quad(Quad::new(triple(1, 10, 2), Ulid(20)), vec![4])
+            .expect("ok");
+        db
+    }
+
+    const SUMMARY: Summary = Summary {
+        nodes: 2,
+        edges: 3,
+        quads: 1,
+    };
+
+    fn dump(db: &MemTripleStore<Ulid, String, Vec<u32>>) -> Vec<u8> {
+        let mut output = Vec::new();
+        assert_eq!(super::write_dataset(db, &mut output).expect("ok"), SUMMARY);
+        output
+    }
+
+    #[test]
+    fn test_checksum() {
+        let mut checksummed = super::Checksummed::new(());
+        checksummed.update(b"123456789");
+        assert_eq!(checksummed.checksum(), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn test_round_trip_mem() {
+        let db = populated();
+        let output = dump(&db);
+        assert_eq!(super::check(output.as_slice()).expect("ok"), SUMMARY);
+
+        let mut restored = MemTripleStore::new(UlidIdGenerator::new());
+        assert_eq!(
+            super::read_dataset(Cursor::new(&output), &mut restored).expect("ok"),
+            SUMMARY
+        );
+        assert!(db.try_eq(&restored).expect("ok"));
+        assert_eq!(
+            restored
+                .get_quad(&Quad::new(triple(1, 10, 2), Ulid(20)))
+                .expect("ok"),
+            Some(vec![4])
+        );
+
+        // Dumps are deterministic, regardless of the ids given to props.
+        assert_eq!(dump(&restored), output);
+    }
+
+    #[test]
+    fn test_round_trip_batches() {
+        let mut db = MemTripleStore::<u64, u64, u64>::new(crate::id::u64::U64IdGenerator::new(0));
+        let count = super::BATCH_SIZE as u64 + 1;
+        db.insert_nodes((0..count).map(|n| (n, n))).expect("ok");
+        db.insert_edges((0..count).map(|n| {
+            (
+                Triple {
+                    sub: n,
+                    pred: 0,
+                    obj: n + 1,
+                },
+                n,
+            )
+        }))
+        .expect("ok");
+        let mut output = Vec::new();
+        super::write(&db, &mut output).expect("ok");
+
+        let mut restored =
+            MemTripleStore::<u64, u64, u64>::new(crate::id::u64::U64IdGenerator::new(0));
+        assert_eq!(
+            super::read_dataset(Cursor::new(&output), &mut restored).expect("ok"),
+            Summary {
+                nodes: count,
+                edges: count,
+                quads: 0
+            }
+        );
+        assert!(db.try_eq(&restored).expect("ok"));
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_round_trip_sled() {
+        let db = populated();
+        let (_tempdir, sled_db) = crate::sled::create_test_db().expect("ok");
+        let mut sled_store =
+            crate::SledTripleStore::new(&sled_db, UlidIdGenerator::new()).expect("ok");
+        super::read_dataset(Cursor::new(dump(&db)), &mut sled_store).expect("ok");
+        assert!(db.try_eq(&sled_store).expect("ok"));
+
+        let mut output = Vec::new();
+        super::write_dataset(&sled_store, &mut output).expect("ok");
+        assert_eq!(output, dump(&db));
+    }
+
+    #[test]
+    fn test_empty() {
+        let db = MemTripleStore::<u64, (), ()>::new(crate::id::u64::U64IdGenerator::new(0));
+        let mut output = Vec::new();
+        super::write(&db, &mut output).expect("ok");
+        assert_eq!(
+            super::check(output.as_slice()).expect("ok"),
+            Summary::default()
+        );
+    }
+
+    #[test]
+    fn test_corruption() {
+        let output = dump(&populated());
+        // Whatever is wrong with the dump, nothing is read from it.
+        let read = |input: &[u8]| {
+            let mut db = MemTripleStore::<Ulid, String, Vec<u32>>::new(UlidIdGenerator::new());
+            let result = super::read_dataset(Cursor::new(input), &mut db);
+            assert_eq!(db, MemTripleStore::new(UlidIdGenerator::new()));
+            result
+        };
+
+        // Any flipped byte is caught, whether in the header, a record or the trailer.
+        for i in 0..output.len() {
+            let mut corrupt = output.clone();
+            corrupt[i] ^= 0x40;
+            assert!(super::check(corrupt.as_slice()).is_err(), "byte {}", i);
+            assert!(read(&corrupt).is_err(), "byte {}", i);
+        }
+
+        // As is a dump which stops early, or which has something after it.
+        for len in 0..output.len() {
+            assert!(super::check(&output[..len]).is_err(), "length {}", len);
+            assert!(read(&output[..len]).is_err(), "length {}", len);
+        }
+        let mut extended = output.clone();
+        extended.push(0);
+        assert!(matches!(
+            super::check(extended.as_slice()),
+            Err(ReadError::Corrupt(_))
+        ));
+
+        assert!(matches!(
+            super::check(&b"<http://a> <http://b> <http://c> .\n"[..]),
+            Err(ReadError::NotADump)
+        ));
+        let mut corrupt = output.clone();
+        corrupt[9] = 2;
+        assert!(matches!(
+            read(&corrupt),
+            Err(ReadError::UnsupportedVersion(2))
+        ));
+    }
+
+    #[test]
+    fn test_bad_checksum() {
+        // The records are intact, so only the checksum shows that something is wrong.
+        let mut output = dump(&populated());
+        *output.last_mut().expect("ok") ^= 0x01;
+
+        let mut db = MemTripleStore::<Ulid, String, Vec<u32>>::new(UlidIdGenerator::new());
+        assert!(matches!(
+            super::read_dataset(Cursor::new(&output), &mut db),
+            Err(ReadError::ChecksumMismatch)
+        ));
+        assert_eq!(db, MemTripleStore::new(UlidIdGenerator::new()));
+
+        // Likewise for a dump without named graphs, read into a store which already has something in it.
+        let mut output = Vec::new();
+        super::write(&populated(), &mut output).expect("ok");
+        *output.last_mut().expect("ok") ^= 0x01;
+        let mut db = MemTripleStore::<Ulid, String, Vec<u32>>::new(UlidIdGenerator::new());
+        db.insert_node(Ulid(5), "e".to_string()).expect("ok");
+        assert!(matches!(
+            super::read(Cursor::new(&output), &mut db),
+            Err(ReadError::ChecksumMismatch)
+        ));
+        assert_eq!(
+            db.iter_vertices()
+                .map(|r| r.expect("ok"))
+                .collect::<Vec<_>>(),
+            [(Ulid(5), "e".to_string())]
+        );
+        assert_eq!(db.iter_edges(crate::EdgeOrder::SPO).count(), 0);
+    }
+
+    #[test]
+    fn test_triples_only() {
+        let db = populated();
+        let mut output = Vec::new();
+        assert_eq!(
+            super::write(&db, &mut output).expect("ok"),
+            Summary {
+                quads: 0,
+                ..SUMMARY
+            }
+        );
+
+        let mut restored = MemTripleStore::new(UlidIdGenerator::new());
+        super::read(Cursor::new(&output), &mut restored).expect("ok");
+        assert!(db.try_eq(&restored).expect("ok"));
+        assert_eq!(restored.iter_graphs().count(), 0);
+
+        // Edges in named graphs are only read by read_dataset, so nothing is read from a dump with them.
+        let mut restored = MemTripleStore::<Ulid, String, Vec<u32>>::new(UlidIdGenerator::new());
+        assert!(matches!(
+            super::read(Cursor::new(dump(&db)), &mut restored),
+            Err(ReadError::NamedGraphs)
+        ));
+        assert_eq!(restored, MemTripleStore::new(UlidIdGenerator::new()));
+    }
+
+    #[test]
+    fn test_mismatched_store() {
+        let output = dump(&populated());
+
+        let mut db =
+            MemTripleStore::<u64, String, Vec<u32>>::new(crate::id::u64::U64IdGenerator::new(0));
+        assert!(matches!(
+            super::read(Cursor::new(&output), &mut db),
+            Err(ReadError::IdSizeMismatch {
+                expected: 8,
+                found: 16
+            })
+        ));
+
+        // Ids of the same size are told apart by the name of their type.
+        let mut renamed = output.clone();
+        let name_at = renamed
+            .windows(4)
+            .position(|window| window == b"ulid")
+            .expect("ok");
+        renamed[name_at..name_at + 4].copy_from_slice(b"uuid");
+        let mut db = MemTripleStore::<Ulid, String, Vec<u32>>::new(UlidIdGenerator::new());
+        assert!(matches!(
+            super::read(Cursor::new(&renamed), &mut db),
+            Err(ReadError::IdTypeMismatch { expected, found }) if expected == "ulid" && found == "uuid"
+        ));
+
+        struct Json;
+        impl Codec for Json {
+            fn name(&self) -> &str {
+                "json"
+            }
+            fn encode<T: serde::Serialize>(&self, _: &T) -> Result<Vec<u8>, CodecError> {
+                unimplemented!()
+            }
+            fn decode<T: serde::de::DeserializeOwned>(&self, _: &[u8]) -> Result<T, CodecError> {
+                unimplemented!()
+            }
+        }
+        let mut db = MemTripleStore::<Ulid, String, Vec<u32>>::new(UlidIdGenerator::new());
+        assert!(matches!(
+            super::read_with(Cursor::new(&output), &mut db, Json),
+            Err(ReadError::CodecMismatch { .. })
+        ));
+    }
+}
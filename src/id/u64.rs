@@ -11,6 +11,9 @@ use crate::{
 impl ConcreteIdType for u64 {
     type ByteArrayType = [u8; 8];
     type TripleByteArrayType = [u8; 24];
+    type QuadByteArrayType = [u8; 32];
+
+    const NAME: &'static str = "u64";
 
     fn to_be_bytes(self) -> Self::ByteArrayType {
         self.to_be_bytes()
@@ -112,12 +115,49 @@ impl ConcreteIdType for u64 {
             })),
         )
     }
+
+    fn encode_quad(graph: Self, triple: &Self::TripleByteArrayType) -> Self::QuadByteArrayType {
+        let mut data = [0u8; 32];
+        data[0..8].copy_from_slice(&graph.to_be_bytes());
+        data[8..32].copy_from_slice(triple);
+        data
+    }
+
+    fn decode_quad(data: &Self::QuadByteArrayType) -> (Self, Self::TripleByteArrayType) {
+        let graph = Self::from_be_bytes(data[0..8].try_into().unwrap());
+        (graph, data[8..32].try_into().unwrap())
+    }
+
+    fn graph_key_bounds(
+        graph: Self,
+    ) -> (
+        std::ops::Bound<Self::QuadByteArrayType>,
+        std::ops::Bound<Self::QuadByteArrayType>,
+    ) {
+        (
+            std::ops::Bound::Included(Self::encode_quad(graph, &[0x00; 24])),
+            std::ops::Bound::Included(Self::encode_quad(graph, &[0xFF; 24])),
+        )
+    }
+
+    #[cfg(feature = "sled")]
+    fn sled_id_generator(
+        counter: sled::Tree,
+        in_use: Option<Self>,
+    ) -> Result<Option<Box<dyn IdGenerator<Self>>>, sled::Error> {
+        let min = in_use.map_or(0, |id| id.saturating_add(1));
+        Ok(Some(Box::new(crate::sled::SledU64IdGenerator::new(
+            counter, min,
+        )?)))
+    }
 }
 
-struct U64IdGenerator {
+#[allow(dead_code)]
+pub(crate) struct U64IdGenerator {
     state: Arc<AtomicU64>,
 }
 
+#[allow(dead_code)]
 impl U64IdGenerator {
     pub fn new(initial_value: u64) -> Self {
         Self {
@@ -8,6 +8,9 @@ use crate::{
 impl ConcreteIdType for Ulid {
     type ByteArrayType = [u8; 16];
     type TripleByteArrayType = [u8; 48];
+    type QuadByteArrayType = [u8; 64];
+
+    const NAME: &'static str = "ulid";
 
     fn to_be_bytes(self) -> Self::ByteArrayType {
         self.0.to_be_bytes()
@@ -104,12 +107,42 @@ impl ConcreteIdType for Ulid {
             })),
         )
     }
+
+    fn encode_quad(graph: Self, triple: &Self::TripleByteArrayType) -> Self::QuadByteArrayType {
+        let mut data = [0u8; 64];
+        data[0..16].copy_from_slice(&graph.to_be_bytes());
+        data[16..64].copy_from_slice(triple);
+        data
+    }
+
+    fn decode_quad(data: &Self::QuadByteArrayType) -> (Self, Self::TripleByteArrayType) {
+        let graph = Ulid(u128::from_be_bytes(data[0..16].try_into().unwrap()));
+        (graph, data[16..64].try_into().unwrap())
+    }
+
+    fn graph_key_bounds(
+        graph: Self,
+    ) -> (
+        std::ops::Bound<Self::QuadByteArrayType>,
+        std::ops::Bound<Self::QuadByteArrayType>,
+    ) {
+        (
+            std::ops::Bound::Included(Self::encode_quad(graph, &[0x00; 48])),
+            std::ops::Bound::Included(Self::encode_quad(graph, &[0xFF; 48])),
+        )
+    }
 }
 
 pub struct UlidIdGenerator {
     state: ulid::Generator,
 }
 
+impl Default for UlidIdGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl UlidIdGenerator {
     pub fn new() -> Self {
         Self {
@@ -234,6 +267,41 @@ mod test {
         )
     }
 
+    #[test]
+    fn test_encode_decode_quad() {
+        let graph = Ulid(0x303132333435363738393A3B3C3D3E3F);
+        let quad = Ulid::encode_quad(graph, &make_data());
+        assert_eq!(
+            quad[0..16],
+            [
+                0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0x3A, 0x3B, 0x3C, 0x3D,
+                0x3E, 0x3F
+            ]
+        );
+        assert_eq!(quad[16..64], make_data());
+        assert_eq!(Ulid::decode_quad(&quad), (graph, make_data()));
+
+        // Scoped bounds stay within the graph.
+        if let (Included(lb), Included(ub)) =
+            Ulid::quad_key_bounds(graph, Ulid::key_bounds_1(id_1()))
+        {
+            assert_eq!(Ulid::decode_quad(&lb).0, graph);
+            assert_eq!(Ulid::decode_quad(&ub).0, graph);
+            let inside = Ulid::encode_quad(
+                graph,
+                &Ulid::encode_spo_triple(&Triple {
+                    sub: id_1(),
+                    pred: id_2(),
+                    obj: id_2(),
+                }),
+            );
+            assert!((lb..=ub).contains(&inside));
+            assert!(!(lb..=ub).contains(&quad));
+        } else {
+            panic!("Bounds should be included on both ends.");
+        }
+    }
+
     fn id_1() -> Ulid {
         Ulid(0xDEADBEEFDEADBEEFDEADBEEFDEADBEEF)
     }
@@ -265,7 +333,7 @@ mod test {
             );
         } else {
             //Bounds should be included on both ends.
-            assert!(false);
+            panic!("Bounds should be included on both ends.");
         }
     }
 
@@ -292,7 +360,7 @@ mod test {
             );
         } else {
             //Bounds should be included on both ends.
-            assert!(false);
+            panic!("Bounds should be included on both ends.");
         }
     }
 }
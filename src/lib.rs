@@ -18,37 +18,67 @@
 //! * `query!{ ? -a-> b }` becomes a query on the position-object-subject table.
 //! * `query!{ a -?-> b }` becomes a query on the object-subject-position table.
 //!
+//! Edges may also be placed in named graphs (see [QuadStore][prelude::QuadStore]), which are stored three more times with the graph
+//! leading each key: Graph, Subject, Predicate, Object; Graph, Predicate, Object, Subject; and Graph, Object, Subject,
+//! Predicate.
+//!
 //! ## Supported Key-Value Backends
 //!   * [Memory](https://docs.rs/simple-triplestore/latest/simple_triplestore/struct.MemTripleStore.html)
 //!   * [Sled](https://docs.rs/simple-triplestore/latest/simple_triplestore/struct.SledTripleStore.html) ( with the `sled` feature )
+//!
+//! ## RDF
+//! With the `rdf` feature, [RdfTripleStore] allows nodes and edges to be addressed by name (see [Entity]) on top of
+//! either backend.
+//! It can be loaded from and written to [N-Triples][rdf::ntriples], [Turtle][rdf::turtle], and, with named graphs,
+//! [N-Quads][rdf::nquads] and [TriG][rdf::trig], and queried with
+//! [SPARQL][rdf::sparql].
+//!
+//! ## Backups
+//! With the `dump` feature, any store can be exported to and imported from a portable binary format with [dump], for
+//! backups or to move data between backends.
 
 use std::collections::HashSet;
 
 #[cfg(test)]
 mod conformance;
+#[cfg(feature = "dump")]
+pub mod dump;
 pub mod id;
 pub mod mem;
+pub mod path;
+pub mod pattern;
 pub mod prelude;
 #[cfg(feature = "rdf")]
 pub mod rdf;
+pub mod reasoner;
 #[cfg(feature = "sled")]
 pub mod sled;
 pub mod traits;
+pub mod traversal;
 pub mod triple;
+pub mod validation;
 
+#[cfg(all(feature = "rdf", feature = "sled"))]
+pub use crate::rdf::SledRdfTripleStore;
+#[cfg(feature = "rdf")]
+pub use crate::rdf::{Entity, MemRdfTripleStore, RdfTripleStore, RdfTripleStoreError};
 #[cfg(feature = "sled")]
-pub use crate::sled::{SledTripleStore, SledTripleStoreError};
+pub use crate::sled::{SledTripleStore, SledTripleStoreError, SledU64IdGenerator};
 pub use crate::{
     id::ulid::UlidIdGenerator,
     mem::MemTripleStore,
-    traits::{ExtendError, IdGenerator, MergeError, Mergeable, QueryError, SetOpsError},
-    triple::{PropsTriple, Triple},
+    traits::{
+        Cursor, ExtendError, IdGenerator, IdGeneratorError, MergeError, Mergeable, Page,
+        PropsFilter, QueryError, QueryPages, RunError, SetOpsError,
+    },
+    triple::{PropsTriple, Quad, Triple},
 };
 
 /// The order for edges which should be returned.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum EdgeOrder {
     /// Subject, Predicate, Object
+    #[default]
     SPO,
 
     /// Predicate, Object, Subject,
@@ -58,10 +88,18 @@ pub enum EdgeOrder {
     OSP,
 }
 
-impl Default for EdgeOrder {
-    fn default() -> Self {
-        Self::SPO
-    }
+/// The order for the edges of named graphs which should be returned. Each ordering is grouped by graph first.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum QuadOrder {
+    /// Graph, Subject, Predicate, Object
+    #[default]
+    GSPO,
+
+    /// Graph, Predicate, Object, Subject
+    GPOS,
+
+    /// Graph, Object, Subject, Predicate
+    GOSP,
 }
 
 /// Represents a query which can be executed on a [TripleStore][crate::TripleStore].
@@ -307,8 +345,80 @@ impl<Id: traits::ConcreteIdType, I: IntoIterator<Item = Triple<Id>>> From<I> for
 ///     )
 /// );
 /// ```
+///
+/// ### Pattern
+/// To match a [basic graph pattern][crate::pattern::Pattern] with variables, for use with
+/// [run_pattern()][crate::traits::TripleStorePattern::run_pattern]. Variables are written `?name`, fixed ids are
+/// written `[id]`, and triples are separated by `.`:
+///
+/// ```
+/// # use ulid::Ulid;
+/// # use simple_triplestore::{prelude::*, pattern::{Pattern, Term}, query};
+/// # let a = Ulid(1);
+/// # let b = Ulid(2);
+/// assert_eq!(
+///     query! { pattern { ?x -[a]-> ?y . ?y -?p-> [b] } },
+///     Pattern::new()
+///         .with(Term::var("x"), a, Term::var("y"))
+///         .with(Term::var("y"), Term::var("p"), b)
+/// );
+/// ```
+///
+/// ### Rule
+/// To build an inference [rule][crate::reasoner::Rule] for a [Reasoner][crate::reasoner::Reasoner], from a body pattern
+/// and a head pattern written as above:
+///
+/// ```
+/// # use ulid::Ulid;
+/// # use simple_triplestore::{prelude::*, pattern::{Pattern, Term}, reasoner::Rule, query};
+/// # let a = Ulid(1);
+/// # let b = Ulid(2);
+/// assert_eq!(
+///     query! { rule { ?x -[a]-> ?y } => { ?y -[b]-> ?x } },
+///     Rule::new(
+///         Pattern::new().with(Term::var("x"), a, Term::var("y")),
+///         Pattern::new().with(Term::var("y"), b, Term::var("x"))
+///     )
+/// );
+/// ```
+///
+/// ### Path
+/// To build a [property path][crate::path::PropertyPath], for use with
+/// [run_path()][crate::traits::TripleStorePattern::run_path]. Predicates are written `[id]` and combined with `^`
+/// (inverse), `/` (sequence) and `|` (alternatives), repeated with `?`, `*` and `+`, and grouped with parentheses:
+///
+/// ```
+/// # use ulid::Ulid;
+/// # use simple_triplestore::{prelude::*, path::PropertyPath, query};
+/// # let a = Ulid(1);
+/// # let b = Ulid(2);
+/// assert_eq!(
+///     query! { path { [a]+ / ^[b] } },
+///     PropertyPath::pred(a).one_or_more().then(PropertyPath::pred(b).inverse())
+/// );
+/// ```
 #[macro_export]
 macro_rules! query {
+    // Basic graph pattern with variables
+    (pattern { $($triples:tt)* }) => {{
+        let mut pattern = $crate::pattern::Pattern::new();
+        $crate::__query_pattern!(@triples pattern $($triples)*);
+        pattern
+    }};
+
+    // Inference rule
+    (rule { $($body:tt)* } => { $($head:tt)* }) => {{
+        $crate::reasoner::Rule::new(
+            $crate::query!(pattern { $($body)* }),
+            $crate::query!(pattern { $($head)* }),
+        )
+    }};
+
+    // Property path
+    (path { $($path:tt)* }) => {{
+        $crate::__query_path!(@alt () () $($path)*)
+    }};
+
     // Match specific source, edge, and destination
     (node props for $nodes:tt) => {{
         $crate::Query::NodeProps($nodes.into_iter().collect())
@@ -8,13 +8,24 @@ use std::{
     hash::{Hash, Hasher},
 };
 
+mod bulk_load;
+mod count;
 mod extend;
+mod get;
+mod index;
 mod insert;
 mod iter;
 mod merge;
+mod paginate;
+mod pattern;
+mod quad;
 mod query;
 mod remove;
 mod set;
+mod stats;
+mod subscribe;
+mod transaction;
+mod verify;
 
 /// A triple store implemented entirely in memory using [BTreeMap][std::collections::BTreeMap].
 ///
@@ -114,6 +125,16 @@ pub struct MemTripleStore<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Pr
     spo_data: BTreeMap<Id::TripleByteArrayType, Id>,
     pos_data: BTreeMap<Id::TripleByteArrayType, Id>,
     osp_data: BTreeMap<Id::TripleByteArrayType, Id>,
+    quad_props: BTreeMap<Id, EdgeProps>,
+    gspo_data: BTreeMap<Id::QuadByteArrayType, Id>,
+    gpos_data: BTreeMap<Id::QuadByteArrayType, Id>,
+    gosp_data: BTreeMap<Id::QuadByteArrayType, Id>,
+    spog_data: BTreeMap<Id::QuadByteArrayType, Id>,
+    ospg_data: BTreeMap<Id::QuadByteArrayType, Id>,
+    node_indexes: BTreeMap<String, index::MemIndex<NodeProps, Id>>,
+    edge_indexes: BTreeMap<String, index::MemIndex<EdgeProps, (Id, Id, Id)>>,
+    predicate_stats: HashMap<Id, crate::traits::PredicateStats>,
+    subscribers: crate::traits::Subscribers<Id, NodeProps, EdgeProps>,
     id_generator: Box<dyn IdGenerator<Id>>,
 }
 
@@ -144,7 +165,7 @@ impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property> std::fmt::Deb
                     k.hash(&mut hash_builder);
                     hash = hash_builder.finish();
                 }
-                (v.clone(), hash)
+                (*v, hash)
             })
             .collect::<HashMap<_, _>>();
 
@@ -175,7 +196,7 @@ impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property> std::fmt::Deb
 
         f.write_str(" Edges (SPO):\n")?;
         for (triple, ulid) in self.spo_data.iter() {
-            let triple = Id::decode_spo_triple(&triple);
+            let triple = Id::decode_spo_triple(triple);
             f.write_fmt(format_args!(
                 "  ({}, {}, {}) -> ",
                 triple.sub, triple.pred, triple.obj
@@ -192,7 +213,7 @@ impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property> std::fmt::Deb
 
         f.write_str(" Edges (POS):\n")?;
         for (triple, ulid) in self.pos_data.iter() {
-            let triple = Id::decode_pos_triple(&triple);
+            let triple = Id::decode_pos_triple(triple);
             f.write_fmt(format_args!(
                 "  ({}, {}, {}) -> ",
                 triple.sub, triple.pred, triple.obj
@@ -209,7 +230,7 @@ impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property> std::fmt::Deb
 
         f.write_str(" Edges (OSP):\n")?;
         for (triple, ulid) in self.osp_data.iter() {
-            let triple = Id::decode_osp_triple(&triple);
+            let triple = Id::decode_osp_triple(triple);
             f.write_fmt(format_args!(
                 "  ({}, {}, {}) -> ",
                 triple.sub, triple.pred, triple.obj
@@ -224,6 +245,19 @@ impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property> std::fmt::Deb
             }
         }
 
+        f.write_str(" Quads (GSPO):\n")?;
+        for (quad, ulid) in self.gspo_data.iter() {
+            let quad = crate::Quad::<Id>::decode(&crate::QuadOrder::GSPO, quad);
+            f.write_fmt(format_args!(
+                "  ({}, {}, {}, {}) -> {:?}\n",
+                quad.graph,
+                quad.sub,
+                quad.pred,
+                quad.obj,
+                self.quad_props.get(ulid)
+            ))?;
+        }
+
         Ok(())
     }
 }
@@ -261,17 +295,18 @@ impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property> PartialEq
             }
         };
 
+        type EdgeEntry<'a, Key, Id> = (&'a Key, &'a Id);
         let mut check_edge =
             move |((self_edge, self_edge_prop_id), (other_edge, other_edge_prop_id)): (
-                (&Id::TripleByteArrayType, &Id),
-                (&Id::TripleByteArrayType, &Id),
+                EdgeEntry<Id::TripleByteArrayType, Id>,
+                EdgeEntry<Id::TripleByteArrayType, Id>,
             )| {
                 // Test the Keys
                 if self_edge.ne(other_edge) {
                     return false;
                 }
                 // Test the Values
-                eq_edge_prop_by_id(self_edge_prop_id.clone(), other_edge_prop_id.clone())
+                eq_edge_prop_by_id(*self_edge_prop_id, *other_edge_prop_id)
             };
 
         // SPO
@@ -281,7 +316,15 @@ impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property> PartialEq
             }
         }
 
-        true
+        // Named graphs
+        self.gspo_data.len() == other.gspo_data.len()
+            && self.gspo_data.iter().zip(other.gspo_data.iter()).all(
+                |((self_quad, self_props_id), (other_quad, other_props_id))| {
+                    self_quad == other_quad
+                        && self.quad_props.get(self_props_id)
+                            == other.quad_props.get(other_props_id)
+                },
+            )
     }
 }
 
@@ -301,7 +344,17 @@ impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
             spo_data: BTreeMap::new(),
             pos_data: BTreeMap::new(),
             osp_data: BTreeMap::new(),
-            id_generator: id_generator,
+            quad_props: BTreeMap::new(),
+            gspo_data: BTreeMap::new(),
+            gpos_data: BTreeMap::new(),
+            gosp_data: BTreeMap::new(),
+            spog_data: BTreeMap::new(),
+            ospg_data: BTreeMap::new(),
+            node_indexes: BTreeMap::new(),
+            edge_indexes: BTreeMap::new(),
+            predicate_stats: HashMap::new(),
+            subscribers: crate::traits::Subscribers::new(),
+            id_generator,
         }
     }
 }
@@ -334,6 +387,12 @@ mod rdf {
         right_to_left: HashMap<Right, Left>,
     }
 
+    impl<Left: IndexType, Right: IndexType> Default for MemHashIndex<Left, Right> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
     impl<Left: IndexType, Right: IndexType> MemHashIndex<Left, Right> {
         pub fn new() -> Self {
             Self {
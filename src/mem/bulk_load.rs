@@ -0,0 +1,139 @@
+use crate::{
+    prelude::*,
+    traits::{sort_last_wins, ConcreteIdType, Event, Property},
+    Triple,
+};
+
+use super::MemTripleStore;
+
+impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
+    TripleStoreBulkLoad<Id, NodeProps, EdgeProps> for MemTripleStore<Id, NodeProps, EdgeProps>
+{
+    fn insert_nodes(
+        &mut self,
+        nodes: impl IntoIterator<Item = (Id, NodeProps)>,
+    ) -> Result<(), Self::Error> {
+        let mut nodes = nodes.into_iter().collect::<Vec<_>>();
+        sort_last_wins(&mut nodes, |(a, _), (b, _)| a.cmp(b));
+        let inserted = if self.subscribers.is_active() {
+            nodes.iter().map(|(node, _)| *node).collect()
+        } else {
+            Vec::new()
+        };
+
+        if self.node_indexes.is_empty() {
+            self.node_props.extend(nodes);
+        } else {
+            for (node, props) in nodes {
+                let old_props = self.node_props.insert(node, props);
+                self.reindex_node(&node, old_props.as_ref());
+            }
+        }
+
+        for node in inserted {
+            self.subscribers.publish(|| Event::NodeInserted {
+                node,
+                props: self.node_props[&node].clone(),
+            });
+        }
+        Ok(())
+    }
+
+    fn bulk_load(
+        &mut self,
+        edges: impl IntoIterator<Item = (Triple<Id>, EdgeProps)>,
+        assume_no_duplicates: bool,
+    ) -> Result<(), Self::Error> {
+        let mut edges = edges
+            .into_iter()
+            .map(|(triple, props)| (Id::encode_spo_triple(&triple), triple, props))
+            .collect::<Vec<_>>();
+        if assume_no_duplicates {
+            edges.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
+        } else {
+            sort_last_wins(&mut edges, |(a, _, _), (b, _, _)| a.cmp(b));
+        }
+
+        let mut old_edges = Vec::new();
+        let mut added = Vec::new();
+        let mut inserted = Vec::new();
+        let mut spo = Vec::with_capacity(edges.len());
+        let mut pos = Vec::with_capacity(edges.len());
+        let mut osp = Vec::with_capacity(edges.len());
+        let mut props = Vec::with_capacity(edges.len());
+        for (spo_triple, triple, edge_props) in edges {
+            let old_props = if assume_no_duplicates {
+                added.push(triple.clone());
+                None
+            } else {
+                let old_props = self.indexed_edge_props(&triple);
+                match self.spo_data.get(&spo_triple) {
+                    Some(old_edge_data_id) => {
+                        self.edge_props.remove(old_edge_data_id);
+                    }
+                    None => added.push(triple.clone()),
+                }
+                old_props
+            };
+            if !self.edge_indexes.is_empty() {
+                old_edges.push((triple.clone(), old_props));
+            }
+
+            let edge_data_id = self.id_generator.fresh();
+            if self.subscribers.is_active() {
+                inserted.push((triple.clone(), edge_data_id));
+            }
+            spo.push((spo_triple, edge_data_id));
+            pos.push((Id::encode_pos_triple(&triple), edge_data_id));
+            osp.push((Id::encode_osp_triple(&triple), edge_data_id));
+            props.push((edge_data_id, edge_props));
+        }
+
+        // The SPO keys are already in order. Stable sorts keep duplicates in the order given, so the last one wins.
+        pos.sort_by(|(a, _), (b, _)| a.cmp(b));
+        osp.sort_by(|(a, _), (b, _)| a.cmp(b));
+        props.sort_unstable_by_key(|(a, _)| *a);
+
+        self.spo_data.extend(spo);
+        self.pos_data.extend(pos);
+        self.osp_data.extend(osp);
+        self.edge_props.extend(props);
+        self.count_edges(&added, true)?;
+
+        for (triple, old_props) in old_edges {
+            self.reindex_edge(&triple, old_props.as_ref());
+        }
+
+        for (triple, edge_data_id) in inserted {
+            self.subscribers.publish(|| Event::EdgeInserted {
+                triple,
+                props: self.edge_props[&edge_data_id].clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{MemTripleStore, UlidIdGenerator};
+
+    #[test]
+    fn test_bulk_load() {
+        crate::conformance::bulk_load::test_bulk_load(MemTripleStore::new(UlidIdGenerator::new()));
+    }
+
+    #[test]
+    fn test_bulk_load_no_duplicates() {
+        crate::conformance::bulk_load::test_bulk_load_no_duplicates(MemTripleStore::new(
+            UlidIdGenerator::new(),
+        ));
+    }
+
+    #[test]
+    fn test_bulk_load_indexed() {
+        crate::conformance::bulk_load::test_bulk_load_indexed(MemTripleStore::new(
+            UlidIdGenerator::new(),
+        ));
+    }
+}
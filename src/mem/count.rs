@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+
+use crate::{
+    prelude::*,
+    traits::{ConcreteIdType, Property},
+};
+
+use super::MemTripleStore;
+
+impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
+    TripleStoreCount<Id, NodeProps, EdgeProps> for MemTripleStore<Id, NodeProps, EdgeProps>
+{
+    fn len_nodes(&self) -> Result<usize, ()> {
+        Ok(self.node_props.len())
+    }
+
+    fn len_edges(&self) -> Result<usize, ()> {
+        Ok(self.spo_data.len())
+    }
+
+    fn count_by_predicate(&self) -> Result<HashMap<Id, usize>, ()> {
+        Ok(self
+            .predicate_stats
+            .iter()
+            .map(|(pred, stats)| (*pred, stats.edges as usize))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{MemTripleStore, UlidIdGenerator};
+
+    #[test]
+    fn test_count() {
+        crate::conformance::count::test_count(MemTripleStore::new(UlidIdGenerator::new()));
+    }
+
+    #[test]
+    fn test_count_aggregates() {
+        crate::conformance::count::test_count_aggregates(MemTripleStore::new(
+            UlidIdGenerator::new(),
+        ));
+    }
+}
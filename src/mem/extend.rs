@@ -1,4 +1,8 @@
-use crate::{prelude::*, traits::ConcreteIdType, traits::Property, ExtendError};
+use crate::{
+    prelude::*,
+    traits::{ConcreteIdType, Event, Property},
+    ExtendError,
+};
 
 use super::MemTripleStore;
 
@@ -13,6 +17,7 @@ impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
 
         for r in other_nodes {
             let (id, data) = r.map_err(|e| ExtendError::Right(e))?;
+            let old_props = self.indexed_node_props(&id);
             match self.node_props.entry(id) {
                 std::collections::btree_map::Entry::Occupied(mut o) => {
                     *o.get_mut() = data;
@@ -21,12 +26,18 @@ impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
                     v.insert(data);
                 }
             }
+            self.reindex_node(&id, old_props.as_ref());
+            self.subscribers.publish(|| Event::NodeInserted {
+                node: id,
+                props: self.node_props[&id].clone(),
+            });
         }
 
         for r in other_edges {
             let (id, other_edge_props) = r.map_err(|e| ExtendError::Right(e))?;
+            let old_props = self.indexed_edge_props(&id);
 
-            match self.spo_data.entry(Id::encode_spo_triple(&id)) {
+            let edge_props_id = match self.spo_data.entry(Id::encode_spo_triple(&id)) {
                 std::collections::btree_map::Entry::Vacant(self_spo_data_v) => {
                     // We don't have this edge already.
                     let other_edge_props_id = self.id_generator.fresh();
@@ -38,12 +49,15 @@ impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
                         .insert(Id::encode_osp_triple(&id), other_edge_props_id);
                     self.edge_props
                         .insert(other_edge_props_id, other_edge_props);
+                    self.count_edges(std::slice::from_ref(&id), true)
+                        .map_err(ExtendError::Left)?;
+                    other_edge_props_id
                 }
 
                 std::collections::btree_map::Entry::Occupied(self_spo_data_o) => {
-                    let self_edge_props_id = self_spo_data_o.get();
+                    let self_edge_props_id = *self_spo_data_o.get();
 
-                    let self_edge_data = self.edge_props.entry(*self_edge_props_id);
+                    let self_edge_data = self.edge_props.entry(self_edge_props_id);
 
                     // Merge our edge props using the existing id.
 
@@ -56,8 +70,14 @@ impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
                             *self_o.get_mut() = other_edge_props;
                         }
                     }
+                    self_edge_props_id
                 }
             };
+            self.reindex_edge(&id, old_props.as_ref());
+            self.subscribers.publish(|| Event::EdgeInserted {
+                props: self.edge_props[&edge_props_id].clone(),
+                triple: id,
+            });
         }
 
         Ok(())
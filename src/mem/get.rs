@@ -0,0 +1,48 @@
+use crate::{
+    prelude::*,
+    traits::{ConcreteIdType, Property},
+    Triple,
+};
+
+use super::MemTripleStore;
+
+impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
+    TripleStoreGet<Id, NodeProps, EdgeProps> for MemTripleStore<Id, NodeProps, EdgeProps>
+{
+    fn get_node(&self, id: &Id) -> Result<Option<NodeProps>, ()> {
+        Ok(self.node_props.get(id).cloned())
+    }
+
+    fn get_edge(&self, triple: &Triple<Id>) -> Result<Option<EdgeProps>, ()> {
+        Ok(self
+            .spo_data
+            .get(&Id::encode_spo_triple(triple))
+            .and_then(|data_id| self.edge_props.get(data_id))
+            .cloned())
+    }
+
+    fn contains_node(&self, id: &Id) -> Result<bool, ()> {
+        Ok(self.node_props.contains_key(id))
+    }
+
+    fn contains_edge(&self, triple: &Triple<Id>) -> Result<bool, ()> {
+        Ok(self.spo_data.contains_key(&Id::encode_spo_triple(triple)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{MemTripleStore, UlidIdGenerator};
+
+    #[test]
+    fn test_get_node() {
+        let db = MemTripleStore::new(UlidIdGenerator::new());
+        crate::conformance::get::test_get_node(db);
+    }
+
+    #[test]
+    fn test_get_edge() {
+        let db = MemTripleStore::new(UlidIdGenerator::new());
+        crate::conformance::get::test_get_edge(db);
+    }
+}
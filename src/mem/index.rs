@@ -0,0 +1,190 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::RangeBounds,
+};
+
+use crate::{
+    prelude::*,
+    traits::{encode_key_range, key_extractor, ConcreteIdType, IndexKey, KeyExtractor, Property},
+    Triple,
+};
+
+use super::MemTripleStore;
+
+/// A secondary index from encoded keys to the set of values which produced them.
+pub(super) struct MemIndex<Props, Value> {
+    extract: KeyExtractor<Props>,
+    entries: BTreeMap<Vec<u8>, BTreeSet<Value>>,
+}
+
+impl<Props, Value: Ord + Clone> MemIndex<Props, Value> {
+    fn new(extract: KeyExtractor<Props>) -> Self {
+        Self {
+            extract,
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// Replace the entries for `value` produced by `old` with those produced by `new`.
+    pub(super) fn update(&mut self, value: &Value, old: Option<&Props>, new: Option<&Props>) {
+        if let Some(old) = old {
+            for key in (self.extract)(old) {
+                if let std::collections::btree_map::Entry::Occupied(mut o) = self.entries.entry(key)
+                {
+                    o.get_mut().remove(value);
+                    if o.get().is_empty() {
+                        o.remove();
+                    }
+                }
+            }
+        }
+
+        if let Some(new) = new {
+            for key in (self.extract)(new) {
+                self.entries.entry(key).or_default().insert(value.clone());
+            }
+        }
+    }
+}
+
+impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
+    MemTripleStore<Id, NodeProps, EdgeProps>
+{
+    /// Clone the current props of a node if they will be needed to update the node indexes.
+    pub(super) fn indexed_node_props(&self, node: &Id) -> Option<NodeProps> {
+        if self.node_indexes.is_empty() {
+            return None;
+        }
+        self.node_props.get(node).cloned()
+    }
+
+    /// Clone the current props of an edge if they will be needed to update the edge indexes.
+    pub(super) fn indexed_edge_props(&self, triple: &Triple<Id>) -> Option<EdgeProps> {
+        if self.edge_indexes.is_empty() {
+            return None;
+        }
+        self.spo_data
+            .get(&Id::encode_spo_triple(triple))
+            .and_then(|data_id| self.edge_props.get(data_id))
+            .cloned()
+    }
+
+    /// Bring the node indexes up to date after the props of `node` changed from `old`.
+    pub(super) fn reindex_node(&mut self, node: &Id, old: Option<&NodeProps>) {
+        let new = self.node_props.get(node);
+        for index in self.node_indexes.values_mut() {
+            index.update(node, old, new);
+        }
+    }
+
+    /// Bring the edge indexes up to date after the props of `triple` changed from `old`.
+    pub(super) fn reindex_edge(&mut self, triple: &Triple<Id>, old: Option<&EdgeProps>) {
+        let new = self
+            .spo_data
+            .get(&Id::encode_spo_triple(triple))
+            .and_then(|data_id| self.edge_props.get(data_id));
+        let value = (triple.sub, triple.pred, triple.obj);
+        for index in self.edge_indexes.values_mut() {
+            index.update(&value, old, new);
+        }
+    }
+}
+
+impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
+    TripleStoreIndex<Id, NodeProps, EdgeProps> for MemTripleStore<Id, NodeProps, EdgeProps>
+{
+    fn register_node_index<K: IndexKey>(
+        &mut self,
+        name: &str,
+        extract: impl Fn(&NodeProps) -> Vec<K> + 'static,
+    ) -> Result<(), ()> {
+        let mut index = MemIndex::new(key_extractor(extract));
+        for (node, props) in self.node_props.iter() {
+            index.update(node, None, Some(props));
+        }
+        self.node_indexes.insert(name.to_string(), index);
+        Ok(())
+    }
+
+    fn register_edge_index<K: IndexKey>(
+        &mut self,
+        name: &str,
+        extract: impl Fn(&EdgeProps) -> Vec<K> + 'static,
+    ) -> Result<(), ()> {
+        let mut index = MemIndex::new(key_extractor(extract));
+        for (key, data_id) in self.spo_data.iter() {
+            let triple = Id::decode_spo_triple(key);
+            if let Some(props) = self.edge_props.get(data_id) {
+                index.update(&(triple.sub, triple.pred, triple.obj), None, Some(props));
+            }
+        }
+        self.edge_indexes.insert(name.to_string(), index);
+        Ok(())
+    }
+
+    fn range_nodes<'a, K: IndexKey>(
+        &'a self,
+        name: &str,
+        range: impl RangeBounds<K>,
+    ) -> impl Iterator<Item = Result<Id, ()>> + 'a {
+        let nodes: Box<dyn Iterator<Item = Result<Id, ()>>> =
+            match (self.node_indexes.get(name), encode_key_range(&range)) {
+                (Some(index), Some(range)) => Box::new(
+                    index
+                        .entries
+                        .range(range)
+                        .flat_map(|(_, nodes)| nodes.iter().map(|node| Ok(*node))),
+                ),
+                (Some(_), None) => Box::new(std::iter::empty()),
+                (None, _) => Box::new(std::iter::once(Err(()))),
+            };
+        nodes
+    }
+
+    fn range_edges<'a, K: IndexKey>(
+        &'a self,
+        name: &str,
+        range: impl RangeBounds<K>,
+    ) -> impl Iterator<Item = Result<Triple<Id>, ()>> + 'a {
+        let edges: Box<dyn Iterator<Item = Result<Triple<Id>, ()>>> =
+            match (self.edge_indexes.get(name), encode_key_range(&range)) {
+                (Some(index), Some(range)) => {
+                    Box::new(index.entries.range(range).flat_map(|(_, edges)| {
+                        edges.iter().map(|(sub, pred, obj)| {
+                            Ok(Triple {
+                                sub: *sub,
+                                pred: *pred,
+                                obj: *obj,
+                            })
+                        })
+                    }))
+                }
+                (Some(_), None) => Box::new(std::iter::empty()),
+                (None, _) => Box::new(std::iter::once(Err(()))),
+            };
+        edges
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{MemTripleStore, UlidIdGenerator};
+
+    #[test]
+    fn test_node_index() {
+        crate::conformance::index::test_node_index(MemTripleStore::new(UlidIdGenerator::new()));
+    }
+
+    #[test]
+    fn test_edge_index() {
+        crate::conformance::index::test_edge_index(MemTripleStore::new(UlidIdGenerator::new()));
+    }
+
+    #[test]
+    fn test_index_merge() {
+        crate::conformance::index::test_index_merge(
+            MemTripleStore::new(UlidIdGenerator::new()),
+            MemTripleStore::new(UlidIdGenerator::new()),
+        );
+    }
+}
@@ -1,17 +1,32 @@
-use crate::{prelude::*, traits::ConcreteIdType, traits::Property, Triple};
+use crate::{
+    prelude::*,
+    traits::{ConcreteIdType, Event, Property},
+    Triple,
+};
 
 use super::MemTripleStore;
 
 impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
     MemTripleStore<Id, NodeProps, EdgeProps>
 {
-    pub(super) fn insert_edge_data_internal(&mut self, triple: &Triple<Id>, new_edge_data_id: &Id) {
-        self.spo_data
-            .insert(Id::encode_spo_triple(&triple), new_edge_data_id.clone());
+    pub(super) fn insert_edge_data_internal(
+        &mut self,
+        triple: &Triple<Id>,
+        new_edge_data_id: &Id,
+    ) -> Result<(), ()> {
+        let added = self
+            .spo_data
+            .insert(Id::encode_spo_triple(triple), *new_edge_data_id)
+            .is_none();
         self.pos_data
-            .insert(Id::encode_pos_triple(&triple), new_edge_data_id.clone());
+            .insert(Id::encode_pos_triple(triple), *new_edge_data_id);
         self.osp_data
-            .insert(Id::encode_osp_triple(&triple), new_edge_data_id.clone());
+            .insert(Id::encode_osp_triple(triple), *new_edge_data_id);
+
+        if added {
+            self.count_edges(std::slice::from_ref(triple), true)?;
+        }
+        Ok(())
     }
 
     /// Handles the case where we are treating the edge data as new for the first time.
@@ -23,7 +38,7 @@ impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
     ) -> Id {
         // Clean up the old data.
         if let Some(old_edge_data_id) = old_edge_data_id {
-            self.edge_props.remove(&old_edge_data_id);
+            self.edge_props.remove(old_edge_data_id);
         }
 
         // Insert the new data with a fresh Ulid.
@@ -31,33 +46,49 @@ impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
         self.edge_props.insert(new_edge_data_id, new_edge_data);
         new_edge_data_id
     }
+
+    /// Replace the props of `node`, without publishing an event.
+    pub(super) fn insert_node_internal(&mut self, node: Id, data: NodeProps) {
+        let old_props = self.indexed_node_props(&node);
+        self.node_props.insert(node, data);
+        self.reindex_node(&node, old_props.as_ref());
+    }
+
+    /// Replace the props of `triple`, without publishing an event. Returns the id of the new props.
+    pub(super) fn insert_edge_internal(
+        &mut self,
+        triple: &Triple<Id>,
+        data: EdgeProps,
+    ) -> Result<Id, ()> {
+        let old_props = self.indexed_edge_props(triple);
+
+        let old_edge_data_id = self.spo_data.get(&Id::encode_spo_triple(triple)).copied();
+        let new_edge_data_id = self.insert_edge_create_data(&old_edge_data_id, data);
+        self.insert_edge_data_internal(triple, &new_edge_data_id)?;
+
+        self.reindex_edge(triple, old_props.as_ref());
+        Ok(new_edge_data_id)
+    }
 }
 
 impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
     TripleStoreInsert<Id, NodeProps, EdgeProps> for MemTripleStore<Id, NodeProps, EdgeProps>
 {
     fn insert_node(&mut self, node: Id, data: NodeProps) -> Result<(), Self::Error> {
-        match self.node_props.entry(node) {
-            std::collections::btree_map::Entry::Occupied(mut o) => {
-                o.insert(data);
-            }
-            std::collections::btree_map::Entry::Vacant(v) => {
-                v.insert(data);
-            }
-        }
+        self.insert_node_internal(node, data);
+        self.subscribers.publish(|| Event::NodeInserted {
+            node,
+            props: self.node_props[&node].clone(),
+        });
         Ok(())
     }
 
     fn insert_edge(&mut self, triple: Triple<Id>, data: EdgeProps) -> Result<(), Self::Error> {
-        let old_edge_data_id = match self.spo_data.entry(Id::encode_spo_triple(&triple)) {
-            std::collections::btree_map::Entry::Vacant(_) => None,
-            std::collections::btree_map::Entry::Occupied(o) => Some(o.get().clone()),
-        };
-
-        let new_edge_data_id = self.insert_edge_create_data(&old_edge_data_id, data);
-
-        self.insert_edge_data_internal(&triple, &new_edge_data_id);
-
+        let new_edge_data_id = self.insert_edge_internal(&triple, data)?;
+        self.subscribers.publish(|| Event::EdgeInserted {
+            props: self.edge_props[&new_edge_data_id].clone(),
+            triple,
+        });
         Ok(())
     }
 }
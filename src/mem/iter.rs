@@ -32,11 +32,11 @@ impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
     }
 }
 
-impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property> TripleStoreIter<Id, NodeProps, EdgeProps>
-    for MemTripleStore<Id, NodeProps, EdgeProps>
+impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
+    TripleStoreIter<Id, NodeProps, EdgeProps> for MemTripleStore<Id, NodeProps, EdgeProps>
 {
     fn vertices(&self) -> Result<impl Iterator<Item = Id>, Self::Error> {
-        Ok(self.node_props.iter().map(|e| e.0.clone()))
+        Ok(self.node_props.iter().map(|e| *e.0))
     }
 
     fn iter_nodes(
@@ -52,7 +52,7 @@ impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property> TripleStoreIt
     fn iter_vertices<'a>(&'a self) -> impl Iterator<Item = Result<(Id, NodeProps), ()>> + 'a {
         self.node_props
             .iter()
-            .map(|(id, props)| Ok((id.clone(), props.clone())))
+            .map(|(id, props)| Ok((*id, props.clone())))
     }
 
     fn iter_edges_with_props<'a>(
@@ -78,7 +78,7 @@ impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property> TripleStoreIt
         };
 
         edges.filter_map(|(k, v)| {
-            MemTripleStore::iter_impl(&self.node_props, &self.edge_props, k, &v)
+            MemTripleStore::iter_impl(&self.node_props, &self.edge_props, k, v)
         })
     }
 
@@ -104,10 +104,7 @@ impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property> TripleStoreIt
             ),
         };
 
-        edges.filter_map(|(k, v)| match self.edge_props.get(&v) {
-            Some(v) => Some(Ok((k, v.clone()))),
-            None => None,
-        })
+        edges.filter_map(|(k, v)| self.edge_props.get(v).map(|v| Ok((k, v.clone()))))
     }
 }
 
@@ -121,7 +118,7 @@ impl<Id: ConcreteIdType, NodeProps: Property + PartialEq, EdgeProps: Property +
         impl Iterator<Item = Result<(Id, NodeProps), Self::Error>>,
         impl Iterator<Item = Result<(Triple<Id>, EdgeProps), Self::Error>>,
     ) {
-        let node_iter = self.node_props.into_iter().map(|o| Ok(o));
+        let node_iter = self.node_props.into_iter().map(Ok);
         let edge_iter = {
             let edges: Box<dyn Iterator<Item = _>> = match order {
                 EdgeOrder::SPO => Box::new(
@@ -141,18 +138,15 @@ impl<Id: ConcreteIdType, NodeProps: Property + PartialEq, EdgeProps: Property +
                 ),
             };
 
-            edges.filter_map(
-                move |(k, v): (Triple<Id>, Id)| match self.edge_props.get(&v) {
-                    Some(v) => Some(Ok((k, v.clone()))),
-                    None => None,
-                },
-            )
+            edges.filter_map(move |(k, v): (Triple<Id>, Id)| {
+                self.edge_props.get(&v).map(|v| Ok((k, v.clone())))
+            })
         };
         (node_iter, edge_iter)
     }
 
     fn into_iter_vertices(self) -> impl Iterator<Item = Result<(Id, NodeProps), ()>> {
-        self.node_props.into_iter().map(|o| Ok(o))
+        self.node_props.into_iter().map(Ok)
     }
 
     fn into_iter_edges_with_props(
@@ -204,10 +198,7 @@ impl<Id: ConcreteIdType, NodeProps: Property + PartialEq, EdgeProps: Property +
             ),
         };
 
-        edges.filter_map(move |(k, v)| match self.edge_props.get(&v) {
-            Some(v) => Some(Ok((k, v.clone()))),
-            None => None,
-        })
+        edges.filter_map(move |(k, v)| self.edge_props.get(&v).map(|v| Ok((k, v.clone()))))
     }
 }
 
@@ -1,5 +1,5 @@
 use crate::{
-    traits::{ConcreteIdType, Mergeable, Property},
+    traits::{ConcreteIdType, Event, Mergeable, MergedProps, Property},
     MergeError, Triple,
 };
 
@@ -14,7 +14,7 @@ impl<Id: ConcreteIdType, NodeProps: Property + Mergeable, EdgeProps: Property +
         new_edge_data: EdgeProps,
     ) -> Id {
         if let Some(old_edge_data_id) = old_edge_data_id {
-            match self.edge_props.entry(old_edge_data_id.clone()) {
+            match self.edge_props.entry(old_edge_data_id) {
                 std::collections::btree_map::Entry::Occupied(mut o) => {
                     o.get_mut().merge(new_edge_data)
                 }
@@ -53,27 +53,52 @@ impl<Id: ConcreteIdType, NodeProps: Property + Mergeable, EdgeProps: Property +
     }
 
     fn merge_node(&mut self, node: Id, data: NodeProps) -> Result<(), ()> {
-        match self.node_props.entry(node) {
+        let old_props = self.indexed_node_props(&node);
+
+        let merged = match self.node_props.entry(node) {
             std::collections::btree_map::Entry::Occupied(mut o) => {
                 o.get_mut().merge(data);
+                true
             }
             std::collections::btree_map::Entry::Vacant(v) => {
                 v.insert(data);
+                false
             }
-        }
+        };
+
+        self.reindex_node(&node, old_props.as_ref());
+        self.subscribers.publish(|| {
+            let props = self.node_props[&node].clone();
+            if merged {
+                Event::PropsMerged(MergedProps::Node { node, props })
+            } else {
+                Event::NodeInserted { node, props }
+            }
+        });
         Ok(())
     }
 
     fn merge_edge(&mut self, triple: Triple<Id>, data: EdgeProps) -> Result<(), ()> {
+        let old_props = self.indexed_edge_props(&triple);
+
         let old_edge_data_id = match self.spo_data.entry(Id::encode_spo_triple(&triple)) {
             std::collections::btree_map::Entry::Vacant(_) => None,
-            std::collections::btree_map::Entry::Occupied(o) => Some(o.get().clone()),
+            std::collections::btree_map::Entry::Occupied(o) => Some(*o.get()),
         };
 
         let new_edge_data_id = self.merge_edge_create_data(old_edge_data_id, data);
 
-        self.insert_edge_data_internal(&triple, &new_edge_data_id);
+        self.insert_edge_data_internal(&triple, &new_edge_data_id)?;
 
+        self.reindex_edge(&triple, old_props.as_ref());
+        self.subscribers.publish(|| {
+            let props = self.edge_props[&new_edge_data_id].clone();
+            if old_edge_data_id.is_some() {
+                Event::PropsMerged(MergedProps::Edge { triple, props })
+            } else {
+                Event::EdgeInserted { triple, props }
+            }
+        });
         Ok(())
     }
 }
@@ -0,0 +1,152 @@
+use std::{collections::BTreeMap, ops::Bound};
+
+use crate::{
+    prelude::*,
+    traits::{
+        collect_page, resume, ConcreteIdType, Cursor, DecodeTriple, KeyRange, Page, Property,
+        QueryPages, QueryScan,
+    },
+    EdgeOrder, Query, Triple,
+};
+
+use super::MemTripleStore;
+
+impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
+    MemTripleStore<Id, NodeProps, EdgeProps>
+{
+    /// Collect a page of `nodes`, which must be in id order.
+    fn page_nodes<'a>(
+        &'a self,
+        nodes: impl Iterator<Item = (&'a Id, &'a NodeProps)>,
+        limit: usize,
+    ) -> Result<Page<(Id, NodeProps)>, ()> {
+        collect_page(
+            nodes.map(|(id, props)| {
+                Ok((
+                    Cursor::new(None, id.to_be_bytes().as_ref()),
+                    (*id, props.clone()),
+                ))
+            }),
+            limit,
+        )
+    }
+
+    /// Page through the given ranges of the edge table for `order`.
+    fn page_table(
+        &self,
+        order: EdgeOrder,
+        ranges: Vec<KeyRange<Id::TripleByteArrayType>>,
+        after: Option<&Cursor>,
+        limit: usize,
+    ) -> Result<Page<(Triple<Id>, EdgeProps)>, ()> {
+        let after = after
+            .map(|cursor| cursor.key(Some(&order)).ok_or(()))
+            .transpose()?;
+        let (table, decode): (&BTreeMap<Id::TripleByteArrayType, Id>, DecodeTriple<Id>) =
+            match order {
+                EdgeOrder::SPO => (&self.spo_data, Id::decode_spo_triple),
+                EdgeOrder::POS => (&self.pos_data, Id::decode_pos_triple),
+                EdgeOrder::OSP => (&self.osp_data, Id::decode_osp_triple),
+            };
+
+        let rows = ranges
+            .into_iter()
+            .filter_map(|bounds| resume(bounds, after.as_ref()))
+            .flat_map(|bounds| table.range(bounds))
+            .filter_map(|(key, data_id)| {
+                self.edge_props.get(data_id).map(|props| {
+                    Ok((
+                        Cursor::new(Some(&order), key.as_ref()),
+                        (decode(key), props.clone()),
+                    ))
+                })
+            });
+        collect_page(rows, limit)
+    }
+}
+
+impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
+    TripleStorePaginate<Id, NodeProps, EdgeProps> for MemTripleStore<Id, NodeProps, EdgeProps>
+{
+    fn page_vertices(
+        &self,
+        after: Option<&Cursor>,
+        limit: usize,
+    ) -> Result<Page<(Id, NodeProps)>, ()> {
+        let start = match after {
+            Some(cursor) => Bound::Excluded(
+                cursor
+                    .key::<Id::ByteArrayType>(None)
+                    .map(|bytes| Id::from_be_bytes(&bytes))
+                    .ok_or(())?,
+            ),
+            None => Bound::Unbounded,
+        };
+        self.page_nodes(self.node_props.range((start, Bound::Unbounded)), limit)
+    }
+
+    fn page_edges(
+        &self,
+        order: EdgeOrder,
+        after: Option<&Cursor>,
+        limit: usize,
+    ) -> Result<Page<(Triple<Id>, EdgeProps)>, ()> {
+        self.page_table(
+            order,
+            vec![(Bound::Unbounded, Bound::Unbounded)],
+            after,
+            limit,
+        )
+    }
+
+    fn page_query(
+        &self,
+        query: Query<Id>,
+        after: Option<&Cursor>,
+        limit: usize,
+    ) -> Result<QueryPages<Id, NodeProps, EdgeProps>, ()> {
+        match QueryScan::new(query) {
+            QueryScan::Nodes(nodes) => {
+                let after = after
+                    .map(|cursor| {
+                        cursor
+                            .key::<Id::ByteArrayType>(None)
+                            .map(|bytes| Id::from_be_bytes(&bytes))
+                            .ok_or(())
+                    })
+                    .transpose()?;
+                let nodes = nodes
+                    .iter()
+                    .filter(|node| after.is_none_or(|after| **node > after))
+                    .filter_map(|node| self.node_props.get_key_value(node));
+                Ok((self.page_nodes(nodes, limit)?, Page::default()))
+            }
+            QueryScan::Edges(order, ranges) => Ok((
+                Page::default(),
+                self.page_table(order, ranges, after, limit)?,
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{MemTripleStore, UlidIdGenerator};
+
+    #[test]
+    fn test_page_vertices() {
+        crate::conformance::paginate::test_page_vertices(MemTripleStore::new(
+            UlidIdGenerator::new(),
+        ));
+    }
+
+    #[test]
+    fn test_page_edges() {
+        crate::conformance::paginate::test_page_edges(MemTripleStore::new(UlidIdGenerator::new()));
+    }
+
+    #[test]
+    fn test_page_query() {
+        crate::conformance::paginate::test_page_query(MemTripleStore::new(UlidIdGenerator::new()));
+    }
+}
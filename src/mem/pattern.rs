@@ -0,0 +1,87 @@
+use crate::{
+    prelude::*,
+    traits::{ConcreteIdType, Property},
+    Triple,
+};
+
+use super::MemTripleStore;
+
+impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
+    TripleStorePattern<Id, NodeProps, EdgeProps> for MemTripleStore<Id, NodeProps, EdgeProps>
+{
+    fn match_triples<'a>(
+        &'a self,
+        sub: Option<Id>,
+        pred: Option<Id>,
+        obj: Option<Id>,
+    ) -> impl Iterator<Item = Result<Triple<Id>, ()>> + 'a {
+        let triples: Box<dyn Iterator<Item = Triple<Id>>> = match (sub, pred, obj) {
+            (Some(sub), Some(pred), Some(obj)) => {
+                let triple = Triple { sub, pred, obj };
+                Box::new(
+                    self.spo_data
+                        .contains_key(&Id::encode_spo_triple(&triple))
+                        .then_some(triple)
+                        .into_iter(),
+                )
+            }
+            (Some(sub), Some(pred), None) => Box::new(
+                self.spo_data
+                    .range(Id::key_bounds_2(sub, pred))
+                    .map(|(key, _)| Id::decode_spo_triple(key)),
+            ),
+            (Some(sub), None, Some(obj)) => Box::new(
+                self.osp_data
+                    .range(Id::key_bounds_2(obj, sub))
+                    .map(|(key, _)| Id::decode_osp_triple(key)),
+            ),
+            (Some(sub), None, None) => Box::new(
+                self.spo_data
+                    .range(Id::key_bounds_1(sub))
+                    .map(|(key, _)| Id::decode_spo_triple(key)),
+            ),
+            (None, Some(pred), Some(obj)) => Box::new(
+                self.pos_data
+                    .range(Id::key_bounds_2(pred, obj))
+                    .map(|(key, _)| Id::decode_pos_triple(key)),
+            ),
+            (None, Some(pred), None) => Box::new(
+                self.pos_data
+                    .range(Id::key_bounds_1(pred))
+                    .map(|(key, _)| Id::decode_pos_triple(key)),
+            ),
+            (None, None, Some(obj)) => Box::new(
+                self.osp_data
+                    .range(Id::key_bounds_1(obj))
+                    .map(|(key, _)| Id::decode_osp_triple(key)),
+            ),
+            (None, None, None) => {
+                Box::new(self.spo_data.keys().map(|key| Id::decode_spo_triple(key)))
+            }
+        };
+        triples.map(Ok)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{MemTripleStore, UlidIdGenerator};
+
+    #[test]
+    fn test_match_triples() {
+        let db = MemTripleStore::new(UlidIdGenerator::new());
+        crate::conformance::pattern::test_match_triples(db);
+    }
+
+    #[test]
+    fn test_run_pattern() {
+        let db = MemTripleStore::new(UlidIdGenerator::new());
+        crate::conformance::pattern::test_run_pattern(db);
+    }
+
+    #[test]
+    fn test_run_path() {
+        let db = MemTripleStore::new(UlidIdGenerator::new());
+        crate::conformance::path::test_run_path(db);
+    }
+}
@@ -0,0 +1,174 @@
+use std::ops::Bound;
+
+use crate::{
+    prelude::*,
+    traits::{quad_ranges, ConcreteIdType, Event, Property},
+    triple::NodeQuadOrder,
+    Quad, QuadOrder, Query,
+};
+
+use super::MemTripleStore;
+
+impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
+    MemTripleStore<Id, NodeProps, EdgeProps>
+{
+    fn quad_table(
+        &self,
+        order: &QuadOrder,
+    ) -> &std::collections::BTreeMap<Id::QuadByteArrayType, Id> {
+        match order {
+            QuadOrder::GSPO => &self.gspo_data,
+            QuadOrder::GPOS => &self.gpos_data,
+            QuadOrder::GOSP => &self.gosp_data,
+        }
+    }
+
+    /// The edges of every named graph which start or end at `node`. Self loops are only listed once.
+    pub(super) fn node_quads(&self, node: &Id) -> Vec<Quad<Id>> {
+        let mut quads = self
+            .spog_data
+            .range(Quad::node_key_bounds(*node))
+            .map(|(key, _)| Quad::decode_node(NodeQuadOrder::SPOG, key))
+            .collect::<Vec<_>>();
+        quads.extend(
+            self.ospg_data
+                .range(Quad::node_key_bounds(*node))
+                .map(|(key, _)| Quad::decode_node(NodeQuadOrder::OSPG, key))
+                .filter(|quad| quad.sub != *node),
+        );
+        quads
+    }
+}
+
+impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
+    QuadStore<Id, NodeProps, EdgeProps> for MemTripleStore<Id, NodeProps, EdgeProps>
+{
+    fn insert_quad(&mut self, quad: Quad<Id>, props: EdgeProps) -> Result<(), Self::Error> {
+        let props_id = match self.gspo_data.get(&quad.encode(&QuadOrder::GSPO)) {
+            Some(props_id) => *props_id,
+            None => {
+                let props_id = self.id_generator.fresh();
+                self.gspo_data
+                    .insert(quad.encode(&QuadOrder::GSPO), props_id);
+                self.gpos_data
+                    .insert(quad.encode(&QuadOrder::GPOS), props_id);
+                self.gosp_data
+                    .insert(quad.encode(&QuadOrder::GOSP), props_id);
+                self.spog_data
+                    .insert(quad.encode_node(NodeQuadOrder::SPOG), props_id);
+                self.ospg_data
+                    .insert(quad.encode_node(NodeQuadOrder::OSPG), props_id);
+                props_id
+            }
+        };
+        self.quad_props.insert(props_id, props);
+
+        self.subscribers.publish(|| {
+            let props = self.quad_props[&props_id].clone();
+            Event::QuadInserted { quad, props }
+        });
+        Ok(())
+    }
+
+    fn get_quad(&self, quad: &Quad<Id>) -> Result<Option<EdgeProps>, Self::Error> {
+        Ok(self
+            .gspo_data
+            .get(&quad.encode(&QuadOrder::GSPO))
+            .and_then(|props_id| self.quad_props.get(props_id))
+            .cloned())
+    }
+
+    fn remove_quad(&mut self, quad: Quad<Id>) -> Result<(), Self::Error> {
+        if let Some(props_id) = self.gspo_data.remove(&quad.encode(&QuadOrder::GSPO)) {
+            self.gpos_data.remove(&quad.encode(&QuadOrder::GPOS));
+            self.gosp_data.remove(&quad.encode(&QuadOrder::GOSP));
+            self.spog_data
+                .remove(&quad.encode_node(NodeQuadOrder::SPOG));
+            self.ospg_data
+                .remove(&quad.encode_node(NodeQuadOrder::OSPG));
+            self.quad_props.remove(&props_id);
+            self.subscribers.publish(|| Event::QuadRemoved { quad });
+        }
+        Ok(())
+    }
+
+    fn iter_graphs<'a>(&'a self) -> impl Iterator<Item = Result<Id, Self::Error>> + 'a {
+        // Skip from each graph to the first key after it.
+        let first = self
+            .gspo_data
+            .keys()
+            .next()
+            .map(|key| Id::decode_quad(key).0);
+        std::iter::successors(first, |graph| {
+            let (_, upper) = Id::graph_key_bounds(*graph);
+            let lower = match upper {
+                Bound::Included(key) => Bound::Excluded(key),
+                bound => bound,
+            };
+            self.gspo_data
+                .range((lower, Bound::Unbounded))
+                .next()
+                .map(|(key, _)| Id::decode_quad(key).0)
+        })
+        .map(Ok)
+    }
+
+    fn iter_quads<'a>(
+        &'a self,
+        order: QuadOrder,
+    ) -> impl Iterator<Item = Result<(Quad<Id>, EdgeProps), Self::Error>> + 'a {
+        self.quad_table(&order).iter().map(move |(key, props_id)| {
+            let props = self.quad_props.get(props_id).ok_or(())?;
+            Ok((Quad::decode(&order, key), props.clone()))
+        })
+    }
+
+    fn iter_query_graphs<'a>(
+        &'a self,
+        graphs: impl IntoIterator<Item = Id>,
+        query: Query<Id>,
+    ) -> impl Iterator<Item = Result<(Quad<Id>, EdgeProps), Self::Error>> + 'a {
+        quad_ranges(graphs, &query)
+            .into_iter()
+            .flat_map(move |(order, bounds)| {
+                self.quad_table(&order)
+                    .range(bounds)
+                    .map(move |(key, props_id)| {
+                        let props = self.quad_props.get(props_id).ok_or(())?;
+                        Ok((Quad::decode(&order, key), props.clone()))
+                    })
+            })
+    }
+
+    fn drop_graph(&mut self, graph: &Id) -> Result<(), Self::Error> {
+        let quads = self
+            .gspo_data
+            .range(Id::graph_key_bounds(*graph))
+            .map(|(key, _)| Quad::decode(&QuadOrder::GSPO, key))
+            .collect::<Vec<_>>();
+        for quad in quads {
+            self.remove_quad(quad)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{MemTripleStore, UlidIdGenerator};
+
+    #[test]
+    fn test_quads() {
+        crate::conformance::quad::test_quads(MemTripleStore::new(UlidIdGenerator::new()));
+    }
+
+    #[test]
+    fn test_query_graphs() {
+        crate::conformance::quad::test_query_graphs(MemTripleStore::new(UlidIdGenerator::new()));
+    }
+
+    #[test]
+    fn test_drop_graph() {
+        crate::conformance::quad::test_drop_graph(MemTripleStore::new(UlidIdGenerator::new()));
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     prelude::*,
-    traits::{ConcreteIdType, Property},
-    Query, QueryError, Triple,
+    traits::{BoxedRows, ConcreteIdType, Property},
+    PropsFilter, Query, QueryError, RunError, Triple,
 };
 
 use super::MemTripleStore;
@@ -11,134 +11,123 @@ impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
 {
     type QueryResult = MemTripleStore<Id, NodeProps, EdgeProps>;
 
-    fn run(
-        &self,
+    fn iter_query_filtered<'a>(
+        &'a self,
         query: Query<Id>,
-    ) -> Result<
-        MemTripleStore<Id, NodeProps, EdgeProps>,
-        QueryError<Self::Error, <<Self as TripleStoreQuery<Id, NodeProps, EdgeProps>>::QueryResult as TripleStoreError>::Error>
-    >{
-        Ok(match query {
-            Query::NodeProps(nodes) => {
-                let mut result =
-                    MemTripleStore::new_from_boxed_id_generator(self.id_generator.clone());
-                for node in nodes {
-                    if let Some(data) = self.node_props.get(&node) {
-                        result.node_props.insert(node, data.clone());
-                    }
-                }
-                result
-            }
-
-            Query::SPO(triples) => {
-                let mut result =
-                    MemTripleStore::new_from_boxed_id_generator(self.id_generator.clone());
-                for (sub, pred, obj) in triples.into_iter() {
+        filter: PropsFilter<'a, Id, NodeProps, EdgeProps>,
+    ) -> (
+        impl Iterator<Item = Result<(Id, NodeProps), ()>> + 'a,
+        impl Iterator<Item = Result<(Triple<Id>, EdgeProps), ()>> + 'a,
+    ) {
+        let edge = move |triple: Triple<Id>, data_id: &Id| {
+            self.edge_props
+                .get(data_id)
+                .filter(|data| filter.keep_edge(&triple, data))
+                .map(|data| Ok((triple, data.clone())))
+        };
+
+        let (nodes, edges): BoxedRows<'_, Id, NodeProps, EdgeProps, ()> = match query {
+            Query::NodeProps(nodes) => (
+                Box::new(nodes.into_iter().filter_map(move |node| {
+                    self.node_props
+                        .get(&node)
+                        .filter(|data| filter.keep_node(&node, data))
+                        .map(|data| Ok((node, data.clone())))
+                })),
+                Box::new(std::iter::empty()),
+            ),
+
+            Query::SPO(triples) => (
+                Box::new(std::iter::empty()),
+                Box::new(triples.into_iter().filter_map(move |(sub, pred, obj)| {
                     let triple = Triple { sub, pred, obj };
-                    if let Some(data_id) = self.spo_data.get(&Id::encode_spo_triple(&triple)) {
-                        if let Some(data) = self.edge_props.get(&data_id) {
-                            result
-                                .insert_edge(triple, data.clone())
-                                .map_err(|e| QueryError::Right(e))?;
-                        }
-                    }
-                }
-                result
-            }
-
-            Query::S(items) => {
-                let mut result =
-                    MemTripleStore::new_from_boxed_id_generator(self.id_generator.clone());
-                for sub in items {
-                    for (key, data_id) in self.spo_data.range(Id::key_bounds_1(sub)) {
-                        if let Some(data) = self.edge_props.get(&data_id) {
-                            result
-                                .insert_edge(Id::decode_spo_triple(&key), data.clone())
-                                .map_err(|e| QueryError::Right(e))?;
-                        }
-                    }
-                }
-                result
-            }
-
-            Query::SP(items) => {
-                let mut result =
-                    MemTripleStore::new_from_boxed_id_generator(self.id_generator.clone());
-                for (sub, pred) in items {
-                    for (key, data_id) in self.spo_data.range(Id::key_bounds_2(sub, pred)) {
-                        if let Some(data) = self.edge_props.get(&data_id) {
-                            result
-                                .insert_edge(Id::decode_spo_triple(&key), data.clone())
-                                .map_err(|e| QueryError::Right(e))?;
-                        }
-                    }
-                }
-                result
-            }
-
-            Query::SO(items) => {
-                let mut result =
-                    MemTripleStore::new_from_boxed_id_generator(self.id_generator.clone());
-                for (sub, obj) in items {
-                    for (key, data_id) in self.osp_data.range(Id::key_bounds_2(obj, sub)) {
-                        if let Some(data) = self.edge_props.get(&data_id) {
-                            result
-                                .insert_edge(Id::decode_osp_triple(key), data.clone())
-                                .map_err(|e| QueryError::Right(e))?;
-                        }
-                    }
-                }
-                result
-            }
-
-            Query::P(items) => {
-                let mut result =
-                    MemTripleStore::new_from_boxed_id_generator(self.id_generator.clone());
-                for pred in items {
-                    for (key, data_id) in self.pos_data.range(Id::key_bounds_1(pred)) {
-                        if let Some(data) = self.edge_props.get(&data_id) {
-                            result
-                                .insert_edge(Id::decode_pos_triple(key), data.clone())
-                                .map_err(|e| QueryError::Right(e))?;
-                        }
-                    }
-                }
-                result
-            }
-
-            Query::PO(items) => {
-                let mut result =
-                    MemTripleStore::new_from_boxed_id_generator(self.id_generator.clone());
-                for (pred, obj) in items {
-                    for (key, data_id) in self.pos_data.range(Id::key_bounds_2(pred, obj)) {
-                        if let Some(data) = self.edge_props.get(&data_id) {
-                            result
-                                .insert_edge(Id::decode_pos_triple(key), data.clone())
-                                .map_err(|e| QueryError::Right(e))?;
-                        }
-                    }
-                }
-                result
-            }
-
-            Query::O(items) => {
-                let mut result =
-                    MemTripleStore::new_from_boxed_id_generator(self.id_generator.clone());
-                for obj in items {
-                    for (key, data_id) in self.osp_data.range(Id::key_bounds_1(obj)) {
-                        if let Some(data) = self.edge_props.get(&data_id) {
-                            result
-                                .insert_edge(Id::decode_osp_triple(key), data.clone())
-                                .map_err(|e| QueryError::Right(e))?;
-                        }
-                    }
-                }
-                result
-            }
-        })
+                    self.spo_data
+                        .get(&Id::encode_spo_triple(&triple))
+                        .and_then(|data_id| edge(triple, data_id))
+                })),
+            ),
+
+            Query::S(items) => (
+                Box::new(std::iter::empty()),
+                Box::new(items.into_iter().flat_map(move |sub| {
+                    self.spo_data
+                        .range(Id::key_bounds_1(sub))
+                        .filter_map(move |(key, data_id)| edge(Id::decode_spo_triple(key), data_id))
+                })),
+            ),
+
+            Query::SP(items) => (
+                Box::new(std::iter::empty()),
+                Box::new(items.into_iter().flat_map(move |(sub, pred)| {
+                    self.spo_data
+                        .range(Id::key_bounds_2(sub, pred))
+                        .filter_map(move |(key, data_id)| edge(Id::decode_spo_triple(key), data_id))
+                })),
+            ),
+
+            Query::SO(items) => (
+                Box::new(std::iter::empty()),
+                Box::new(items.into_iter().flat_map(move |(sub, obj)| {
+                    self.osp_data
+                        .range(Id::key_bounds_2(obj, sub))
+                        .filter_map(move |(key, data_id)| edge(Id::decode_osp_triple(key), data_id))
+                })),
+            ),
+
+            Query::P(items) => (
+                Box::new(std::iter::empty()),
+                Box::new(items.into_iter().flat_map(move |pred| {
+                    self.pos_data
+                        .range(Id::key_bounds_1(pred))
+                        .filter_map(move |(key, data_id)| edge(Id::decode_pos_triple(key), data_id))
+                })),
+            ),
+
+            Query::PO(items) => (
+                Box::new(std::iter::empty()),
+                Box::new(items.into_iter().flat_map(move |(pred, obj)| {
+                    self.pos_data
+                        .range(Id::key_bounds_2(pred, obj))
+                        .filter_map(move |(key, data_id)| edge(Id::decode_pos_triple(key), data_id))
+                })),
+            ),
+
+            Query::O(items) => (
+                Box::new(std::iter::empty()),
+                Box::new(items.into_iter().flat_map(move |obj| {
+                    self.osp_data
+                        .range(Id::key_bounds_1(obj))
+                        .filter_map(move |(key, data_id)| edge(Id::decode_osp_triple(key), data_id))
+                })),
+            ),
+        };
+
+        (nodes, edges)
     }
-}
 
+    fn run_filtered(
+        &self,
+        query: Query<Id>,
+        filter: PropsFilter<Id, NodeProps, EdgeProps>,
+    ) -> Result<MemTripleStore<Id, NodeProps, EdgeProps>, RunError<Self, Id, NodeProps, EdgeProps>>
+    {
+        let mut result = MemTripleStore::new_from_boxed_id_generator(self.id_generator.clone());
+
+        let (nodes, edges) = self.iter_query_filtered(query, filter);
+        for r in nodes {
+            let (node, data) = r.map_err(QueryError::Left)?;
+            result.insert_node(node, data).map_err(QueryError::Right)?;
+        }
+        for r in edges {
+            let (triple, data) = r.map_err(QueryError::Left)?;
+            result
+                .insert_edge(triple, data)
+                .map_err(QueryError::Right)?;
+        }
+
+        Ok(result)
+    }
+}
 #[cfg(test)]
 mod test {
     use crate::{MemTripleStore, UlidIdGenerator};
@@ -186,4 +175,14 @@ mod test {
     fn test_query_os() {
         crate::conformance::query::test_query_os(MemTripleStore::new(UlidIdGenerator::new()));
     }
+
+    #[test]
+    fn test_iter_query() {
+        crate::conformance::query::test_iter_query(MemTripleStore::new(UlidIdGenerator::new()));
+    }
+
+    #[test]
+    fn test_query_filtered() {
+        crate::conformance::query::test_query_filtered(MemTripleStore::new(UlidIdGenerator::new()));
+    }
 }
@@ -2,7 +2,7 @@ use std::borrow::Borrow;
 
 use crate::{
     prelude::*,
-    traits::{ConcreteIdType, Property},
+    traits::{ConcreteIdType, Event, Property},
     Triple,
 };
 
@@ -17,7 +17,7 @@ impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
             (Vec::new(), Vec::new()),
             |(mut triples, mut edge_data_ids), (triple, edge_data_id)| {
                 triples.push(Id::decode_spo_triple(triple));
-                edge_data_ids.push(edge_data_id.clone());
+                edge_data_ids.push(*edge_data_id);
                 (triples, edge_data_ids)
             },
         )
@@ -29,7 +29,7 @@ impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
             (Vec::new(), Vec::new()),
             |(mut triples, mut edge_data_ids), (triple, edge_data_id)| {
                 triples.push(Id::decode_osp_triple(triple));
-                edge_data_ids.push(edge_data_id.clone());
+                edge_data_ids.push(*edge_data_id);
                 (triples, edge_data_ids)
             },
         )
@@ -41,28 +41,30 @@ impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
 {
     fn remove_node(&mut self, node: impl Borrow<Id>) -> Result<(), Self::Error> {
         // Find all uses of this node in the edges.
-        let (forward_triples, forward_edge_data_ids) = self.get_spo_edge_range(node.borrow());
-        let (backward_triples, backward_edge_data_ids) = self.get_osp_edge_range(node.borrow());
+        let (forward_triples, _) = self.get_spo_edge_range(node.borrow());
+        let (backward_triples, _) = self.get_osp_edge_range(node.borrow());
 
         // Remove the node props.
-        self.node_props.remove(node.borrow());
-
-        // Remove all the edge props for all the edges we'll be removing.
-        for edge_data_id in forward_edge_data_ids
-            .into_iter()
-            .chain(backward_edge_data_ids.into_iter())
-        {
-            self.edge_props.remove(&edge_data_id);
+        let old_props = self.node_props.remove(node.borrow());
+        if let Some(old_props) = &old_props {
+            self.reindex_node(node.borrow(), Some(old_props));
         }
 
-        // Remove the forward and backward edges
-        for edge in forward_triples
-            .into_iter()
-            .chain(backward_triples.into_iter())
-        {
+        // Remove the forward and backward edges, which also cleans up their edge props.
+        for edge in forward_triples.into_iter().chain(backward_triples) {
             self.remove_edge(edge)?;
         }
 
+        // Remove its edges in the named graphs too.
+        for quad in self.node_quads(node.borrow()) {
+            self.remove_quad(quad)?;
+        }
+
+        if old_props.is_some() {
+            self.subscribers.publish(|| Event::NodeRemoved {
+                node: *node.borrow(),
+            });
+        }
         Ok(())
     }
 
@@ -76,7 +78,12 @@ impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
             self.osp_data.remove(&Id::encode_osp_triple(&triple));
 
             // Clean up the edge props.
-            self.edge_props.remove(&edge_data_id);
+            if let Some(old_props) = self.edge_props.remove(&edge_data_id) {
+                self.reindex_edge(&triple, Some(&old_props));
+            }
+
+            self.count_edges(std::slice::from_ref(&triple), false)?;
+            self.subscribers.publish(|| Event::EdgeRemoved { triple });
         }
         Ok(())
     }
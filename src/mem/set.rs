@@ -142,10 +142,10 @@ impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
                         .map_err(|e| SetOpsError::Result(e))?;
                     self_node = self_nodes.next().transpose()?;
                 } else if right_key < left_key {
-                    removed_nodes.insert(right_key.clone());
+                    removed_nodes.insert(*right_key);
                     other_node = other_nodes.next().transpose()?;
                 } else {
-                    removed_nodes.insert(right_key.clone());
+                    removed_nodes.insert(*right_key);
                     self_node = self_nodes.next().transpose()?;
                     other_node = other_nodes.next().transpose()?;
                 }
@@ -0,0 +1,60 @@
+use crate::{
+    prelude::*,
+    traits::{stats_delta, ConcreteIdType, Property, Statistics},
+    Triple,
+};
+
+use super::MemTripleStore;
+
+impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
+    MemTripleStore<Id, NodeProps, EdgeProps>
+{
+    /// Update the predicate statistics for the distinct `triples`, which have just been added or removed.
+    pub(super) fn count_edges(&mut self, triples: &[Triple<Id>], added: bool) -> Result<(), ()> {
+        if triples.is_empty() {
+            return Ok(());
+        }
+        for (pred, delta) in stats_delta(self, triples, added)? {
+            let stats = self.predicate_stats.entry(pred).or_default();
+            delta.apply(stats);
+            if stats.edges == 0 {
+                self.predicate_stats.remove(&pred);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
+    TripleStoreStats<Id, NodeProps, EdgeProps> for MemTripleStore<Id, NodeProps, EdgeProps>
+{
+    fn statistics(&self) -> Result<Statistics<Id>, ()> {
+        Ok(Statistics {
+            nodes: self.node_props.len() as u64,
+            edges: self.spo_data.len() as u64,
+            predicates: self.predicate_stats.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{MemTripleStore, UlidIdGenerator};
+
+    #[test]
+    fn test_statistics() {
+        crate::conformance::stats::test_statistics(MemTripleStore::new(UlidIdGenerator::new()));
+    }
+
+    #[test]
+    fn test_statistics_bulk() {
+        crate::conformance::stats::test_statistics_bulk(
+            MemTripleStore::new(UlidIdGenerator::new()),
+        );
+    }
+
+    #[test]
+    fn test_explain() {
+        crate::conformance::stats::test_explain(MemTripleStore::new(UlidIdGenerator::new()));
+    }
+}
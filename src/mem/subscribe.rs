@@ -0,0 +1,60 @@
+use crate::{
+    prelude::*,
+    traits::{ConcreteIdType, EventFilter, Property, Subscription},
+};
+
+use super::MemTripleStore;
+
+impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
+    TripleStoreSubscribe<Id, NodeProps, EdgeProps> for MemTripleStore<Id, NodeProps, EdgeProps>
+{
+    fn subscribe(&self, filter: EventFilter<Id>) -> Subscription<Id, NodeProps, EdgeProps> {
+        self.subscribers.subscribe(filter)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{MemTripleStore, UlidIdGenerator};
+
+    #[test]
+    fn test_subscribe() {
+        crate::conformance::subscribe::test_subscribe(MemTripleStore::new(UlidIdGenerator::new()));
+    }
+
+    #[test]
+    fn test_subscribe_merge() {
+        crate::conformance::subscribe::test_subscribe_merge(MemTripleStore::new(
+            UlidIdGenerator::new(),
+        ));
+    }
+
+    #[test]
+    fn test_subscribe_bulk() {
+        crate::conformance::subscribe::test_subscribe_bulk(MemTripleStore::new(
+            UlidIdGenerator::new(),
+        ));
+    }
+
+    #[test]
+    fn test_subscribe_extend() {
+        crate::conformance::subscribe::test_subscribe_extend(
+            MemTripleStore::new(UlidIdGenerator::new()),
+            MemTripleStore::new(UlidIdGenerator::new()),
+        );
+    }
+
+    #[test]
+    fn test_subscribe_filter() {
+        crate::conformance::subscribe::test_subscribe_filter(MemTripleStore::new(
+            UlidIdGenerator::new(),
+        ));
+    }
+
+    #[test]
+    fn test_subscribe_quads() {
+        crate::conformance::subscribe::test_subscribe_quads(MemTripleStore::new(
+            UlidIdGenerator::new(),
+        ));
+    }
+}
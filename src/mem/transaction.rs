@@ -0,0 +1,88 @@
+use crate::{
+    prelude::*,
+    traits::{ConcreteIdType, Event, MergedProps, Operation, Property, Transaction},
+};
+
+use super::MemTripleStore;
+
+impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
+    TripleStoreTransaction<Id, NodeProps, EdgeProps> for MemTripleStore<Id, NodeProps, EdgeProps>
+{
+    fn transaction<R>(
+        &mut self,
+        f: impl FnOnce(&mut Transaction<Id, NodeProps, EdgeProps, ()>) -> Result<R, ()>,
+    ) -> Result<R, ()> {
+        let mut tx = Transaction::new();
+        let result = f(&mut tx)?;
+
+        // Nothing below can fail, so the operations are applied all or nothing.
+        for operation in tx.into_operations() {
+            match operation {
+                Operation::InsertNode(node, props) => self.insert_node(node, props)?,
+                Operation::InsertEdge(triple, props) => self.insert_edge(triple, props)?,
+                Operation::MergeNode(node, props, merge) => {
+                    match self.node_props.get(&node).cloned() {
+                        Some(mut old) => {
+                            merge(&mut old, props);
+                            self.insert_node_internal(node, old);
+                            self.subscribers.publish(|| {
+                                let props = self.node_props[&node].clone();
+                                Event::PropsMerged(MergedProps::Node { node, props })
+                            });
+                        }
+                        None => self.insert_node(node, props)?,
+                    }
+                }
+                Operation::MergeEdge(triple, props, merge) => match self.get_edge(&triple)? {
+                    Some(mut old) => {
+                        merge(&mut old, props);
+                        let new_edge_data_id = self.insert_edge_internal(&triple, old)?;
+                        self.subscribers.publish(|| {
+                            let props = self.edge_props[&new_edge_data_id].clone();
+                            Event::PropsMerged(MergedProps::Edge { triple, props })
+                        });
+                    }
+                    None => self.insert_edge(triple, props)?,
+                },
+                Operation::RemoveNode(node) => self.remove_node(node)?,
+                Operation::RemoveEdge(triple) => self.remove_edge(triple)?,
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{MemTripleStore, UlidIdGenerator};
+
+    #[test]
+    fn test_transaction_commit() {
+        crate::conformance::transaction::test_transaction_commit(MemTripleStore::new(
+            UlidIdGenerator::new(),
+        ));
+    }
+
+    #[test]
+    fn test_transaction_rollback() {
+        crate::conformance::transaction::test_transaction_rollback(
+            MemTripleStore::new(UlidIdGenerator::new()),
+            (),
+        );
+    }
+
+    #[test]
+    fn test_transaction_merge() {
+        crate::conformance::transaction::test_transaction_merge(MemTripleStore::new(
+            UlidIdGenerator::new(),
+        ));
+    }
+
+    #[test]
+    fn test_transaction_remove_node_quads() {
+        crate::conformance::transaction::test_transaction_remove_node_quads(MemTripleStore::new(
+            UlidIdGenerator::new(),
+        ));
+    }
+}
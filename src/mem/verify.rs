@@ -0,0 +1,194 @@
+use crate::{
+    prelude::*,
+    traits::{
+        check_quad_tables, check_tables, count_predicates, ConcreteIdType, IntegrityReport,
+        Property,
+    },
+    triple::NodeQuadOrder,
+    Quad, QuadOrder,
+};
+
+use super::MemTripleStore;
+
+impl<Id: ConcreteIdType, NodeProps: Property, EdgeProps: Property>
+    TripleStoreVerify<Id, NodeProps, EdgeProps> for MemTripleStore<Id, NodeProps, EdgeProps>
+{
+    fn verify(&self) -> Result<IntegrityReport<Id>, ()> {
+        let mut report = check_tables(
+            self.spo_data
+                .iter()
+                .map(|(key, props_id)| Ok((Id::decode_spo_triple(key), *props_id))),
+            self.pos_data
+                .iter()
+                .map(|(key, props_id)| Ok((Id::decode_pos_triple(key), *props_id))),
+            self.osp_data
+                .iter()
+                .map(|(key, props_id)| Ok((Id::decode_osp_triple(key), *props_id))),
+            self.edge_props.keys().map(|props_id| Ok(*props_id)),
+        )?;
+        check_quad_tables(
+            &mut report,
+            self.gspo_data
+                .iter()
+                .map(|(key, props_id)| Ok((Quad::decode(&QuadOrder::GSPO, key), *props_id))),
+            self.gpos_data
+                .iter()
+                .map(|(key, props_id)| Ok((Quad::decode(&QuadOrder::GPOS, key), *props_id))),
+            self.gosp_data
+                .iter()
+                .map(|(key, props_id)| Ok((Quad::decode(&QuadOrder::GOSP, key), *props_id))),
+            self.spog_data.iter().map(|(key, props_id)| {
+                Ok((Quad::decode_node(NodeQuadOrder::SPOG, key), *props_id))
+            }),
+            self.ospg_data.iter().map(|(key, props_id)| {
+                Ok((Quad::decode_node(NodeQuadOrder::OSPG, key), *props_id))
+            }),
+            self.quad_props.keys().map(|props_id| Ok(*props_id)),
+        )?;
+        // The node and edge counts are read from the tables themselves, so only the predicates can drift.
+        report.stale_statistics =
+            count_predicates(self.match_triples(None, None, None))? != self.predicate_stats;
+        Ok(report)
+    }
+
+    fn repair(&mut self) -> Result<IntegrityReport<Id>, ()> {
+        let report = self.verify()?;
+
+        for triple in &report.missing_props {
+            self.spo_data.remove(&Id::encode_spo_triple(triple));
+        }
+        self.pos_data = self
+            .spo_data
+            .iter()
+            .map(|(key, props_id)| {
+                (
+                    Id::encode_pos_triple(&Id::decode_spo_triple(key)),
+                    *props_id,
+                )
+            })
+            .collect();
+        self.osp_data = self
+            .spo_data
+            .iter()
+            .map(|(key, props_id)| {
+                (
+                    Id::encode_osp_triple(&Id::decode_spo_triple(key)),
+                    *props_id,
+                )
+            })
+            .collect();
+        for props_id in &report.orphaned_props {
+            self.edge_props.remove(props_id);
+        }
+
+        for quad in &report.missing_quad_props {
+            self.gspo_data.remove(&quad.encode(&QuadOrder::GSPO));
+        }
+        let reorder = |encode: &dyn Fn(&Quad<Id>) -> Id::QuadByteArrayType| {
+            self.gspo_data
+                .iter()
+                .map(|(key, props_id)| (encode(&Quad::decode(&QuadOrder::GSPO, key)), *props_id))
+                .collect()
+        };
+        self.gpos_data = reorder(&|quad| quad.encode(&QuadOrder::GPOS));
+        self.gosp_data = reorder(&|quad| quad.encode(&QuadOrder::GOSP));
+        self.spog_data = reorder(&|quad| quad.encode_node(NodeQuadOrder::SPOG));
+        self.ospg_data = reorder(&|quad| quad.encode_node(NodeQuadOrder::OSPG));
+        for props_id in &report.orphaned_quad_props {
+            self.quad_props.remove(props_id);
+        }
+
+        self.predicate_stats = count_predicates(self.match_triples(None, None, None))?;
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ulid::Ulid;
+
+    use crate::{
+        prelude::*, traits::ConcreteIdType, triple::NodeQuadOrder, MemTripleStore, Quad, QuadOrder,
+        Triple, UlidIdGenerator,
+    };
+
+    #[test]
+    fn test_verify_consistent() {
+        crate::conformance::verify::test_verify_consistent(MemTripleStore::new(
+            UlidIdGenerator::new(),
+        ));
+    }
+
+    #[test]
+    fn test_repair() {
+        let mut db = MemTripleStore::<Ulid, (), String>::new(UlidIdGenerator::new());
+        let triple = |n: u128| Triple {
+            sub: Ulid(1),
+            pred: Ulid(10),
+            obj: Ulid(n),
+        };
+        let quad = |n: u128| Quad::new(triple(n), Ulid(20));
+        for n in 2..6 {
+            db.insert_edge(triple(n), n.to_string()).expect("ok");
+            db.insert_quad(quad(n), n.to_string()).expect("ok");
+        }
+
+        // Lose an edge from POS, an edge's props, and point an OSP entry elsewhere; then leave behind stray props
+        // and a POS entry for an edge which does not exist.
+        db.pos_data.remove(&Ulid::encode_pos_triple(&triple(2)));
+        let lost_props = db.spo_data[&Ulid::encode_spo_triple(&triple(3))];
+        db.edge_props.remove(&lost_props);
+        db.osp_data
+            .insert(Ulid::encode_osp_triple(&triple(4)), Ulid(99));
+        db.edge_props.insert(Ulid(100), "stray".to_string());
+        db.pos_data
+            .insert(Ulid::encode_pos_triple(&triple(6)), Ulid(100));
+        db.predicate_stats.clear();
+
+        // Likewise in the named graph: lose a quad from GPOS, another from OSPG and another's props, and leave behind
+        // stray props.
+        db.gpos_data.remove(&quad(2).encode(&QuadOrder::GPOS));
+        db.ospg_data
+            .remove(&quad(5).encode_node(NodeQuadOrder::OSPG));
+        let lost_props = db.gspo_data[&quad(3).encode(&QuadOrder::GSPO)];
+        db.quad_props.remove(&lost_props);
+        db.quad_props.insert(Ulid(101), "stray".to_string());
+
+        let report = db.verify().expect("ok");
+        assert!(!report.is_consistent());
+        assert_eq!(report.edges, 4);
+        assert_eq!(report.pos_mismatches, [triple(2), triple(6)]);
+        assert_eq!(report.osp_mismatches, [triple(4)]);
+        assert_eq!(report.missing_props, [triple(3)]);
+        assert_eq!(report.orphaned_props, [Ulid(100)]);
+        assert_eq!(report.quads, 4);
+        assert_eq!(report.gpos_mismatches, [quad(2)]);
+        assert!(report.gosp_mismatches.is_empty());
+        assert!(report.spog_mismatches.is_empty());
+        assert_eq!(report.ospg_mismatches, [quad(5)]);
+        assert_eq!(report.missing_quad_props, [quad(3)]);
+        assert_eq!(report.orphaned_quad_props, [Ulid(101)]);
+        assert!(report.stale_statistics);
+
+        assert_eq!(db.repair().expect("ok"), report);
+        assert!(db.verify().expect("ok").is_consistent());
+        assert_eq!(db.get_edge(&triple(2)).expect("ok"), Some("2".to_string()));
+        assert_eq!(db.get_edge(&triple(3)).expect("ok"), None);
+        assert_eq!(db.get_edge(&triple(4)).expect("ok"), Some("4".to_string()));
+        assert_eq!(db.get_edge(&triple(6)).expect("ok"), None);
+        assert_eq!(
+            db.count(crate::query! { ? -[Ulid(10)]-> ? }).expect("ok"),
+            3
+        );
+        assert_eq!(db.statistics().expect("ok").edges, 3);
+        assert_eq!(db.get_quad(&quad(3)).expect("ok"), None);
+        assert_eq!(
+            db.iter_quads(QuadOrder::GPOS)
+                .map(|r| r.expect("ok").0)
+                .collect::<Vec<_>>(),
+            [quad(2), quad(4), quad(5)]
+        );
+        assert_eq!(db.node_quads(&Ulid(5)), [quad(5)]);
+    }
+}
@@ -0,0 +1,461 @@
+//! Property paths: regular expressions over predicates, for queries like "reachable via one or more `part_of` edges".
+//!
+//! A [PropertyPath] is evaluated with [TripleStorePattern::run_path][crate::traits::TripleStorePattern::run_path],
+//! which returns every `(start, end)` pair connected by the path. Repetitions keep track of the nodes already expanded,
+//! so cycles in the graph terminate.
+//!
+//! When the start of a path is bound, each predicate step is a range scan over the SPO table; when only the end is
+//! bound the path is evaluated backwards over the POS table.
+//!
+//! Paths are most easily created using the [query][crate::query] macro, which supports `[p]` predicates, `^` inverse,
+//! `/` sequence, `|` alternatives, the `?`, `*` and `+` repetitions, and parentheses for grouping.
+//!
+//! # Example
+//! ```
+//! # use ulid::Ulid;
+//! # use simple_triplestore::{prelude::*, MemTripleStore, Triple, UlidIdGenerator};
+//! let mut db = MemTripleStore::<Ulid, (), ()>::new(UlidIdGenerator::new());
+//!
+//! let (cat, mammal, animal, dog) = (Ulid(1), Ulid(2), Ulid(3), Ulid(4));
+//! let sub_class_of = Ulid(10);
+//!
+//! db.insert_edge(Triple { sub: cat, pred: sub_class_of, obj: mammal }, ())?;
+//! db.insert_edge(Triple { sub: dog, pred: sub_class_of, obj: mammal }, ())?;
+//! db.insert_edge(Triple { sub: mammal, pred: sub_class_of, obj: animal }, ())?;
+//!
+//! // Every superclass of cat.
+//! let supers = db.run_path(Some(cat), &query! { path { [sub_class_of]+ } }, None)?;
+//! assert_eq!(supers, [(cat, mammal), (cat, animal)].into());
+//!
+//! // Every sibling class of cat, including itself.
+//! let siblings = db.run_path(Some(cat), &query! { path { [sub_class_of] / ^[sub_class_of] } }, None)?;
+//! assert_eq!(siblings, [(cat, cat), (cat, dog)].into());
+//! # Ok::<(), ()>(())
+//! ```
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    prelude::*,
+    traits::{IdType, Property},
+};
+
+/// A path through the graph, described by the predicates of the edges along it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PropertyPath<Id: IdType> {
+    /// A single edge with the given predicate.
+    Pred(Id),
+
+    /// The inner path, followed from end to start (`^p`).
+    Inverse(Box<PropertyPath<Id>>),
+
+    /// Each path in turn, with the end of one being the start of the next (`p1 / p2`). An empty sequence matches
+    /// zero edges.
+    Sequence(Vec<PropertyPath<Id>>),
+
+    /// Any one of the paths (`p1 | p2`). An empty alternative matches nothing.
+    Alternative(Vec<PropertyPath<Id>>),
+
+    /// Zero or one repetitions of the inner path (`p?`).
+    ZeroOrOne(Box<PropertyPath<Id>>),
+
+    /// Zero or more repetitions of the inner path (`p*`).
+    ZeroOrMore(Box<PropertyPath<Id>>),
+
+    /// One or more repetitions of the inner path (`p+`).
+    OneOrMore(Box<PropertyPath<Id>>),
+}
+
+impl<Id: IdType> From<Id> for PropertyPath<Id> {
+    fn from(pred: Id) -> Self {
+        PropertyPath::Pred(pred)
+    }
+}
+
+impl<Id: IdType> PropertyPath<Id> {
+    /// Construct a path of a single edge.
+    pub fn pred(pred: Id) -> Self {
+        PropertyPath::Pred(pred)
+    }
+
+    /// Follow this path backwards.
+    pub fn inverse(self) -> Self {
+        PropertyPath::Inverse(Box::new(self))
+    }
+
+    /// Follow this path and then `next`.
+    pub fn then(self, next: impl Into<PropertyPath<Id>>) -> Self {
+        match self {
+            PropertyPath::Sequence(mut paths) => {
+                paths.push(next.into());
+                PropertyPath::Sequence(paths)
+            }
+            path => PropertyPath::Sequence(vec![path, next.into()]),
+        }
+    }
+
+    /// Follow either this path or `other`.
+    pub fn or(self, other: impl Into<PropertyPath<Id>>) -> Self {
+        match self {
+            PropertyPath::Alternative(mut paths) => {
+                paths.push(other.into());
+                PropertyPath::Alternative(paths)
+            }
+            path => PropertyPath::Alternative(vec![path, other.into()]),
+        }
+    }
+
+    /// Follow this path zero or one times.
+    pub fn zero_or_one(self) -> Self {
+        PropertyPath::ZeroOrOne(Box::new(self))
+    }
+
+    /// Follow this path zero or more times.
+    pub fn zero_or_more(self) -> Self {
+        PropertyPath::ZeroOrMore(Box::new(self))
+    }
+
+    /// Follow this path one or more times.
+    pub fn one_or_more(self) -> Self {
+        PropertyPath::OneOrMore(Box::new(self))
+    }
+}
+
+/// Evaluate `path`, returning every `(start, end)` pair it connects which agrees with the bound `sub` and `obj`.
+pub(crate) fn evaluate<Id, NodeProps, EdgeProps, T>(
+    db: &T,
+    sub: Option<Id>,
+    path: &PropertyPath<Id>,
+    obj: Option<Id>,
+) -> Result<HashSet<(Id, Id)>, T::Error>
+where
+    Id: IdType,
+    NodeProps: Property,
+    EdgeProps: Property,
+    T: TripleStorePattern<Id, NodeProps, EdgeProps> + ?Sized,
+{
+    match (sub, obj) {
+        (Some(sub), Some(obj)) => Ok(if forward(db, path, &sub)?.contains(&obj) {
+            [(sub, obj)].into()
+        } else {
+            HashSet::new()
+        }),
+        (Some(sub), None) => Ok(forward(db, path, &sub)?
+            .into_iter()
+            .map(|obj| (sub.clone(), obj))
+            .collect()),
+        (None, Some(obj)) => Ok(backward(db, path, &obj)?
+            .into_iter()
+            .map(|sub| (sub, obj.clone()))
+            .collect()),
+        (None, None) => pairs(db, path),
+    }
+}
+
+/// The nodes reachable from `start` along `path`.
+fn forward<Id, NodeProps, EdgeProps, T>(
+    db: &T,
+    path: &PropertyPath<Id>,
+    start: &Id,
+) -> Result<HashSet<Id>, T::Error>
+where
+    Id: IdType,
+    NodeProps: Property,
+    EdgeProps: Property,
+    T: TripleStorePattern<Id, NodeProps, EdgeProps> + ?Sized,
+{
+    match path {
+        PropertyPath::Pred(pred) => db
+            .match_triples(Some(start.clone()), Some(pred.clone()), None)
+            .map(|r| r.map(|triple| triple.obj))
+            .collect(),
+        PropertyPath::Inverse(inner) => backward(db, inner, start),
+        PropertyPath::Sequence(paths) => {
+            let mut nodes = HashSet::from([start.clone()]);
+            for path in paths {
+                let mut next = HashSet::new();
+                for node in nodes {
+                    next.extend(forward(db, path, &node)?);
+                }
+                nodes = next;
+            }
+            Ok(nodes)
+        }
+        PropertyPath::Alternative(paths) => {
+            let mut nodes = HashSet::new();
+            for path in paths {
+                nodes.extend(forward(db, path, start)?);
+            }
+            Ok(nodes)
+        }
+        PropertyPath::ZeroOrOne(inner) => {
+            let mut nodes = forward(db, inner, start)?;
+            nodes.insert(start.clone());
+            Ok(nodes)
+        }
+        PropertyPath::ZeroOrMore(inner) => {
+            let mut nodes = closure(start, |node| forward(db, inner, node))?;
+            nodes.insert(start.clone());
+            Ok(nodes)
+        }
+        PropertyPath::OneOrMore(inner) => closure(start, |node| forward(db, inner, node)),
+    }
+}
+
+/// The nodes from which `end` is reachable along `path`.
+fn backward<Id, NodeProps, EdgeProps, T>(
+    db: &T,
+    path: &PropertyPath<Id>,
+    end: &Id,
+) -> Result<HashSet<Id>, T::Error>
+where
+    Id: IdType,
+    NodeProps: Property,
+    EdgeProps: Property,
+    T: TripleStorePattern<Id, NodeProps, EdgeProps> + ?Sized,
+{
+    match path {
+        PropertyPath::Pred(pred) => db
+            .match_triples(None, Some(pred.clone()), Some(end.clone()))
+            .map(|r| r.map(|triple| triple.sub))
+            .collect(),
+        PropertyPath::Inverse(inner) => forward(db, inner, end),
+        PropertyPath::Sequence(paths) => {
+            let mut nodes = HashSet::from([end.clone()]);
+            for path in paths.iter().rev() {
+                let mut next = HashSet::new();
+                for node in nodes {
+                    next.extend(backward(db, path, &node)?);
+                }
+                nodes = next;
+            }
+            Ok(nodes)
+        }
+        PropertyPath::Alternative(paths) => {
+            let mut nodes = HashSet::new();
+            for path in paths {
+                nodes.extend(backward(db, path, end)?);
+            }
+            Ok(nodes)
+        }
+        PropertyPath::ZeroOrOne(inner) => {
+            let mut nodes = backward(db, inner, end)?;
+            nodes.insert(end.clone());
+            Ok(nodes)
+        }
+        PropertyPath::ZeroOrMore(inner) => {
+            let mut nodes = closure(end, |node| backward(db, inner, node))?;
+            nodes.insert(end.clone());
+            Ok(nodes)
+        }
+        PropertyPath::OneOrMore(inner) => closure(end, |node| backward(db, inner, node)),
+    }
+}
+
+/// Every `(start, end)` pair connected by `path`.
+///
+/// Paths which can match zero edges connect every node which appears in an edge to itself.
+fn pairs<Id, NodeProps, EdgeProps, T>(
+    db: &T,
+    path: &PropertyPath<Id>,
+) -> Result<HashSet<(Id, Id)>, T::Error>
+where
+    Id: IdType,
+    NodeProps: Property,
+    EdgeProps: Property,
+    T: TripleStorePattern<Id, NodeProps, EdgeProps> + ?Sized,
+{
+    match path {
+        PropertyPath::Pred(pred) => db
+            .match_triples(None, Some(pred.clone()), None)
+            .map(|r| r.map(|triple| (triple.sub, triple.obj)))
+            .collect(),
+        PropertyPath::Inverse(inner) => Ok(pairs(db, inner)?
+            .into_iter()
+            .map(|(sub, obj)| (obj, sub))
+            .collect()),
+        PropertyPath::Sequence(paths) => {
+            let Some((first, rest)) = paths.split_first() else {
+                return identity(db);
+            };
+            let mut result = pairs(db, first)?;
+            for path in rest {
+                // Each intermediate node is only expanded once, however many starts reach it.
+                let mut reachable: HashMap<Id, HashSet<Id>> = HashMap::new();
+                let mut next = HashSet::new();
+                for (start, node) in result {
+                    if !reachable.contains_key(&node) {
+                        let ends = forward(db, path, &node)?;
+                        reachable.insert(node.clone(), ends);
+                    }
+                    for end in reachable[&node].iter() {
+                        next.insert((start.clone(), end.clone()));
+                    }
+                }
+                result = next;
+            }
+            Ok(result)
+        }
+        PropertyPath::Alternative(paths) => {
+            let mut result = HashSet::new();
+            for path in paths {
+                result.extend(pairs(db, path)?);
+            }
+            Ok(result)
+        }
+        PropertyPath::ZeroOrOne(inner) => {
+            let mut result = pairs(db, inner)?;
+            result.extend(identity(db)?);
+            Ok(result)
+        }
+        PropertyPath::ZeroOrMore(inner) | PropertyPath::OneOrMore(inner) => {
+            let starts = pairs(db, inner)?
+                .into_iter()
+                .map(|(start, _)| start)
+                .collect::<HashSet<_>>();
+            let mut result = HashSet::new();
+            for start in starts {
+                for end in closure(&start, |node| forward(db, inner, node))? {
+                    result.insert((start.clone(), end));
+                }
+            }
+            if matches!(path, PropertyPath::ZeroOrMore(_)) {
+                result.extend(identity(db)?);
+            }
+            Ok(result)
+        }
+    }
+}
+
+/// A `(node, node)` pair for every node which appears in an edge.
+fn identity<Id, NodeProps, EdgeProps, T>(db: &T) -> Result<HashSet<(Id, Id)>, T::Error>
+where
+    Id: IdType,
+    NodeProps: Property,
+    EdgeProps: Property,
+    T: TripleStorePattern<Id, NodeProps, EdgeProps> + ?Sized,
+{
+    let mut result = HashSet::new();
+    for triple in db.match_triples(None, None, None) {
+        let triple = triple?;
+        result.insert((triple.sub.clone(), triple.sub));
+        result.insert((triple.obj.clone(), triple.obj));
+    }
+    Ok(result)
+}
+
+/// The nodes reachable from `start` by one or more applications of `step`.
+///
+/// Each node is expanded at most once, so this terminates on cyclic graphs.
+fn closure<Id: IdType, E>(
+    start: &Id,
+    mut step: impl FnMut(&Id) -> Result<HashSet<Id>, E>,
+) -> Result<HashSet<Id>, E> {
+    let mut reached = HashSet::new();
+    let mut expanded = HashSet::from([start.clone()]);
+    let mut frontier = vec![start.clone()];
+
+    while let Some(node) = frontier.pop() {
+        for next in step(&node)? {
+            reached.insert(next.clone());
+            if expanded.insert(next.clone()) {
+                frontier.push(next);
+            }
+        }
+    }
+
+    Ok(reached)
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __query_path {
+    // Split on `|`, which binds loosest.
+    (@alt ($($cur:tt)*) ($($done:expr,)*)) => {
+        $crate::__query_path!(@alts ($($done,)* $crate::__query_path!(@seq () () $($cur)*),))
+    };
+
+    (@alt ($($cur:tt)*) ($($done:expr,)*) | $($rest:tt)*) => {
+        $crate::__query_path!(@alt () ($($done,)* $crate::__query_path!(@seq () () $($cur)*),) $($rest)*)
+    };
+
+    (@alt ($($cur:tt)*) ($($done:expr,)*) $next:tt $($rest:tt)*) => {
+        $crate::__query_path!(@alt ($($cur)* $next) ($($done,)*) $($rest)*)
+    };
+
+    (@alts ($path:expr,)) => { $path };
+
+    (@alts ($($paths:expr,)*)) => {
+        $crate::path::PropertyPath::Alternative(vec![$($paths),*])
+    };
+
+    // Then on `/`.
+    (@seq ($($cur:tt)*) ($($done:expr,)*)) => {
+        $crate::__query_path!(@seqs ($($done,)* $crate::__query_path!(@unary $($cur)*),))
+    };
+
+    (@seq ($($cur:tt)*) ($($done:expr,)*) / $($rest:tt)*) => {
+        $crate::__query_path!(@seq () ($($done,)* $crate::__query_path!(@unary $($cur)*),) $($rest)*)
+    };
+
+    (@seq ($($cur:tt)*) ($($done:expr,)*) $next:tt $($rest:tt)*) => {
+        $crate::__query_path!(@seq ($($cur)* $next) ($($done,)*) $($rest)*)
+    };
+
+    (@seqs ($path:expr,)) => { $path };
+
+    (@seqs ($($paths:expr,)*)) => {
+        $crate::path::PropertyPath::Sequence(vec![$($paths),*])
+    };
+
+    // `^` applies to the repeated element, as in SPARQL.
+    (@unary ^ $($rest:tt)*) => {
+        $crate::path::PropertyPath::Inverse(Box::new($crate::__query_path!(@unary $($rest)*)))
+    };
+
+    (@unary [$pred:expr] $($mods:tt)*) => {
+        $crate::__query_path!(@mods ($crate::path::PropertyPath::Pred($pred)) $($mods)*)
+    };
+
+    (@unary ($($inner:tt)*) $($mods:tt)*) => {
+        $crate::__query_path!(@mods ($crate::__query_path!(@alt () () $($inner)*)) $($mods)*)
+    };
+
+    (@mods ($path:expr)) => { $path };
+
+    (@mods ($path:expr) ? $($mods:tt)*) => {
+        $crate::__query_path!(@mods ($crate::path::PropertyPath::ZeroOrOne(Box::new($path))) $($mods)*)
+    };
+
+    (@mods ($path:expr) * $($mods:tt)*) => {
+        $crate::__query_path!(@mods ($crate::path::PropertyPath::ZeroOrMore(Box::new($path))) $($mods)*)
+    };
+
+    (@mods ($path:expr) + $($mods:tt)*) => {
+        $crate::__query_path!(@mods ($crate::path::PropertyPath::OneOrMore(Box::new($path))) $($mods)*)
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::PropertyPath;
+    use crate::query;
+
+    #[test]
+    fn test_macro() {
+        let (a, b, c) = (1u64, 2u64, 3u64);
+        let p = PropertyPath::pred;
+
+        assert_eq!(query! { path { [a] } }, p(a));
+        assert_eq!(query! { path { [a]+ } }, p(a).one_or_more());
+        assert_eq!(query! { path { ^[a]* } }, p(a).zero_or_more().inverse());
+        assert_eq!(query! { path { (^[a])? } }, p(a).inverse().zero_or_one());
+        assert_eq!(query! { path { [a] / [b] / [c] } }, p(a).then(b).then(c));
+        assert_eq!(query! { path { [a] | [b] / [c] } }, p(a).or(p(b).then(c)));
+        assert_eq!(
+            query! { path { ([a] | [b])+ / ^[c] } },
+            p(a).or(b).one_or_more().then(p(c).inverse())
+        );
+        assert_eq!(query! { path { [a]+* } }, p(a).one_or_more().zero_or_more());
+    }
+}
@@ -0,0 +1,537 @@
+//! Basic graph patterns: several triple patterns which share named variables.
+//!
+//! A [Pattern] is evaluated with [TripleStorePattern::run_pattern][crate::traits::TripleStorePattern::run_pattern],
+//! which joins its [TriplePattern]s using the SPO, POS and OSP tables and returns one set of [Bindings] per match.
+//!
+//! Patterns are most easily created using the [query][crate::query] macro.
+//!
+//! # Example
+//! ```
+//! # use ulid::Ulid;
+//! # use simple_triplestore::{prelude::*, MemTripleStore, Triple, UlidIdGenerator};
+//! let mut db = MemTripleStore::<Ulid, (), ()>::new(UlidIdGenerator::new());
+//!
+//! let (alice, bob, carol, acme) = (Ulid(1), Ulid(2), Ulid(3), Ulid(4));
+//! let (knows, works_at) = (Ulid(10), Ulid(11));
+//!
+//! db.insert_edge(Triple { sub: alice, pred: knows, obj: bob }, ())?;
+//! db.insert_edge(Triple { sub: alice, pred: knows, obj: carol }, ())?;
+//! db.insert_edge(Triple { sub: bob, pred: works_at, obj: acme }, ())?;
+//!
+//! // Who knows someone that works at acme?
+//! let solutions = db.run_pattern(&query! { pattern { ?x -[knows]-> ?y . ?y -[works_at]-> [acme] } })?;
+//!
+//! assert_eq!(solutions.len(), 1);
+//! assert_eq!(solutions[0]["x"], alice);
+//! assert_eq!(solutions[0]["y"], bob);
+//! # Ok::<(), ()>(())
+//! ```
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    traits::{IdType, Statistics},
+    EdgeOrder, Triple,
+};
+
+/// One variable assignment which satisfies a [Pattern], keyed by variable name.
+pub type Bindings<Id> = HashMap<String, Id>;
+
+/// A component of a [TriplePattern]: either a named variable or a fixed id.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Term<Id: IdType> {
+    /// A variable, which matches anything but must be bound consistently across the whole [Pattern].
+    Var(String),
+
+    /// A fixed id.
+    Const(Id),
+}
+
+impl<Id: IdType> Term<Id> {
+    /// Construct a variable term.
+    pub fn var(name: impl Into<String>) -> Self {
+        Term::Var(name.into())
+    }
+
+    /// The id this term refers to under `bindings`, or `None` if it is an unbound variable.
+    fn resolve(&self, bindings: &Bindings<Id>) -> Option<Id> {
+        match self {
+            Term::Var(name) => bindings.get(name).cloned(),
+            Term::Const(id) => Some(id.clone()),
+        }
+    }
+}
+
+impl<Id: IdType> From<Id> for Term<Id> {
+    fn from(id: Id) -> Self {
+        Term::Const(id)
+    }
+}
+
+/// A single triple in a [Pattern], any part of which may be a variable.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TriplePattern<Id: IdType> {
+    pub sub: Term<Id>,
+    pub pred: Term<Id>,
+    pub obj: Term<Id>,
+}
+
+impl<Id: IdType> TriplePattern<Id> {
+    pub fn new(
+        sub: impl Into<Term<Id>>,
+        pred: impl Into<Term<Id>>,
+        obj: impl Into<Term<Id>>,
+    ) -> Self {
+        Self {
+            sub: sub.into(),
+            pred: pred.into(),
+            obj: obj.into(),
+        }
+    }
+
+    fn terms(&self) -> [&Term<Id>; 3] {
+        [&self.sub, &self.pred, &self.obj]
+    }
+
+    /// Substitute `bindings` into this pattern, producing the lookup to perform. `None` matches anything.
+    pub(crate) fn resolve(&self, bindings: &Bindings<Id>) -> (Option<Id>, Option<Id>, Option<Id>) {
+        (
+            self.sub.resolve(bindings),
+            self.pred.resolve(bindings),
+            self.obj.resolve(bindings),
+        )
+    }
+
+    /// Extend `bindings` with the variables bound by matching `triple`, or `None` if they conflict.
+    pub(crate) fn bind(&self, triple: Triple<Id>, bindings: &Bindings<Id>) -> Option<Bindings<Id>> {
+        let mut result = bindings.clone();
+        for (term, id) in [
+            (&self.sub, triple.sub),
+            (&self.pred, triple.pred),
+            (&self.obj, triple.obj),
+        ] {
+            match term {
+                Term::Const(c) => {
+                    if *c != id {
+                        return None;
+                    }
+                }
+                Term::Var(name) => match result.get(name) {
+                    Some(bound) if *bound != id => return None,
+                    Some(_) => {}
+                    None => {
+                        result.insert(name.clone(), id);
+                    }
+                },
+            }
+        }
+        Some(result)
+    }
+}
+
+/// A basic graph pattern: a conjunction of [TriplePattern]s which share named variables.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern<Id: IdType> {
+    pub triples: Vec<TriplePattern<Id>>,
+}
+
+impl<Id: IdType> Default for Pattern<Id> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Id: IdType> Pattern<Id> {
+    /// Construct an empty pattern, which has exactly one (empty) solution.
+    pub fn new() -> Self {
+        Self {
+            triples: Vec::new(),
+        }
+    }
+
+    /// Add a triple pattern.
+    pub fn with(
+        mut self,
+        sub: impl Into<Term<Id>>,
+        pred: impl Into<Term<Id>>,
+        obj: impl Into<Term<Id>>,
+    ) -> Self {
+        self.triples.push(TriplePattern::new(sub, pred, obj));
+        self
+    }
+
+    /// The names of the variables in this pattern, in order of first appearance.
+    pub fn variables(&self) -> Vec<&str> {
+        let mut seen = HashSet::new();
+        self.triples
+            .iter()
+            .flat_map(|t| t.terms())
+            .filter_map(|term| match term {
+                Term::Var(name) if seen.insert(name.as_str()) => Some(name.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// One step of a [Plan]: a triple pattern to look up, and how.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanStep<Id: IdType> {
+    /// The triple pattern.
+    pub pattern: TriplePattern<Id>,
+
+    /// The table the lookup ranges over, chosen by which terms are bound when the step runs.
+    pub order: EdgeOrder,
+
+    /// Which of the subject, predicate and object are bound, by constants or by earlier steps.
+    pub bound: [bool; 3],
+
+    /// The estimated number of edges matched by each lookup.
+    pub estimate: f64,
+
+    /// The estimated number of solutions after this step.
+    pub rows: f64,
+}
+
+/// The order in which the triple patterns of a [Pattern] are joined, as produced by
+/// [TripleStorePattern::explain][crate::traits::TripleStorePattern::explain].
+///
+/// Formats as one line per step.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Plan<Id: IdType> {
+    pub steps: Vec<PlanStep<Id>>,
+}
+
+/// The estimated number of edges matching a triple pattern with the given terms bound.
+///
+/// Assumes that subjects and objects are spread evenly over the edges with each predicate. When the predicate is not a
+/// constant the averages over the whole store are used instead.
+fn estimate<Id: IdType>(
+    pattern: &TriplePattern<Id>,
+    [sub, pred, obj]: [bool; 3],
+    stats: &Statistics<Id>,
+) -> f64 {
+    let (edges, subjects, objects) = match &pattern.pred {
+        Term::Const(p) => {
+            let p = stats.predicate(p);
+            (p.edges, p.subjects, p.objects)
+        }
+        Term::Var(_) => (stats.edges, stats.nodes, stats.nodes),
+    };
+    if edges == 0 {
+        return 0.0;
+    }
+
+    let mut estimate = edges as f64;
+    if sub {
+        estimate /= subjects.max(1) as f64;
+    }
+    if obj {
+        estimate /= objects.max(1) as f64;
+    }
+    if pred && matches!(pattern.pred, Term::Var(_)) {
+        estimate /= stats.predicates.len().max(1) as f64;
+    }
+    estimate
+}
+
+impl<Id: IdType> Plan<Id> {
+    /// Plan the joins of `pattern` using `stats`.
+    ///
+    /// Greedily picks the triple pattern with the fewest estimated matches per lookup, preferring patterns which share
+    /// a variable with those already joined so that no cross products are formed early. Ties go to the earlier pattern.
+    pub fn new(pattern: &Pattern<Id>, stats: &Statistics<Id>) -> Self {
+        let mut remaining = pattern.triples.iter().collect::<Vec<_>>();
+        let mut bound_vars = HashSet::new();
+        let mut rows = 1.0;
+        let mut steps = Vec::with_capacity(remaining.len());
+
+        while !remaining.is_empty() {
+            let is_bound = |term: &Term<Id>| match term {
+                Term::Const(_) => true,
+                Term::Var(name) => bound_vars.contains(name.as_str()),
+            };
+
+            let (index, bound, estimate) = remaining
+                .iter()
+                .enumerate()
+                .map(|(i, t)| {
+                    let bound = t.terms().map(is_bound);
+                    (i, bound, estimate(t, bound, stats))
+                })
+                .min_by(|(i, bound_a, a), (j, bound_b, b)| {
+                    let connected = |t: &TriplePattern<Id>| {
+                        bound_vars.is_empty()
+                            || t.terms().iter().any(|term| {
+                                matches!(term, Term::Var(name) if bound_vars.contains(name.as_str()))
+                            })
+                    };
+                    connected(remaining[*j])
+                        .cmp(&connected(remaining[*i]))
+                        .then(a.total_cmp(b))
+                        .then(
+                            bound_b
+                                .iter()
+                                .filter(|b| **b)
+                                .count()
+                                .cmp(&bound_a.iter().filter(|b| **b).count()),
+                        )
+                        .then(i.cmp(j))
+                })
+                .expect("remaining is not empty");
+
+            let next = remaining.remove(index);
+            for term in next.terms() {
+                if let Term::Var(name) = term {
+                    bound_vars.insert(name.as_str());
+                }
+            }
+
+            // The same choice of table as `match_triples`.
+            let order = match bound {
+                [_, false, true] => EdgeOrder::OSP,
+                [false, true, _] => EdgeOrder::POS,
+                _ => EdgeOrder::SPO,
+            };
+            rows *= estimate;
+            steps.push(PlanStep {
+                pattern: next.clone(),
+                order,
+                bound,
+                estimate,
+                rows,
+            });
+        }
+
+        Self { steps }
+    }
+}
+
+impl<Id: IdType> std::fmt::Display for Plan<Id> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, step) in self.steps.iter().enumerate() {
+            let mut terms = Vec::with_capacity(3);
+            for term in step.pattern.terms() {
+                terms.push(match term {
+                    Term::Var(name) => format!("?{}", name),
+                    Term::Const(id) => format!("[{:?}]", id),
+                });
+            }
+            writeln!(
+                f,
+                "{}. {} -{}-> {} using {:?} (~{:.1} per lookup, ~{:.1} rows)",
+                i + 1,
+                terms[0],
+                terms[1],
+                terms[2],
+                step.order,
+                step.estimate,
+                step.rows
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __query_pattern {
+    (@triples $pattern:ident) => {};
+
+    (@triples $pattern:ident ? $sub:ident - $($rest:tt)*) => {
+        $crate::__query_pattern!(@pred $pattern ($crate::pattern::Term::Var(stringify!($sub).to_string())) $($rest)*)
+    };
+
+    (@triples $pattern:ident [$sub:expr] - $($rest:tt)*) => {
+        $crate::__query_pattern!(@pred $pattern ($crate::pattern::Term::Const($sub)) $($rest)*)
+    };
+
+    (@pred $pattern:ident $sub:tt ? $pred:ident -> $($rest:tt)*) => {
+        $crate::__query_pattern!(@obj $pattern $sub ($crate::pattern::Term::Var(stringify!($pred).to_string())) $($rest)*)
+    };
+
+    (@pred $pattern:ident $sub:tt [$pred:expr] -> $($rest:tt)*) => {
+        $crate::__query_pattern!(@obj $pattern $sub ($crate::pattern::Term::Const($pred)) $($rest)*)
+    };
+
+    (@obj $pattern:ident $sub:tt $pred:tt ? $obj:ident $(. $($rest:tt)*)?) => {
+        $pattern.triples.push($crate::pattern::TriplePattern {
+            sub: $sub,
+            pred: $pred,
+            obj: $crate::pattern::Term::Var(stringify!($obj).to_string()),
+        });
+        $($crate::__query_pattern!(@triples $pattern $($rest)*);)?
+    };
+
+    (@obj $pattern:ident $sub:tt $pred:tt [$obj:expr] $(. $($rest:tt)*)?) => {
+        $pattern.triples.push($crate::pattern::TriplePattern {
+            sub: $sub,
+            pred: $pred,
+            obj: $crate::pattern::Term::Const($obj),
+        });
+        $($crate::__query_pattern!(@triples $pattern $($rest)*);)?
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Pattern, Plan, Term, TriplePattern};
+    use crate::{
+        query,
+        traits::{PredicateStats, Statistics},
+        EdgeOrder, Triple,
+    };
+
+    #[test]
+    fn test_macro() {
+        let (knows, works_at, acme) = (1u64, 2u64, 3u64);
+        let pattern =
+            query! { pattern { ?x -[knows]-> ?y . ?y -[works_at]-> [acme] . [acme] -?p-> ?x } };
+
+        assert_eq!(
+            pattern,
+            Pattern::new()
+                .with(Term::var("x"), knows, Term::var("y"))
+                .with(Term::var("y"), works_at, acme)
+                .with(acme, Term::var("p"), Term::var("x"))
+        );
+        assert_eq!(pattern.variables(), ["x", "y", "p"]);
+    }
+
+    #[test]
+    fn test_bind() {
+        let pattern = TriplePattern::new(Term::var("x"), 1u64, Term::var("x"));
+        let bindings = Default::default();
+
+        assert_eq!(
+            pattern.bind(
+                Triple {
+                    sub: 2,
+                    pred: 1,
+                    obj: 2
+                },
+                &bindings
+            ),
+            Some([("x".to_string(), 2)].into())
+        );
+        assert_eq!(
+            pattern.bind(
+                Triple {
+                    sub: 2,
+                    pred: 1,
+                    obj: 3
+                },
+                &bindings
+            ),
+            None
+        );
+        assert_eq!(
+            pattern.bind(
+                Triple {
+                    sub: 2,
+                    pred: 4,
+                    obj: 2
+                },
+                &bindings
+            ),
+            None
+        );
+    }
+
+    fn stats(predicates: &[(u64, u64, u64, u64)]) -> Statistics<u64> {
+        Statistics {
+            nodes: 100,
+            edges: predicates.iter().map(|(_, edges, _, _)| edges).sum(),
+            predicates: predicates
+                .iter()
+                .map(|(pred, edges, subjects, objects)| {
+                    (
+                        *pred,
+                        PredicateStats {
+                            edges: *edges,
+                            subjects: *subjects,
+                            objects: *objects,
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_join_order() {
+        let pattern = Pattern::new()
+            .with(Term::var("a"), Term::var("p"), Term::var("b"))
+            .with(Term::var("b"), 1u64, Term::var("c"))
+            .with(Term::var("c"), 2u64, 3u64);
+
+        let plan = Plan::new(&pattern, &stats(&[(1, 100, 50, 10), (2, 20, 20, 2)]));
+        assert_eq!(
+            plan.steps
+                .iter()
+                .map(|step| &step.pattern)
+                .collect::<Vec<_>>(),
+            [
+                &pattern.triples[2],
+                &pattern.triples[1],
+                &pattern.triples[0]
+            ]
+        );
+        assert_eq!(
+            plan.steps
+                .iter()
+                .map(|step| (step.order.clone(), step.bound))
+                .collect::<Vec<_>>(),
+            [
+                (EdgeOrder::POS, [false, true, true]),
+                (EdgeOrder::POS, [false, true, true]),
+                (EdgeOrder::OSP, [false, false, true])
+            ]
+        );
+        assert_eq!(
+            plan.steps
+                .iter()
+                .map(|step| (step.estimate, step.rows))
+                .collect::<Vec<_>>(),
+            [(10.0, 10.0), (10.0, 100.0), (1.2, 120.0)]
+        );
+    }
+
+    #[test]
+    fn test_join_order_uses_statistics() {
+        let pattern = query! { pattern { ?x -[1u64]-> ?y . ?y -[2u64]-> ?z } };
+        let order = |stats: &Statistics<u64>| {
+            Plan::new(&pattern, stats)
+                .steps
+                .into_iter()
+                .map(|step| step.pattern)
+                .collect::<Vec<_>>()
+        };
+
+        // Start with whichever predicate is rarer.
+        assert_eq!(
+            order(&stats(&[(1, 5, 5, 5), (2, 1000, 100, 100)])),
+            [pattern.triples[0].clone(), pattern.triples[1].clone()]
+        );
+        assert_eq!(
+            order(&stats(&[(1, 1000, 100, 100), (2, 5, 5, 5)])),
+            [pattern.triples[1].clone(), pattern.triples[0].clone()]
+        );
+
+        // A predicate which is never used matches nothing, so it goes first.
+        assert_eq!(
+            order(&stats(&[(1, 5, 5, 5)])),
+            [pattern.triples[1].clone(), pattern.triples[0].clone()]
+        );
+    }
+
+    #[test]
+    fn test_plan_display() {
+        let pattern = query! { pattern { ?x -[1u64]-> [2u64] } };
+        let plan = Plan::new(&pattern, &stats(&[(1, 10, 10, 5)]));
+        assert_eq!(
+            plan.to_string(),
+            "1. ?x -[1]-> [2] using POS (~2.0 per lookup, ~2.0 rows)\n"
+        );
+    }
+}
@@ -1,5 +1,8 @@
 pub use crate::query;
 pub use crate::traits::{
-    TripleStore, TripleStoreError, TripleStoreExtend, TripleStoreInsert, TripleStoreIntoIter,
-    TripleStoreIter, TripleStoreMerge, TripleStoreQuery, TripleStoreRemove, TripleStoreSetOps,
+    QuadStore, TripleStore, TripleStoreBulkLoad, TripleStoreCount, TripleStoreError,
+    TripleStoreExtend, TripleStoreGet, TripleStoreIndex, TripleStoreInsert, TripleStoreIntoIter,
+    TripleStoreIter, TripleStoreMerge, TripleStorePaginate, TripleStorePattern, TripleStoreQuery,
+    TripleStoreRemove, TripleStoreSetOps, TripleStoreStats, TripleStoreSubscribe,
+    TripleStoreTransaction, TripleStoreVerify,
 };
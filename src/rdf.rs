@@ -1,18 +1,178 @@
+//! An RDF-style layer on top of a [TripleStore] which allows nodes and edges to be addressed by name.
+//!
+//! Each [Entity] is a name (e.g. an IRI), a literal, a blank node or a raw [Ulid]. Names and literals are mapped to
+//! [Ulid]s through a [BidirIndex] and the resulting triples are stored in any `TripleStore<Ulid, _, _>`.
+//!
+//! # Example
+//! ```
+//! # use simple_triplestore::{prelude::*, rdf::{Entity, MemRdfTripleStore}, EdgeOrder, Triple};
+//! let mut db = MemRdfTripleStore::<String, String>::new_mem();
+//!
+//! db.insert_node("alice".into(), "Alice".to_string())?;
+//! db.insert_node("bob".into(), "Bob".to_string())?;
+//! db.insert_edge(
+//!     Triple {
+//!         sub: "alice".into(),
+//!         pred: "knows".into(),
+//!         obj: "bob".into(),
+//!     },
+//!     "since 2020".to_string(),
+//! )?;
+//!
+//! let edges = db
+//!     .iter_edges(EdgeOrder::SPO)
+//!     .map(|r| r.map(|(triple, _)| triple))
+//!     .collect::<Result<Vec<_>, _>>()?;
+//!
+//! assert_eq!(
+//!     edges,
+//!     [Triple {
+//!         sub: Entity::from("alice"),
+//!         pred: Entity::from("knows"),
+//!         obj: Entity::from("bob"),
+//!     }]
+//! );
+//! # Ok::<(), simple_triplestore::rdf::RdfTripleStoreError<_, _>>(())
+//! ```
+
 use ulid::Ulid;
 
-use crate::traits::{BidirIndex, Property, TripleStore, TripleStoreError};
+use crate::{
+    mem::MemHashIndex,
+    traits::{BidirIndex, Property, TripleStore, TripleStoreError},
+    MemTripleStore, UlidIdGenerator,
+};
 
+mod count;
 mod extend;
+mod get;
 mod insert;
 mod iter;
 mod merge;
+pub mod nquads;
+pub mod ntriples;
+mod pattern;
+mod quad;
 mod query;
 mod remove;
+pub mod sparql;
+mod stats;
+pub mod trig;
+pub mod turtle;
+pub mod vocab;
 
-#[derive(Debug, Hash, PartialEq, Eq, Clone)]
-enum Entity {
+/// A node or edge identifier in an [RdfTripleStore].
+#[derive(Debug, Hash, PartialEq, Eq, Clone, PartialOrd, Ord)]
+#[cfg_attr(feature = "sled", derive(serde::Serialize, serde::Deserialize))]
+pub enum Entity {
+    /// A named entity (e.g. an IRI), which is mapped to a [Ulid] by the name index.
     String(String),
+
+    /// An anonymous entity which is stored directly by its [Ulid].
     Ulid(Ulid),
+
+    /// An RDF literal, which is mapped to a [Ulid] by the name index.
+    Literal(Literal),
+
+    /// An RDF blank node, identified by a label.
+    ///
+    /// Like names, blank nodes are assigned a fresh [Ulid] by the name index, so a blank node is never confused with
+    /// the [Entity::Ulid] of its label.
+    BlankNode(Ulid),
+}
+
+/// An RDF literal: a lexical form with either a datatype or a language tag, but not both.
+///
+/// Plain literals have neither.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, PartialOrd, Ord)]
+#[cfg_attr(feature = "sled", derive(serde::Serialize, serde::Deserialize))]
+pub struct Literal {
+    lexical: String,
+    datatype: Option<String>,
+    lang: Option<String>,
+}
+
+impl Literal {
+    /// A plain literal.
+    pub fn plain(lexical: impl Into<String>) -> Self {
+        Literal {
+            lexical: lexical.into(),
+            datatype: None,
+            lang: None,
+        }
+    }
+
+    /// A literal with the given datatype IRI.
+    ///
+    /// `xsd:string` is the datatype of plain literals, so it produces the same literal as [Literal::plain].
+    pub fn typed(lexical: impl Into<String>, datatype: impl Into<String>) -> Self {
+        let datatype = datatype.into();
+        Literal {
+            lexical: lexical.into(),
+            datatype: (datatype != vocab::xsd::STRING).then_some(datatype),
+            lang: None,
+        }
+    }
+
+    /// A language-tagged literal.
+    pub fn lang(lexical: impl Into<String>, lang: impl Into<String>) -> Self {
+        Literal {
+            lexical: lexical.into(),
+            datatype: None,
+            lang: Some(lang.into()),
+        }
+    }
+
+    /// The lexical form.
+    pub fn lexical(&self) -> &str {
+        &self.lexical
+    }
+
+    /// The datatype IRI, or `None` for plain and language-tagged literals.
+    pub fn datatype(&self) -> Option<&str> {
+        self.datatype.as_deref()
+    }
+
+    /// The language tag, or `None` if the literal is not language-tagged.
+    pub fn language(&self) -> Option<&str> {
+        self.lang.as_deref()
+    }
+}
+
+impl std::fmt::Display for Literal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{:?}", self.lexical))?;
+        if let Some(lang) = &self.lang {
+            f.write_fmt(format_args!("@{}", lang))?;
+        } else if let Some(datatype) = &self.datatype {
+            f.write_fmt(format_args!("^^<{}>", datatype))?;
+        }
+        Ok(())
+    }
+}
+
+impl Entity {
+    /// A plain literal.
+    pub fn literal(lexical: impl Into<String>) -> Self {
+        Entity::Literal(Literal::plain(lexical))
+    }
+
+    /// A literal with the given datatype IRI.
+    ///
+    /// `xsd:string` is the datatype of plain literals, so it produces the same entity as [Entity::literal].
+    pub fn typed_literal(lexical: impl Into<String>, datatype: impl Into<String>) -> Self {
+        Entity::Literal(Literal::typed(lexical, datatype))
+    }
+
+    /// A language-tagged literal.
+    pub fn lang_literal(lexical: impl Into<String>, lang: impl Into<String>) -> Self {
+        Entity::Literal(Literal::lang(lexical, lang))
+    }
+
+    /// A new blank node.
+    pub fn blank_node() -> Self {
+        Entity::BlankNode(Ulid::new())
+    }
 }
 
 impl From<String> for Entity {
@@ -27,24 +187,72 @@ impl From<&str> for Entity {
     }
 }
 
+impl From<Literal> for Entity {
+    fn from(value: Literal) -> Self {
+        Entity::Literal(value)
+    }
+}
+
 impl From<Ulid> for Entity {
     fn from(value: Ulid) -> Self {
         Entity::Ulid(value)
     }
 }
 
+impl std::fmt::Display for Entity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Entity::String(s) => f.write_str(s),
+            Entity::Ulid(id) => f.write_fmt(format_args!("{}", id)),
+            Entity::Literal(literal) => literal.fmt(f),
+            Entity::BlankNode(id) => f.write_fmt(format_args!("_:{}", id)),
+        }
+    }
+}
+
+/// Errors which can occur while using an [RdfTripleStore].
 #[derive(Debug)]
-enum RdfTripleStoreError<NameIndexStorageError, GraphStorageError> {
+pub enum RdfTripleStoreError<NameIndexStorageError, GraphStorageError> {
+    /// The name index returned an error.
     NameIndexStorageError(NameIndexStorageError),
+
+    /// The underlying graph storage returned an error.
     GraphStorageError(GraphStorageError),
-    #[allow(dead_code)]
+
+    /// A named entity or literal was looked up which has never been inserted.
     NameNotFound(String),
 }
 
-struct RdfTripleStore<
+impl<NameIndexStorageError: std::fmt::Debug, GraphStorageError: std::fmt::Debug> std::fmt::Display
+    for RdfTripleStoreError<NameIndexStorageError, GraphStorageError>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RdfTripleStoreError::NameIndexStorageError(e) => {
+                f.write_fmt(format_args!("name index storage error: {:?}", e))
+            }
+            RdfTripleStoreError::GraphStorageError(e) => {
+                f.write_fmt(format_args!("graph storage error: {:?}", e))
+            }
+            RdfTripleStoreError::NameNotFound(name) => {
+                f.write_fmt(format_args!("name not found: {}", name))
+            }
+        }
+    }
+}
+
+impl<NameIndexStorageError: std::fmt::Debug, GraphStorageError: std::fmt::Debug> std::error::Error
+    for RdfTripleStoreError<NameIndexStorageError, GraphStorageError>
+{
+}
+
+/// A triplestore which addresses nodes and edges by [Entity] rather than by raw id.
+///
+/// See [MemRdfTripleStore] and [SledRdfTripleStore] for ready-made backends.
+pub struct RdfTripleStore<
     NodeProps: Property,
     EdgeProps: Property,
-    NameIndex: BidirIndex<Left = String, Right = Ulid>,
+    NameIndex: BidirIndex<Left = Entity, Right = Ulid>,
     TripleStorage: TripleStore<Ulid, NodeProps, EdgeProps>,
 > {
     name_index: NameIndex,
@@ -52,13 +260,56 @@ struct RdfTripleStore<
     _phantom: std::marker::PhantomData<(NodeProps, EdgeProps)>,
 }
 
+/// An [RdfTripleStore] which is held entirely in memory.
+pub type MemRdfTripleStore<NodeProps, EdgeProps> = RdfTripleStore<
+    NodeProps,
+    EdgeProps,
+    MemHashIndex<Entity, Ulid>,
+    MemTripleStore<Ulid, NodeProps, EdgeProps>,
+>;
+
+impl<NodeProps: Property, EdgeProps: Property> MemRdfTripleStore<NodeProps, EdgeProps> {
+    /// Construct a new, empty, in-memory RDF triplestore.
+    pub fn new_mem() -> Self {
+        Self::new(
+            MemHashIndex::new(),
+            MemTripleStore::new(UlidIdGenerator::new()),
+        )
+    }
+}
+
+/// An [RdfTripleStore] which is backed by [sled](https://sled.rs).
+#[cfg(feature = "sled")]
+pub type SledRdfTripleStore<NodeProps, EdgeProps> = RdfTripleStore<
+    NodeProps,
+    EdgeProps,
+    crate::sled::SledHashIndex<Entity, Ulid>,
+    crate::SledTripleStore<Ulid, NodeProps, EdgeProps>,
+>;
+
+#[cfg(feature = "sled")]
+impl<
+        NodeProps: Property + serde::Serialize + serde::de::DeserializeOwned,
+        EdgeProps: Property + serde::Serialize + serde::de::DeserializeOwned,
+    > SledRdfTripleStore<NodeProps, EdgeProps>
+{
+    /// Open an RDF triplestore within the given sled database.
+    pub fn new_sled(db: &sled::Db) -> Result<Self, crate::SledTripleStoreError> {
+        Ok(Self::new(
+            crate::sled::SledHashIndex::new(db)?,
+            crate::SledTripleStore::new(db, UlidIdGenerator::new())?,
+        ))
+    }
+}
+
 impl<
         NodeProps: Property,
         EdgeProps: Property,
-        NameIndex: BidirIndex<Left = String, Right = Ulid>,
+        NameIndex: BidirIndex<Left = Entity, Right = Ulid>,
         TripleStorage: TripleStore<Ulid, NodeProps, EdgeProps>,
     > RdfTripleStore<NodeProps, EdgeProps, NameIndex, TripleStorage>
 {
+    /// Construct a new RDF triplestore from a name index and the graph storage.
     pub fn new(name_index: NameIndex, graph: TripleStorage) -> Self {
         Self {
             name_index,
@@ -67,65 +318,79 @@ impl<
         }
     }
 
+    /// Get the [Ulid] for the given entity, assigning a new one if the name, literal or blank node has not been seen
+    /// before.
     pub fn lookup_or_create_entity(
         &mut self,
         entity: &Entity,
     ) -> Result<Ulid, RdfTripleStoreError<NameIndex::Error, TripleStorage::Error>> {
         match entity {
-            Entity::String(s) => {
+            Entity::Ulid(id) => Ok(*id),
+            Entity::String(_) | Entity::Literal(_) | Entity::BlankNode(_) => {
                 let result = self
                     .name_index
-                    .left_to_right(&s)
-                    .map_err(|e| RdfTripleStoreError::NameIndexStorageError(e))?;
+                    .left_to_right(entity)
+                    .map_err(RdfTripleStoreError::NameIndexStorageError)?;
 
                 if let Some(id) = result {
                     Ok(id)
                 } else {
                     let id = Ulid::new();
                     self.name_index
-                        .set(s.clone(), id)
-                        .map_err(|e| RdfTripleStoreError::NameIndexStorageError(e))?;
+                        .set(entity.clone(), id)
+                        .map_err(RdfTripleStoreError::NameIndexStorageError)?;
                     Ok(id)
                 }
             }
-            Entity::Ulid(id) => Ok(id.clone()),
         }
     }
 
+    /// Get the [Ulid] for the given entity, or [RdfTripleStoreError::NameNotFound] if the name, literal or blank node
+    /// is unknown.
     pub fn lookup_entity(
         &self,
         entity: &Entity,
     ) -> Result<Ulid, RdfTripleStoreError<NameIndex::Error, TripleStorage::Error>> {
         match entity {
-            Entity::String(s) => self
+            Entity::Ulid(id) => Ok(*id),
+            Entity::String(_) | Entity::Literal(_) | Entity::BlankNode(_) => self
                 .name_index
-                .left_to_right(&s)
-                .map_err(|e| RdfTripleStoreError::NameIndexStorageError(e))?
-                .ok_or(RdfTripleStoreError::NameNotFound(s.clone())),
-            Entity::Ulid(id) => Ok(id.clone()),
+                .left_to_right(entity)
+                .map_err(RdfTripleStoreError::NameIndexStorageError)?
+                .ok_or_else(|| RdfTripleStoreError::NameNotFound(entity.to_string())),
         }
     }
 
+    /// Get the [Entity] for the given [Ulid] in the given name index.
     pub fn lookup_id(
         name_index: &NameIndex,
         id: &Ulid,
     ) -> Result<Entity, RdfTripleStoreError<NameIndex::Error, TripleStorage::Error>> {
-        Ok(
-            match name_index
-                .right_to_left(id)
-                .map_err(|e| RdfTripleStoreError::NameIndexStorageError(e))?
-            {
-                Some(s) => Entity::String(s),
-                None => Entity::Ulid(id.clone()),
-            },
-        )
+        Ok(name_index
+            .right_to_left(id)
+            .map_err(RdfTripleStoreError::NameIndexStorageError)?
+            .unwrap_or(Entity::Ulid(*id)))
     }
 }
 
 impl<
         NodeProps: Property,
         EdgeProps: Property,
-        NameIndex: BidirIndex<Left = String, Right = Ulid>,
+        NameIndex: BidirIndex<Left = Entity, Right = Ulid>,
+        TripleStorage: TripleStore<Ulid, NodeProps, EdgeProps> + std::fmt::Debug,
+    > std::fmt::Debug for RdfTripleStore<NodeProps, EdgeProps, NameIndex, TripleStorage>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RdfTripleStore")
+            .field("graph", &self.graph)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<
+        NodeProps: Property,
+        EdgeProps: Property,
+        NameIndex: BidirIndex<Left = Entity, Right = Ulid>,
         TripleStorage: TripleStore<Ulid, NodeProps, EdgeProps>,
     > TripleStore<Entity, NodeProps, EdgeProps>
     for RdfTripleStore<NodeProps, EdgeProps, NameIndex, TripleStorage>
@@ -135,7 +400,7 @@ impl<
 impl<
         NodeProps: Property,
         EdgeProps: Property,
-        NameIndex: BidirIndex<Left = String, Right = Ulid>,
+        NameIndex: BidirIndex<Left = Entity, Right = Ulid>,
         TripleStorage: TripleStore<Ulid, NodeProps, EdgeProps>,
     > TripleStoreError for RdfTripleStore<NodeProps, EdgeProps, NameIndex, TripleStorage>
 {
@@ -148,12 +413,12 @@ mod test {
 
     use ulid::Ulid;
 
-    use crate::traits::{TripleStoreInsert, TripleStoreIter};
+    use crate::traits::{TripleStore, TripleStoreInsert, TripleStoreIter};
     use crate::{MemTripleStore, PropsTriple, Triple, UlidIdGenerator};
 
     use crate::mem::MemHashIndex;
 
-    use super::RdfTripleStore;
+    use super::{Entity, MemRdfTripleStore, RdfTripleStore};
 
     #[test]
     fn test_new() {
@@ -209,4 +474,127 @@ mod test {
             .into()
         )
     }
+
+    fn check_literals_and_blank_nodes<T: TripleStore<Entity, (), ()>>(mut db: T) {
+        let blank = Entity::blank_node();
+        let literals = [
+            Entity::literal("42"),
+            Entity::typed_literal("42", super::vocab::xsd::INTEGER),
+            Entity::lang_literal("chat", "fr"),
+            Entity::lang_literal("chat", "en"),
+        ];
+
+        for obj in literals.iter().chain([&blank]) {
+            db.insert_edge(
+                Triple {
+                    sub: "s".into(),
+                    pred: "p".into(),
+                    obj: obj.clone(),
+                },
+                (),
+            )
+            .expect("ok");
+        }
+
+        // Inserting the same literal again reuses its id.
+        db.insert_edge(
+            Triple {
+                sub: "s".into(),
+                pred: "p".into(),
+                obj: Entity::typed_literal("42", super::vocab::xsd::INTEGER),
+            },
+            (),
+        )
+        .expect("ok");
+
+        let expected = literals
+            .iter()
+            .chain([&blank])
+            .cloned()
+            .collect::<HashSet<_>>();
+
+        let objects = db
+            .iter_edges(crate::EdgeOrder::SPO)
+            .map(|r| r.expect("ok").0.obj)
+            .collect::<Vec<_>>();
+        assert_eq!(objects.len(), 5);
+        assert_eq!(objects.into_iter().collect::<HashSet<_>>(), expected);
+
+        let result = db.run(crate::Query::P(["p".into()].into())).expect("ok");
+        assert_eq!(
+            result
+                .iter_edges(crate::EdgeOrder::SPO)
+                .map(|r| r.expect("ok").0.obj)
+                .collect::<HashSet<_>>(),
+            expected
+        );
+
+        let result = db
+            .run(crate::Query::O([Entity::lang_literal("chat", "fr")].into()))
+            .expect("ok");
+        assert_eq!(
+            result
+                .iter_edges(crate::EdgeOrder::SPO)
+                .map(|r| r.expect("ok").0)
+                .collect::<Vec<_>>(),
+            [Triple {
+                sub: "s".into(),
+                pred: "p".into(),
+                obj: Entity::lang_literal("chat", "fr"),
+            }]
+        );
+
+        // Unknown literals are not found.
+        assert!(db
+            .run(crate::Query::O([Entity::literal("missing")].into()))
+            .is_err());
+
+        // A blank node and the raw id of its label are different entities.
+        let Entity::BlankNode(label) = blank else {
+            unreachable!("blank_node returns a blank node");
+        };
+        db.insert_edge(
+            Triple {
+                sub: "s".into(),
+                pred: "q".into(),
+                obj: Entity::Ulid(label),
+            },
+            (),
+        )
+        .expect("ok");
+        let result = db.run(crate::Query::O([blank.clone()].into())).expect("ok");
+        assert_eq!(
+            result
+                .iter_edges(crate::EdgeOrder::SPO)
+                .map(|r| r.expect("ok").0.pred)
+                .collect::<Vec<_>>(),
+            [Entity::from("p")]
+        );
+        let result = db
+            .run(crate::Query::O([Entity::Ulid(label)].into()))
+            .expect("ok");
+        assert_eq!(
+            result
+                .iter_edges(crate::EdgeOrder::SPO)
+                .map(|r| r.expect("ok").0)
+                .collect::<Vec<_>>(),
+            [Triple {
+                sub: "s".into(),
+                pred: "q".into(),
+                obj: Entity::Ulid(label),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_literals_and_blank_nodes_mem() {
+        check_literals_and_blank_nodes(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_literals_and_blank_nodes_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        check_literals_and_blank_nodes(super::SledRdfTripleStore::new_sled(&db).expect("ok"));
+    }
 }
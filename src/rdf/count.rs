@@ -0,0 +1,49 @@
+use super::{Entity, RdfTripleStore, RdfTripleStoreError};
+use crate::traits::{BidirIndex, Property, TripleStore, TripleStoreCount};
+use ulid::Ulid;
+
+impl<
+        NodeProps: Property,
+        EdgeProps: Property,
+        NameIndex: BidirIndex<Left = Entity, Right = Ulid>,
+        TripleStorage: TripleStore<Ulid, NodeProps, EdgeProps>,
+    > TripleStoreCount<Entity, NodeProps, EdgeProps>
+    for RdfTripleStore<NodeProps, EdgeProps, NameIndex, TripleStorage>
+{
+    fn len_nodes(&self) -> Result<usize, Self::Error> {
+        self.graph
+            .len_nodes()
+            .map_err(RdfTripleStoreError::GraphStorageError)
+    }
+
+    fn len_edges(&self) -> Result<usize, Self::Error> {
+        self.graph
+            .len_edges()
+            .map_err(RdfTripleStoreError::GraphStorageError)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::rdf::MemRdfTripleStore;
+
+    #[test]
+    fn test_count_mem() {
+        let db = MemRdfTripleStore::new_mem();
+        crate::conformance::count::test_count(db);
+    }
+
+    #[test]
+    fn test_count_aggregates_mem() {
+        let db = MemRdfTripleStore::new_mem();
+        crate::conformance::count::test_count_aggregates(db);
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_count_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let db = crate::rdf::SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::count::test_count(db);
+    }
+}
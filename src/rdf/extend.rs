@@ -8,7 +8,7 @@ use ulid::Ulid;
 impl<
         NodeProps: Property,
         EdgeProps: Property,
-        NameIndex: BidirIndex<Left = String, Right = Ulid>,
+        NameIndex: BidirIndex<Left = Entity, Right = Ulid>,
         TripleStorage: TripleStore<Ulid, NodeProps, EdgeProps>,
     > TripleStoreExtend<Entity, NodeProps, EdgeProps>
     for RdfTripleStore<NodeProps, EdgeProps, NameIndex, TripleStorage>
@@ -36,3 +36,27 @@ impl<
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::rdf::MemRdfTripleStore;
+    #[cfg(feature = "sled")]
+    use crate::rdf::SledRdfTripleStore;
+
+    #[test]
+    fn test_extend_mem() {
+        let left = MemRdfTripleStore::new_mem();
+        let right = MemRdfTripleStore::new_mem();
+        crate::conformance::extend::test_extend(left, right);
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_extend_sled() {
+        let (_left_tempdir, left_db) = crate::sled::create_test_db().expect("ok");
+        let (_right_tempdir, right_db) = crate::sled::create_test_db().expect("ok");
+        let left = SledRdfTripleStore::new_sled(&left_db).expect("ok");
+        let right = SledRdfTripleStore::new_sled(&right_db).expect("ok");
+        crate::conformance::extend::test_extend(left, right);
+    }
+}
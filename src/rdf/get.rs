@@ -0,0 +1,111 @@
+use super::{Entity, RdfTripleStore, RdfTripleStoreError};
+use crate::{
+    traits::{BidirIndex, Property, TripleStore, TripleStoreError, TripleStoreGet},
+    Triple,
+};
+use ulid::Ulid;
+
+impl<
+        NodeProps: Property,
+        EdgeProps: Property,
+        NameIndex: BidirIndex<Left = Entity, Right = Ulid>,
+        TripleStorage: TripleStore<Ulid, NodeProps, EdgeProps>,
+    > RdfTripleStore<NodeProps, EdgeProps, NameIndex, TripleStorage>
+{
+    /// Translate a triple to ids, returning `None` if any of its entities has never been inserted.
+    fn lookup_triple(
+        &self,
+        triple: &Triple<Entity>,
+    ) -> Result<Option<Triple<Ulid>>, <Self as TripleStoreError>::Error> {
+        match (
+            self.lookup_pattern_entity(&triple.sub)?,
+            self.lookup_pattern_entity(&triple.pred)?,
+            self.lookup_pattern_entity(&triple.obj)?,
+        ) {
+            (Some(sub), Some(pred), Some(obj)) => Ok(Some(Triple { sub, pred, obj })),
+            _ => Ok(None),
+        }
+    }
+}
+
+impl<
+        NodeProps: Property,
+        EdgeProps: Property,
+        NameIndex: BidirIndex<Left = Entity, Right = Ulid>,
+        TripleStorage: TripleStore<Ulid, NodeProps, EdgeProps>,
+    > TripleStoreGet<Entity, NodeProps, EdgeProps>
+    for RdfTripleStore<NodeProps, EdgeProps, NameIndex, TripleStorage>
+{
+    fn get_node(&self, id: &Entity) -> Result<Option<NodeProps>, Self::Error> {
+        match self.lookup_pattern_entity(id)? {
+            Some(id) => self
+                .graph
+                .get_node(&id)
+                .map_err(RdfTripleStoreError::GraphStorageError),
+            None => Ok(None),
+        }
+    }
+
+    fn get_edge(&self, triple: &Triple<Entity>) -> Result<Option<EdgeProps>, Self::Error> {
+        match self.lookup_triple(triple)? {
+            Some(triple) => self
+                .graph
+                .get_edge(&triple)
+                .map_err(RdfTripleStoreError::GraphStorageError),
+            None => Ok(None),
+        }
+    }
+
+    fn contains_node(&self, id: &Entity) -> Result<bool, Self::Error> {
+        match self.lookup_pattern_entity(id)? {
+            Some(id) => self
+                .graph
+                .contains_node(&id)
+                .map_err(RdfTripleStoreError::GraphStorageError),
+            None => Ok(false),
+        }
+    }
+
+    fn contains_edge(&self, triple: &Triple<Entity>) -> Result<bool, Self::Error> {
+        match self.lookup_triple(triple)? {
+            Some(triple) => self
+                .graph
+                .contains_edge(&triple)
+                .map_err(RdfTripleStoreError::GraphStorageError),
+            None => Ok(false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::rdf::MemRdfTripleStore;
+
+    #[test]
+    fn test_get_node_mem() {
+        let db = MemRdfTripleStore::new_mem();
+        crate::conformance::get::test_get_node(db);
+    }
+
+    #[test]
+    fn test_get_edge_mem() {
+        let db = MemRdfTripleStore::new_mem();
+        crate::conformance::get::test_get_edge(db);
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_get_node_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let db = crate::rdf::SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::get::test_get_node(db);
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_get_edge_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let db = crate::rdf::SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::get::test_get_edge(db);
+    }
+}
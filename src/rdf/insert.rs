@@ -8,7 +8,7 @@ use ulid::Ulid;
 impl<
         NodeProps: Property,
         EdgeProps: Property,
-        NameIndex: BidirIndex<Left = String, Right = Ulid>,
+        NameIndex: BidirIndex<Left = Entity, Right = Ulid>,
         TripleStorage: TripleStore<Ulid, NodeProps, EdgeProps>,
     > TripleStoreInsert<Entity, NodeProps, EdgeProps>
     for RdfTripleStore<NodeProps, EdgeProps, NameIndex, TripleStorage>
@@ -17,13 +17,46 @@ impl<
         let id = self.lookup_or_create_entity(&entity)?;
         self.graph
             .insert_node(id, props)
-            .map_err(|e| RdfTripleStoreError::GraphStorageError(e))
+            .map_err(RdfTripleStoreError::GraphStorageError)
     }
 
     fn insert_edge(&mut self, triple: Triple<Entity>, props: EdgeProps) -> Result<(), Self::Error> {
         let triple = triple.try_map(|entity| self.lookup_or_create_entity(&entity))?;
         self.graph
             .insert_edge(triple, props)
-            .map_err(|e| RdfTripleStoreError::GraphStorageError(e))
+            .map_err(RdfTripleStoreError::GraphStorageError)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::rdf::MemRdfTripleStore;
+    #[cfg(feature = "sled")]
+    use crate::rdf::SledRdfTripleStore;
+
+    #[test]
+    fn test_insert_node_mem() {
+        crate::conformance::insert::test_insert_node(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_insert_node_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let rdf_db = SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::insert::test_insert_node(rdf_db);
+    }
+
+    #[test]
+    fn test_insert_edge_mem() {
+        crate::conformance::insert::test_insert_edge(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_insert_edge_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let rdf_db = SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::insert::test_insert_edge(rdf_db);
     }
 }
@@ -10,7 +10,7 @@ use ulid::Ulid;
 impl<
         NodeProps: Property,
         EdgeProps: Property,
-        NameIndex: BidirIndex<Left = String, Right = Ulid>,
+        NameIndex: BidirIndex<Left = Entity, Right = Ulid>,
         TripleStorage: TripleStore<Ulid, NodeProps, EdgeProps>,
     > TripleStoreIter<Entity, NodeProps, EdgeProps>
     for RdfTripleStore<NodeProps, EdgeProps, NameIndex, TripleStorage>
@@ -19,7 +19,7 @@ impl<
         Ok(self
             .graph
             .vertices()
-            .map_err(|e| super::RdfTripleStoreError::GraphStorageError(e))?
+            .map_err(super::RdfTripleStoreError::GraphStorageError)?
             .map(|id| Self::lookup_id(&self.name_index, &id))
             .collect::<Result<Vec<_>, _>>()?
             .into_iter())
@@ -90,7 +90,7 @@ impl<
 impl<
         NodeProps: Property,
         EdgeProps: Property,
-        NameIndex: BidirIndex<Left = String, Right = Ulid>,
+        NameIndex: BidirIndex<Left = Entity, Right = Ulid>,
         TripleStorage: TripleStore<Ulid, NodeProps, EdgeProps>,
     > TripleStoreIntoIter<Entity, NodeProps, EdgeProps>
     for RdfTripleStore<NodeProps, EdgeProps, NameIndex, TripleStorage>
@@ -164,3 +164,192 @@ impl<
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::rdf::MemRdfTripleStore;
+    #[cfg(feature = "sled")]
+    use crate::rdf::SledRdfTripleStore;
+
+    #[test]
+    fn test_iter_spo_mem() {
+        crate::conformance::iter::test_iter_spo(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_iter_spo_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let rdf_db = SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::iter::test_iter_spo(rdf_db);
+    }
+
+    #[test]
+    fn test_iter_pos_mem() {
+        crate::conformance::iter::test_iter_pos(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_iter_pos_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let rdf_db = SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::iter::test_iter_pos(rdf_db);
+    }
+
+    #[test]
+    fn test_iter_osp_mem() {
+        crate::conformance::iter::test_iter_osp(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_iter_osp_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let rdf_db = SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::iter::test_iter_osp(rdf_db);
+    }
+
+    #[test]
+    fn test_iter_edge_spo_mem() {
+        crate::conformance::iter::test_iter_edge_spo(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_iter_edge_spo_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let rdf_db = SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::iter::test_iter_edge_spo(rdf_db);
+    }
+
+    #[test]
+    fn test_iter_edge_pos_mem() {
+        crate::conformance::iter::test_iter_edge_pos(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_iter_edge_pos_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let rdf_db = SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::iter::test_iter_edge_pos(rdf_db);
+    }
+
+    #[test]
+    fn test_iter_edge_osp_mem() {
+        crate::conformance::iter::test_iter_edge_osp(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_iter_edge_osp_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let rdf_db = SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::iter::test_iter_edge_osp(rdf_db);
+    }
+
+    #[test]
+    fn test_iter_node_mem() {
+        crate::conformance::iter::test_iter_node(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_iter_node_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let rdf_db = SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::iter::test_iter_node(rdf_db);
+    }
+
+    #[test]
+    fn test_into_iter_spo_mem() {
+        crate::conformance::iter::test_into_iter_spo(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_into_iter_spo_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let rdf_db = SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::iter::test_into_iter_spo(rdf_db);
+    }
+
+    #[test]
+    fn test_into_iter_pos_mem() {
+        crate::conformance::iter::test_into_iter_pos(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_into_iter_pos_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let rdf_db = SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::iter::test_into_iter_pos(rdf_db);
+    }
+
+    #[test]
+    fn test_into_iter_osp_mem() {
+        crate::conformance::iter::test_into_iter_osp(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_into_iter_osp_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let rdf_db = SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::iter::test_into_iter_osp(rdf_db);
+    }
+
+    #[test]
+    fn test_into_iter_edge_spo_mem() {
+        crate::conformance::iter::test_into_iter_edge_spo(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_into_iter_edge_spo_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let rdf_db = SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::iter::test_into_iter_edge_spo(rdf_db);
+    }
+
+    #[test]
+    fn test_into_iter_edge_pos_mem() {
+        crate::conformance::iter::test_into_iter_edge_pos(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_into_iter_edge_pos_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let rdf_db = SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::iter::test_into_iter_edge_pos(rdf_db);
+    }
+
+    #[test]
+    fn test_into_iter_edge_osp_mem() {
+        crate::conformance::iter::test_into_iter_edge_osp(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_into_iter_edge_osp_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let rdf_db = SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::iter::test_into_iter_edge_osp(rdf_db);
+    }
+
+    #[test]
+    fn test_into_iter_node_mem() {
+        crate::conformance::iter::test_into_iter_node(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_into_iter_node_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let rdf_db = SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::iter::test_into_iter_node(rdf_db);
+    }
+}
@@ -8,7 +8,7 @@ use ulid::Ulid;
 impl<
         NodeProps: Property + Mergeable,
         EdgeProps: Property + Mergeable,
-        NameIndex: BidirIndex<Left = String, Right = Ulid>,
+        NameIndex: BidirIndex<Left = Entity, Right = Ulid>,
         TripleStorage: TripleStore<Ulid, NodeProps, EdgeProps> + TripleStoreMerge<Ulid, NodeProps, EdgeProps>,
     > TripleStoreMerge<Entity, NodeProps, EdgeProps>
     for RdfTripleStore<NodeProps, EdgeProps, NameIndex, TripleStorage>
@@ -41,7 +41,7 @@ impl<
 
         self.graph
             .merge_node(id, props)
-            .map_err(|e| RdfTripleStoreError::GraphStorageError(e))
+            .map_err(RdfTripleStoreError::GraphStorageError)
     }
 
     fn merge_edge(
@@ -52,6 +52,61 @@ impl<
         let triple = triple.try_map(|entity| self.lookup_or_create_entity(&entity))?;
         self.graph
             .merge_edge(triple, props)
-            .map_err(|e| RdfTripleStoreError::GraphStorageError(e))
+            .map_err(RdfTripleStoreError::GraphStorageError)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::rdf::MemRdfTripleStore;
+    #[cfg(feature = "sled")]
+    use crate::rdf::SledRdfTripleStore;
+
+    #[test]
+    fn test_merge_mem() {
+        crate::conformance::merge::test_merge(MemRdfTripleStore::new_mem);
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_merge_sled() {
+        let mut tempdirs = Vec::new();
+        crate::conformance::merge::test_merge(|| {
+            let (tempdir, db) = crate::sled::create_test_db().expect("ok");
+            tempdirs.push(tempdir);
+            SledRdfTripleStore::new_sled(&db).expect("ok")
+        });
+    }
+
+    #[test]
+    fn test_merge_node_mem() {
+        crate::conformance::merge::test_merge_node(MemRdfTripleStore::new_mem);
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_merge_node_sled() {
+        let mut tempdirs = Vec::new();
+        crate::conformance::merge::test_merge_node(|| {
+            let (tempdir, db) = crate::sled::create_test_db().expect("ok");
+            tempdirs.push(tempdir);
+            SledRdfTripleStore::new_sled(&db).expect("ok")
+        });
+    }
+
+    #[test]
+    fn test_merge_edge_mem() {
+        crate::conformance::merge::test_merge_edge(MemRdfTripleStore::new_mem);
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_merge_edge_sled() {
+        let mut tempdirs = Vec::new();
+        crate::conformance::merge::test_merge_edge(|| {
+            let (tempdir, db) = crate::sled::create_test_db().expect("ok");
+            tempdirs.push(tempdir);
+            SledRdfTripleStore::new_sled(&db).expect("ok")
+        });
     }
 }
@@ -0,0 +1,269 @@
+//! Reading and writing [N-Quads](https://www.w3.org/TR/n-quads/).
+//!
+//! N-Quads is N-Triples with an optional graph label after the object. Statements without one are loaded into the
+//! default graph and statements with one into that named graph of a [QuadStore]. Terms are mapped to entities as in
+//! [ntriples][super::ntriples].
+//!
+//! # Example
+//! ```
+//! # use simple_triplestore::{prelude::*, rdf::{nquads, Entity, MemRdfTripleStore}};
+//! let mut db = MemRdfTripleStore::<(), ()>::new_mem();
+//!
+//! let input = concat!(
+//!     "<http://example.com/alice> <http://xmlns.com/foaf/0.1/knows> <http://example.com/bob> .\n",
+//!     "<http://example.com/bob> <http://xmlns.com/foaf/0.1/knows> <http://example.com/carol> <http://example.com/doc> .\n",
+//! );
+//! nquads::load(input.as_bytes(), &mut db)?;
+//! assert_eq!(
+//!     db.iter_graphs().collect::<Result<Vec<_>, _>>()?,
+//!     [Entity::from("http://example.com/doc")]
+//! );
+//!
+//! let mut output = Vec::new();
+//! nquads::write(&db, &mut output)?;
+//! assert_eq!(String::from_utf8(output).unwrap(), input);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::io::{BufRead, Write};
+
+use super::{
+    ntriples::{check_triple, write_term, NTriplesParser},
+    Entity,
+};
+use crate::{
+    traits::{Property, QuadStore},
+    EdgeOrder, Quad, QuadOrder, Triple,
+};
+
+pub use super::ntriples::{LoadError, ParseError, WriteError};
+
+/// A streaming N-Quads parser which yields each statement's [Triple] along with its graph label, if it has one.
+///
+/// Input is read a line at a time, so arbitrarily large documents can be parsed without buffering them.
+pub struct NQuadsParser<R: BufRead> {
+    statements: NTriplesParser<R>,
+}
+
+impl<R: BufRead> NQuadsParser<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            statements: NTriplesParser::with_graphs(reader),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for NQuadsParser<R> {
+    type Item = Result<(Triple<Entity>, Option<Entity>), ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.statements.next_statement()
+    }
+}
+
+/// Parse an N-Quads document from `reader`.
+pub fn parse<R: BufRead>(reader: R) -> NQuadsParser<R> {
+    NQuadsParser::new(reader)
+}
+
+/// Load an N-Quads document into `db`, using `EdgeProps::default()` for every edge.
+///
+/// Returns the number of statements inserted.
+pub fn load<
+    R: BufRead,
+    NodeProps: Property,
+    EdgeProps: Property + Default,
+    T: QuadStore<Entity, NodeProps, EdgeProps>,
+>(
+    reader: R,
+    db: &mut T,
+) -> Result<usize, LoadError<T::Error>> {
+    load_with(reader, db, |_, _| EdgeProps::default())
+}
+
+/// Load an N-Quads document into `db`, computing the properties of each edge from its triple and graph label with
+/// `edge_props`.
+///
+/// Returns the number of statements inserted.
+pub fn load_with<
+    R: BufRead,
+    NodeProps: Property,
+    EdgeProps: Property,
+    T: QuadStore<Entity, NodeProps, EdgeProps>,
+>(
+    reader: R,
+    db: &mut T,
+    mut edge_props: impl FnMut(&Triple<Entity>, Option<&Entity>) -> EdgeProps,
+) -> Result<usize, LoadError<T::Error>> {
+    let mut count = 0;
+    for r in parse(reader) {
+        let (triple, graph) = r.map_err(LoadError::Parse)?;
+        let props = edge_props(&triple, graph.as_ref());
+        match graph {
+            Some(graph) => db.insert_quad(Quad::new(triple, graph), props),
+            None => db.insert_edge(triple, props),
+        }
+        .map_err(LoadError::Store)?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Write every edge in `db` to `writer` as N-Quads.
+///
+/// The default graph is written first in [EdgeOrder::SPO] order, followed by the named graphs in [QuadOrder::GSPO]
+/// order.
+pub fn write<
+    W: Write,
+    NodeProps: Property,
+    EdgeProps: Property,
+    T: QuadStore<Entity, NodeProps, EdgeProps>,
+>(
+    db: &T,
+    mut writer: W,
+) -> Result<(), WriteError<T::Error>> {
+    for r in db.iter_edges(EdgeOrder::SPO) {
+        let (triple, _) = r.map_err(WriteError::Store)?;
+        let triple = check_triple(triple)?;
+        write_statement(&mut writer, &triple, None).map_err(WriteError::Io)?;
+    }
+    for r in db.iter_quads(QuadOrder::GSPO) {
+        let (quad, _) = r.map_err(WriteError::Store)?;
+        let triple = check_triple(quad.triple())?;
+        write_statement(&mut writer, &triple, Some(&quad.graph)).map_err(WriteError::Io)?;
+    }
+    writer.flush().map_err(WriteError::Io)
+}
+
+fn write_statement(
+    writer: &mut impl Write,
+    triple: &Triple<Entity>,
+    graph: Option<&Entity>,
+) -> std::io::Result<()> {
+    write_term(writer, &triple.sub)?;
+    writer.write_all(b" ")?;
+    write_term(writer, &triple.pred)?;
+    writer.write_all(b" ")?;
+    write_term(writer, &triple.obj)?;
+    if let Some(graph) = graph {
+        writer.write_all(b" ")?;
+        write_term(writer, graph)?;
+    }
+    writer.write_all(b" .\n")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        prelude::*,
+        rdf::{Entity, MemRdfTripleStore},
+        Quad, QuadOrder, Triple,
+    };
+
+    use super::ParseError;
+    use crate::rdf::ntriples::Statement;
+
+    fn parse_all(input: &str) -> Result<Vec<Statement>, ParseError> {
+        super::parse(input.as_bytes()).collect()
+    }
+
+    #[test]
+    fn test_parse_graphs() {
+        let statements = parse_all(concat!(
+            "<http://s> <http://p> <http://o> .\n",
+            "<http://s> <http://p> \"x\"@en <http://g> . # comment\n",
+            "_:a <http://p> _:b _:g.\n",
+        ))
+        .expect("ok");
+        assert_eq!(statements.len(), 3);
+        assert_eq!(statements[0].1, None);
+        assert_eq!(
+            statements[1],
+            (
+                Triple {
+                    sub: "http://s".into(),
+                    pred: "http://p".into(),
+                    obj: Entity::lang_literal("x", "en"),
+                },
+                Some("http://g".into())
+            )
+        );
+        assert!(matches!(statements[2].1, Some(Entity::BlankNode(_))));
+        assert_ne!(statements[2].1, Some(statements[2].0.obj.clone()));
+    }
+
+    #[test]
+    fn test_parse_error_position() {
+        match parse_all("<http://s> <http://p> <http://o> \"g\" .\n") {
+            Err(ParseError::Syntax { line, column, .. }) => {
+                assert_eq!((line, column), (1, 34));
+            }
+            r => panic!("expected a syntax error, got {:?}", r),
+        }
+
+        // N-Triples does not accept a graph label.
+        assert!(crate::rdf::ntriples::parse(
+            "<http://s> <http://p> <http://o> <http://g> .\n".as_bytes()
+        )
+        .any(|r| r.is_err()));
+    }
+
+    #[test]
+    fn test_load_and_write() {
+        let input = concat!(
+            "<http://example.com/a> <http://example.com/knows> <http://example.com/b> .\n",
+            "<http://example.com/a> <http://example.com/knows> <http://example.com/b> <http://example.com/g1> .\n",
+            "<http://example.com/b> <http://example.com/name> \"B\" <http://example.com/g1> .\n",
+            "<http://example.com/a> <http://example.com/name> \"A\" <http://example.com/g2> .\n",
+        );
+
+        let mut db = MemRdfTripleStore::<(), String>::new_mem();
+        assert_eq!(
+            super::load_with(input.as_bytes(), &mut db, |_, graph| graph
+                .map(Entity::to_string)
+                .unwrap_or_default())
+            .expect("ok"),
+            4
+        );
+        assert_eq!(db.iter_edges(Default::default()).count(), 1);
+        assert_eq!(
+            db.get_quad(&Quad::new(
+                Triple {
+                    sub: "http://example.com/b".into(),
+                    pred: "http://example.com/name".into(),
+                    obj: Entity::literal("B"),
+                },
+                "http://example.com/g1".into()
+            ))
+            .expect("ok"),
+            Some("http://example.com/g1".to_string())
+        );
+
+        // Writing and re-reading produces the same statements, though ids may put them in another order.
+        let lines = |document: &str| {
+            let mut lines = document.lines().map(str::to_string).collect::<Vec<_>>();
+            lines.sort();
+            lines
+        };
+        let mut output = Vec::new();
+        super::write(&db, &mut output).expect("ok");
+        let output = String::from_utf8(output).expect("utf8");
+        let mut reloaded = MemRdfTripleStore::<(), ()>::new_mem();
+        assert_eq!(
+            super::load(output.as_bytes(), &mut reloaded).expect("ok"),
+            4
+        );
+        assert_eq!(reloaded.iter_quads(QuadOrder::GSPO).count(), 3);
+
+        let mut rewritten = Vec::new();
+        super::write(&reloaded, &mut rewritten).expect("ok");
+        assert_eq!(
+            lines(&String::from_utf8(rewritten).expect("utf8")),
+            lines(&output)
+        );
+        assert_eq!(lines(&output), lines(input));
+        assert!(output.starts_with(
+            "<http://example.com/a> <http://example.com/knows> <http://example.com/b> .\n"
+        ));
+    }
+}
@@ -0,0 +1,773 @@
+//! Reading and writing [N-Triples](https://www.w3.org/TR/n-triples/).
+//!
+//! IRIs are mapped to [Entity::String], literals to [Entity::Literal] and blank nodes to [Entity::BlankNode] with a fresh
+//! [Ulid] per document, so the same label within one document always refers to the same node.
+//!
+//! # Example
+//! ```
+//! # use simple_triplestore::{prelude::*, rdf::{ntriples, MemRdfTripleStore}, EdgeOrder};
+//! let mut db = MemRdfTripleStore::<(), ()>::new_mem();
+//!
+//! let input = "<http://example.com/alice> <http://xmlns.com/foaf/0.1/knows> <http://example.com/bob> .\n";
+//! ntriples::load(input.as_bytes(), &mut db)?;
+//!
+//! let mut output = Vec::new();
+//! ntriples::write(&db, &mut output)?;
+//! assert_eq!(String::from_utf8(output).unwrap(), input);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::{
+    collections::HashMap,
+    io::{BufRead, Write},
+};
+
+use ulid::Ulid;
+
+use super::Entity;
+use crate::{
+    traits::{Property, TripleStoreInsert, TripleStoreIter},
+    EdgeOrder, Triple,
+};
+
+/// Errors which can occur while reading an RDF document.
+#[derive(Debug)]
+pub enum ParseError {
+    /// The underlying reader failed.
+    Io(std::io::Error),
+
+    /// The document is malformed. `line` and `column` are 1-based and count characters.
+    Syntax {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::Io(e) => f.write_fmt(format_args!("io error: {}", e)),
+            ParseError::Syntax {
+                line,
+                column,
+                message,
+            } => f.write_fmt(format_args!(
+                "syntax error at line {}, column {}: {}",
+                line, column, message
+            )),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<std::io::Error> for ParseError {
+    fn from(e: std::io::Error) -> Self {
+        ParseError::Io(e)
+    }
+}
+
+/// A parsed triple along with its graph label, if it has one.
+pub(crate) type Statement = (Triple<Entity>, Option<Entity>);
+
+/// Errors which can occur while loading an RDF document into a triplestore.
+#[derive(Debug)]
+pub enum LoadError<StoreError> {
+    /// The document could not be read.
+    Parse(ParseError),
+
+    /// The triplestore returned an error.
+    Store(StoreError),
+}
+
+impl<StoreError: std::fmt::Debug> std::fmt::Display for LoadError<StoreError> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::Parse(e) => e.fmt(f),
+            LoadError::Store(e) => f.write_fmt(format_args!("store error: {:?}", e)),
+        }
+    }
+}
+
+impl<StoreError: std::fmt::Debug> std::error::Error for LoadError<StoreError> {}
+
+/// Errors which can occur while writing a triplestore as an RDF document.
+#[derive(Debug)]
+pub enum WriteError<StoreError> {
+    /// The underlying writer failed.
+    Io(std::io::Error),
+
+    /// The triplestore returned an error.
+    Store(StoreError),
+
+    /// An edge cannot be written as an RDF statement, because its subject is a literal or its predicate is not an IRI.
+    InvalidTriple(Box<Triple<Entity>>),
+}
+
+impl<StoreError: std::fmt::Debug> std::fmt::Display for WriteError<StoreError> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WriteError::Io(e) => f.write_fmt(format_args!("io error: {}", e)),
+            WriteError::Store(e) => f.write_fmt(format_args!("store error: {:?}", e)),
+            WriteError::InvalidTriple(triple) => f.write_fmt(format_args!(
+                "invalid triple: {} {} {}",
+                format_term(&triple.sub),
+                format_term(&triple.pred),
+                format_term(&triple.obj)
+            )),
+        }
+    }
+}
+
+impl<StoreError: std::fmt::Debug> std::error::Error for WriteError<StoreError> {}
+
+/// A streaming N-Triples parser which yields one [Triple] per statement.
+///
+/// Input is read a line at a time, so arbitrarily large documents can be parsed without buffering them.
+pub struct NTriplesParser<R: BufRead> {
+    lines: std::io::Lines<R>,
+    line: usize,
+    blank_nodes: HashMap<String, Ulid>,
+    graphs: bool,
+}
+
+impl<R: BufRead> NTriplesParser<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            lines: reader.lines(),
+            line: 0,
+            blank_nodes: HashMap::new(),
+            graphs: false,
+        }
+    }
+
+    /// A parser which also accepts an optional graph label after the object, as in N-Quads.
+    pub(crate) fn with_graphs(reader: R) -> Self {
+        Self {
+            graphs: true,
+            ..Self::new(reader)
+        }
+    }
+
+    /// Parse the next statement, along with its graph label if it has one.
+    pub(crate) fn next_statement(&mut self) -> Option<Result<Statement, ParseError>> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(ParseError::Io(e))),
+            };
+            self.line += 1;
+
+            match self.parse_line(&line) {
+                Ok(Some(statement)) => return Some(Ok(statement)),
+                Ok(None) => continue,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+
+    fn parse_line(&mut self, line: &str) -> Result<Option<Statement>, ParseError> {
+        let chars = line.chars().collect::<Vec<_>>();
+        let mut cursor = Cursor {
+            chars: &chars,
+            pos: 0,
+            line: self.line,
+        };
+
+        cursor.skip_ws();
+        if cursor.at_end_of_statement() {
+            return Ok(None);
+        }
+
+        let sub = match cursor.peek() {
+            Some('<') => Entity::String(cursor.iri()?),
+            Some('_') => self.blank_node(cursor.blank_node_label()?),
+            _ => return Err(cursor.error("expected an IRI or blank node as the subject")),
+        };
+
+        cursor.skip_ws();
+        let pred = match cursor.peek() {
+            Some('<') => Entity::String(cursor.iri()?),
+            _ => return Err(cursor.error("expected an IRI as the predicate")),
+        };
+
+        cursor.skip_ws();
+        let obj = match cursor.peek() {
+            Some('<') => Entity::String(cursor.iri()?),
+            Some('_') => self.blank_node(cursor.blank_node_label()?),
+            Some('"') => {
+                let (lexical, datatype, lang) = cursor.literal()?;
+                literal_entity(lexical, datatype, lang)
+            }
+            _ => return Err(cursor.error("expected an IRI, blank node or literal as the object")),
+        };
+
+        cursor.skip_ws();
+        let graph = match cursor.peek() {
+            Some('<') if self.graphs => Some(Entity::String(cursor.iri()?)),
+            Some('_') if self.graphs => Some(self.blank_node(cursor.blank_node_label()?)),
+            _ => None,
+        };
+
+        cursor.skip_ws();
+        cursor.expect('.')?;
+        cursor.skip_ws();
+        if !cursor.at_end_of_statement() {
+            return Err(cursor.error("unexpected characters after '.'"));
+        }
+
+        Ok(Some((Triple { sub, pred, obj }, graph)))
+    }
+
+    fn blank_node(&mut self, label: String) -> Entity {
+        if let Some(id) = self.blank_nodes.get(&label) {
+            return Entity::BlankNode(*id);
+        }
+        let id = Ulid::new();
+        self.blank_nodes.insert(label, id);
+        Entity::BlankNode(id)
+    }
+}
+
+impl<R: BufRead> Iterator for NTriplesParser<R> {
+    type Item = Result<Triple<Entity>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.next_statement()?.map(|(triple, _)| triple))
+    }
+}
+
+/// Parse an N-Triples document from `reader`.
+pub fn parse<R: BufRead>(reader: R) -> NTriplesParser<R> {
+    NTriplesParser::new(reader)
+}
+
+/// Load an N-Triples document into `db`, using `EdgeProps::default()` for every edge.
+///
+/// Returns the number of triples inserted.
+pub fn load<
+    R: BufRead,
+    NodeProps: Property,
+    EdgeProps: Property + Default,
+    T: TripleStoreInsert<Entity, NodeProps, EdgeProps>,
+>(
+    reader: R,
+    db: &mut T,
+) -> Result<usize, LoadError<T::Error>> {
+    load_with(reader, db, |_| EdgeProps::default())
+}
+
+/// Load an N-Triples document into `db`, computing the properties of each edge with `edge_props`.
+///
+/// Returns the number of triples inserted.
+pub fn load_with<
+    R: BufRead,
+    NodeProps: Property,
+    EdgeProps: Property,
+    T: TripleStoreInsert<Entity, NodeProps, EdgeProps>,
+>(
+    reader: R,
+    db: &mut T,
+    mut edge_props: impl FnMut(&Triple<Entity>) -> EdgeProps,
+) -> Result<usize, LoadError<T::Error>> {
+    let mut count = 0;
+    for r in parse(reader) {
+        let triple = r.map_err(LoadError::Parse)?;
+        let props = edge_props(&triple);
+        db.insert_edge(triple, props).map_err(LoadError::Store)?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Write every edge in `db` to `writer` as N-Triples, in [EdgeOrder::SPO] order.
+///
+/// Fails with [WriteError::InvalidTriple] on an edge with a literal subject or a predicate which is not an IRI, since
+/// no N-Triples statement can express it.
+pub fn write<
+    W: Write,
+    NodeProps: Property,
+    EdgeProps: Property,
+    T: TripleStoreIter<Entity, NodeProps, EdgeProps>,
+>(
+    db: &T,
+    mut writer: W,
+) -> Result<(), WriteError<T::Error>> {
+    for r in db.iter_edges(EdgeOrder::SPO) {
+        let (triple, _) = r.map_err(WriteError::Store)?;
+        let triple = check_triple(triple)?;
+        write_term(&mut writer, &triple.sub).map_err(WriteError::Io)?;
+        writer.write_all(b" ").map_err(WriteError::Io)?;
+        write_term(&mut writer, &triple.pred).map_err(WriteError::Io)?;
+        writer.write_all(b" ").map_err(WriteError::Io)?;
+        write_term(&mut writer, &triple.obj).map_err(WriteError::Io)?;
+        writer.write_all(b" .\n").map_err(WriteError::Io)?;
+    }
+    writer.flush().map_err(WriteError::Io)
+}
+
+/// Check that the terms of `triple` are allowed in their positions: the subject may not be a literal and the predicate
+/// must be an IRI.
+pub(crate) fn check_triple<StoreError>(
+    triple: Triple<Entity>,
+) -> Result<Triple<Entity>, WriteError<StoreError>> {
+    match (&triple.sub, &triple.pred) {
+        (Entity::Literal(_), _) => Err(WriteError::InvalidTriple(Box::new(triple))),
+        (_, Entity::String(_)) => Ok(triple),
+        _ => Err(WriteError::InvalidTriple(Box::new(triple))),
+    }
+}
+
+/// Build the [Entity] for a parsed literal.
+pub(crate) fn literal_entity(
+    lexical: String,
+    datatype: Option<String>,
+    lang: Option<String>,
+) -> Entity {
+    match (datatype, lang) {
+        (_, Some(lang)) => Entity::lang_literal(lexical, lang),
+        (Some(datatype), None) => Entity::typed_literal(lexical, datatype),
+        (None, None) => Entity::literal(lexical),
+    }
+}
+
+/// Write a single term in N-Triples syntax.
+pub(crate) fn write_term(writer: &mut impl Write, entity: &Entity) -> std::io::Result<()> {
+    writer.write_all(format_term(entity).as_bytes())
+}
+
+/// Format a single term in N-Triples syntax.
+pub(crate) fn format_term(entity: &Entity) -> String {
+    match entity {
+        Entity::String(s) => format_iri(s),
+        Entity::Literal(literal) => {
+            let mut term = String::with_capacity(literal.lexical().len() + 2);
+            term.push('"');
+            escape_string(&mut term, literal.lexical());
+            term.push('"');
+            if let Some(lang) = literal.language() {
+                term.push('@');
+                term.push_str(lang);
+            } else if let Some(datatype) = literal.datatype() {
+                term.push_str("^^");
+                term.push_str(&format_iri(datatype));
+            }
+            term
+        }
+        Entity::Ulid(id) | Entity::BlankNode(id) => format!("_:{}", id),
+    }
+}
+
+/// Format an IRI as `<iri>`, escaping any characters which are not allowed.
+pub(crate) fn format_iri(iri: &str) -> String {
+    let mut term = String::with_capacity(iri.len() + 2);
+    term.push('<');
+    escape_iri(&mut term, iri);
+    term.push('>');
+    term
+}
+
+pub(crate) fn escape_iri(out: &mut String, iri: &str) {
+    for c in iri.chars() {
+        if is_iri_char(c) {
+            out.push(c);
+        } else {
+            out.push_str(&format!("\\u{:04X}", c as u32));
+        }
+    }
+}
+
+pub(crate) fn escape_string(out: &mut String, s: &str) {
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            c => out.push(c),
+        }
+    }
+}
+
+pub(crate) fn is_iri_char(c: char) -> bool {
+    !matches!(
+        c,
+        '\u{00}'..='\u{20}' | '<' | '>' | '"' | '{' | '}' | '|' | '^' | '`' | '\\'
+    )
+}
+
+pub(crate) fn is_pn_chars_u(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == ':'
+}
+
+pub(crate) fn is_pn_chars(c: char) -> bool {
+    is_pn_chars_u(c) || c.is_ascii_digit() || c == '-' || c == '\u{B7}' || c.is_alphanumeric()
+}
+
+struct Cursor<'a> {
+    chars: &'a [char],
+    pos: usize,
+    line: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::Syntax {
+            line: self.line,
+            column: self.pos + 1,
+            message: message.into(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek();
+        if c.is_some() {
+            self.pos += 1;
+        }
+        c
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ParseError> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.pos += 1;
+                Ok(())
+            }
+            Some(c) => Err(self.error(format!("expected '{}' but found '{}'", expected, c))),
+            None => Err(self.error(format!("expected '{}' but found end of line", expected))),
+        }
+    }
+
+    fn skip_ws(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t')) {
+            self.pos += 1;
+        }
+    }
+
+    fn at_end_of_statement(&self) -> bool {
+        matches!(self.peek(), None | Some('#'))
+    }
+
+    fn iri(&mut self) -> Result<String, ParseError> {
+        self.expect('<')?;
+        let mut iri = String::new();
+        loop {
+            match self.peek() {
+                Some('>') => {
+                    self.pos += 1;
+                    return Ok(iri);
+                }
+                Some('\\') => {
+                    self.pos += 1;
+                    iri.push(self.uchar()?);
+                }
+                Some(c) if is_iri_char(c) => {
+                    self.pos += 1;
+                    iri.push(c);
+                }
+                Some(c) => return Err(self.error(format!("invalid character {:?} in IRI", c))),
+                None => return Err(self.error("unterminated IRI")),
+            }
+        }
+    }
+
+    fn uchar(&mut self) -> Result<char, ParseError> {
+        let digits = match self.bump() {
+            Some('u') => 4,
+            Some('U') => 8,
+            _ => return Err(self.error("expected \\u or \\U escape")),
+        };
+        let start = self.pos;
+        let mut value = 0u32;
+        for _ in 0..digits {
+            let digit = self
+                .peek()
+                .and_then(|c| c.to_digit(16))
+                .ok_or_else(|| self.error("expected a hexadecimal digit"))?;
+            value = value * 16 + digit;
+            self.pos += 1;
+        }
+        char::from_u32(value).ok_or_else(|| {
+            self.pos = start;
+            self.error(format!("invalid code point U+{:X}", value))
+        })
+    }
+
+    fn blank_node_label(&mut self) -> Result<String, ParseError> {
+        self.expect('_')?;
+        self.expect(':')?;
+        let mut label = String::new();
+        match self.peek() {
+            Some(c) if is_pn_chars_u(c) || c.is_ascii_digit() => {
+                self.pos += 1;
+                label.push(c);
+            }
+            _ => return Err(self.error("expected a blank node label")),
+        }
+        while let Some(c) = self.peek() {
+            if is_pn_chars(c) || c == '.' {
+                self.pos += 1;
+                label.push(c);
+            } else {
+                break;
+            }
+        }
+        // A label may not end with '.', so any trailing dots belong to the statement terminator.
+        while label.ends_with('.') {
+            label.pop();
+            self.pos -= 1;
+        }
+        Ok(label)
+    }
+
+    fn literal(&mut self) -> Result<(String, Option<String>, Option<String>), ParseError> {
+        self.expect('"')?;
+        let mut lexical = String::new();
+        loop {
+            match self.bump() {
+                Some('"') => break,
+                Some('\\') => lexical.push(self.echar()?),
+                Some(c) => lexical.push(c),
+                None => return Err(self.error("unterminated string literal")),
+            }
+        }
+
+        match self.peek() {
+            Some('@') => {
+                self.pos += 1;
+                Ok((lexical, None, Some(self.lang_tag()?)))
+            }
+            Some('^') => {
+                self.pos += 1;
+                self.expect('^')?;
+                Ok((lexical, Some(self.iri()?), None))
+            }
+            _ => Ok((lexical, None, None)),
+        }
+    }
+
+    fn echar(&mut self) -> Result<char, ParseError> {
+        match self.peek() {
+            Some('u' | 'U') => self.uchar(),
+            Some(c) => {
+                let escaped = match c {
+                    't' => '\t',
+                    'b' => '\u{08}',
+                    'n' => '\n',
+                    'r' => '\r',
+                    'f' => '\u{0C}',
+                    '"' => '"',
+                    '\'' => '\'',
+                    '\\' => '\\',
+                    _ => return Err(self.error(format!("invalid escape sequence '\\{}'", c))),
+                };
+                self.pos += 1;
+                Ok(escaped)
+            }
+            None => Err(self.error("unterminated escape sequence")),
+        }
+    }
+
+    fn lang_tag(&mut self) -> Result<String, ParseError> {
+        let mut tag = String::new();
+        while let Some(c) = self.peek().filter(|c| c.is_ascii_alphabetic()) {
+            self.pos += 1;
+            tag.push(c);
+        }
+        if tag.is_empty() {
+            return Err(self.error("expected a language tag"));
+        }
+        while self.peek() == Some('-') {
+            self.pos += 1;
+            tag.push('-');
+            let start = tag.len();
+            while let Some(c) = self.peek().filter(|c| c.is_ascii_alphanumeric()) {
+                self.pos += 1;
+                tag.push(c);
+            }
+            if tag.len() == start {
+                return Err(self.error("expected a language subtag"));
+            }
+        }
+        Ok(tag)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use crate::{
+        prelude::*,
+        rdf::{vocab::xsd, Entity, MemRdfTripleStore},
+        EdgeOrder, Triple,
+    };
+
+    use super::ParseError;
+
+    fn parse_all(input: &str) -> Result<Vec<Triple<Entity>>, ParseError> {
+        super::parse(input.as_bytes()).collect()
+    }
+
+    #[test]
+    fn test_parse_iris() {
+        let triples = parse_all(
+            "# A comment\n\n<http://a> <http://b> <http://c> . # trailing\n\t<http://a>\t<http://b>\t<http://d>.\n",
+        )
+        .expect("ok");
+        assert_eq!(
+            triples,
+            [
+                Triple {
+                    sub: "http://a".into(),
+                    pred: "http://b".into(),
+                    obj: "http://c".into(),
+                },
+                Triple {
+                    sub: "http://a".into(),
+                    pred: "http://b".into(),
+                    obj: "http://d".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_blank_nodes() {
+        let triples = parse_all("_:x <http://p> _:y .\n_:y <http://p> _:x.\n").expect("ok");
+        assert_eq!(triples.len(), 2);
+        assert!(matches!(triples[0].sub, Entity::BlankNode(_)));
+        assert_eq!(triples[0].sub, triples[1].obj);
+        assert_eq!(triples[0].obj, triples[1].sub);
+        assert_ne!(triples[0].sub, triples[0].obj);
+
+        // Labels are scoped to a single document.
+        let other = parse_all("_:x <http://p> _:y .\n").expect("ok");
+        assert_ne!(triples[0].sub, other[0].sub);
+    }
+
+    #[test]
+    fn test_parse_literals() {
+        let triples = parse_all(concat!(
+            "<http://s> <http://p> \"plain \\\"quoted\\\"\\n\" .\n",
+            "<http://s> <http://p> \"chat\"@fr .\n",
+            "<http://s> <http://p> \"42\"^^<http://www.w3.org/2001/XMLSchema#integer> .\n",
+            "<http://s> <http://p> \"x\"^^<http://www.w3.org/2001/XMLSchema#string> .\n",
+            "<http://s> <http://p> \"\\u00E9\" .\n",
+        ))
+        .expect("ok");
+        assert_eq!(
+            triples.into_iter().map(|t| t.obj).collect::<Vec<_>>(),
+            [
+                Entity::literal("plain \"quoted\"\n"),
+                Entity::lang_literal("chat", "fr"),
+                Entity::typed_literal("42", xsd::INTEGER),
+                Entity::literal("x"),
+                Entity::literal("\u{E9}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_error_position() {
+        match parse_all("<http://s> <http://p> <http://o> .\n<http://s> _:p <http://o> .\n") {
+            Err(ParseError::Syntax { line, column, .. }) => {
+                assert_eq!((line, column), (2, 12));
+            }
+            r => panic!("expected a syntax error, got {:?}", r),
+        }
+
+        match parse_all("<http://s> <http://p> \"open .\n") {
+            Err(ParseError::Syntax { line, column, .. }) => {
+                assert_eq!((line, column), (1, 30));
+            }
+            r => panic!("expected a syntax error, got {:?}", r),
+        }
+
+        match parse_all("<http://s> <http://p> <http://o> . <http://x>\n") {
+            Err(ParseError::Syntax { line, column, .. }) => {
+                assert_eq!((line, column), (1, 36));
+            }
+            r => panic!("expected a syntax error, got {:?}", r),
+        }
+    }
+
+    #[test]
+    fn test_load_and_write() {
+        let input = concat!(
+            "<http://example.com/a> <http://example.com/knows> <http://example.com/b> .\n",
+            "<http://example.com/a> <http://example.com/name> \"Alice \\\"A\\\"\"@en .\n",
+            "_:n0 <http://example.com/knows> <http://example.com/a> .\n",
+        );
+
+        let mut db = MemRdfTripleStore::<(), ()>::new_mem();
+        assert_eq!(super::load(input.as_bytes(), &mut db).expect("ok"), 3);
+
+        let mut output = Vec::new();
+        super::write(&db, &mut output).expect("ok");
+        let output = String::from_utf8(output).expect("utf8");
+
+        // Writing and re-reading produces the same graph, up to blank node ids.
+        let mut reloaded = MemRdfTripleStore::<(), ()>::new_mem();
+        assert_eq!(
+            super::load(output.as_bytes(), &mut reloaded).expect("ok"),
+            3
+        );
+
+        let named_edges = |db: &MemRdfTripleStore<(), ()>| {
+            db.iter_edges(EdgeOrder::SPO)
+                .map(|r| r.expect("ok").0)
+                .filter(|t| matches!(t.sub, Entity::String(_)))
+                .collect::<HashSet<_>>()
+        };
+        assert_eq!(named_edges(&db), named_edges(&reloaded));
+        assert_eq!(named_edges(&db).len(), 2);
+        assert!(output.contains("_:"));
+    }
+
+    #[test]
+    fn test_write_invalid_triple() {
+        let mut db = MemRdfTripleStore::<(), ()>::new_mem();
+        let literal_subject = Triple {
+            sub: Entity::literal("a"),
+            pred: "http://b".into(),
+            obj: "http://c".into(),
+        };
+        db.insert_edge(literal_subject.clone(), ()).expect("ok");
+        assert!(matches!(
+            super::write(&db, Vec::new()),
+            Err(super::WriteError::InvalidTriple(triple)) if *triple == literal_subject
+        ));
+
+        let mut db = MemRdfTripleStore::<(), ()>::new_mem();
+        let literal_predicate = Triple {
+            sub: "http://a".into(),
+            pred: Entity::literal("b"),
+            obj: "http://c".into(),
+        };
+        db.insert_edge(literal_predicate.clone(), ()).expect("ok");
+        assert!(matches!(
+            super::write(&db, Vec::new()),
+            Err(super::WriteError::InvalidTriple(triple)) if *triple == literal_predicate
+        ));
+    }
+
+    #[test]
+    fn test_load_with_props() {
+        let input = "<http://a> <http://b> <http://c> .\n<http://a> <http://b> <http://d> .\n";
+        let mut db = MemRdfTripleStore::<(), String>::new_mem();
+        super::load_with(input.as_bytes(), &mut db, |triple| triple.obj.to_string()).expect("ok");
+
+        let mut props = db
+            .iter_edges(EdgeOrder::SPO)
+            .map(|r| r.expect("ok").1)
+            .collect::<Vec<_>>();
+        props.sort();
+        assert_eq!(props, ["http://c", "http://d"]);
+    }
+}
@@ -0,0 +1,196 @@
+use super::{Entity, RdfTripleStore, RdfTripleStoreError};
+use crate::{
+    pattern::{Bindings, Pattern, Term, TriplePattern},
+    traits::{BidirIndex, Property, TripleStore, TripleStorePattern},
+    Triple,
+};
+use ulid::Ulid;
+
+impl<
+        NodeProps: Property,
+        EdgeProps: Property,
+        NameIndex: BidirIndex<Left = Entity, Right = Ulid>,
+        TripleStorage: TripleStore<Ulid, NodeProps, EdgeProps>,
+    > RdfTripleStore<NodeProps, EdgeProps, NameIndex, TripleStorage>
+{
+    /// Look up the id for an entity in a pattern, returning `None` if the entity has never been inserted and so
+    /// cannot match anything.
+    pub(super) fn lookup_pattern_entity(
+        &self,
+        entity: &Entity,
+    ) -> Result<Option<Ulid>, RdfTripleStoreError<NameIndex::Error, TripleStorage::Error>> {
+        match self.lookup_entity(entity) {
+            Ok(id) => Ok(Some(id)),
+            Err(RdfTripleStoreError::NameNotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl<
+        NodeProps: Property,
+        EdgeProps: Property,
+        NameIndex: BidirIndex<Left = Entity, Right = Ulid>,
+        TripleStorage: TripleStore<Ulid, NodeProps, EdgeProps>,
+    > TripleStorePattern<Entity, NodeProps, EdgeProps>
+    for RdfTripleStore<NodeProps, EdgeProps, NameIndex, TripleStorage>
+{
+    fn match_triples<'a>(
+        &'a self,
+        sub: Option<Entity>,
+        pred: Option<Entity>,
+        obj: Option<Entity>,
+    ) -> impl Iterator<Item = Result<Triple<Entity>, Self::Error>> + 'a {
+        let lookup = |entity: Option<Entity>| match entity {
+            Some(entity) => self.lookup_pattern_entity(&entity).map(|id| id.map(Some)),
+            None => Ok(Some(None)),
+        };
+
+        let ids = (|| Ok((lookup(sub)?, lookup(pred)?, lookup(obj)?)))();
+
+        let triples: Box<dyn Iterator<Item = Result<Triple<Entity>, Self::Error>>> = match ids {
+            Ok((Some(sub), Some(pred), Some(obj))) => {
+                Box::new(self.graph.match_triples(sub, pred, obj).map(|r| match r {
+                    Ok(triple) => triple.try_map(|id| Self::lookup_id(&self.name_index, &id)),
+                    Err(e) => Err(RdfTripleStoreError::GraphStorageError(e)),
+                }))
+            }
+            // One of the entities is unknown, so nothing can match.
+            Ok(_) => Box::new(std::iter::empty()),
+            Err(e) => Box::new(std::iter::once(Err(e))),
+        };
+        triples
+    }
+
+    fn run_pattern(&self, pattern: &Pattern<Entity>) -> Result<Vec<Bindings<Entity>>, Self::Error> {
+        // Translate the pattern so the join runs entirely against the underlying graph.
+        let mut id_pattern = Pattern::new();
+        for triple in pattern.triples.iter() {
+            let mut terms = Vec::with_capacity(3);
+            for term in [&triple.sub, &triple.pred, &triple.obj] {
+                terms.push(match term {
+                    Term::Var(name) => Term::Var(name.clone()),
+                    Term::Const(entity) => match self.lookup_pattern_entity(entity)? {
+                        Some(id) => Term::Const(id),
+                        None => return Ok(Vec::new()),
+                    },
+                });
+            }
+            let [sub, pred, obj]: [Term<Ulid>; 3] = terms.try_into().expect("three terms");
+            id_pattern.triples.push(TriplePattern { sub, pred, obj });
+        }
+
+        self.graph
+            .run_pattern(&id_pattern)
+            .map_err(RdfTripleStoreError::GraphStorageError)?
+            .into_iter()
+            .map(|bindings| {
+                bindings
+                    .into_iter()
+                    .map(|(name, id)| Ok((name, Self::lookup_id(&self.name_index, &id)?)))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        prelude::*,
+        rdf::{Entity, MemRdfTripleStore},
+        Triple,
+    };
+
+    #[test]
+    fn test_match_triples_mem() {
+        let db = MemRdfTripleStore::new_mem();
+        crate::conformance::pattern::test_match_triples(db);
+    }
+
+    #[test]
+    fn test_run_pattern_mem() {
+        let db = MemRdfTripleStore::new_mem();
+        crate::conformance::pattern::test_run_pattern(db);
+    }
+
+    #[test]
+    fn test_run_path_mem() {
+        let db = MemRdfTripleStore::new_mem();
+        crate::conformance::path::test_run_path(db);
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_match_triples_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let db = crate::rdf::SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::pattern::test_match_triples(db);
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_run_pattern_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let db = crate::rdf::SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::pattern::test_run_pattern(db);
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_run_path_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let db = crate::rdf::SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::path::test_run_path(db);
+    }
+
+    #[test]
+    fn test_named_entities() {
+        let mut db = MemRdfTripleStore::<(), ()>::new_mem();
+        db.insert_edge(
+            Triple {
+                sub: "alice".into(),
+                pred: "knows".into(),
+                obj: "bob".into(),
+            },
+            (),
+        )
+        .expect("ok");
+        db.insert_edge(
+            Triple {
+                sub: "bob".into(),
+                pred: "name".into(),
+                obj: Entity::literal("Bob"),
+            },
+            (),
+        )
+        .expect("ok");
+
+        let solutions = db
+            .run_pattern(&query! { pattern {
+                [Entity::from("alice")] -[Entity::from("knows")]-> ?friend .
+                ?friend -[Entity::from("name")]-> ?name
+            } })
+            .expect("ok");
+        assert_eq!(
+            solutions,
+            [[
+                ("friend".to_string(), Entity::from("bob")),
+                ("name".to_string(), Entity::literal("Bob")),
+            ]
+            .into()]
+        );
+
+        // Entities which were never inserted match nothing rather than failing.
+        assert!(db
+            .run_pattern(&query! { pattern { ?x -[Entity::from("unknown")]-> ?y } })
+            .expect("ok")
+            .is_empty());
+        assert_eq!(
+            db.match_triples(Some("carol".into()), None, None)
+                .collect::<Result<Vec<_>, _>>()
+                .expect("ok"),
+            []
+        );
+    }
+}
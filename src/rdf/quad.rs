@@ -0,0 +1,213 @@
+use super::{Entity, RdfTripleStore, RdfTripleStoreError};
+use crate::{
+    traits::{BidirIndex, BoxedIter, Property, QuadStore, TripleStoreError},
+    Quad, QuadOrder, Query,
+};
+use ulid::Ulid;
+
+impl<
+        NodeProps: Property,
+        EdgeProps: Property,
+        NameIndex: BidirIndex<Left = Entity, Right = Ulid>,
+        TripleStorage: QuadStore<Ulid, NodeProps, EdgeProps>,
+    > RdfTripleStore<NodeProps, EdgeProps, NameIndex, TripleStorage>
+{
+    /// Translate a quad to ids, returning `None` if any of its entities has never been inserted.
+    fn lookup_quad(
+        &self,
+        quad: &Quad<Entity>,
+    ) -> Result<Option<Quad<Ulid>>, <Self as TripleStoreError>::Error> {
+        match (
+            self.lookup_pattern_entity(&quad.sub)?,
+            self.lookup_pattern_entity(&quad.pred)?,
+            self.lookup_pattern_entity(&quad.obj)?,
+            self.lookup_pattern_entity(&quad.graph)?,
+        ) {
+            (Some(sub), Some(pred), Some(obj), Some(graph)) => Ok(Some(Quad {
+                sub,
+                pred,
+                obj,
+                graph,
+            })),
+            _ => Ok(None),
+        }
+    }
+}
+
+impl<
+        NodeProps: Property,
+        EdgeProps: Property,
+        NameIndex: BidirIndex<Left = Entity, Right = Ulid>,
+        TripleStorage: QuadStore<Ulid, NodeProps, EdgeProps>,
+    > QuadStore<Entity, NodeProps, EdgeProps>
+    for RdfTripleStore<NodeProps, EdgeProps, NameIndex, TripleStorage>
+{
+    fn insert_quad(&mut self, quad: Quad<Entity>, props: EdgeProps) -> Result<(), Self::Error> {
+        let quad = quad.try_map(|entity| self.lookup_or_create_entity(&entity))?;
+        self.graph
+            .insert_quad(quad, props)
+            .map_err(RdfTripleStoreError::GraphStorageError)
+    }
+
+    fn get_quad(&self, quad: &Quad<Entity>) -> Result<Option<EdgeProps>, Self::Error> {
+        match self.lookup_quad(quad)? {
+            Some(quad) => self
+                .graph
+                .get_quad(&quad)
+                .map_err(RdfTripleStoreError::GraphStorageError),
+            None => Ok(None),
+        }
+    }
+
+    fn remove_quad(&mut self, quad: Quad<Entity>) -> Result<(), Self::Error> {
+        self.graph
+            .remove_quad(quad.try_map(|entity| self.lookup_entity(&entity))?)
+            .map_err(RdfTripleStoreError::GraphStorageError)
+    }
+
+    fn iter_graphs<'a>(&'a self) -> impl Iterator<Item = Result<Entity, Self::Error>> + 'a {
+        self.graph.iter_graphs().map(|r| match r {
+            Ok(graph) => Self::lookup_id(&self.name_index, &graph),
+            Err(e) => Err(RdfTripleStoreError::GraphStorageError(e)),
+        })
+    }
+
+    fn iter_quads<'a>(
+        &'a self,
+        order: QuadOrder,
+    ) -> impl Iterator<Item = Result<(Quad<Entity>, EdgeProps), Self::Error>> + 'a {
+        self.graph.iter_quads(order).map(|r| match r {
+            Ok((quad, edge_props)) => Ok((
+                quad.try_map(|id| Self::lookup_id(&self.name_index, &id))?,
+                edge_props,
+            )),
+            Err(e) => Err(RdfTripleStoreError::GraphStorageError(e)),
+        })
+    }
+
+    fn iter_query_graphs<'a>(
+        &'a self,
+        graphs: impl IntoIterator<Item = Entity>,
+        query: Query<Entity>,
+    ) -> impl Iterator<Item = Result<(Quad<Entity>, EdgeProps), Self::Error>> + 'a {
+        // A graph which has never been named holds nothing, so it is left out rather than reported.
+        let graphs = graphs
+            .into_iter()
+            .filter_map(|graph| self.lookup_pattern_entity(&graph).transpose())
+            .collect::<Result<Vec<_>, _>>();
+        let query = query.try_map(|entity: Entity| self.lookup_entity(&entity));
+
+        let quads: BoxedIter<'_, (Quad<Entity>, EdgeProps), Self::Error> = match (graphs, query) {
+            (Ok(graphs), Ok(query)) => Box::new(self.graph.iter_query_graphs(graphs, query).map(
+                |r| match r {
+                    Ok((quad, edge_props)) => Ok((
+                        quad.try_map(|id| Self::lookup_id(&self.name_index, &id))?,
+                        edge_props,
+                    )),
+                    Err(e) => Err(RdfTripleStoreError::GraphStorageError(e)),
+                },
+            )),
+            (Err(e), _) | (_, Err(e)) => Box::new(std::iter::once(Err(e))),
+        };
+        quads
+    }
+
+    fn drop_graph(&mut self, graph: &Entity) -> Result<(), Self::Error> {
+        match self.lookup_pattern_entity(graph)? {
+            Some(graph) => self
+                .graph
+                .drop_graph(&graph)
+                .map_err(RdfTripleStoreError::GraphStorageError),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        prelude::*,
+        rdf::{Entity, MemRdfTripleStore},
+        Quad, QuadOrder, Query, Triple,
+    };
+
+    #[test]
+    fn test_quads_mem() {
+        crate::conformance::quad::test_quads(MemRdfTripleStore::new_mem());
+    }
+
+    #[test]
+    fn test_query_graphs_mem() {
+        crate::conformance::quad::test_query_graphs(MemRdfTripleStore::new_mem());
+    }
+
+    #[test]
+    fn test_drop_graph_mem() {
+        crate::conformance::quad::test_drop_graph(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_quads_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let rdf_db = crate::rdf::SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::quad::test_quads(rdf_db);
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_drop_graph_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let rdf_db = crate::rdf::SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::quad::test_drop_graph(rdf_db);
+    }
+
+    #[test]
+    fn test_named_graphs() {
+        let mut db = MemRdfTripleStore::<(), String>::new_mem();
+        let edge = Triple {
+            sub: Entity::from("alice"),
+            pred: Entity::from("knows"),
+            obj: Entity::from("bob"),
+        };
+        let quad = Quad::new(edge.clone(), Entity::from("doc"));
+        db.insert_quad(quad.clone(), "from doc".to_string())
+            .expect("ok");
+
+        assert_eq!(
+            db.iter_graphs().collect::<Result<Vec<_>, _>>().expect("ok"),
+            [Entity::from("doc")]
+        );
+        assert_eq!(
+            db.iter_quads(QuadOrder::GSPO)
+                .collect::<Result<Vec<_>, _>>()
+                .expect("ok"),
+            [(quad.clone(), "from doc".to_string())]
+        );
+        assert_eq!(db.get_edge(&edge).expect("ok"), None);
+
+        // Unknown names find nothing, except in a query where they are reported.
+        let elsewhere = Quad::new(edge.clone(), Entity::from("elsewhere"));
+        assert_eq!(db.get_quad(&elsewhere).expect("ok"), None);
+        assert_eq!(
+            db.iter_query_graphs(
+                [Entity::from("doc"), Entity::from("elsewhere")],
+                Query::S([Entity::from("alice")].into())
+            )
+            .collect::<Result<Vec<_>, _>>()
+            .expect("ok"),
+            [(quad.clone(), "from doc".to_string())]
+        );
+        assert!(db
+            .iter_query_graphs(
+                [Entity::from("doc")],
+                Query::S([Entity::from("carol")].into())
+            )
+            .any(|r| r.is_err()));
+        db.drop_graph(&Entity::from("elsewhere")).expect("ok");
+
+        db.drop_graph(&Entity::from("doc")).expect("ok");
+        assert_eq!(db.get_quad(&quad).expect("ok"), None);
+        assert_eq!(db.iter_graphs().count(), 0);
+    }
+}
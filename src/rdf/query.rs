@@ -1,17 +1,15 @@
 use super::{Entity, RdfTripleStore, RdfTripleStoreError};
 use crate::{
     mem::MemHashIndex,
-    traits::{
-        BidirIndex, Property, TripleStore, TripleStoreError, TripleStoreIter, TripleStoreQuery,
-    },
-    Query, QueryError,
+    traits::{BidirIndex, BoxedRows, Property, TripleStore, TripleStoreIter, TripleStoreQuery},
+    PropsFilter, Query, QueryError, RunError, Triple,
 };
 use ulid::Ulid;
 
 impl<
         NodeProps: Property,
         EdgeProps: Property,
-        NameIndex: BidirIndex<Left = String, Right = Ulid>,
+        NameIndex: BidirIndex<Left = Entity, Right = Ulid>,
         TripleStorage: TripleStore<Ulid, NodeProps, EdgeProps>,
     > TripleStoreQuery<Entity, NodeProps, EdgeProps>
     for RdfTripleStore<NodeProps, EdgeProps, NameIndex, TripleStorage>
@@ -19,41 +17,140 @@ impl<
     type QueryResult = RdfTripleStore<
         NodeProps,
         EdgeProps,
-        MemHashIndex<String, Ulid>,
+        MemHashIndex<Entity, Ulid>,
         TripleStorage::QueryResult,
     >;
 
-    fn run(
+    fn iter_query_filtered<'a>(
+        &'a self,
+        query: Query<Entity>,
+        filter: PropsFilter<'a, Entity, NodeProps, EdgeProps>,
+    ) -> (
+        impl Iterator<Item = Result<(Entity, NodeProps), Self::Error>> + 'a,
+        impl Iterator<Item = Result<(Triple<Entity>, EdgeProps), Self::Error>> + 'a,
+    ) {
+        let node_query = matches!(query, Query::NodeProps(_));
+
+        let (nodes, edges): BoxedRows<'_, Entity, NodeProps, EdgeProps, Self::Error> = match query
+            .try_map(|entity: Entity| self.lookup_entity(&entity))
+        {
+            Ok(query) => {
+                // The filter is written against entities, so it is applied once ids have been mapped back.
+                let (nodes, edges) = self.graph.iter_query(query);
+                (
+                    Box::new(
+                        nodes
+                            .map(|r| match r {
+                                Ok((id, node_props)) => {
+                                    Ok((Self::lookup_id(&self.name_index, &id)?, node_props))
+                                }
+                                Err(e) => Err(RdfTripleStoreError::GraphStorageError(e)),
+                            })
+                            .filter(move |r| match r {
+                                Ok((entity, node_props)) => filter.keep_node(entity, node_props),
+                                Err(_) => true,
+                            }),
+                    ),
+                    Box::new(
+                        edges
+                            .map(|r| match r {
+                                Ok((triple, edge_props)) => Ok((
+                                    triple.try_map(|id| Self::lookup_id(&self.name_index, &id))?,
+                                    edge_props,
+                                )),
+                                Err(e) => Err(RdfTripleStoreError::GraphStorageError(e)),
+                            })
+                            .filter(move |r| match r {
+                                Ok((triple, edge_props)) => filter.keep_edge(triple, edge_props),
+                                Err(_) => true,
+                            }),
+                    ),
+                )
+            }
+            Err(e) if node_query => (
+                Box::new(std::iter::once(Err(e))),
+                Box::new(std::iter::empty()),
+            ),
+            Err(e) => (
+                Box::new(std::iter::empty()),
+                Box::new(std::iter::once(Err(e))),
+            ),
+        };
+
+        (nodes, edges)
+    }
+
+    // Name index errors carry the offending entities, so the error type is large.
+    #[allow(clippy::result_large_err)]
+    fn run_filtered(
         &self,
         query: Query<Entity>,
-    ) -> Result<Self::QueryResult, QueryError<Self::Error, <<Self as TripleStoreQuery<Entity, NodeProps, EdgeProps>>::QueryResult as TripleStoreError>::Error>>{
-        let mut mem_index: MemHashIndex<String, Ulid> = MemHashIndex::new();
+        filter: PropsFilter<Entity, NodeProps, EdgeProps>,
+    ) -> Result<Self::QueryResult, RunError<Self, Entity, NodeProps, EdgeProps>> {
+        let mut mem_index: MemHashIndex<Entity, Ulid> = MemHashIndex::new();
 
         // Translate the query into one we can execute on the underlying graph.
         let query = query.try_map(|entity: Entity| {
             // Perform a lookup and record the result into mem_index.
-            self.lookup_entity(&entity).map_err(|e| QueryError::Left(e))
+            self.lookup_entity(&entity).map_err(QueryError::Left)
         })?;
 
+        // Translate the filter so it can be pushed down into the underlying graph. Ids which fail to resolve are
+        // kept, so that the error is reported when recording them below.
+        let keep_node = |id: &Ulid, node_props: &NodeProps| {
+            Self::lookup_id(&self.name_index, id)
+                .map(|entity| filter.keep_node(&entity, node_props))
+                .unwrap_or(true)
+        };
+        let keep_edge = |triple: &Triple<Ulid>, edge_props: &EdgeProps| {
+            triple
+                .clone()
+                .try_map(|id| Self::lookup_id(&self.name_index, &id))
+                .map(|triple| filter.keep_edge(&triple, edge_props))
+                .unwrap_or(true)
+        };
+        let mut graph_filter = PropsFilter::new();
+        if filter.has_nodes() {
+            graph_filter = graph_filter.nodes(&keep_node);
+        }
+        if filter.has_edges() {
+            graph_filter = graph_filter.edges(&keep_edge);
+        }
+
         // Execute the query on the underlying graph.
-        let query_graph = self.graph.run(query).map_err(|e| match e {
-            QueryError::Left(e) => QueryError::Left(RdfTripleStoreError::GraphStorageError(e)),
-            QueryError::Right(e) => QueryError::Right(RdfTripleStoreError::GraphStorageError(e)),
-        })?;
+        let query_graph = self
+            .graph
+            .run_filtered(query, graph_filter)
+            .map_err(|e| match e {
+                QueryError::Left(e) => QueryError::Left(RdfTripleStoreError::GraphStorageError(e)),
+                QueryError::Right(e) => {
+                    QueryError::Right(RdfTripleStoreError::GraphStorageError(e))
+                }
+            })?;
 
         // Populate the new name index with any associations we'll need.
+        let mut record_id = |id: Ulid| {
+            let entity = Self::lookup_id(&self.name_index, &id).map_err(QueryError::Left)?;
+            if matches!(entity, Entity::Ulid(_)) {
+                return Ok(());
+            }
+            let recorded = mem_index
+                .left_to_right(&entity)
+                .map_err(|e| QueryError::Right(RdfTripleStoreError::NameIndexStorageError(e)))?;
+            if recorded.is_none() {
+                mem_index.set(entity, id).map_err(|e| {
+                    QueryError::Right(RdfTripleStoreError::NameIndexStorageError(e))
+                })?;
+            }
+            Ok(())
+        };
+
         let vertices = query_graph
             .vertices()
             .map_err(|e| QueryError::Right(RdfTripleStoreError::GraphStorageError(e)))?;
 
         for id in vertices {
-            if let Entity::String(s) =
-                Self::lookup_id(&self.name_index, &id).map_err(|e| QueryError::Left(e))?
-            {
-                mem_index.set(s, id).map_err(|e| {
-                    QueryError::Right(RdfTripleStoreError::NameIndexStorageError(e))
-                })?;
-            }
+            record_id(id)?;
         }
 
         for r in query_graph.iter_edges(crate::EdgeOrder::SPO) {
@@ -61,16 +158,147 @@ impl<
                 r.map_err(|e| QueryError::Right(RdfTripleStoreError::GraphStorageError(e)))?;
 
             for id in [triple.sub, triple.pred, triple.obj] {
-                if let Entity::String(s) =
-                    Self::lookup_id(&self.name_index, &id).map_err(|e| QueryError::Left(e))?
-                {
-                    mem_index.set(s, id).map_err(|e| {
-                        QueryError::Right(RdfTripleStoreError::NameIndexStorageError(e))
-                    })?;
-                }
+                record_id(id)?;
             }
         }
 
         Ok(RdfTripleStore::new(mem_index, query_graph))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::rdf::MemRdfTripleStore;
+    #[cfg(feature = "sled")]
+    use crate::rdf::SledRdfTripleStore;
+
+    #[test]
+    fn test_query_node_props_mem() {
+        crate::conformance::query::test_query_node_props(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_query_node_props_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let rdf_db = SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::query::test_query_node_props(rdf_db);
+    }
+
+    #[test]
+    fn test_query_edge_props_mem() {
+        crate::conformance::query::test_query_edge_props(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_query_edge_props_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let rdf_db = SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::query::test_query_edge_props(rdf_db);
+    }
+
+    #[test]
+    fn test_query_s_mem() {
+        crate::conformance::query::test_query_s(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_query_s_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let rdf_db = SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::query::test_query_s(rdf_db);
+    }
+
+    #[test]
+    fn test_query_sp_mem() {
+        crate::conformance::query::test_query_sp(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_query_sp_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let rdf_db = SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::query::test_query_sp(rdf_db);
+    }
+
+    #[test]
+    fn test_query_p_mem() {
+        crate::conformance::query::test_query_p(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_query_p_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let rdf_db = SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::query::test_query_p(rdf_db);
+    }
+
+    #[test]
+    fn test_query_po_mem() {
+        crate::conformance::query::test_query_po(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_query_po_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let rdf_db = SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::query::test_query_po(rdf_db);
+    }
+
+    #[test]
+    fn test_query_o_mem() {
+        crate::conformance::query::test_query_o(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_query_o_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let rdf_db = SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::query::test_query_o(rdf_db);
+    }
+
+    #[test]
+    fn test_query_os_mem() {
+        crate::conformance::query::test_query_os(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_query_os_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let rdf_db = SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::query::test_query_os(rdf_db);
+    }
+
+    #[test]
+    fn test_iter_query_mem() {
+        crate::conformance::query::test_iter_query(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_iter_query_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let rdf_db = SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::query::test_iter_query(rdf_db);
+    }
+
+    #[test]
+    fn test_query_filtered_mem() {
+        crate::conformance::query::test_query_filtered(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_query_filtered_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let rdf_db = SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::query::test_query_filtered(rdf_db);
+    }
+}
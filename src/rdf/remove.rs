@@ -5,7 +5,7 @@ use ulid::Ulid;
 impl<
         NodeProps: Property,
         EdgeProps: Property,
-        NameIndex: BidirIndex<Left = String, Right = Ulid>,
+        NameIndex: BidirIndex<Left = Entity, Right = Ulid>,
         TripleStorage: TripleStore<Ulid, NodeProps, EdgeProps>,
     > TripleStoreRemove<Entity, NodeProps, EdgeProps>
     for RdfTripleStore<NodeProps, EdgeProps, NameIndex, TripleStorage>
@@ -13,12 +13,45 @@ impl<
     fn remove_node(&mut self, entity: impl std::borrow::Borrow<Entity>) -> Result<(), Self::Error> {
         self.graph
             .remove_node(self.lookup_entity(entity.borrow())?)
-            .map_err(|e| RdfTripleStoreError::GraphStorageError(e))
+            .map_err(RdfTripleStoreError::GraphStorageError)
     }
 
     fn remove_edge(&mut self, triple: crate::Triple<Entity>) -> Result<(), Self::Error> {
         self.graph
             .remove_edge(triple.try_map(|entity| self.lookup_entity(&entity))?)
-            .map_err(|e| RdfTripleStoreError::GraphStorageError(e))
+            .map_err(RdfTripleStoreError::GraphStorageError)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::rdf::MemRdfTripleStore;
+    #[cfg(feature = "sled")]
+    use crate::rdf::SledRdfTripleStore;
+
+    #[test]
+    fn test_remove_node_mem() {
+        crate::conformance::remove::test_remove_node(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_remove_node_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let rdf_db = SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::remove::test_remove_node(rdf_db);
+    }
+
+    #[test]
+    fn test_remove_edge_mem() {
+        crate::conformance::remove::test_remove_edge(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_remove_edge_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let rdf_db = SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::remove::test_remove_edge(rdf_db);
     }
 }
@@ -0,0 +1,806 @@
+//! A [SPARQL 1.1](https://www.w3.org/TR/sparql11-query/) query engine for [RdfTripleStore][super::RdfTripleStore].
+//!
+//! The supported subset is:
+//!   * `SELECT` (with `DISTINCT`/`REDUCED` and `*`), `ASK` and `CONSTRUCT` (including `CONSTRUCT WHERE`).
+//!   * `PREFIX` and `BASE`.
+//!   * Basic graph patterns, including the `;`, `,`, `a` and `[]` shorthands.
+//!   * `OPTIONAL`, `UNION` and nested groups.
+//!   * `FILTER` with logical, comparison and arithmetic operators, and the functions `BOUND`, `STR`, `LANG`,
+//!     `DATATYPE`, `isIRI`/`isURI`, `isBLANK`, `isLITERAL`, `isNUMERIC`, `sameTerm`, `langMatches`, `CONTAINS`,
+//!     `STRSTARTS`, `STRENDS`, `STRLEN`, `UCASE`, `LCASE` and `REGEX`.
+//!   * `ORDER BY`, `LIMIT` and `OFFSET`.
+//!
+//! Basic graph patterns are evaluated with [TripleStorePattern::run_pattern], so queries run against any
+//! [RdfTripleStore][super::RdfTripleStore] regardless of the backend.
+//!
+//! # Example
+//! ```
+//! # use simple_triplestore::{prelude::*, rdf::{sparql, turtle, Entity, MemRdfTripleStore}};
+//! let mut db = MemRdfTripleStore::<(), ()>::new_mem();
+//! turtle::load(
+//!     r#"
+//!     @prefix ex: <http://example.com/> .
+//!     ex:alice ex:name "Alice" ; ex:age 42 ; ex:knows ex:bob .
+//!     ex:bob ex:name "Bob" ; ex:age 27 .
+//!     "#
+//!     .as_bytes(),
+//!     &mut db,
+//! )?;
+//!
+//! let results = sparql::query(
+//!     &db,
+//!     r#"
+//!     PREFIX ex: <http://example.com/>
+//!     SELECT ?name WHERE { ?person ex:name ?name ; ex:age ?age . FILTER(?age > 30) }
+//!     "#,
+//! )?;
+//!
+//! let sparql::QueryResults::Solutions { solutions, .. } = results else {
+//!     panic!("expected solutions");
+//! };
+//! assert_eq!(solutions.len(), 1);
+//! assert_eq!(solutions[0]["name"], Entity::literal("Alice"));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::collections::{HashMap, HashSet};
+
+use ulid::Ulid;
+
+use super::{ntriples::ParseError, Entity, MemRdfTripleStore, RdfTripleStoreError};
+use crate::{
+    mem::MemHashIndexError,
+    pattern::{Bindings, Pattern, Term, TriplePattern},
+    traits::{Property, TripleStoreInsert, TripleStorePattern},
+    Triple,
+};
+
+mod expression;
+mod parser;
+
+/// Errors which can occur while running a SPARQL query.
+#[derive(Debug)]
+pub enum SparqlError<E> {
+    /// The query could not be parsed.
+    Parse(ParseError),
+
+    /// The triplestore returned an error.
+    Store(E),
+
+    /// The result of a `CONSTRUCT` query could not be built.
+    Construct(Box<RdfTripleStoreError<MemHashIndexError<Entity, Ulid>, ()>>),
+}
+
+impl<E: std::fmt::Debug> std::fmt::Display for SparqlError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SparqlError::Parse(e) => e.fmt(f),
+            SparqlError::Store(e) => f.write_fmt(format_args!("store error: {:?}", e)),
+            SparqlError::Construct(e) => f.write_fmt(format_args!("construct error: {}", e)),
+        }
+    }
+}
+
+impl<E: std::fmt::Debug> std::error::Error for SparqlError<E> {}
+
+impl<E> From<ParseError> for SparqlError<E> {
+    fn from(e: ParseError) -> Self {
+        SparqlError::Parse(e)
+    }
+}
+
+/// The result of a SPARQL query.
+#[derive(Debug)]
+pub enum QueryResults<NodeProps: Property, EdgeProps: Property> {
+    /// The result of a `SELECT` query.
+    ///
+    /// Variables which are unbound in a solution (e.g. because an `OPTIONAL` did not match) are absent from its
+    /// bindings.
+    Solutions {
+        /// The projected variables, in order.
+        variables: Vec<String>,
+        solutions: Vec<Bindings<Entity>>,
+    },
+
+    /// The result of an `ASK` query.
+    Boolean(bool),
+
+    /// The result of a `CONSTRUCT` query.
+    Graph(Box<MemRdfTripleStore<NodeProps, EdgeProps>>),
+}
+
+/// A parsed SPARQL query, which may be evaluated any number of times.
+#[derive(Debug, Clone)]
+pub struct SparqlQuery {
+    form: QueryForm,
+    group: Group,
+    modifiers: Modifiers,
+}
+
+#[derive(Debug, Clone)]
+enum QueryForm {
+    Select {
+        distinct: bool,
+        /// `None` for `SELECT *`.
+        projection: Option<Vec<String>>,
+    },
+    Ask,
+    Construct {
+        template: Vec<TriplePattern<Entity>>,
+    },
+}
+
+/// A group graph pattern: `{ ... }`.
+#[derive(Debug, Clone, Default)]
+struct Group {
+    elements: Vec<GroupElement>,
+    /// Filters apply to the whole group, wherever they appear in it.
+    filters: Vec<Expression>,
+}
+
+#[derive(Debug, Clone)]
+enum GroupElement {
+    /// A basic graph pattern. Blank nodes are variables whose names start with `_:`.
+    Triples(Vec<TriplePattern<Entity>>),
+    Optional(Group),
+    /// One or more groups joined by `UNION`. A single nested group is a union with one alternative.
+    Union(Vec<Group>),
+}
+
+#[derive(Debug, Clone, Default)]
+struct Modifiers {
+    /// Ordering conditions, and whether each is descending.
+    order: Vec<(Expression, bool)>,
+    limit: Option<usize>,
+    offset: usize,
+}
+
+#[derive(Debug, Clone)]
+enum Expression {
+    Var(String),
+    Const(Entity),
+    Or(Box<Expression>, Box<Expression>),
+    And(Box<Expression>, Box<Expression>),
+    Not(Box<Expression>),
+    Negate(Box<Expression>),
+    Binary(Operator, Box<Expression>, Box<Expression>),
+    Bound(String),
+    Call(Function, Vec<Expression>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Equal,
+    NotEqual,
+    Less,
+    Greater,
+    LessOrEqual,
+    GreaterOrEqual,
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Function {
+    Str,
+    Lang,
+    Datatype,
+    IsIri,
+    IsBlank,
+    IsLiteral,
+    IsNumeric,
+    SameTerm,
+    LangMatches,
+    Contains,
+    StrStarts,
+    StrEnds,
+    StrLen,
+    UCase,
+    LCase,
+    Regex,
+}
+
+impl Function {
+    /// The minimum and maximum number of arguments.
+    fn arity(&self) -> (usize, usize) {
+        match self {
+            Function::Str
+            | Function::Lang
+            | Function::Datatype
+            | Function::IsIri
+            | Function::IsBlank
+            | Function::IsLiteral
+            | Function::IsNumeric
+            | Function::StrLen
+            | Function::UCase
+            | Function::LCase => (1, 1),
+            Function::SameTerm
+            | Function::LangMatches
+            | Function::Contains
+            | Function::StrStarts
+            | Function::StrEnds => (2, 2),
+            Function::Regex => (2, 3),
+        }
+    }
+}
+
+impl Group {
+    /// The triple patterns directly in this group.
+    fn triples(&self) -> impl Iterator<Item = &TriplePattern<Entity>> {
+        self.elements.iter().flat_map(|element| match element {
+            GroupElement::Triples(triples) => triples.as_slice(),
+            _ => &[],
+        })
+    }
+
+    /// Add the variables mentioned in this group's patterns to `variables`, in order of first appearance.
+    fn collect_variables(&self, variables: &mut Vec<String>) {
+        for element in self.elements.iter() {
+            match element {
+                GroupElement::Triples(triples) => {
+                    for triple in triples {
+                        for term in [&triple.sub, &triple.pred, &triple.obj] {
+                            if let Term::Var(name) = term {
+                                if !name.starts_with("_:") && !variables.contains(name) {
+                                    variables.push(name.clone());
+                                }
+                            }
+                        }
+                    }
+                }
+                GroupElement::Optional(group) => group.collect_variables(variables),
+                GroupElement::Union(groups) => {
+                    for group in groups {
+                        group.collect_variables(variables);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Parse a SPARQL query.
+pub fn parse(query: &str) -> Result<SparqlQuery, ParseError> {
+    parser::SparqlParser::new(query).query()
+}
+
+/// Parse and evaluate a SPARQL query against `db`.
+///
+/// Edges created by `CONSTRUCT` get default properties.
+pub fn query<
+    NodeProps: Property,
+    EdgeProps: Property + Default,
+    T: TripleStorePattern<Entity, NodeProps, EdgeProps>,
+>(
+    db: &T,
+    query: &str,
+) -> Result<QueryResults<NodeProps, EdgeProps>, SparqlError<T::Error>> {
+    query_with(db, query, |_| EdgeProps::default())
+}
+
+/// Parse and evaluate a SPARQL query against `db`, calling `edge_props` for each edge created by `CONSTRUCT`.
+pub fn query_with<
+    NodeProps: Property,
+    EdgeProps: Property,
+    T: TripleStorePattern<Entity, NodeProps, EdgeProps>,
+>(
+    db: &T,
+    query: &str,
+    edge_props: impl FnMut(&Triple<Entity>) -> EdgeProps,
+) -> Result<QueryResults<NodeProps, EdgeProps>, SparqlError<T::Error>> {
+    parse(query)?.evaluate(db, edge_props)
+}
+
+impl SparqlQuery {
+    /// Evaluate this query against `db`, calling `edge_props` for each edge created by `CONSTRUCT`.
+    pub fn evaluate<
+        NodeProps: Property,
+        EdgeProps: Property,
+        T: TripleStorePattern<Entity, NodeProps, EdgeProps>,
+    >(
+        &self,
+        db: &T,
+        mut edge_props: impl FnMut(&Triple<Entity>) -> EdgeProps,
+    ) -> Result<QueryResults<NodeProps, EdgeProps>, SparqlError<T::Error>> {
+        let mut solutions = evaluate_group(db, &self.group).map_err(SparqlError::Store)?;
+
+        if let QueryForm::Ask = self.form {
+            return Ok(QueryResults::Boolean(!solutions.is_empty()));
+        }
+
+        if !self.modifiers.order.is_empty() {
+            solutions = self.order(solutions);
+        }
+
+        match &self.form {
+            QueryForm::Select {
+                distinct,
+                projection,
+            } => {
+                let variables = match projection {
+                    Some(variables) => variables.clone(),
+                    None => {
+                        let mut variables = Vec::new();
+                        self.group.collect_variables(&mut variables);
+                        variables
+                    }
+                };
+
+                let mut seen = HashSet::new();
+                let solutions = solutions
+                    .into_iter()
+                    .map(|mut bindings| {
+                        bindings.retain(|name, _| variables.contains(name));
+                        bindings
+                    })
+                    .filter(|bindings| {
+                        !distinct
+                            || seen.insert(
+                                variables
+                                    .iter()
+                                    .map(|v| bindings.get(v).cloned())
+                                    .collect::<Vec<_>>(),
+                            )
+                    })
+                    .skip(self.modifiers.offset)
+                    .take(self.modifiers.limit.unwrap_or(usize::MAX))
+                    .collect();
+
+                Ok(QueryResults::Solutions {
+                    variables,
+                    solutions,
+                })
+            }
+
+            QueryForm::Construct { template } => {
+                let mut graph = MemRdfTripleStore::new_mem();
+                for bindings in solutions
+                    .iter()
+                    .skip(self.modifiers.offset)
+                    .take(self.modifiers.limit.unwrap_or(usize::MAX))
+                {
+                    // Blank nodes in the template are fresh for each solution.
+                    let mut blank_nodes = HashMap::new();
+                    let mut instantiate = |term: &Term<Entity>| match term {
+                        Term::Const(entity) => Some(entity.clone()),
+                        Term::Var(name) if name.starts_with("_:") => Some(
+                            blank_nodes
+                                .entry(name.clone())
+                                .or_insert_with(Entity::blank_node)
+                                .clone(),
+                        ),
+                        Term::Var(name) => bindings.get(name).cloned(),
+                    };
+
+                    for triple in template {
+                        let (Some(sub), Some(pred), Some(obj)) = (
+                            instantiate(&triple.sub),
+                            instantiate(&triple.pred),
+                            instantiate(&triple.obj),
+                        ) else {
+                            continue;
+                        };
+                        // Triples with unbound variables or literal subjects and predicates are skipped.
+                        if matches!(sub, Entity::Literal(_))
+                            || matches!(pred, Entity::Literal(_) | Entity::BlankNode(_))
+                        {
+                            continue;
+                        }
+
+                        let triple = Triple { sub, pred, obj };
+                        let props = edge_props(&triple);
+                        graph
+                            .insert_edge(triple, props)
+                            .map_err(|e| SparqlError::Construct(Box::new(e)))?;
+                    }
+                }
+                Ok(QueryResults::Graph(Box::new(graph)))
+            }
+
+            QueryForm::Ask => unreachable!("handled above"),
+        }
+    }
+
+    fn order(&self, solutions: Vec<Bindings<Entity>>) -> Vec<Bindings<Entity>> {
+        let mut keyed = solutions
+            .into_iter()
+            .map(|bindings| {
+                let keys = self
+                    .modifiers
+                    .order
+                    .iter()
+                    .map(|(expression, _)| expression::evaluate(expression, &bindings))
+                    .collect::<Vec<_>>();
+                (keys, bindings)
+            })
+            .collect::<Vec<_>>();
+
+        keyed.sort_by(|(left, _), (right, _)| {
+            left.iter()
+                .zip(right.iter())
+                .zip(self.modifiers.order.iter())
+                .map(|((l, r), (_, descending))| {
+                    let ordering = expression::order(l.as_ref(), r.as_ref());
+                    if *descending {
+                        ordering.reverse()
+                    } else {
+                        ordering
+                    }
+                })
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        keyed.into_iter().map(|(_, bindings)| bindings).collect()
+    }
+}
+
+fn evaluate_group<NodeProps: Property, EdgeProps: Property, T>(
+    db: &T,
+    group: &Group,
+) -> Result<Vec<Bindings<Entity>>, T::Error>
+where
+    T: TripleStorePattern<Entity, NodeProps, EdgeProps>,
+{
+    let solutions = evaluate_group_patterns(db, group)?;
+    Ok(solutions
+        .into_iter()
+        .filter(|bindings| {
+            group
+                .filters
+                .iter()
+                .all(|filter| expression::is_true(filter, bindings))
+        })
+        .collect())
+}
+
+/// Evaluate the patterns in a group, without applying its filters.
+fn evaluate_group_patterns<NodeProps: Property, EdgeProps: Property, T>(
+    db: &T,
+    group: &Group,
+) -> Result<Vec<Bindings<Entity>>, T::Error>
+where
+    T: TripleStorePattern<Entity, NodeProps, EdgeProps>,
+{
+    let mut solutions = vec![Bindings::new()];
+
+    for element in group.elements.iter() {
+        solutions = match element {
+            GroupElement::Triples(triples) => {
+                let matches = db.run_pattern(&Pattern {
+                    triples: triples.clone(),
+                })?;
+                join(&solutions, &matches, &[])
+            }
+            GroupElement::Optional(optional) => {
+                // Filters in an optional group decide whether it matches, rather than removing solutions.
+                let matches = evaluate_group_patterns(db, optional)?;
+                let mut result = Vec::new();
+                for left in solutions.iter() {
+                    let joined = join(
+                        std::slice::from_ref(left),
+                        &matches,
+                        optional.filters.as_slice(),
+                    );
+                    if joined.is_empty() {
+                        result.push(left.clone());
+                    } else {
+                        result.extend(joined);
+                    }
+                }
+                result
+            }
+            GroupElement::Union(alternatives) => {
+                let mut matches = Vec::new();
+                for alternative in alternatives {
+                    matches.extend(evaluate_group(db, alternative)?);
+                }
+                join(&solutions, &matches, &[])
+            }
+        };
+
+        if solutions.is_empty() {
+            break;
+        }
+    }
+
+    Ok(solutions)
+}
+
+/// Join two sets of solutions, keeping merged solutions which satisfy all of `filters`.
+fn join(
+    left: &[Bindings<Entity>],
+    right: &[Bindings<Entity>],
+    filters: &[Expression],
+) -> Vec<Bindings<Entity>> {
+    let mut result = Vec::new();
+    for l in left {
+        for r in right {
+            let compatible = r
+                .iter()
+                .all(|(name, value)| l.get(name).is_none_or(|v| v == value));
+            if !compatible {
+                continue;
+            }
+            let mut merged = l.clone();
+            merged.extend(r.iter().map(|(k, v)| (k.clone(), v.clone())));
+            if filters
+                .iter()
+                .all(|filter| expression::is_true(filter, &merged))
+            {
+                result.push(merged);
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::{parse, query, QueryResults};
+    use crate::{
+        rdf::{turtle, Entity, MemRdfTripleStore},
+        traits::{TripleStore, TripleStoreIter},
+        EdgeOrder, Triple,
+    };
+
+    const DATA: &str = r#"
+        @prefix ex: <http://example.com/> .
+        ex:alice a ex:Person ; ex:name "Alice" ; ex:age 42 ; ex:knows ex:bob, ex:carol .
+        ex:bob a ex:Person ; ex:name "Bob"@en ; ex:age 27 ; ex:knows ex:carol .
+        ex:carol a ex:Person ; ex:name "Carol" ; ex:email "carol@example.com" .
+        ex:acme a ex:Company ; ex:name "ACME" .
+    "#;
+
+    const PREFIX: &str = "PREFIX ex: <http://example.com/>\n";
+
+    fn ex(local: &str) -> Entity {
+        Entity::from(format!("http://example.com/{}", local))
+    }
+
+    fn select<T: TripleStore<Entity, (), ()>>(db: &T, text: &str) -> Vec<Vec<Option<Entity>>> {
+        match query(db, &format!("{}{}", PREFIX, text)).expect("ok") {
+            QueryResults::Solutions {
+                variables,
+                solutions,
+            } => solutions
+                .into_iter()
+                .map(|bindings| variables.iter().map(|v| bindings.get(v).cloned()).collect())
+                .collect(),
+            _ => panic!("expected solutions"),
+        }
+    }
+
+    fn ask<T: TripleStore<Entity, (), ()>>(db: &T, text: &str) -> bool {
+        match query(db, &format!("{}{}", PREFIX, text)).expect("ok") {
+            QueryResults::Boolean(result) => result,
+            _ => panic!("expected a boolean"),
+        }
+    }
+
+    fn check_select<T: TripleStore<Entity, (), ()>>(mut db: T) {
+        turtle::load(DATA.as_bytes(), &mut db).expect("ok");
+
+        // Basic graph pattern.
+        assert_eq!(
+            select(
+                &db,
+                "SELECT ?name WHERE { ?x ex:knows ex:carol . ?x ex:name ?name } ORDER BY ?name"
+            ),
+            [
+                [Some(Entity::literal("Alice"))],
+                [Some(Entity::lang_literal("Bob", "en"))]
+            ]
+        );
+
+        // OPTIONAL leaves variables unbound.
+        assert_eq!(
+            select(
+                &db,
+                "SELECT ?x ?email WHERE { ?x a ex:Person OPTIONAL { ?x ex:email ?email } } ORDER BY ?x"
+            ),
+            [
+                vec![Some(ex("alice")), None],
+                vec![Some(ex("bob")), None],
+                vec![Some(ex("carol")), Some(Entity::literal("carol@example.com"))],
+            ]
+        );
+
+        // UNION
+        assert_eq!(
+            select(
+                &db,
+                "SELECT ?x WHERE { { ?x ex:age 42 } UNION { ?x a ex:Company } } ORDER BY ?x"
+            ),
+            [[Some(ex("acme"))], [Some(ex("alice"))]]
+        );
+
+        // FILTER over literals.
+        assert_eq!(
+            select(
+                &db,
+                "SELECT ?x WHERE { ?x ex:age ?age FILTER(?age >= 27 && ?age * 2 < 60) }"
+            ),
+            [[Some(ex("bob"))]]
+        );
+        assert_eq!(
+            select(
+                &db,
+                r#"SELECT ?x WHERE { ?x ex:name ?name FILTER(REGEX(?name, "^a", "i") || lang(?name) = "en") } ORDER BY DESC(?x)"#
+            ),
+            [[Some(ex("bob"))], [Some(ex("alice"))], [Some(ex("acme"))]]
+        );
+        assert_eq!(
+            select(
+                &db,
+                "SELECT ?x WHERE { ?x a ex:Person OPTIONAL { ?x ex:email ?email } FILTER(!BOUND(?email)) } ORDER BY ?x"
+            ),
+            [[Some(ex("alice"))], [Some(ex("bob"))]]
+        );
+        assert_eq!(
+            select(
+                &db,
+                r#"SELECT ?x WHERE { ?x ex:name ?name FILTER(STRSTARTS(STR(?name), "C") && isLiteral(?name)) }"#
+            ),
+            [[Some(ex("carol"))]]
+        );
+
+        // DISTINCT, ORDER BY, LIMIT and OFFSET.
+        assert_eq!(
+            select(
+                &db,
+                "SELECT DISTINCT ?type WHERE { ?x a ?type } ORDER BY ?type"
+            ),
+            [[Some(ex("Company"))], [Some(ex("Person"))]]
+        );
+        assert_eq!(
+            select(
+                &db,
+                "SELECT ?x WHERE { ?x a ex:Person } ORDER BY DESC(?x) LIMIT 1 OFFSET 1"
+            ),
+            [[Some(ex("bob"))]]
+        );
+
+        // Blank nodes and SELECT *.
+        assert_eq!(
+            select(
+                &db,
+                "SELECT * WHERE { ?x ex:knows _:someone . _:someone ex:knows [ a ex:Person ; ex:name \"Carol\" ] }"
+            ),
+            [[Some(ex("alice"))]]
+        );
+
+        // Unknown IRIs match nothing.
+        assert!(select(&db, "SELECT ?x WHERE { ?x ex:unknown ?y }").is_empty());
+    }
+
+    fn check_ask<T: TripleStore<Entity, (), ()>>(mut db: T) {
+        turtle::load(DATA.as_bytes(), &mut db).expect("ok");
+
+        assert!(ask(&db, "ASK { ex:alice ex:knows ex:bob }"));
+        assert!(ask(&db, "ASK WHERE { ?x ex:age ?age FILTER(?age > 40) }"));
+        assert!(!ask(&db, "ASK { ex:bob ex:knows ex:alice }"));
+        assert!(!ask(&db, "ASK { ?x ex:age ?age FILTER(?age > \"40\") }"));
+    }
+
+    fn check_construct<T: TripleStore<Entity, (), ()>>(mut db: T) {
+        turtle::load(DATA.as_bytes(), &mut db).expect("ok");
+
+        let QueryResults::Graph(graph) = query(
+            &db,
+            &format!(
+                "{}CONSTRUCT {{ ?y ex:knownBy ?x . ?x ex:friend [ ex:name ?name ] }} WHERE {{ ?x ex:knows ?y . ?y ex:name ?name }}",
+                PREFIX
+            ),
+        )
+        .expect("ok") else {
+            panic!("expected a graph");
+        };
+
+        let edges = graph
+            .iter_edges(EdgeOrder::SPO)
+            .map(|r| r.expect("ok").0)
+            .collect::<Vec<_>>();
+        assert_eq!(edges.len(), 9);
+
+        let known_by = edges
+            .iter()
+            .filter(|t| t.pred == ex("knownBy"))
+            .cloned()
+            .collect::<HashSet<_>>();
+        assert_eq!(
+            known_by,
+            [
+                (ex("bob"), ex("alice")),
+                (ex("carol"), ex("alice")),
+                (ex("carol"), ex("bob")),
+            ]
+            .into_iter()
+            .map(|(sub, obj)| Triple {
+                sub,
+                pred: ex("knownBy"),
+                obj
+            })
+            .collect()
+        );
+
+        // Each solution gets its own blank node.
+        assert_eq!(
+            edges
+                .iter()
+                .filter(|t| t.pred == ex("friend"))
+                .map(|t| t.obj.clone())
+                .filter(|obj| matches!(obj, Entity::BlankNode(_)))
+                .collect::<HashSet<_>>()
+                .len(),
+            3
+        );
+
+        let QueryResults::Graph(graph) = query(
+            &db,
+            &format!("{}CONSTRUCT WHERE {{ ?x ex:age ?age }}", PREFIX),
+        )
+        .expect("ok") else {
+            panic!("expected a graph");
+        };
+        assert_eq!(graph.iter_edges(EdgeOrder::SPO).count(), 2);
+    }
+
+    #[test]
+    fn test_select_mem() {
+        check_select(MemRdfTripleStore::new_mem());
+    }
+
+    #[test]
+    fn test_ask_mem() {
+        check_ask(MemRdfTripleStore::new_mem());
+    }
+
+    #[test]
+    fn test_construct_mem() {
+        check_construct(MemRdfTripleStore::new_mem());
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_select_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        check_select(crate::rdf::SledRdfTripleStore::new_sled(&db).expect("ok"));
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_ask_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        check_ask(crate::rdf::SledRdfTripleStore::new_sled(&db).expect("ok"));
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_construct_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        check_construct(crate::rdf::SledRdfTripleStore::new_sled(&db).expect("ok"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for text in [
+            "SELECT WHERE { ?x ?p ?o }",
+            "SELECT ?x { ?x ?p ?o",
+            "SELECT ?x WHERE { ?x ?p ?o } LIMIT",
+            "SELECT ?x WHERE { ?x undefined:p ?o }",
+            "SELECT ?x WHERE { ?x ?p ?o FILTER(CONTAINS(?x)) }",
+            "SELECT ?x FROM <http://example.com/> WHERE { ?x ?p ?o }",
+            "DESCRIBE ?x WHERE { ?x ?p ?o }",
+            "ASK { ?x ?p ?o } garbage",
+        ] {
+            assert!(parse(text).is_err(), "{}", text);
+        }
+
+        assert!(parse("select ?x where { ?x ?p ?o . } order by ?x limit 10 offset 5").is_ok());
+    }
+}
@@ -0,0 +1,388 @@
+//! Evaluation of `FILTER` and `ORDER BY` expressions.
+//!
+//! Evaluation errors (e.g. comparing a string with a number, or referring to an unbound variable) are represented by
+//! `None`, and cause a filter to reject the solution.
+
+use std::cmp::Ordering;
+
+use super::{Expression, Function, Operator};
+use crate::{
+    pattern::Bindings,
+    rdf::{
+        ntriples::literal_entity,
+        vocab::{rdf, xsd},
+        Entity,
+    },
+};
+
+const INTEGER_TYPES: &[&str] = &[
+    xsd::INTEGER,
+    "http://www.w3.org/2001/XMLSchema#int",
+    "http://www.w3.org/2001/XMLSchema#long",
+    "http://www.w3.org/2001/XMLSchema#short",
+    "http://www.w3.org/2001/XMLSchema#byte",
+    "http://www.w3.org/2001/XMLSchema#nonNegativeInteger",
+    "http://www.w3.org/2001/XMLSchema#nonPositiveInteger",
+    "http://www.w3.org/2001/XMLSchema#positiveInteger",
+    "http://www.w3.org/2001/XMLSchema#negativeInteger",
+    "http://www.w3.org/2001/XMLSchema#unsignedLong",
+    "http://www.w3.org/2001/XMLSchema#unsignedInt",
+    "http://www.w3.org/2001/XMLSchema#unsignedShort",
+    "http://www.w3.org/2001/XMLSchema#unsignedByte",
+];
+const FLOAT: &str = "http://www.w3.org/2001/XMLSchema#float";
+
+#[derive(Debug, Clone, Copy)]
+enum Number {
+    Integer(i64),
+    Decimal(f64),
+    Double(f64),
+}
+
+impl Number {
+    fn as_f64(self) -> f64 {
+        match self {
+            Number::Integer(i) => i as f64,
+            Number::Decimal(f) | Number::Double(f) => f,
+        }
+    }
+
+    fn into_entity(self) -> Entity {
+        match self {
+            Number::Integer(i) => number_literal(i.to_string(), xsd::INTEGER),
+            Number::Decimal(f) => {
+                let mut lexical = f.to_string();
+                if !lexical.contains('.') {
+                    lexical.push_str(".0");
+                }
+                number_literal(lexical, xsd::DECIMAL)
+            }
+            Number::Double(f) => number_literal(format!("{:E}", f), xsd::DOUBLE),
+        }
+    }
+}
+
+/// The value of a term, as far as expressions are concerned.
+enum Value<'a> {
+    Number(Number),
+    /// A simple literal, an `xsd:string` or a language-tagged string.
+    String {
+        lexical: &'a str,
+        lang: Option<&'a str>,
+    },
+    Boolean(bool),
+    /// Any other term, which only supports equality.
+    Other,
+}
+
+impl<'a> Value<'a> {
+    fn of(entity: &'a Entity) -> Self {
+        let Entity::Literal(literal) = entity else {
+            return Value::Other;
+        };
+
+        let lexical = literal.lexical();
+        match literal.datatype() {
+            None => Value::String {
+                lexical,
+                lang: literal.language(),
+            },
+            Some(xsd::BOOLEAN) => match lexical {
+                "true" | "1" => Value::Boolean(true),
+                "false" | "0" => Value::Boolean(false),
+                _ => Value::Other,
+            },
+            Some(datatype) if INTEGER_TYPES.contains(&datatype) => lexical
+                .trim_start_matches('+')
+                .parse()
+                .map_or(Value::Other, |i| Value::Number(Number::Integer(i))),
+            Some(xsd::DECIMAL) => lexical
+                .parse()
+                .map_or(Value::Other, |f| Value::Number(Number::Decimal(f))),
+            Some(xsd::DOUBLE | FLOAT) => lexical
+                .parse()
+                .map_or(Value::Other, |f| Value::Number(Number::Double(f))),
+            Some(_) => Value::Other,
+        }
+    }
+}
+
+fn number_literal(lexical: String, datatype: &str) -> Entity {
+    literal_entity(lexical, Some(datatype.to_string()), None)
+}
+
+fn boolean(value: bool) -> Entity {
+    literal_entity(value.to_string(), Some(xsd::BOOLEAN.to_string()), None)
+}
+
+fn string(lexical: String, lang: Option<&str>) -> Entity {
+    literal_entity(lexical, None, lang.map(str::to_string))
+}
+
+/// Whether `expression` evaluates to true, per the SPARQL effective boolean value rules.
+pub(super) fn is_true(expression: &Expression, bindings: &Bindings<Entity>) -> bool {
+    evaluate(expression, bindings)
+        .and_then(|value| effective_boolean_value(&value))
+        .unwrap_or(false)
+}
+
+fn effective_boolean_value(entity: &Entity) -> Option<bool> {
+    match Value::of(entity) {
+        Value::Boolean(b) => Some(b),
+        Value::String { lexical, .. } => Some(!lexical.is_empty()),
+        Value::Number(n) => {
+            let f = n.as_f64();
+            Some(f != 0.0 && !f.is_nan())
+        }
+        Value::Other => None,
+    }
+}
+
+pub(super) fn evaluate(expression: &Expression, bindings: &Bindings<Entity>) -> Option<Entity> {
+    match expression {
+        Expression::Var(name) => bindings.get(name).cloned(),
+        Expression::Const(entity) => Some(entity.clone()),
+
+        // `||` and `&&` tolerate an error on one side if the other side decides the result.
+        Expression::Or(left, right) => {
+            let left = evaluate(left, bindings).and_then(|v| effective_boolean_value(&v));
+            let right = evaluate(right, bindings).and_then(|v| effective_boolean_value(&v));
+            match (left, right) {
+                (Some(true), _) | (_, Some(true)) => Some(boolean(true)),
+                (Some(false), Some(false)) => Some(boolean(false)),
+                _ => None,
+            }
+        }
+        Expression::And(left, right) => {
+            let left = evaluate(left, bindings).and_then(|v| effective_boolean_value(&v));
+            let right = evaluate(right, bindings).and_then(|v| effective_boolean_value(&v));
+            match (left, right) {
+                (Some(false), _) | (_, Some(false)) => Some(boolean(false)),
+                (Some(true), Some(true)) => Some(boolean(true)),
+                _ => None,
+            }
+        }
+        Expression::Not(inner) => {
+            let value = evaluate(inner, bindings)?;
+            Some(boolean(!effective_boolean_value(&value)?))
+        }
+        Expression::Negate(inner) => {
+            let value = evaluate(inner, bindings)?;
+            match Value::of(&value) {
+                Value::Number(Number::Integer(i)) => {
+                    Some(Number::Integer(i.checked_neg()?).into_entity())
+                }
+                Value::Number(Number::Decimal(f)) => Some(Number::Decimal(-f).into_entity()),
+                Value::Number(Number::Double(f)) => Some(Number::Double(-f).into_entity()),
+                _ => None,
+            }
+        }
+        Expression::Binary(operator, left, right) => {
+            let left = evaluate(left, bindings)?;
+            let right = evaluate(right, bindings)?;
+            binary(*operator, &left, &right)
+        }
+        Expression::Bound(name) => Some(boolean(bindings.contains_key(name))),
+        Expression::Call(function, args) => {
+            let args = args
+                .iter()
+                .map(|arg| evaluate(arg, bindings))
+                .collect::<Option<Vec<_>>>()?;
+            call(*function, &args)
+        }
+    }
+}
+
+fn binary(operator: Operator, left: &Entity, right: &Entity) -> Option<Entity> {
+    match operator {
+        Operator::Equal => Some(boolean(equal(left, right)?)),
+        Operator::NotEqual => Some(boolean(!equal(left, right)?)),
+        Operator::Less => Some(boolean(compare(left, right)?.is_lt())),
+        Operator::Greater => Some(boolean(compare(left, right)?.is_gt())),
+        Operator::LessOrEqual => Some(boolean(compare(left, right)?.is_le())),
+        Operator::GreaterOrEqual => Some(boolean(compare(left, right)?.is_ge())),
+        Operator::Add | Operator::Subtract | Operator::Multiply | Operator::Divide => {
+            let (Value::Number(l), Value::Number(r)) = (Value::of(left), Value::of(right)) else {
+                return None;
+            };
+            arithmetic(operator, l, r).map(Number::into_entity)
+        }
+    }
+}
+
+fn arithmetic(operator: Operator, left: Number, right: Number) -> Option<Number> {
+    match (left, right) {
+        (Number::Integer(l), Number::Integer(r)) if operator != Operator::Divide => {
+            Some(Number::Integer(match operator {
+                Operator::Add => l.checked_add(r)?,
+                Operator::Subtract => l.checked_sub(r)?,
+                _ => l.checked_mul(r)?,
+            }))
+        }
+        _ => {
+            let (l, r) = (left.as_f64(), right.as_f64());
+            let result = match operator {
+                Operator::Add => l + r,
+                Operator::Subtract => l - r,
+                Operator::Multiply => l * r,
+                _ => l / r,
+            };
+            if matches!(left, Number::Double(_)) || matches!(right, Number::Double(_)) {
+                Some(Number::Double(result))
+            } else if result.is_finite() {
+                Some(Number::Decimal(result))
+            } else {
+                // Decimal division by zero is an error.
+                None
+            }
+        }
+    }
+}
+
+/// `=` on two terms. Literals of unknown datatypes which are not identical cannot be compared.
+fn equal(left: &Entity, right: &Entity) -> Option<bool> {
+    match (Value::of(left), Value::of(right)) {
+        (Value::Number(l), Value::Number(r)) => Some(l.as_f64() == r.as_f64()),
+        (Value::Boolean(l), Value::Boolean(r)) => Some(l == r),
+        (Value::String { .. }, Value::String { .. }) => Some(left == right),
+        (Value::Other, Value::Other)
+            if matches!(left, Entity::Literal(_)) && matches!(right, Entity::Literal(_)) =>
+        {
+            (left == right).then_some(true)
+        }
+        (Value::Other, Value::Other) => Some(left == right),
+        // Otherwise a literal is being compared with something of a different kind.
+        _ if matches!(left, Entity::Literal(_)) && matches!(right, Entity::Literal(_)) => None,
+        _ => Some(false),
+    }
+}
+
+/// `<` and friends, which are only defined on numbers, strings and booleans.
+fn compare(left: &Entity, right: &Entity) -> Option<Ordering> {
+    match (Value::of(left), Value::of(right)) {
+        (Value::Number(l), Value::Number(r)) => l.as_f64().partial_cmp(&r.as_f64()),
+        (Value::Boolean(l), Value::Boolean(r)) => Some(l.cmp(&r)),
+        (
+            Value::String {
+                lexical: l,
+                lang: l_lang,
+            },
+            Value::String {
+                lexical: r,
+                lang: r_lang,
+            },
+        ) if l_lang == r_lang => Some(l.cmp(r)),
+        _ => None,
+    }
+}
+
+/// The ordering used by `ORDER BY`: unbound, then blank nodes, then IRIs, then literals.
+pub(super) fn order(left: Option<&Entity>, right: Option<&Entity>) -> Ordering {
+    fn rank(entity: Option<&Entity>) -> u8 {
+        match entity {
+            None => 0,
+            Some(Entity::BlankNode(_)) => 1,
+            Some(Entity::String(_) | Entity::Ulid(_)) => 2,
+            Some(Entity::Literal(_)) => 3,
+        }
+    }
+
+    match (left, right) {
+        (Some(l), Some(r)) if rank(left) == 3 && rank(right) == 3 => {
+            compare(l, r).unwrap_or_else(|| l.cmp(r))
+        }
+        (Some(l), Some(r)) if rank(left) == rank(right) => l.cmp(r),
+        _ => rank(left).cmp(&rank(right)),
+    }
+}
+
+/// The lexical form and language of a string literal argument.
+fn string_arg(entity: &Entity) -> Option<(&str, Option<&str>)> {
+    match Value::of(entity) {
+        Value::String { lexical, lang } => Some((lexical, lang)),
+        _ => None,
+    }
+}
+
+fn call(function: Function, args: &[Entity]) -> Option<Entity> {
+    match function {
+        Function::Str => match &args[0] {
+            Entity::String(iri) => Some(string(iri.clone(), None)),
+            Entity::Literal(literal) => Some(string(literal.lexical().to_string(), None)),
+            _ => None,
+        },
+        Function::Lang => match &args[0] {
+            Entity::Literal(literal) => Some(string(
+                literal.language().unwrap_or_default().to_string(),
+                None,
+            )),
+            _ => None,
+        },
+        Function::Datatype => match &args[0] {
+            Entity::Literal(literal) => Some(match (literal.datatype(), literal.language()) {
+                (Some(datatype), _) => Entity::from(datatype),
+                (None, Some(_)) => Entity::from(rdf::LANG_STRING),
+                (None, None) => Entity::from(xsd::STRING),
+            }),
+            _ => None,
+        },
+        Function::IsIri => Some(boolean(matches!(
+            args[0],
+            Entity::String(_) | Entity::Ulid(_)
+        ))),
+        Function::IsBlank => Some(boolean(matches!(args[0], Entity::BlankNode(_)))),
+        Function::IsLiteral => Some(boolean(matches!(args[0], Entity::Literal(_)))),
+        Function::IsNumeric => Some(boolean(matches!(Value::of(&args[0]), Value::Number(_)))),
+        Function::SameTerm => Some(boolean(args[0] == args[1])),
+        Function::LangMatches => {
+            let (tag, _) = string_arg(&args[0])?;
+            let (range, _) = string_arg(&args[1])?;
+            let tag = tag.to_ascii_lowercase();
+            let range = range.to_ascii_lowercase();
+            Some(boolean(if range == "*" {
+                !tag.is_empty()
+            } else {
+                tag == range || tag.starts_with(&format!("{}-", range))
+            }))
+        }
+        Function::Contains | Function::StrStarts | Function::StrEnds => {
+            let (haystack, _) = string_arg(&args[0])?;
+            let (needle, _) = string_arg(&args[1])?;
+            Some(boolean(match function {
+                Function::Contains => haystack.contains(needle),
+                Function::StrStarts => haystack.starts_with(needle),
+                _ => haystack.ends_with(needle),
+            }))
+        }
+        Function::StrLen => {
+            let (lexical, _) = string_arg(&args[0])?;
+            Some(Number::Integer(lexical.chars().count() as i64).into_entity())
+        }
+        Function::UCase => {
+            let (lexical, lang) = string_arg(&args[0])?;
+            Some(string(lexical.to_uppercase(), lang))
+        }
+        Function::LCase => {
+            let (lexical, lang) = string_arg(&args[0])?;
+            Some(string(lexical.to_lowercase(), lang))
+        }
+        Function::Regex => {
+            let (text, _) = string_arg(&args[0])?;
+            let (pattern, _) = string_arg(&args[1])?;
+            let flags = match args.get(2) {
+                Some(flags) => string_arg(flags)?.0,
+                None => "",
+            };
+            if !flags.chars().all(|c| matches!(c, 'i' | 's' | 'm' | 'x')) {
+                return None;
+            }
+            let pattern = if flags.is_empty() {
+                pattern.to_string()
+            } else {
+                format!("(?{}){}", flags, pattern)
+            };
+            let regex = regex::Regex::new(&pattern).ok()?;
+            Some(boolean(regex.is_match(text)))
+        }
+    }
+}
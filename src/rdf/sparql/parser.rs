@@ -0,0 +1,625 @@
+use super::{
+    Expression, Function, Group, GroupElement, Modifiers, Operator, QueryForm, SparqlQuery,
+};
+use crate::{
+    pattern::{Term, TriplePattern},
+    rdf::{
+        ntriples::{literal_entity, ParseError},
+        turtle::{is_pn_chars_base, is_pn_chars_u, TurtleParser},
+        vocab::{rdf, xsd},
+        Entity,
+    },
+};
+
+/// Parses SPARQL queries, using a [TurtleParser] to read IRIs, prefixed names and literals.
+pub(crate) struct SparqlParser {
+    input: TurtleParser,
+    anonymous: usize,
+}
+
+impl SparqlParser {
+    pub(crate) fn new(query: &str) -> Self {
+        Self {
+            input: TurtleParser::new(query),
+            anonymous: 0,
+        }
+    }
+
+    pub(crate) fn query(mut self) -> Result<SparqlQuery, ParseError> {
+        self.prologue()?;
+
+        let form = if self.keyword("SELECT") {
+            self.select_clause()?
+        } else if self.keyword("CONSTRUCT") {
+            if self.keyword("WHERE") {
+                // The short form uses the pattern as its own template.
+                let group = self.construct_where()?;
+                let modifiers = self.modifiers()?;
+                return self.finish(SparqlQuery {
+                    form: QueryForm::Construct {
+                        template: group.triples().cloned().collect(),
+                    },
+                    group,
+                    modifiers,
+                });
+            }
+            QueryForm::Construct {
+                template: self.construct_template()?,
+            }
+        } else if self.keyword("ASK") {
+            QueryForm::Ask
+        } else {
+            return Err(self.input.error("expected SELECT, CONSTRUCT or ASK"));
+        };
+
+        if self.at_keyword("FROM") {
+            return Err(self.input.error("dataset clauses are not supported"));
+        }
+        self.keyword("WHERE");
+        let group = self.group()?;
+        let modifiers = self.modifiers()?;
+
+        self.finish(SparqlQuery {
+            form,
+            group,
+            modifiers,
+        })
+    }
+
+    fn finish(&mut self, query: SparqlQuery) -> Result<SparqlQuery, ParseError> {
+        self.input.skip_ws();
+        match self.input.peek() {
+            None => Ok(query),
+            Some(c) => Err(self
+                .input
+                .error(format!("unexpected '{}' after end of query", c))),
+        }
+    }
+
+    /// Whether `keyword` is next, ignoring case.
+    fn at_keyword(&mut self, keyword: &str) -> bool {
+        self.input.skip_ws();
+        self.input.at_keyword(keyword, true)
+    }
+
+    /// Consume `keyword` if it is next, ignoring case.
+    fn keyword(&mut self, keyword: &str) -> bool {
+        if self.at_keyword(keyword) {
+            self.input.pos_advance(keyword.chars().count());
+            self.input.skip_ws();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consume `token` if it is next.
+    fn punct(&mut self, token: &str) -> bool {
+        self.input.skip_ws();
+        if token
+            .chars()
+            .enumerate()
+            .all(|(i, c)| self.input.peek_at(i) == Some(c))
+        {
+            self.input.pos_advance(token.chars().count());
+            self.input.skip_ws();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), ParseError> {
+        self.input.skip_ws();
+        self.input.expect(c)?;
+        self.input.skip_ws();
+        Ok(())
+    }
+
+    fn prologue(&mut self) -> Result<(), ParseError> {
+        loop {
+            if self.keyword("PREFIX") {
+                self.input.prefix_body()?;
+            } else if self.keyword("BASE") {
+                self.input.base_body()?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn select_clause(&mut self) -> Result<QueryForm, ParseError> {
+        let distinct = self.keyword("DISTINCT") || self.keyword("REDUCED");
+
+        if self.punct("*") {
+            return Ok(QueryForm::Select {
+                distinct,
+                projection: None,
+            });
+        }
+
+        let mut variables = Vec::new();
+        while matches!(self.input.peek(), Some('?' | '$')) {
+            variables.push(self.var()?);
+            self.input.skip_ws();
+        }
+        if variables.is_empty() {
+            return Err(self.input.error("expected '*' or a list of variables"));
+        }
+        Ok(QueryForm::Select {
+            distinct,
+            projection: Some(variables),
+        })
+    }
+
+    fn construct_template(&mut self) -> Result<Vec<TriplePattern<Entity>>, ParseError> {
+        self.expect('{')?;
+        let mut template = Vec::new();
+        while self.input.peek() != Some('}') {
+            self.triples_same_subject(&mut template)?;
+            if !self.punct(".") {
+                break;
+            }
+        }
+        self.expect('}')?;
+        Ok(template)
+    }
+
+    fn construct_where(&mut self) -> Result<Group, ParseError> {
+        let triples = self.construct_template()?;
+        Ok(Group {
+            elements: vec![GroupElement::Triples(triples)],
+            filters: Vec::new(),
+        })
+    }
+
+    fn modifiers(&mut self) -> Result<Modifiers, ParseError> {
+        let mut modifiers = Modifiers::default();
+
+        if self.keyword("ORDER") {
+            if !self.keyword("BY") {
+                return Err(self.input.error("expected BY"));
+            }
+            loop {
+                let descending = if self.keyword("ASC") {
+                    false
+                } else if self.keyword("DESC") {
+                    true
+                } else if matches!(self.input.peek(), Some('?' | '$' | '(')) || self.at_function() {
+                    modifiers.order.push((self.primary()?, false));
+                    continue;
+                } else {
+                    break;
+                };
+                self.expect('(')?;
+                let expression = self.expression()?;
+                self.expect(')')?;
+                modifiers.order.push((expression, descending));
+            }
+            if modifiers.order.is_empty() {
+                return Err(self.input.error("expected an ordering condition"));
+            }
+        }
+
+        loop {
+            if self.keyword("LIMIT") {
+                modifiers.limit = Some(self.integer()?);
+            } else if self.keyword("OFFSET") {
+                modifiers.offset = self.integer()?;
+            } else {
+                return Ok(modifiers);
+            }
+        }
+    }
+
+    fn integer(&mut self) -> Result<usize, ParseError> {
+        let mut digits = String::new();
+        while let Some(c) = self.input.peek().filter(|c| c.is_ascii_digit()) {
+            self.input.bump();
+            digits.push(c);
+        }
+        self.input.skip_ws();
+        digits
+            .parse()
+            .map_err(|_| self.input.error("expected a non-negative integer"))
+    }
+
+    fn var(&mut self) -> Result<String, ParseError> {
+        match self.input.peek() {
+            Some('?' | '$') => {
+                self.input.bump();
+            }
+            _ => return Err(self.input.error("expected a variable")),
+        }
+        let mut name = String::new();
+        while let Some(c) = self.input.peek().filter(|c| is_varname_char(*c)) {
+            self.input.bump();
+            name.push(c);
+        }
+        if name.is_empty() {
+            return Err(self.input.error("expected a variable name"));
+        }
+        Ok(name)
+    }
+
+    fn group(&mut self) -> Result<Group, ParseError> {
+        self.expect('{')?;
+        let mut group = Group::default();
+
+        loop {
+            self.input.skip_ws();
+            match self.input.peek() {
+                Some('}') => break,
+                None => return Err(self.input.error("unterminated group pattern")),
+                Some('{') => {
+                    let mut alternatives = vec![self.group()?];
+                    while self.keyword("UNION") {
+                        alternatives.push(self.group()?);
+                    }
+                    group.elements.push(GroupElement::Union(alternatives));
+                }
+                _ if self.keyword("OPTIONAL") => {
+                    group.elements.push(GroupElement::Optional(self.group()?));
+                }
+                _ if self.keyword("FILTER") => {
+                    group.filters.push(self.constraint()?);
+                }
+                _ => {
+                    let mut triples = Vec::new();
+                    self.triples_same_subject(&mut triples)?;
+                    while self.punct(".") {
+                        if matches!(self.input.peek(), Some('{' | '}'))
+                            || self.at_keyword("OPTIONAL")
+                            || self.at_keyword("FILTER")
+                        {
+                            break;
+                        }
+                        self.triples_same_subject(&mut triples)?;
+                    }
+                    // Adjacent triple blocks belong to the same basic graph pattern.
+                    match group.elements.last_mut() {
+                        Some(GroupElement::Triples(existing)) => existing.extend(triples),
+                        _ => group.elements.push(GroupElement::Triples(triples)),
+                    }
+                    continue;
+                }
+            }
+            self.punct(".");
+        }
+
+        self.expect('}')?;
+        Ok(group)
+    }
+
+    fn constraint(&mut self) -> Result<Expression, ParseError> {
+        if self.input.peek() == Some('(') {
+            self.expect('(')?;
+            let expression = self.expression()?;
+            self.expect(')')?;
+            Ok(expression)
+        } else if self.at_function() {
+            self.function_call()
+        } else {
+            Err(self.input.error("expected '(' or a function call"))
+        }
+    }
+
+    fn triples_same_subject(
+        &mut self,
+        triples: &mut Vec<TriplePattern<Entity>>,
+    ) -> Result<(), ParseError> {
+        self.input.skip_ws();
+        if self.input.peek() == Some('[') {
+            let sub = self.anonymous_node(triples)?;
+            if !matches!(self.input.peek(), Some('.' | '}') | None) {
+                self.property_list(&sub, triples)?;
+            }
+        } else {
+            let sub = self.term(triples)?;
+            self.property_list(&sub, triples)?;
+        }
+        Ok(())
+    }
+
+    fn property_list(
+        &mut self,
+        sub: &Term<Entity>,
+        triples: &mut Vec<TriplePattern<Entity>>,
+    ) -> Result<(), ParseError> {
+        loop {
+            let pred = self.verb()?;
+            loop {
+                let obj = self.term(triples)?;
+                triples.push(TriplePattern {
+                    sub: sub.clone(),
+                    pred: pred.clone(),
+                    obj,
+                });
+                if !self.punct(",") {
+                    break;
+                }
+            }
+
+            if !self.punct(";") {
+                return Ok(());
+            }
+            while self.punct(";") {}
+            if matches!(self.input.peek(), Some('.' | ']' | '}') | None) {
+                return Ok(());
+            }
+        }
+    }
+
+    fn verb(&mut self) -> Result<Term<Entity>, ParseError> {
+        self.input.skip_ws();
+        let term = if self.input.at_keyword("a", false) {
+            self.input.bump();
+            Term::Const(Entity::from(rdf::TYPE))
+        } else {
+            match self.input.peek() {
+                Some('?' | '$') => Term::Var(self.var()?),
+                Some(c) if c == '<' || c == ':' || is_pn_chars_base(c) => {
+                    Term::Const(Entity::String(self.input.iri()?))
+                }
+                _ => return Err(self.input.error("expected a predicate")),
+            }
+        };
+        self.input.skip_ws();
+        Ok(term)
+    }
+
+    /// Parse a subject or object. Nested blank node property lists add their triples to `triples`.
+    fn term(
+        &mut self,
+        triples: &mut Vec<TriplePattern<Entity>>,
+    ) -> Result<Term<Entity>, ParseError> {
+        self.input.skip_ws();
+        let term = match self.input.peek() {
+            Some('?' | '$') => Term::Var(self.var()?),
+            Some('_') if self.input.peek_at(1) == Some(':') => {
+                self.input.pos_advance(2);
+                let mut label = String::new();
+                match self.input.peek() {
+                    Some(c) if is_pn_chars_u(c) || c.is_ascii_digit() => {
+                        self.input.bump();
+                        label.push(c);
+                    }
+                    _ => return Err(self.input.error("expected a blank node label")),
+                }
+                self.input.name_tail(&mut label, false)?;
+                // Blank nodes in patterns act as variables which are never projected.
+                Term::Var(format!("_:{}", label))
+            }
+            Some('[') => return self.anonymous_node(triples),
+            Some('(') => return Err(self.input.error("collections are not supported")),
+            Some('"' | '\'') => Term::Const(self.input.rdf_literal()?),
+            Some(c) if c.is_ascii_digit() || matches!(c, '+' | '-' | '.') => {
+                Term::Const(self.input.numeric_literal()?)
+            }
+            Some(_) if self.input.at_keyword("true", false) => {
+                self.input.pos_advance(4);
+                Term::Const(boolean(true))
+            }
+            Some(_) if self.input.at_keyword("false", false) => {
+                self.input.pos_advance(5);
+                Term::Const(boolean(false))
+            }
+            Some(c) if c == '<' || c == ':' || is_pn_chars_base(c) => {
+                Term::Const(Entity::String(self.input.iri()?))
+            }
+            _ => return Err(self.input.error("expected a term")),
+        };
+        self.input.skip_ws();
+        Ok(term)
+    }
+
+    /// Parse `[]` or `[ predicate object ... ]`.
+    fn anonymous_node(
+        &mut self,
+        triples: &mut Vec<TriplePattern<Entity>>,
+    ) -> Result<Term<Entity>, ParseError> {
+        self.expect('[')?;
+        // '[' can never appear in a blank node label, so this cannot collide with `_:label`.
+        let node = Term::Var(format!("_:[{}]", self.anonymous));
+        self.anonymous += 1;
+        if self.input.peek() != Some(']') {
+            self.property_list(&node, triples)?;
+        }
+        self.expect(']')?;
+        Ok(node)
+    }
+
+    fn expression(&mut self) -> Result<Expression, ParseError> {
+        let mut left = self.and_expression()?;
+        while self.punct("||") {
+            let right = self.and_expression()?;
+            left = Expression::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn and_expression(&mut self) -> Result<Expression, ParseError> {
+        let mut left = self.relational_expression()?;
+        while self.punct("&&") {
+            let right = self.relational_expression()?;
+            left = Expression::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn relational_expression(&mut self) -> Result<Expression, ParseError> {
+        let left = self.additive_expression()?;
+        let operator = if self.punct("=") {
+            Operator::Equal
+        } else if self.punct("!=") {
+            Operator::NotEqual
+        } else if self.punct("<=") {
+            Operator::LessOrEqual
+        } else if self.punct(">=") {
+            Operator::GreaterOrEqual
+        } else if self.punct("<") {
+            Operator::Less
+        } else if self.punct(">") {
+            Operator::Greater
+        } else {
+            return Ok(left);
+        };
+        let right = self.additive_expression()?;
+        Ok(Expression::Binary(
+            operator,
+            Box::new(left),
+            Box::new(right),
+        ))
+    }
+
+    fn additive_expression(&mut self) -> Result<Expression, ParseError> {
+        let mut left = self.multiplicative_expression()?;
+        loop {
+            let operator = if self.punct("+") {
+                Operator::Add
+            } else if self.punct("-") {
+                Operator::Subtract
+            } else {
+                return Ok(left);
+            };
+            let right = self.multiplicative_expression()?;
+            left = Expression::Binary(operator, Box::new(left), Box::new(right));
+        }
+    }
+
+    fn multiplicative_expression(&mut self) -> Result<Expression, ParseError> {
+        let mut left = self.unary_expression()?;
+        loop {
+            let operator = if self.punct("*") {
+                Operator::Multiply
+            } else if self.punct("/") {
+                Operator::Divide
+            } else {
+                return Ok(left);
+            };
+            let right = self.unary_expression()?;
+            left = Expression::Binary(operator, Box::new(left), Box::new(right));
+        }
+    }
+
+    fn unary_expression(&mut self) -> Result<Expression, ParseError> {
+        if self.input.peek() == Some('!') && self.input.peek_at(1) != Some('=') {
+            self.expect('!')?;
+            Ok(Expression::Not(Box::new(self.unary_expression()?)))
+        } else if self.punct("-") {
+            Ok(Expression::Negate(Box::new(self.unary_expression()?)))
+        } else if self.punct("+") {
+            self.unary_expression()
+        } else {
+            self.primary()
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expression, ParseError> {
+        self.input.skip_ws();
+        let expression = match self.input.peek() {
+            Some('(') => {
+                self.expect('(')?;
+                let expression = self.expression()?;
+                self.expect(')')?;
+                return Ok(expression);
+            }
+            Some('?' | '$') => Expression::Var(self.var()?),
+            Some('"' | '\'') => Expression::Const(self.input.rdf_literal()?),
+            Some(c) if c.is_ascii_digit() || c == '.' => {
+                Expression::Const(self.input.numeric_literal()?)
+            }
+            Some(_) if self.input.at_keyword("true", true) => {
+                self.input.pos_advance(4);
+                Expression::Const(boolean(true))
+            }
+            Some(_) if self.input.at_keyword("false", true) => {
+                self.input.pos_advance(5);
+                Expression::Const(boolean(false))
+            }
+            Some(_) if self.at_function() => return self.function_call(),
+            Some(c) if c == '<' || c == ':' || is_pn_chars_base(c) => {
+                Expression::Const(Entity::String(self.input.iri()?))
+            }
+            _ => return Err(self.input.error("expected an expression")),
+        };
+        self.input.skip_ws();
+        Ok(expression)
+    }
+
+    fn at_function(&mut self) -> bool {
+        self.at_keyword("BOUND") || FUNCTIONS.iter().any(|(name, _)| self.at_keyword(name))
+    }
+
+    fn function_call(&mut self) -> Result<Expression, ParseError> {
+        if self.keyword("BOUND") {
+            self.expect('(')?;
+            let var = self.var()?;
+            self.expect(')')?;
+            return Ok(Expression::Bound(var));
+        }
+
+        let function = FUNCTIONS
+            .iter()
+            .find(|(name, _)| self.keyword(name))
+            .map(|(_, function)| *function)
+            .ok_or_else(|| self.input.error("expected a function call"))?;
+
+        self.expect('(')?;
+        let mut args = Vec::new();
+        if !self.punct(")") {
+            loop {
+                args.push(self.expression()?);
+                if !self.punct(",") {
+                    break;
+                }
+            }
+            self.expect(')')?;
+        }
+
+        let (min, max) = function.arity();
+        if args.len() < min || args.len() > max {
+            return Err(self.input.error(format!(
+                "wrong number of arguments to {}",
+                FUNCTIONS
+                    .iter()
+                    .find(|(_, f)| *f == function)
+                    .map_or("function", |(name, _)| name)
+            )));
+        }
+        Ok(Expression::Call(function, args))
+    }
+}
+
+/// Built-in functions by name, other than `BOUND` which takes a variable rather than an expression.
+const FUNCTIONS: &[(&str, Function)] = &[
+    ("STRSTARTS", Function::StrStarts),
+    ("STRENDS", Function::StrEnds),
+    ("STRLEN", Function::StrLen),
+    ("STR", Function::Str),
+    ("LANGMATCHES", Function::LangMatches),
+    ("LANG", Function::Lang),
+    ("DATATYPE", Function::Datatype),
+    ("ISIRI", Function::IsIri),
+    ("ISURI", Function::IsIri),
+    ("ISBLANK", Function::IsBlank),
+    ("ISLITERAL", Function::IsLiteral),
+    ("ISNUMERIC", Function::IsNumeric),
+    ("SAMETERM", Function::SameTerm),
+    ("CONTAINS", Function::Contains),
+    ("UCASE", Function::UCase),
+    ("LCASE", Function::LCase),
+    ("REGEX", Function::Regex),
+];
+
+fn boolean(value: bool) -> Entity {
+    literal_entity(value.to_string(), Some(xsd::BOOLEAN.to_string()), None)
+}
+
+fn is_varname_char(c: char) -> bool {
+    is_pn_chars_u(c)
+        || c.is_ascii_digit()
+        || c == '\u{B7}'
+        || ('\u{300}'..='\u{36F}').contains(&c)
+        || ('\u{203F}'..='\u{2040}').contains(&c)
+}
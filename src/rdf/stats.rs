@@ -0,0 +1,53 @@
+use super::{Entity, RdfTripleStore, RdfTripleStoreError};
+use crate::traits::{BidirIndex, Property, Statistics, TripleStore, TripleStoreStats};
+use ulid::Ulid;
+
+impl<
+        NodeProps: Property,
+        EdgeProps: Property,
+        NameIndex: BidirIndex<Left = Entity, Right = Ulid>,
+        TripleStorage: TripleStore<Ulid, NodeProps, EdgeProps>,
+    > TripleStoreStats<Entity, NodeProps, EdgeProps>
+    for RdfTripleStore<NodeProps, EdgeProps, NameIndex, TripleStorage>
+{
+    fn statistics(&self) -> Result<Statistics<Entity>, Self::Error> {
+        let stats = self
+            .graph
+            .statistics()
+            .map_err(RdfTripleStoreError::GraphStorageError)?;
+        Ok(Statistics {
+            nodes: stats.nodes,
+            edges: stats.edges,
+            predicates: stats
+                .predicates
+                .into_iter()
+                .map(|(id, stats)| Ok((Self::lookup_id(&self.name_index, &id)?, stats)))
+                .collect::<Result<_, Self::Error>>()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::rdf::MemRdfTripleStore;
+
+    #[test]
+    fn test_statistics_mem() {
+        let db = MemRdfTripleStore::new_mem();
+        crate::conformance::stats::test_statistics(db);
+    }
+
+    #[test]
+    fn test_explain_mem() {
+        let db = MemRdfTripleStore::new_mem();
+        crate::conformance::stats::test_explain(db);
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_statistics_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let db = crate::rdf::SledRdfTripleStore::new_sled(&db).expect("ok");
+        crate::conformance::stats::test_statistics(db);
+    }
+}
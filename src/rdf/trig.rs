@@ -0,0 +1,481 @@
+//! Reading and writing [TriG](https://www.w3.org/TR/trig/).
+//!
+//! TriG extends Turtle with `{ ... }` blocks, each optionally labelled with the IRI or blank node of a named graph (and
+//! optionally preceded by `GRAPH`). Triples outside a labelled block are loaded into the default graph and triples
+//! inside one into that named graph of a [QuadStore]. Everything else is as in [turtle][super::turtle].
+//!
+//! # Example
+//! ```
+//! # use std::collections::BTreeMap;
+//! # use simple_triplestore::{prelude::*, rdf::{trig, Entity, MemRdfTripleStore}};
+//! let mut db = MemRdfTripleStore::<(), ()>::new_mem();
+//!
+//! trig::load(
+//!     r#"
+//!     @prefix ex: <http://example.com/> .
+//!     ex:alice ex:knows ex:bob .
+//!     ex:doc { ex:bob ex:knows ex:carol . }
+//!     "#
+//!     .as_bytes(),
+//!     &mut db,
+//! )?;
+//! assert_eq!(
+//!     db.iter_graphs().collect::<Result<Vec<_>, _>>()?,
+//!     [Entity::from("http://example.com/doc")]
+//! );
+//!
+//! let prefixes = BTreeMap::from([("ex".to_string(), "http://example.com/".to_string())]);
+//! let mut output = Vec::new();
+//! trig::write(&db, &mut output, &prefixes)?;
+//! assert_eq!(
+//!     String::from_utf8(output).unwrap(),
+//!     "@prefix ex: <http://example.com/> .\n\nex:alice ex:knows ex:bob .\n\nex:doc {\n    ex:bob ex:knows ex:carol .\n}\n"
+//! );
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    io::{Read, Write},
+};
+
+use super::{
+    ntriples::check_triple,
+    turtle::{format_term, write_prefixes, TriplesWriter, TurtleParser},
+    Entity,
+};
+use crate::{
+    traits::{Property, QuadStore},
+    EdgeOrder, Quad, QuadOrder, Triple,
+};
+
+pub use super::ntriples::{LoadError, ParseError, WriteError};
+
+/// A TriG parser which yields each [Triple] of a document along with its graph label, if it has one.
+pub struct TriGParser {
+    input: TurtleParser,
+}
+
+impl TriGParser {
+    pub fn new(input: &str) -> Self {
+        Self {
+            input: TurtleParser::new(input),
+        }
+    }
+
+    /// Resolve relative IRIs against `base` until the document declares its own.
+    pub fn with_base(self, base: impl Into<String>) -> Self {
+        Self {
+            input: self.input.with_base(base),
+        }
+    }
+}
+
+impl Iterator for TriGParser {
+    type Item = Result<(Triple<Entity>, Option<Entity>), ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.input.next_with(block)
+    }
+}
+
+/// Parse a directive, a graph block, or triples in the default graph.
+fn block(input: &mut TurtleParser) -> Result<(), ParseError> {
+    if input.directive()? {
+        return Ok(());
+    }
+
+    if input.at_keyword("GRAPH", true) {
+        input.pos_advance(5);
+        input.skip_ws();
+        let label = match input.peek() {
+            Some('[') => anon(input)?,
+            Some('(') => return Err(input.error("expected a graph label")),
+            _ => input.subject()?,
+        };
+        input.skip_ws();
+        return wrapped_graph(input, Some(label));
+    }
+
+    match input.peek() {
+        Some('{') => wrapped_graph(input, None),
+        Some('[') if at_anon_label(input) => {
+            let label = anon(input)?;
+            input.skip_ws();
+            wrapped_graph(input, Some(label))
+        }
+        Some('[' | '(') => input.triples_statement(),
+        _ => {
+            // Either the label of a graph or the subject of some triples.
+            let label = input.subject()?;
+            input.skip_ws();
+            if input.peek() == Some('{') {
+                wrapped_graph(input, Some(label))
+            } else {
+                input.predicate_object_list(&label)?;
+                input.skip_ws();
+                input.expect('.')
+            }
+        }
+    }
+}
+
+/// Whether `[]` followed by `{` is next, which labels a graph with a fresh blank node.
+fn at_anon_label(input: &TurtleParser) -> bool {
+    let mut offset = 1;
+    while input.peek_at(offset).is_some_and(char::is_whitespace) {
+        offset += 1;
+    }
+    if input.peek_at(offset) != Some(']') {
+        return false;
+    }
+    offset += 1;
+    while input.peek_at(offset).is_some_and(char::is_whitespace) {
+        offset += 1;
+    }
+    input.peek_at(offset) == Some('{')
+}
+
+/// Parse `[]`.
+fn anon(input: &mut TurtleParser) -> Result<Entity, ParseError> {
+    input.expect('[')?;
+    input.skip_ws();
+    input.expect(']')?;
+    Ok(input.fresh_blank_node())
+}
+
+/// Parse `'{' triplesBlock? '}'`, where the final '.' of the block may be left out.
+fn wrapped_graph(input: &mut TurtleParser, graph: Option<Entity>) -> Result<(), ParseError> {
+    input.expect('{')?;
+    input.skip_ws();
+    input.set_graph(graph);
+    while input.peek() != Some('}') {
+        input.triples()?;
+        input.skip_ws();
+        match input.peek() {
+            Some('.') => {
+                input.bump();
+                input.skip_ws();
+            }
+            Some('}') => {}
+            _ => return Err(input.error("expected '.' or '}'")),
+        }
+    }
+    input.bump();
+    input.set_graph(None);
+    Ok(())
+}
+
+/// Parse a TriG document.
+pub fn parse(input: &str) -> TriGParser {
+    TriGParser::new(input)
+}
+
+/// Load a TriG document into `db`, using `EdgeProps::default()` for every edge.
+///
+/// Returns the number of triples inserted.
+pub fn load<
+    R: Read,
+    NodeProps: Property,
+    EdgeProps: Property + Default,
+    T: QuadStore<Entity, NodeProps, EdgeProps>,
+>(
+    reader: R,
+    db: &mut T,
+) -> Result<usize, LoadError<T::Error>> {
+    load_with(reader, db, |_, _| EdgeProps::default())
+}
+
+/// Load a TriG document into `db`, computing the properties of each edge from its triple and graph label with
+/// `edge_props`.
+///
+/// Returns the number of triples inserted.
+pub fn load_with<
+    R: Read,
+    NodeProps: Property,
+    EdgeProps: Property,
+    T: QuadStore<Entity, NodeProps, EdgeProps>,
+>(
+    mut reader: R,
+    db: &mut T,
+    mut edge_props: impl FnMut(&Triple<Entity>, Option<&Entity>) -> EdgeProps,
+) -> Result<usize, LoadError<T::Error>> {
+    let mut input = String::new();
+    reader
+        .read_to_string(&mut input)
+        .map_err(|e| LoadError::Parse(ParseError::Io(e)))?;
+
+    let mut count = 0;
+    for r in parse(&input) {
+        let (triple, graph) = r.map_err(LoadError::Parse)?;
+        let props = edge_props(&triple, graph.as_ref());
+        match graph {
+            Some(graph) => db.insert_quad(Quad::new(triple, graph), props),
+            None => db.insert_edge(triple, props),
+        }
+        .map_err(LoadError::Store)?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Write every edge in `db` to `writer` as TriG.
+///
+/// The default graph is written first as plain Turtle, followed by a block for each named graph. `prefixes` is used as
+/// in [turtle::write][super::turtle::write].
+pub fn write<
+    W: Write,
+    NodeProps: Property,
+    EdgeProps: Property,
+    T: QuadStore<Entity, NodeProps, EdgeProps>,
+>(
+    db: &T,
+    mut writer: W,
+    prefixes: &BTreeMap<String, String>,
+) -> Result<(), WriteError<T::Error>> {
+    write_prefixes(&mut writer, prefixes).map_err(WriteError::Io)?;
+
+    let mut triples = TriplesWriter::new(prefixes, "");
+    for r in db.iter_edges(EdgeOrder::SPO) {
+        let (triple, _) = r.map_err(WriteError::Store)?;
+        let triple = check_triple(triple)?;
+        triples.write(&mut writer, triple).map_err(WriteError::Io)?;
+    }
+    let mut written = triples.finish(&mut writer).map_err(WriteError::Io)?;
+
+    let mut current: Option<(Entity, TriplesWriter)> = None;
+    for r in db.iter_quads(QuadOrder::GSPO) {
+        let (quad, _) = r.map_err(WriteError::Store)?;
+        let triple = check_triple(quad.triple())?;
+
+        let triples = match &mut current {
+            Some((graph, triples)) if *graph == quad.graph => triples,
+            _ => {
+                if let Some((_, triples)) = current.take() {
+                    triples.finish(&mut writer).map_err(WriteError::Io)?;
+                    writer.write_all(b"}\n").map_err(WriteError::Io)?;
+                }
+                if written {
+                    writer.write_all(b"\n").map_err(WriteError::Io)?;
+                }
+                writer
+                    .write_fmt(format_args!("{} {{\n", format_term(&quad.graph, prefixes)))
+                    .map_err(WriteError::Io)?;
+                written = true;
+                &mut current
+                    .insert((quad.graph.clone(), TriplesWriter::new(prefixes, "    ")))
+                    .1
+            }
+        };
+        triples.write(&mut writer, triple).map_err(WriteError::Io)?;
+    }
+    if let Some((_, triples)) = current {
+        triples.finish(&mut writer).map_err(WriteError::Io)?;
+        writer.write_all(b"}\n").map_err(WriteError::Io)?;
+    }
+
+    writer.flush().map_err(WriteError::Io)
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{BTreeMap, HashSet};
+
+    use crate::{
+        prelude::*,
+        rdf::{Entity, MemRdfTripleStore},
+        EdgeOrder, QuadOrder, Triple,
+    };
+
+    use super::ParseError;
+    use crate::rdf::ntriples::Statement;
+
+    fn parse_all(input: &str) -> Result<Vec<Statement>, ParseError> {
+        super::parse(input).collect()
+    }
+
+    fn triple(sub: &str, pred: &str, obj: &str) -> Triple<Entity> {
+        Triple {
+            sub: sub.into(),
+            pred: pred.into(),
+            obj: obj.into(),
+        }
+    }
+
+    #[test]
+    fn test_parse_graphs() {
+        let statements = parse_all(
+            r#"
+            @prefix ex: <http://example.com/> .
+            ex:a ex:p ex:b .
+            { ex:a ex:p ex:c }
+            ex:g1 { ex:a ex:p ex:d ; ex:q ex:e . ex:f ex:p ex:a . }
+            GRAPH <http://example.com/g2> { ex:a ex:p [ ex:q ex:b ] }
+            graph ex:g1 { }
+            ex:a ex:p ex:g .
+            "#,
+        )
+        .expect("ok");
+
+        let g1 = Some(Entity::from("http://example.com/g1"));
+        let g2 = Some(Entity::from("http://example.com/g2"));
+        assert_eq!(statements.len(), 8);
+        assert_eq!(
+            statements[..5],
+            [
+                (
+                    triple(
+                        "http://example.com/a",
+                        "http://example.com/p",
+                        "http://example.com/b"
+                    ),
+                    None
+                ),
+                (
+                    triple(
+                        "http://example.com/a",
+                        "http://example.com/p",
+                        "http://example.com/c"
+                    ),
+                    None
+                ),
+                (
+                    triple(
+                        "http://example.com/a",
+                        "http://example.com/p",
+                        "http://example.com/d"
+                    ),
+                    g1.clone()
+                ),
+                (
+                    triple(
+                        "http://example.com/a",
+                        "http://example.com/q",
+                        "http://example.com/e"
+                    ),
+                    g1.clone()
+                ),
+                (
+                    triple(
+                        "http://example.com/f",
+                        "http://example.com/p",
+                        "http://example.com/a"
+                    ),
+                    g1
+                ),
+            ]
+        );
+        assert!(statements[5..7].iter().all(|(_, graph)| *graph == g2));
+        assert_eq!(
+            statements[7],
+            (
+                triple(
+                    "http://example.com/a",
+                    "http://example.com/p",
+                    "http://example.com/g"
+                ),
+                None
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_blank_node_graphs() {
+        let statements = parse_all(
+            "_:g { <http://a> <http://p> <http://b> }\n[] { <http://a> <http://p> <http://c> }\n[ <http://p> <http://d> ] .\n",
+        )
+        .expect("ok");
+        assert_eq!(statements.len(), 3);
+        assert!(matches!(statements[0].1, Some(Entity::BlankNode(_))));
+        assert!(matches!(statements[1].1, Some(Entity::BlankNode(_))));
+        assert_ne!(statements[0].1, statements[1].1);
+        assert_eq!(statements[2].1, None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        // Graphs may not be nested or labelled with a literal.
+        for input in [
+            "<http://g> { <http://g2> { <http://a> <http://p> <http://b> } }",
+            "GRAPH \"g\" { <http://a> <http://p> <http://b> }",
+            "<http://g> { <http://a> <http://p> <http://b> ",
+            "<http://g> { <http://a> <http://p> }",
+        ] {
+            assert!(parse_all(input).is_err(), "{input}");
+        }
+
+        match parse_all("<http://g> {\n  <http://a> <http://p> <http://b> <http://c> }") {
+            Err(ParseError::Syntax { line, column, .. }) => {
+                assert_eq!((line, column), (2, 36));
+            }
+            r => panic!("expected a syntax error, got {:?}", r),
+        }
+    }
+
+    #[test]
+    fn test_load_and_write() {
+        let input = r#"
+            @prefix ex: <http://example.com/> .
+            ex:a ex:knows ex:b .
+            ex:g1 { ex:a ex:knows ex:b, ex:c ; ex:name "A"@en . }
+            ex:g2 { ex:b ex:knows ex:a }
+        "#;
+        let mut db = MemRdfTripleStore::<(), String>::new_mem();
+        assert_eq!(
+            super::load_with(input.as_bytes(), &mut db, |_, graph| graph
+                .map(Entity::to_string)
+                .unwrap_or_default())
+            .expect("ok"),
+            5
+        );
+
+        let prefixes = BTreeMap::from([("ex".to_string(), "http://example.com/".to_string())]);
+        let mut output = Vec::new();
+        super::write(&db, &mut output, &prefixes).expect("ok");
+        let output = String::from_utf8(output).expect("utf8");
+
+        assert!(
+            output.starts_with("@prefix ex: <http://example.com/> .\n\nex:a ex:knows ex:b .\n\n")
+        );
+        assert!(output.contains("ex:g2 {\n    ex:b ex:knows ex:a .\n}\n"));
+        assert!(output.contains("\n    ex:a "));
+        assert!(output.contains(" ;\n        ex:"));
+
+        // The output can be read back to the same graphs.
+        let mut reloaded = MemRdfTripleStore::<(), String>::new_mem();
+        super::load_with(output.as_bytes(), &mut reloaded, |_, graph| {
+            graph.map(Entity::to_string).unwrap_or_default()
+        })
+        .expect("ok");
+        let edges = |db: &MemRdfTripleStore<(), String>| {
+            db.iter_edges(EdgeOrder::SPO)
+                .map(|r| r.expect("ok").0)
+                .collect::<HashSet<_>>()
+        };
+        let quads = |db: &MemRdfTripleStore<(), String>| {
+            db.iter_quads(QuadOrder::GSPO)
+                .map(|r| r.expect("ok"))
+                .collect::<HashSet<_>>()
+        };
+        assert_eq!(edges(&db), edges(&reloaded));
+        assert_eq!(quads(&db), quads(&reloaded));
+        assert_eq!(quads(&db).len(), 4);
+    }
+
+    #[test]
+    fn test_write_named_graphs_only() {
+        let mut db = MemRdfTripleStore::<(), ()>::new_mem();
+        super::load(
+            "<http://g> { <http://a> <http://p> <http://b> }".as_bytes(),
+            &mut db,
+        )
+        .expect("ok");
+
+        let mut output = Vec::new();
+        super::write(&db, &mut output, &BTreeMap::new()).expect("ok");
+        assert_eq!(
+            String::from_utf8(output).expect("utf8"),
+            "<http://g> {\n    <http://a> <http://p> <http://b> .\n}\n"
+        );
+    }
+}
@@ -0,0 +1,1336 @@
+//! Reading and writing [Turtle](https://www.w3.org/TR/turtle/).
+//!
+//! The parser supports the full Turtle grammar: `@prefix`/`@base` (and the SPARQL-style `PREFIX`/`BASE`), the `;` and
+//! `,` shorthands, `a`, collections, blank node property lists, and string, numeric and boolean literals. As with
+//! [ntriples], IRIs are mapped to [Entity::String], literals to [Entity::Literal] and blank nodes to
+//! [Entity::BlankNode].
+//!
+//! The writer groups edges by subject and shortens IRIs using a caller-supplied prefix map.
+//!
+//! # Example
+//! ```
+//! # use std::collections::BTreeMap;
+//! # use simple_triplestore::{prelude::*, rdf::{turtle, MemRdfTripleStore}};
+//! let mut db = MemRdfTripleStore::<(), ()>::new_mem();
+//!
+//! turtle::load(
+//!     r#"
+//!     @prefix ex: <http://example.com/> .
+//!     ex:alice ex:knows ex:bob, ex:carol .
+//!     "#
+//!     .as_bytes(),
+//!     &mut db,
+//! )?;
+//!
+//! let prefixes = BTreeMap::from([("ex".to_string(), "http://example.com/".to_string())]);
+//! let mut output = Vec::new();
+//! turtle::write(&db, &mut output, &prefixes)?;
+//!
+//! let output = String::from_utf8(output).unwrap();
+//! assert!(output.starts_with("@prefix ex: <http://example.com/> .\n\nex:alice ex:knows "));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    io::{Read, Write},
+};
+
+use ulid::Ulid;
+
+use super::{
+    ntriples::{self, escape_iri, escape_string, is_iri_char, literal_entity, Statement},
+    vocab::{rdf, xsd},
+    Entity,
+};
+use crate::{
+    traits::{Property, TripleStoreInsert, TripleStoreIter},
+    EdgeOrder, Triple,
+};
+
+pub use super::ntriples::{LoadError, ParseError, WriteError};
+
+/// A Turtle parser which yields the [Triple]s of a document in order.
+pub struct TurtleParser {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+    column: usize,
+    base: Option<String>,
+    prefixes: HashMap<String, String>,
+    blank_nodes: HashMap<String, Ulid>,
+    graph: Option<Entity>,
+    pending: VecDeque<Statement>,
+    failed: bool,
+}
+
+impl TurtleParser {
+    pub fn new(input: &str) -> Self {
+        Self {
+            chars: input.chars().collect(),
+            pos: 0,
+            line: 1,
+            column: 1,
+            base: None,
+            prefixes: HashMap::new(),
+            blank_nodes: HashMap::new(),
+            graph: None,
+            pending: VecDeque::new(),
+            failed: false,
+        }
+    }
+
+    /// Resolve relative IRIs against `base` until the document declares its own.
+    pub fn with_base(mut self, base: impl Into<String>) -> Self {
+        self.base = Some(base.into());
+        self
+    }
+
+    /// The prefixes declared so far, mapping prefix name to namespace IRI.
+    pub fn prefixes(&self) -> &HashMap<String, String> {
+        &self.prefixes
+    }
+
+    pub(crate) fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::Syntax {
+            line: self.line,
+            column: self.column,
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn peek(&self) -> Option<char> {
+        self.peek_at(0)
+    }
+
+    pub(crate) fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    pub(crate) fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    pub(crate) fn expect(&mut self, expected: char) -> Result<(), ParseError> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.bump();
+                Ok(())
+            }
+            Some(c) => Err(self.error(format!("expected '{}' but found '{}'", expected, c))),
+            None => Err(self.error(format!("expected '{}' but found end of input", expected))),
+        }
+    }
+
+    /// Skip whitespace and comments.
+    pub(crate) fn skip_ws(&mut self) {
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() {
+                self.bump();
+            } else if c == '#' {
+                while !matches!(self.peek(), None | Some('\n')) {
+                    self.bump();
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Whether `keyword` is next in the input as a whole word.
+    pub(crate) fn at_keyword(&self, keyword: &str, ignore_case: bool) -> bool {
+        let len = keyword.chars().count();
+        keyword.chars().enumerate().all(|(i, k)| {
+            self.peek_at(i)
+                .is_some_and(|c| c == k || (ignore_case && c.eq_ignore_ascii_case(&k)))
+        }) && !self
+            .peek_at(len)
+            .is_some_and(|c| is_pn_chars(c) || c == ':')
+    }
+
+    fn emit(&mut self, sub: Entity, pred: Entity, obj: Entity) {
+        self.pending
+            .push_back((Triple { sub, pred, obj }, self.graph.clone()));
+    }
+
+    /// Set the graph which the triples parsed from here on belong to.
+    pub(crate) fn set_graph(&mut self, graph: Option<Entity>) {
+        self.graph = graph;
+    }
+
+    /// Parse statements with `statement` until a triple is ready, and yield it along with its graph.
+    pub(crate) fn next_with(
+        &mut self,
+        mut statement: impl FnMut(&mut Self) -> Result<(), ParseError>,
+    ) -> Option<Result<Statement, ParseError>> {
+        loop {
+            if let Some(triple) = self.pending.pop_front() {
+                return Some(Ok(triple));
+            }
+            if self.failed {
+                return None;
+            }
+
+            self.skip_ws();
+            self.peek()?;
+
+            if let Err(e) = statement(self) {
+                self.failed = true;
+                self.pending.clear();
+                return Some(Err(e));
+            }
+        }
+    }
+
+    pub(crate) fn fresh_blank_node(&mut self) -> Entity {
+        Entity::blank_node()
+    }
+
+    fn labelled_blank_node(&mut self, label: String) -> Entity {
+        if let Some(id) = self.blank_nodes.get(&label) {
+            return Entity::BlankNode(*id);
+        }
+        let id = Ulid::new();
+        self.blank_nodes.insert(label, id);
+        Entity::BlankNode(id)
+    }
+
+    /// Parse a directive, if one is next in the input.
+    ///
+    /// Returns whether a directive was parsed.
+    pub(crate) fn directive(&mut self) -> Result<bool, ParseError> {
+        if self.peek() == Some('@') {
+            self.bump();
+            if self.at_keyword("prefix", false) {
+                self.pos_advance(6);
+                self.prefix_body()?;
+            } else if self.at_keyword("base", false) {
+                self.pos_advance(4);
+                self.base_body()?;
+            } else {
+                return Err(self.error("expected '@prefix' or '@base'"));
+            }
+            self.skip_ws();
+            self.expect('.')?;
+            Ok(true)
+        } else if self.at_keyword("PREFIX", true) {
+            self.pos_advance(6);
+            self.prefix_body()?;
+            Ok(true)
+        } else if self.at_keyword("BASE", true) {
+            self.pos_advance(4);
+            self.base_body()?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    pub(crate) fn pos_advance(&mut self, n: usize) {
+        for _ in 0..n {
+            self.bump();
+        }
+    }
+
+    pub(crate) fn prefix_body(&mut self) -> Result<(), ParseError> {
+        self.skip_ws();
+        let prefix = self.pn_prefix()?;
+        self.expect(':')?;
+        self.skip_ws();
+        let namespace = self.iriref()?;
+        self.prefixes.insert(prefix, namespace);
+        Ok(())
+    }
+
+    pub(crate) fn base_body(&mut self) -> Result<(), ParseError> {
+        self.skip_ws();
+        self.base = Some(self.iriref()?);
+        Ok(())
+    }
+
+    /// Parse a directive or `triples '.'`.
+    fn statement(&mut self) -> Result<(), ParseError> {
+        if self.directive()? {
+            Ok(())
+        } else {
+            self.triples_statement()
+        }
+    }
+
+    /// Parse `triples '.'`.
+    pub(crate) fn triples_statement(&mut self) -> Result<(), ParseError> {
+        self.triples()?;
+        self.skip_ws();
+        self.expect('.')
+    }
+
+    /// Parse the `triples` production, without the trailing '.'.
+    pub(crate) fn triples(&mut self) -> Result<(), ParseError> {
+        if self.peek() == Some('[') {
+            let sub = self.blank_node_property_list()?;
+            self.skip_ws();
+            if !matches!(self.peek(), Some('.' | '}') | None) {
+                self.predicate_object_list(&sub)?;
+            }
+        } else {
+            let sub = self.subject()?;
+            self.skip_ws();
+            self.predicate_object_list(&sub)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn subject(&mut self) -> Result<Entity, ParseError> {
+        match self.peek() {
+            Some('_') if self.peek_at(1) == Some(':') => self.blank_node_label(),
+            Some('(') => self.collection(),
+            Some('<') => Ok(Entity::String(self.iriref()?)),
+            Some(c) if c == ':' || is_pn_chars_base(c) => Ok(Entity::String(self.prefixed_name()?)),
+            _ => Err(self.error("expected a subject")),
+        }
+    }
+
+    pub(crate) fn predicate_object_list(&mut self, sub: &Entity) -> Result<(), ParseError> {
+        loop {
+            let pred = self.verb()?;
+            self.skip_ws();
+            self.object_list(sub, &pred)?;
+            self.skip_ws();
+
+            if self.peek() != Some(';') {
+                return Ok(());
+            }
+            while self.peek() == Some(';') {
+                self.bump();
+                self.skip_ws();
+            }
+            if matches!(self.peek(), Some('.' | ']' | '}') | None) {
+                return Ok(());
+            }
+        }
+    }
+
+    fn verb(&mut self) -> Result<Entity, ParseError> {
+        if self.at_keyword("a", false) {
+            self.bump();
+            return Ok(Entity::from(rdf::TYPE));
+        }
+        match self.peek() {
+            Some('<') => Ok(Entity::String(self.iriref()?)),
+            Some(c) if c == ':' || is_pn_chars_base(c) => Ok(Entity::String(self.prefixed_name()?)),
+            _ => Err(self.error("expected a predicate")),
+        }
+    }
+
+    fn object_list(&mut self, sub: &Entity, pred: &Entity) -> Result<(), ParseError> {
+        loop {
+            let obj = self.object()?;
+            self.emit(sub.clone(), pred.clone(), obj);
+            self.skip_ws();
+            if self.peek() == Some(',') {
+                self.bump();
+                self.skip_ws();
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Entity, ParseError> {
+        match self.peek() {
+            Some('<') => Ok(Entity::String(self.iriref()?)),
+            Some('_') if self.peek_at(1) == Some(':') => self.blank_node_label(),
+            Some('(') => self.collection(),
+            Some('[') => self.blank_node_property_list(),
+            Some('"' | '\'') => self.rdf_literal(),
+            Some(c) if c.is_ascii_digit() || matches!(c, '+' | '-' | '.') => self.numeric_literal(),
+            Some('t') if self.at_keyword("true", false) => {
+                self.pos_advance(4);
+                Ok(literal_entity(
+                    "true".to_string(),
+                    Some(xsd::BOOLEAN.to_string()),
+                    None,
+                ))
+            }
+            Some('f') if self.at_keyword("false", false) => {
+                self.pos_advance(5);
+                Ok(literal_entity(
+                    "false".to_string(),
+                    Some(xsd::BOOLEAN.to_string()),
+                    None,
+                ))
+            }
+            Some(c) if c == ':' || is_pn_chars_base(c) => Ok(Entity::String(self.prefixed_name()?)),
+            _ => Err(self.error("expected an object")),
+        }
+    }
+
+    fn blank_node_property_list(&mut self) -> Result<Entity, ParseError> {
+        self.expect('[')?;
+        self.skip_ws();
+        let node = self.fresh_blank_node();
+        if self.peek() != Some(']') {
+            self.predicate_object_list(&node)?;
+            self.skip_ws();
+        }
+        self.expect(']')?;
+        Ok(node)
+    }
+
+    fn collection(&mut self) -> Result<Entity, ParseError> {
+        self.expect('(')?;
+        self.skip_ws();
+
+        let mut items = Vec::new();
+        while self.peek() != Some(')') {
+            if self.peek().is_none() {
+                return Err(self.error("unterminated collection"));
+            }
+            items.push(self.object()?);
+            self.skip_ws();
+        }
+        self.bump();
+
+        let mut head = Entity::from(rdf::NIL);
+        for item in items.into_iter().rev() {
+            let node = self.fresh_blank_node();
+            self.emit(node.clone(), Entity::from(rdf::FIRST), item);
+            self.emit(node.clone(), Entity::from(rdf::REST), head);
+            head = node;
+        }
+        Ok(head)
+    }
+
+    pub(crate) fn iri(&mut self) -> Result<String, ParseError> {
+        match self.peek() {
+            Some('<') => self.iriref(),
+            _ => self.prefixed_name(),
+        }
+    }
+
+    pub(crate) fn iriref(&mut self) -> Result<String, ParseError> {
+        self.expect('<')?;
+        let mut iri = String::new();
+        loop {
+            match self.peek() {
+                Some('>') => {
+                    self.bump();
+                    break;
+                }
+                Some('\\') => {
+                    self.bump();
+                    iri.push(self.uchar()?);
+                }
+                Some(c) if is_iri_char(c) => {
+                    self.bump();
+                    iri.push(c);
+                }
+                Some(c) => return Err(self.error(format!("invalid character {:?} in IRI", c))),
+                None => return Err(self.error("unterminated IRI")),
+            }
+        }
+        Ok(match &self.base {
+            Some(base) => resolve_iri(base, &iri),
+            None => iri,
+        })
+    }
+
+    fn uchar(&mut self) -> Result<char, ParseError> {
+        let digits = match self.peek() {
+            Some('u') => 4,
+            Some('U') => 8,
+            _ => return Err(self.error("expected \\u or \\U escape")),
+        };
+        self.bump();
+        let mut value = 0u32;
+        for _ in 0..digits {
+            let digit = self
+                .peek()
+                .and_then(|c| c.to_digit(16))
+                .ok_or_else(|| self.error("expected a hexadecimal digit"))?;
+            value = value * 16 + digit;
+            self.bump();
+        }
+        char::from_u32(value).ok_or_else(|| self.error(format!("invalid code point U+{:X}", value)))
+    }
+
+    fn pn_prefix(&mut self) -> Result<String, ParseError> {
+        let mut prefix = String::new();
+        match self.peek() {
+            Some(':') => return Ok(prefix),
+            Some(c) if is_pn_chars_base(c) => {
+                self.bump();
+                prefix.push(c);
+            }
+            _ => return Err(self.error("expected a prefix name")),
+        }
+        self.name_tail(&mut prefix, false)?;
+        Ok(prefix)
+    }
+
+    /// Consume `(PN_CHARS | '.')* PN_CHARS`, never ending on a '.'.
+    pub(crate) fn name_tail(&mut self, name: &mut String, local: bool) -> Result<(), ParseError> {
+        loop {
+            match self.peek() {
+                Some('.') if self.continues_name_after_dots(local) => {
+                    self.bump();
+                    name.push('.');
+                }
+                Some('.') => return Ok(()),
+                Some(c) if local && c == ':' => {
+                    self.bump();
+                    name.push(c);
+                }
+                Some('%') if local => self.percent(name)?,
+                Some('\\') if local => self.local_escape(name)?,
+                Some(c) if is_pn_chars(c) => {
+                    self.bump();
+                    name.push(c);
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// Whether the run of '.'s at the current position is followed by more of the name.
+    fn continues_name_after_dots(&self, local: bool) -> bool {
+        let mut offset = 0;
+        while self.peek_at(offset) == Some('.') {
+            offset += 1;
+        }
+        self.peek_at(offset)
+            .is_some_and(|c| is_pn_chars(c) || (local && matches!(c, ':' | '%' | '\\')))
+    }
+
+    fn percent(&mut self, name: &mut String) -> Result<(), ParseError> {
+        self.expect('%')?;
+        name.push('%');
+        for _ in 0..2 {
+            match self.peek() {
+                Some(c) if c.is_ascii_hexdigit() => {
+                    self.bump();
+                    name.push(c);
+                }
+                _ => return Err(self.error("expected a hexadecimal digit")),
+            }
+        }
+        Ok(())
+    }
+
+    fn local_escape(&mut self, name: &mut String) -> Result<(), ParseError> {
+        self.expect('\\')?;
+        match self.peek() {
+            Some(c) if "_~.-!$&'()*+,;=/?#@%".contains(c) => {
+                self.bump();
+                name.push(c);
+                Ok(())
+            }
+            _ => Err(self.error("invalid escape in local name")),
+        }
+    }
+
+    pub(crate) fn prefixed_name(&mut self) -> Result<String, ParseError> {
+        let (line, column) = (self.line, self.column);
+        let prefix = self.pn_prefix()?;
+        self.expect(':')?;
+
+        let mut local = String::new();
+        match self.peek() {
+            Some(c) if is_pn_chars_u(c) || c == ':' || c.is_ascii_digit() => {
+                self.bump();
+                local.push(c);
+                self.name_tail(&mut local, true)?;
+            }
+            Some('%') => {
+                self.percent(&mut local)?;
+                self.name_tail(&mut local, true)?;
+            }
+            Some('\\') => {
+                self.local_escape(&mut local)?;
+                self.name_tail(&mut local, true)?;
+            }
+            _ => {}
+        }
+
+        match self.prefixes.get(&prefix) {
+            Some(namespace) => Ok(format!("{}{}", namespace, local)),
+            None => Err(ParseError::Syntax {
+                line,
+                column,
+                message: format!("undefined prefix '{}:'", prefix),
+            }),
+        }
+    }
+
+    fn blank_node_label(&mut self) -> Result<Entity, ParseError> {
+        self.expect('_')?;
+        self.expect(':')?;
+        let mut label = String::new();
+        match self.peek() {
+            Some(c) if is_pn_chars_u(c) || c.is_ascii_digit() => {
+                self.bump();
+                label.push(c);
+            }
+            _ => return Err(self.error("expected a blank node label")),
+        }
+        self.name_tail(&mut label, false)?;
+        Ok(self.labelled_blank_node(label))
+    }
+
+    pub(crate) fn rdf_literal(&mut self) -> Result<Entity, ParseError> {
+        let lexical = self.string()?;
+        match self.peek() {
+            Some('@') => {
+                self.bump();
+                let lang = self.lang_tag()?;
+                Ok(literal_entity(lexical, None, Some(lang)))
+            }
+            Some('^') => {
+                self.bump();
+                self.expect('^')?;
+                let datatype = self.iri()?;
+                Ok(literal_entity(lexical, Some(datatype), None))
+            }
+            _ => Ok(literal_entity(lexical, None, None)),
+        }
+    }
+
+    pub(crate) fn string(&mut self) -> Result<String, ParseError> {
+        let quote = match self.peek() {
+            Some(c @ ('"' | '\'')) => c,
+            _ => return Err(self.error("expected a string")),
+        };
+        let long = self.peek_at(1) == Some(quote) && self.peek_at(2) == Some(quote);
+        self.pos_advance(if long { 3 } else { 1 });
+
+        let mut value = String::new();
+        loop {
+            match self.peek() {
+                None => return Err(self.error("unterminated string literal")),
+                Some(c) if c == quote => {
+                    self.bump();
+                    if !long {
+                        return Ok(value);
+                    }
+                    // A long string ends at the last of any run of three or more quotes.
+                    if self.peek() == Some(quote)
+                        && self.peek_at(1) == Some(quote)
+                        && self.peek_at(2) != Some(quote)
+                    {
+                        self.pos_advance(2);
+                        return Ok(value);
+                    }
+                    value.push(c);
+                }
+                Some('\\') => {
+                    self.bump();
+                    value.push(self.echar()?);
+                }
+                Some(c @ ('\n' | '\r')) if !long => {
+                    return Err(
+                        self.error(format!("unexpected {:?} in single-line string literal", c))
+                    )
+                }
+                Some(c) => {
+                    self.bump();
+                    value.push(c);
+                }
+            }
+        }
+    }
+
+    fn echar(&mut self) -> Result<char, ParseError> {
+        let escaped = match self.peek() {
+            Some('u' | 'U') => return self.uchar(),
+            Some('t') => '\t',
+            Some('b') => '\u{08}',
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('f') => '\u{0C}',
+            Some('"') => '"',
+            Some('\'') => '\'',
+            Some('\\') => '\\',
+            Some(c) => return Err(self.error(format!("invalid escape sequence '\\{}'", c))),
+            None => return Err(self.error("unterminated escape sequence")),
+        };
+        self.bump();
+        Ok(escaped)
+    }
+
+    fn lang_tag(&mut self) -> Result<String, ParseError> {
+        let mut tag = String::new();
+        while let Some(c) = self.peek().filter(|c| c.is_ascii_alphabetic()) {
+            self.bump();
+            tag.push(c);
+        }
+        if tag.is_empty() {
+            return Err(self.error("expected a language tag"));
+        }
+        while self.peek() == Some('-') && self.peek_at(1).is_some_and(|c| c.is_ascii_alphanumeric())
+        {
+            self.bump();
+            tag.push('-');
+            while let Some(c) = self.peek().filter(|c| c.is_ascii_alphanumeric()) {
+                self.bump();
+                tag.push(c);
+            }
+        }
+        Ok(tag)
+    }
+
+    pub(crate) fn numeric_literal(&mut self) -> Result<Entity, ParseError> {
+        let mut lexical = String::new();
+        if let Some(c @ ('+' | '-')) = self.peek() {
+            self.bump();
+            lexical.push(c);
+        }
+
+        let mut digits = self.digits(&mut lexical);
+        let mut datatype = xsd::INTEGER;
+        if self.peek() == Some('.') && self.peek_at(1).is_some_and(|c| c.is_ascii_digit()) {
+            self.bump();
+            lexical.push('.');
+            digits += self.digits(&mut lexical);
+            datatype = xsd::DECIMAL;
+        }
+        if digits == 0 {
+            return Err(self.error("expected a number"));
+        }
+        if let Some(c @ ('e' | 'E')) = self.peek() {
+            self.bump();
+            lexical.push(c);
+            if let Some(c @ ('+' | '-')) = self.peek() {
+                self.bump();
+                lexical.push(c);
+            }
+            if self.digits(&mut lexical) == 0 {
+                return Err(self.error("expected an exponent"));
+            }
+            datatype = xsd::DOUBLE;
+        }
+
+        Ok(literal_entity(lexical, Some(datatype.to_string()), None))
+    }
+
+    fn digits(&mut self, out: &mut String) -> usize {
+        let mut count = 0;
+        while let Some(c) = self.peek().filter(|c| c.is_ascii_digit()) {
+            self.bump();
+            out.push(c);
+            count += 1;
+        }
+        count
+    }
+}
+
+impl Iterator for TurtleParser {
+    type Item = Result<Triple<Entity>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.next_with(Self::statement)?.map(|(triple, _)| triple))
+    }
+}
+
+/// Parse a Turtle document.
+pub fn parse(input: &str) -> TurtleParser {
+    TurtleParser::new(input)
+}
+
+/// Load a Turtle document into `db`, using `EdgeProps::default()` for every edge.
+///
+/// Returns the number of triples inserted.
+pub fn load<
+    R: Read,
+    NodeProps: Property,
+    EdgeProps: Property + Default,
+    T: TripleStoreInsert<Entity, NodeProps, EdgeProps>,
+>(
+    reader: R,
+    db: &mut T,
+) -> Result<usize, LoadError<T::Error>> {
+    load_with(reader, db, |_| EdgeProps::default())
+}
+
+/// Load a Turtle document into `db`, computing the properties of each edge with `edge_props`.
+///
+/// Returns the number of triples inserted.
+pub fn load_with<
+    R: Read,
+    NodeProps: Property,
+    EdgeProps: Property,
+    T: TripleStoreInsert<Entity, NodeProps, EdgeProps>,
+>(
+    mut reader: R,
+    db: &mut T,
+    mut edge_props: impl FnMut(&Triple<Entity>) -> EdgeProps,
+) -> Result<usize, LoadError<T::Error>> {
+    let mut input = String::new();
+    reader
+        .read_to_string(&mut input)
+        .map_err(|e| LoadError::Parse(ParseError::Io(e)))?;
+
+    let mut count = 0;
+    for r in parse(&input) {
+        let triple = r.map_err(LoadError::Parse)?;
+        let props = edge_props(&triple);
+        db.insert_edge(triple, props).map_err(LoadError::Store)?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Write every edge in `db` to `writer` as Turtle.
+///
+/// `prefixes` maps prefix names to namespace IRIs. Each prefix is declared at the top of the document and IRIs which
+/// fall within a namespace are written as prefixed names.
+pub fn write<
+    W: Write,
+    NodeProps: Property,
+    EdgeProps: Property,
+    T: TripleStoreIter<Entity, NodeProps, EdgeProps>,
+>(
+    db: &T,
+    mut writer: W,
+    prefixes: &BTreeMap<String, String>,
+) -> Result<(), WriteError<T::Error>> {
+    write_prefixes(&mut writer, prefixes).map_err(WriteError::Io)?;
+
+    let mut triples = TriplesWriter::new(prefixes, "");
+    for r in db.iter_edges(EdgeOrder::SPO) {
+        let (triple, _) = r.map_err(WriteError::Store)?;
+        let triple = ntriples::check_triple(triple)?;
+        triples.write(&mut writer, triple).map_err(WriteError::Io)?;
+    }
+    triples.finish(&mut writer).map_err(WriteError::Io)?;
+
+    writer.flush().map_err(WriteError::Io)
+}
+
+/// Declare each of `prefixes`, followed by a blank line if there are any.
+pub(crate) fn write_prefixes(
+    writer: &mut impl Write,
+    prefixes: &BTreeMap<String, String>,
+) -> std::io::Result<()> {
+    for (prefix, namespace) in prefixes {
+        let mut iri = String::new();
+        escape_iri(&mut iri, namespace);
+        writer.write_fmt(format_args!("@prefix {}: <{}> .\n", prefix, iri))?;
+    }
+    if !prefixes.is_empty() {
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+/// Writes a run of triples in subject order, grouping those which share a subject into one statement.
+pub(crate) struct TriplesWriter<'a> {
+    prefixes: &'a BTreeMap<String, String>,
+    indent: &'a str,
+    current: Option<(Entity, Entity)>,
+}
+
+impl<'a> TriplesWriter<'a> {
+    /// Each statement is indented by `indent`.
+    pub(crate) fn new(prefixes: &'a BTreeMap<String, String>, indent: &'a str) -> Self {
+        Self {
+            prefixes,
+            indent,
+            current: None,
+        }
+    }
+
+    pub(crate) fn write(
+        &mut self,
+        writer: &mut impl Write,
+        triple: Triple<Entity>,
+    ) -> std::io::Result<()> {
+        let prefixes = self.prefixes;
+        let output = match &self.current {
+            Some((sub, pred)) if *sub == triple.sub && *pred == triple.pred => {
+                format!(", {}", format_term(&triple.obj, prefixes))
+            }
+            Some((sub, _)) if *sub == triple.sub => format!(
+                " ;\n{}    {} {}",
+                self.indent,
+                format_predicate(&triple.pred, prefixes),
+                format_term(&triple.obj, prefixes)
+            ),
+            _ => format!(
+                "{}{}{} {} {}",
+                if self.current.is_some() { " .\n" } else { "" },
+                self.indent,
+                format_term(&triple.sub, prefixes),
+                format_predicate(&triple.pred, prefixes),
+                format_term(&triple.obj, prefixes)
+            ),
+        };
+        writer.write_all(output.as_bytes())?;
+
+        self.current = Some((triple.sub, triple.pred));
+        Ok(())
+    }
+
+    /// End the last statement. Returns whether anything was written.
+    pub(crate) fn finish(self, writer: &mut impl Write) -> std::io::Result<bool> {
+        if self.current.is_some() {
+            writer.write_all(b" .\n")?;
+        }
+        Ok(self.current.is_some())
+    }
+}
+
+fn format_predicate(entity: &Entity, prefixes: &BTreeMap<String, String>) -> String {
+    match entity {
+        Entity::String(iri) if iri == rdf::TYPE => "a".to_string(),
+        _ => format_term(entity, prefixes),
+    }
+}
+
+pub(crate) fn format_term(entity: &Entity, prefixes: &BTreeMap<String, String>) -> String {
+    match entity {
+        Entity::String(iri) => format_iri(iri, prefixes),
+        Entity::Literal(literal) => match literal.datatype() {
+            Some(datatype) => {
+                let mut term = String::with_capacity(literal.lexical().len() + 2);
+                term.push('"');
+                escape_string(&mut term, literal.lexical());
+                term.push_str("\"^^");
+                term.push_str(&format_iri(datatype, prefixes));
+                term
+            }
+            None => ntriples::format_term(entity),
+        },
+        _ => ntriples::format_term(entity),
+    }
+}
+
+fn format_iri(iri: &str, prefixes: &BTreeMap<String, String>) -> String {
+    shorten_iri(iri, prefixes).unwrap_or_else(|| ntriples::format_iri(iri))
+}
+
+fn shorten_iri(iri: &str, prefixes: &BTreeMap<String, String>) -> Option<String> {
+    prefixes
+        .iter()
+        .filter(|(_, namespace)| iri.starts_with(namespace.as_str()))
+        .filter(|(_, namespace)| is_valid_local_name(&iri[namespace.len()..]))
+        .max_by_key(|(_, namespace)| namespace.len())
+        .map(|(prefix, namespace)| format!("{}:{}", prefix, &iri[namespace.len()..]))
+}
+
+fn is_valid_local_name(local: &str) -> bool {
+    let mut chars = local.chars();
+    match chars.next() {
+        None => return true,
+        Some(c) if is_pn_chars_u(c) || c == ':' || c.is_ascii_digit() => {}
+        Some(_) => return false,
+    }
+    !local.ends_with('.') && chars.all(|c| is_pn_chars(c) || c == '.' || c == ':')
+}
+
+/// Resolve `reference` against `base` following [RFC 3986](https://www.rfc-editor.org/rfc/rfc3986#section-5.2).
+pub(crate) fn resolve_iri(base: &str, reference: &str) -> String {
+    if has_scheme(reference) {
+        return reference.to_string();
+    }
+
+    let (scheme, base_rest) = base.split_once(':').unwrap_or(("", base));
+    let base_rest = base_rest.split('#').next().unwrap_or_default();
+    let (base_authority, base_path_and_query) = split_authority(base_rest);
+    let (base_path, base_query) = match base_path_and_query.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (base_path_and_query, None),
+    };
+
+    if reference.starts_with("//") {
+        return format!("{}:{}", scheme, reference);
+    }
+
+    let (reference, fragment) = match reference.split_once('#') {
+        Some((reference, fragment)) => (reference, Some(fragment)),
+        None => (reference, None),
+    };
+    let (reference_path, reference_query) = match reference.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (reference, None),
+    };
+
+    let (path, query) = if reference_path.is_empty() {
+        (base_path.to_string(), reference_query.or(base_query))
+    } else if reference_path.starts_with('/') {
+        (remove_dot_segments(reference_path), reference_query)
+    } else {
+        let merged = if base_authority.is_some() && base_path.is_empty() {
+            format!("/{}", reference_path)
+        } else {
+            match base_path.rfind('/') {
+                Some(i) => format!("{}{}", &base_path[..=i], reference_path),
+                None => reference_path.to_string(),
+            }
+        };
+        (remove_dot_segments(&merged), reference_query)
+    };
+
+    let mut result = format!("{}:", scheme);
+    if let Some(authority) = base_authority {
+        result.push_str("//");
+        result.push_str(authority);
+    }
+    result.push_str(&path);
+    if let Some(query) = query {
+        result.push('?');
+        result.push_str(query);
+    }
+    if let Some(fragment) = fragment {
+        result.push('#');
+        result.push_str(fragment);
+    }
+    result
+}
+
+fn has_scheme(iri: &str) -> bool {
+    match iri.find(':') {
+        Some(i) => {
+            let scheme = &iri[..i];
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        }
+        None => false,
+    }
+}
+
+fn split_authority(rest: &str) -> (Option<&str>, &str) {
+    match rest.strip_prefix("//") {
+        Some(rest) => {
+            let end = rest.find(['/', '?']).unwrap_or(rest.len());
+            (Some(&rest[..end]), &rest[end..])
+        }
+        None => (None, rest),
+    }
+}
+
+fn remove_dot_segments(path: &str) -> String {
+    let mut output: Vec<&str> = Vec::new();
+    let segments = path.split('/').collect::<Vec<_>>();
+    for (i, segment) in segments.iter().enumerate() {
+        let last = i == segments.len() - 1;
+        match *segment {
+            "." => {
+                if last {
+                    output.push("");
+                }
+            }
+            ".." => {
+                if output.len() > 1 {
+                    output.pop();
+                }
+                if last {
+                    output.push("");
+                }
+            }
+            segment => output.push(segment),
+        }
+    }
+    output.join("/")
+}
+
+pub(crate) fn is_pn_chars_base(c: char) -> bool {
+    c.is_alphabetic()
+}
+
+pub(crate) fn is_pn_chars_u(c: char) -> bool {
+    is_pn_chars_base(c) || c == '_'
+}
+
+pub(crate) fn is_pn_chars(c: char) -> bool {
+    is_pn_chars_u(c) || c.is_alphanumeric() || c == '-' || c == '\u{B7}'
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{BTreeMap, HashSet};
+
+    use crate::{
+        prelude::*,
+        rdf::{
+            vocab::{rdf, xsd},
+            Entity, MemRdfTripleStore,
+        },
+        EdgeOrder, Triple,
+    };
+
+    use super::ParseError;
+
+    fn parse_all(input: &str) -> Result<Vec<Triple<Entity>>, ParseError> {
+        super::parse(input).collect()
+    }
+
+    fn triple(sub: &str, pred: &str, obj: &str) -> Triple<Entity> {
+        Triple {
+            sub: sub.into(),
+            pred: pred.into(),
+            obj: obj.into(),
+        }
+    }
+
+    #[test]
+    fn test_parse_prefixes_and_shorthand() {
+        let triples = parse_all(
+            r#"
+            @prefix ex: <http://example.com/> .
+            PREFIX foaf: <http://xmlns.com/foaf/0.1/>
+            @prefix : <http://default.com/> .
+
+            ex:alice a foaf:Person ;
+                foaf:knows ex:bob, ex:carol ;
+                foaf:name "Alice" ;
+                .
+            :x :y :z . # comment
+            "#,
+        )
+        .expect("ok");
+
+        assert_eq!(
+            triples,
+            [
+                triple(
+                    "http://example.com/alice",
+                    rdf::TYPE,
+                    "http://xmlns.com/foaf/0.1/Person"
+                ),
+                triple(
+                    "http://example.com/alice",
+                    "http://xmlns.com/foaf/0.1/knows",
+                    "http://example.com/bob"
+                ),
+                triple(
+                    "http://example.com/alice",
+                    "http://xmlns.com/foaf/0.1/knows",
+                    "http://example.com/carol"
+                ),
+                Triple {
+                    sub: "http://example.com/alice".into(),
+                    pred: "http://xmlns.com/foaf/0.1/name".into(),
+                    obj: Entity::literal("Alice"),
+                },
+                triple(
+                    "http://default.com/x",
+                    "http://default.com/y",
+                    "http://default.com/z"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_base() {
+        let triples = parse_all(
+            r#"
+            @base <http://example.com/a/b> .
+            <c> <#p> <../d> .
+            BASE <http://other.com/>
+            </x> <?q> <//host/y> .
+            "#,
+        )
+        .expect("ok");
+
+        assert_eq!(
+            triples,
+            [
+                triple(
+                    "http://example.com/a/c",
+                    "http://example.com/a/b#p",
+                    "http://example.com/d"
+                ),
+                triple("http://other.com/x", "http://other.com/?q", "http://host/y"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_literals() {
+        let triples = parse_all(
+            r#"
+            @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+            <s> <p> 'single', """long "quoted"
+text""", "chat"@fr, "42"^^xsd:integer, 42, -1.5, 1e10, true, false, "esc\t\u00E9" .
+            "#,
+        )
+        .expect("ok");
+
+        assert_eq!(
+            triples.into_iter().map(|t| t.obj).collect::<Vec<_>>(),
+            [
+                Entity::literal("single"),
+                Entity::literal("long \"quoted\"\ntext"),
+                Entity::lang_literal("chat", "fr"),
+                Entity::typed_literal("42", xsd::INTEGER),
+                Entity::typed_literal("42", xsd::INTEGER),
+                Entity::typed_literal("-1.5", xsd::DECIMAL),
+                Entity::typed_literal("1e10", xsd::DOUBLE),
+                Entity::typed_literal("true", xsd::BOOLEAN),
+                Entity::typed_literal("false", xsd::BOOLEAN),
+                Entity::literal("esc\t\u{E9}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_blank_nodes() {
+        let triples = parse_all(
+            r#"
+            @prefix ex: <http://example.com/> .
+            _:a ex:knows [ ex:name "Bob" ; ex:knows _:a ] .
+            [ ex:name "Anon" ] .
+            [] ex:p ex:o .
+            "#,
+        )
+        .expect("ok");
+
+        assert_eq!(triples.len(), 5);
+        let a = triples[2].sub.clone();
+        let bob = triples[0].sub.clone();
+        assert!(matches!(a, Entity::BlankNode(_)));
+        assert!(matches!(bob, Entity::BlankNode(_)));
+        assert_eq!(triples[0].pred, Entity::from("http://example.com/name"));
+        assert_eq!(
+            triples[1],
+            Triple {
+                sub: bob.clone(),
+                pred: "http://example.com/knows".into(),
+                obj: a.clone()
+            }
+        );
+        assert_eq!(triples[2].obj, bob);
+        assert_ne!(triples[3].sub, triples[4].sub);
+    }
+
+    #[test]
+    fn test_parse_names() {
+        let triples = parse_all(
+            r#"
+            @prefix ex: <http://example.com/> .
+            ex:a..b ex:p%20q ex:c\,d.
+            _:x.y ex:p ex:v:1.
+            <s> <p> """a""""", ''.
+            "#,
+        )
+        .expect("ok");
+
+        assert_eq!(
+            triples[0],
+            triple(
+                "http://example.com/a..b",
+                "http://example.com/p%20q",
+                "http://example.com/c,d"
+            )
+        );
+        assert_eq!(triples[1].obj, Entity::from("http://example.com/v:1"));
+        assert_eq!(triples[2].obj, Entity::literal("a\"\""));
+        assert_eq!(triples[3].obj, Entity::literal(""));
+    }
+
+    #[test]
+    fn test_parse_collections() {
+        let triples = parse_all("<s> <p> ( <a> \"b\" ) . <s> <q> () .").expect("ok");
+
+        let edges = triples
+            .iter()
+            .map(|t| (t.sub.clone(), t.pred.clone(), t.obj.clone()))
+            .collect::<Vec<_>>();
+        let head = edges
+            .iter()
+            .find(|(s, p, _)| *s == Entity::from("s") && *p == Entity::from("p"))
+            .expect("head")
+            .2
+            .clone();
+        let first = |node: &Entity| {
+            edges
+                .iter()
+                .find(|(s, p, _)| s == node && *p == Entity::from(rdf::FIRST))
+                .map(|(_, _, o)| o.clone())
+        };
+        let rest = |node: &Entity| {
+            edges
+                .iter()
+                .find(|(s, p, _)| s == node && *p == Entity::from(rdf::REST))
+                .map(|(_, _, o)| o.clone())
+        };
+
+        assert_eq!(first(&head), Some(Entity::from("a")));
+        let second = rest(&head).expect("rest");
+        assert_eq!(first(&second), Some(Entity::literal("b")));
+        assert_eq!(rest(&second), Some(Entity::from(rdf::NIL)));
+        assert!(triples.contains(&triple("s", "q", rdf::NIL)));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        match parse_all("@prefix ex: <http://example.com/> .\nex:a ex:b\n  foo:c .") {
+            Err(ParseError::Syntax { line, column, .. }) => assert_eq!((line, column), (3, 3)),
+            r => panic!("expected a syntax error, got {:?}", r),
+        }
+
+        match parse_all("<a> <b> <c>\n<d> <e> <f> .") {
+            Err(ParseError::Syntax { line, column, .. }) => assert_eq!((line, column), (2, 1)),
+            r => panic!("expected a syntax error, got {:?}", r),
+        }
+
+        // Triples preceding an error are still produced.
+        let mut parser = super::parse("<a> <b> <c> . <a> <b> .");
+        assert!(matches!(parser.next(), Some(Ok(_))));
+        assert!(matches!(parser.next(), Some(Err(_))));
+        assert!(parser.next().is_none());
+    }
+
+    #[test]
+    fn test_write() {
+        let mut db = MemRdfTripleStore::<(), ()>::new_mem();
+        super::load(
+            r#"
+            @prefix ex: <http://example.com/> .
+            ex:alice a ex:Person ; ex:knows ex:bob, ex:carol ; ex:name "Alice"@en .
+            ex:bob ex:knows <http://other.com/x> .
+            "#
+            .as_bytes(),
+            &mut db,
+        )
+        .expect("ok");
+
+        let prefixes = BTreeMap::from([
+            ("ex".to_string(), "http://example.com/".to_string()),
+            ("rdf".to_string(), rdf::NAMESPACE.to_string()),
+        ]);
+        let mut output = Vec::new();
+        super::write(&db, &mut output, &prefixes).expect("ok");
+        let output = String::from_utf8(output).expect("utf8");
+
+        assert!(output.starts_with(
+            "@prefix ex: <http://example.com/> .\n@prefix rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#> .\n\n"
+        ));
+        // One statement per subject.
+        assert_eq!(output.lines().filter(|l| l.starts_with("ex:")).count(), 2);
+        assert!(output.contains("ex:bob ex:knows <http://other.com/x> .\n"));
+        assert!(output.contains("a ex:Person"));
+        assert!(output.contains("\"Alice\"@en"));
+
+        // The output can be read back to the same graph.
+        let mut reloaded = MemRdfTripleStore::<(), ()>::new_mem();
+        super::load(output.as_bytes(), &mut reloaded).expect("ok");
+        let edges = |db: &MemRdfTripleStore<(), ()>| {
+            db.iter_edges(EdgeOrder::SPO)
+                .map(|r| r.expect("ok").0)
+                .collect::<HashSet<_>>()
+        };
+        assert_eq!(edges(&db), edges(&reloaded));
+    }
+}
@@ -0,0 +1,576 @@
+//! Forward-chaining inference with Horn rules over triple patterns.
+//!
+//! A [Rule] derives the triples in its head for every match of its body, e.g. "if `?a` is a subclass of `?b` and `?x`
+//! has type `?a`, then `?x` has type `?b`". A [Reasoner] applies its rules repeatedly until nothing new can be derived,
+//! writing the inferred edges either back into the store ([materialize][Reasoner::materialize]) or into a separate
+//! overlay store ([materialize_into][Reasoner::materialize_into]).
+//!
+//! Evaluation is semi-naive: after the first round, a rule is only matched where at least one body pattern is satisfied
+//! by a triple inferred in the previous round, so each round only does work proportional to what is new.
+//!
+//! The props of each inferred edge are produced by a callback which is given the [Derivation], so provenance can be
+//! recorded alongside the edge.
+//!
+//! Rules are most easily created using the [query][crate::query] macro, and the RDFS entailment rules are available
+//! with [Reasoner::rdfs].
+//!
+//! # Example
+//! ```
+//! # use ulid::Ulid;
+//! # use simple_triplestore::{prelude::*, reasoner::Reasoner, MemTripleStore, Triple, UlidIdGenerator};
+//! let mut db = MemTripleStore::<Ulid, (), bool>::new(UlidIdGenerator::new());
+//!
+//! let (alice, bob, carol) = (Ulid(1), Ulid(2), Ulid(3));
+//! let (reports_to, manages) = (Ulid(10), Ulid(11));
+//!
+//! db.insert_edge(Triple { sub: alice, pred: reports_to, obj: bob }, false)?;
+//! db.insert_edge(Triple { sub: bob, pred: reports_to, obj: carol }, false)?;
+//!
+//! let reasoner = Reasoner::new()
+//!     .with_rule(query! { rule { ?x -[reports_to]-> ?y . ?y -[reports_to]-> ?z } => { ?x -[reports_to]-> ?z } })
+//!     .with_rule(query! { rule { ?x -[reports_to]-> ?y } => { ?y -[manages]-> ?x } });
+//!
+//! // Mark inferred edges with `true`.
+//! let inferred = reasoner.materialize(&mut db, |_| true)?;
+//! assert_eq!(inferred, 4);
+//! assert_eq!(db.get_edge(&Triple { sub: carol, pred: manages, obj: alice })?, Some(true));
+//! # Ok::<(), ()>(())
+//! ```
+
+use std::collections::HashSet;
+
+use crate::{
+    pattern::{Bindings, Pattern, Term},
+    prelude::*,
+    traits::{IdType, Property},
+    QueryError, Triple,
+};
+
+/// A Horn rule: every match of the `body` pattern implies the triples in the `head`.
+///
+/// Variables in the head are bound by the body. Head triples which use a variable that does not appear in the body are
+/// never derived.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule<Id: IdType> {
+    pub body: Pattern<Id>,
+    pub head: Pattern<Id>,
+}
+
+impl<Id: IdType> Rule<Id> {
+    pub fn new(body: Pattern<Id>, head: Pattern<Id>) -> Self {
+        Self { body, head }
+    }
+}
+
+/// The ids of the vocabulary used by the RDFS entailment rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RdfsVocab<Id: IdType> {
+    pub rdf_type: Id,
+    pub sub_class_of: Id,
+    pub sub_property_of: Id,
+    pub domain: Id,
+    pub range: Id,
+}
+
+#[cfg(feature = "rdf")]
+impl RdfsVocab<crate::Entity> {
+    /// The standard RDF and RDFS IRIs.
+    pub fn rdf() -> Self {
+        use crate::rdf::vocab::{rdf, rdfs};
+        Self {
+            rdf_type: rdf::TYPE.into(),
+            sub_class_of: rdfs::SUB_CLASS_OF.into(),
+            sub_property_of: rdfs::SUB_PROPERTY_OF.into(),
+            domain: rdfs::DOMAIN.into(),
+            range: rdfs::RANGE.into(),
+        }
+    }
+}
+
+/// Why an edge was inferred, passed to the callback which produces its props.
+#[derive(Debug)]
+pub struct Derivation<'a, Id: IdType> {
+    /// The rule which inferred the edge.
+    pub rule: &'a Rule<Id>,
+
+    /// The position of the rule in the [Reasoner].
+    pub rule_index: usize,
+
+    /// The inferred edge.
+    pub triple: &'a Triple<Id>,
+
+    /// The edges which matched the rule body, in the order of the body's triple patterns.
+    pub premises: &'a [Triple<Id>],
+}
+
+struct Inferred<Id: IdType> {
+    rule_index: usize,
+    triple: Triple<Id>,
+    premises: Vec<Triple<Id>>,
+}
+
+/// Looks up the stored triples which match a pattern, with `None` as a wildcard.
+type MatchTriples<'a, Id, E> =
+    dyn Fn(Option<Id>, Option<Id>, Option<Id>) -> Result<Vec<Triple<Id>>, E> + 'a;
+
+/// A partial match of a rule body: the variables bound so far, and the triple matched by each body pattern.
+type Solution<Id> = (Bindings<Id>, Vec<Option<Triple<Id>>>);
+
+/// A set of [Rule]s which can be evaluated to a fixpoint against a [TripleStore].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reasoner<Id: IdType> {
+    rules: Vec<Rule<Id>>,
+}
+
+impl<Id: IdType> Default for Reasoner<Id> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Id: IdType> Reasoner<Id> {
+    /// Construct a reasoner with no rules.
+    pub fn new() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Construct a reasoner with the RDFS entailment rules for domains, ranges, subproperties and subclasses (rdfs2,
+    /// rdfs3, rdfs5, rdfs7, rdfs9 and rdfs11).
+    pub fn rdfs(vocab: &RdfsVocab<Id>) -> Self {
+        let var = |name: &str| Term::<Id>::var(name);
+        let RdfsVocab {
+            rdf_type,
+            sub_class_of,
+            sub_property_of,
+            domain,
+            range,
+        } = vocab.clone();
+
+        Self {
+            rules: vec![
+                // rdfs2
+                Rule::new(
+                    Pattern::new().with(var("p"), domain, var("c")).with(
+                        var("x"),
+                        var("p"),
+                        var("y"),
+                    ),
+                    Pattern::new().with(var("x"), rdf_type.clone(), var("c")),
+                ),
+                // rdfs3
+                Rule::new(
+                    Pattern::new().with(var("p"), range, var("c")).with(
+                        var("x"),
+                        var("p"),
+                        var("y"),
+                    ),
+                    Pattern::new().with(var("y"), rdf_type.clone(), var("c")),
+                ),
+                // rdfs5
+                Rule::new(
+                    Pattern::new()
+                        .with(var("p"), sub_property_of.clone(), var("q"))
+                        .with(var("q"), sub_property_of.clone(), var("r")),
+                    Pattern::new().with(var("p"), sub_property_of.clone(), var("r")),
+                ),
+                // rdfs7
+                Rule::new(
+                    Pattern::new()
+                        .with(var("p"), sub_property_of, var("q"))
+                        .with(var("x"), var("p"), var("y")),
+                    Pattern::new().with(var("x"), var("q"), var("y")),
+                ),
+                // rdfs9
+                Rule::new(
+                    Pattern::new()
+                        .with(var("a"), sub_class_of.clone(), var("b"))
+                        .with(var("x"), rdf_type.clone(), var("a")),
+                    Pattern::new().with(var("x"), rdf_type, var("b")),
+                ),
+                // rdfs11
+                Rule::new(
+                    Pattern::new()
+                        .with(var("a"), sub_class_of.clone(), var("b"))
+                        .with(var("b"), sub_class_of.clone(), var("c")),
+                    Pattern::new().with(var("a"), sub_class_of, var("c")),
+                ),
+            ],
+        }
+    }
+
+    /// Add a rule.
+    pub fn with_rule(mut self, rule: Rule<Id>) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// The rules, in the order they were added.
+    pub fn rules(&self) -> &[Rule<Id>] {
+        &self.rules
+    }
+
+    /// Infer edges until a fixpoint is reached, inserting them into `db`.
+    ///
+    /// `props` is called once per inferred edge to produce its props. Returns the number of edges inferred.
+    pub fn materialize<NodeProps: Property, EdgeProps: Property, T>(
+        &self,
+        db: &mut T,
+        mut props: impl FnMut(&Derivation<Id>) -> EdgeProps,
+    ) -> Result<usize, T::Error>
+    where
+        T: TripleStorePattern<Id, NodeProps, EdgeProps>
+            + TripleStoreInsert<Id, NodeProps, EdgeProps>,
+    {
+        let mut delta = None;
+        let mut total = 0;
+
+        loop {
+            let inferred = self.derive(
+                &|sub, pred, obj| db.match_triples(sub, pred, obj).collect(),
+                delta.as_ref(),
+            )?;
+            if inferred.is_empty() {
+                return Ok(total);
+            }
+
+            let mut next = HashSet::new();
+            for inferred in inferred {
+                let edge_props = props(&self.derivation(&inferred));
+                db.insert_edge(inferred.triple.clone(), edge_props)?;
+                next.insert(inferred.triple);
+            }
+            total += next.len();
+            delta = Some(next);
+        }
+    }
+
+    /// Infer edges until a fixpoint is reached, inserting them into `overlay` and leaving `db` unchanged.
+    ///
+    /// Rules are matched against the union of both stores, and edges which already exist in either are not inferred
+    /// again. `props` is called once per inferred edge to produce its props. Returns the number of edges inferred.
+    pub fn materialize_into<NodeProps: Property, EdgeProps: Property, T, O>(
+        &self,
+        db: &T,
+        overlay: &mut O,
+        mut props: impl FnMut(&Derivation<Id>) -> EdgeProps,
+    ) -> Result<usize, QueryError<T::Error, O::Error>>
+    where
+        T: TripleStorePattern<Id, NodeProps, EdgeProps>,
+        O: TripleStorePattern<Id, NodeProps, EdgeProps>
+            + TripleStoreInsert<Id, NodeProps, EdgeProps>,
+    {
+        let mut delta = None;
+        let mut total = 0;
+
+        loop {
+            let inferred = self.derive(
+                &|sub, pred, obj| {
+                    let mut triples = db
+                        .match_triples(sub.clone(), pred.clone(), obj.clone())
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(QueryError::Left)?;
+                    for triple in overlay.match_triples(sub, pred, obj) {
+                        triples.push(triple.map_err(QueryError::Right)?);
+                    }
+                    Ok(triples)
+                },
+                delta.as_ref(),
+            )?;
+            if inferred.is_empty() {
+                return Ok(total);
+            }
+
+            let mut next = HashSet::new();
+            for inferred in inferred {
+                let edge_props = props(&self.derivation(&inferred));
+                overlay
+                    .insert_edge(inferred.triple.clone(), edge_props)
+                    .map_err(QueryError::Right)?;
+                next.insert(inferred.triple);
+            }
+            total += next.len();
+            delta = Some(next);
+        }
+    }
+
+    fn derivation<'a>(&'a self, inferred: &'a Inferred<Id>) -> Derivation<'a, Id> {
+        Derivation {
+            rule: &self.rules[inferred.rule_index],
+            rule_index: inferred.rule_index,
+            triple: &inferred.triple,
+            premises: &inferred.premises,
+        }
+    }
+
+    /// Run one round of evaluation, returning the triples which are derived but not yet stored.
+    ///
+    /// With no `delta` every rule is matched in full. Otherwise each body pattern in turn is matched against `delta`
+    /// and the rest of the body against the store, so only derivations which use a new triple are found.
+    fn derive<E>(
+        &self,
+        match_triples: &MatchTriples<Id, E>,
+        delta: Option<&HashSet<Triple<Id>>>,
+    ) -> Result<Vec<Inferred<Id>>, E> {
+        let mut seen = HashSet::new();
+        let mut inferred = Vec::new();
+
+        for (rule_index, rule) in self.rules.iter().enumerate() {
+            let atoms = &rule.body.triples;
+            let seeds = match delta {
+                None => vec![None],
+                Some(_) => (0..atoms.len()).map(Some).collect(),
+            };
+
+            for seed in seeds {
+                let mut solutions: Vec<Solution<Id>> = match (seed, delta) {
+                    (Some(i), Some(delta)) => delta
+                        .iter()
+                        .filter_map(|triple| {
+                            let bindings = atoms[i].bind(triple.clone(), &Bindings::new())?;
+                            let mut premises = vec![None; atoms.len()];
+                            premises[i] = Some(triple.clone());
+                            Some((bindings, premises))
+                        })
+                        .collect(),
+                    _ => vec![(Bindings::new(), vec![None; atoms.len()])],
+                };
+
+                for (j, atom) in atoms.iter().enumerate() {
+                    if solutions.is_empty() {
+                        break;
+                    }
+                    if seed == Some(j) {
+                        continue;
+                    }
+
+                    let mut next = Vec::new();
+                    for (bindings, premises) in solutions.iter() {
+                        let (sub, pred, obj) = atom.resolve(bindings);
+                        for triple in match_triples(sub, pred, obj)? {
+                            if let Some(extended) = atom.bind(triple.clone(), bindings) {
+                                let mut premises = premises.clone();
+                                premises[j] = Some(triple);
+                                next.push((extended, premises));
+                            }
+                        }
+                    }
+                    solutions = next;
+                }
+
+                for (bindings, premises) in solutions {
+                    for head in rule.head.triples.iter() {
+                        let (Some(sub), Some(pred), Some(obj)) = head.resolve(&bindings) else {
+                            continue;
+                        };
+                        let triple = Triple { sub, pred, obj };
+                        if !seen.insert(triple.clone()) {
+                            continue;
+                        }
+                        let existing = match_triples(
+                            Some(triple.sub.clone()),
+                            Some(triple.pred.clone()),
+                            Some(triple.obj.clone()),
+                        )?;
+                        if existing.is_empty() {
+                            inferred.push(Inferred {
+                                rule_index,
+                                triple,
+                                premises: premises.iter().flatten().cloned().collect(),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(inferred)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use ulid::Ulid;
+
+    use super::{RdfsVocab, Reasoner};
+    use crate::{prelude::*, EdgeOrder, MemTripleStore, Triple, UlidIdGenerator};
+
+    fn edges<T: TripleStore<Ulid, (), String>>(db: &T) -> HashSet<(Triple<Ulid>, String)> {
+        db.iter_edges(EdgeOrder::SPO)
+            .map(|r| r.expect("ok"))
+            .collect()
+    }
+
+    fn triple(sub: u128, pred: u128, obj: u128) -> Triple<Ulid> {
+        Triple {
+            sub: Ulid(sub),
+            pred: Ulid(pred),
+            obj: Ulid(obj),
+        }
+    }
+
+    const TYPE: u128 = 100;
+    const SUB_CLASS_OF: u128 = 101;
+    const SUB_PROPERTY_OF: u128 = 102;
+    const DOMAIN: u128 = 103;
+    const RANGE: u128 = 104;
+
+    fn vocab() -> RdfsVocab<Ulid> {
+        RdfsVocab {
+            rdf_type: Ulid(TYPE),
+            sub_class_of: Ulid(SUB_CLASS_OF),
+            sub_property_of: Ulid(SUB_PROPERTY_OF),
+            domain: Ulid(DOMAIN),
+            range: Ulid(RANGE),
+        }
+    }
+
+    fn test_rules<T: TripleStore<Ulid, (), String>>(mut db: T) {
+        let (ancestor, parent) = (Ulid(10), Ulid(11));
+
+        for (sub, obj) in [(1, 2), (2, 3), (3, 4), (4, 1)] {
+            db.insert_edge(triple(sub, 11, obj), "stated".into())
+                .expect("ok");
+        }
+
+        let reasoner = Reasoner::new()
+            .with_rule(query! { rule { ?x -[parent]-> ?y } => { ?x -[ancestor]-> ?y } })
+            .with_rule(query! { rule { ?x -[ancestor]-> ?y . ?y -[parent]-> ?z } => { ?x -[ancestor]-> ?z } });
+
+        // Record which rule produced each edge and from how many premises.
+        let inferred = reasoner
+            .materialize(&mut db, |d| {
+                format!("rule {} from {}", d.rule_index, d.premises.len())
+            })
+            .expect("ok");
+
+        // The cycle makes every node an ancestor of every node, including itself.
+        assert_eq!(inferred, 16);
+        let edges = edges(&db);
+        for sub in 1..=4 {
+            for obj in 1..=4 {
+                assert!(edges.iter().any(|(t, _)| *t == triple(sub, 10, obj)));
+            }
+        }
+        assert!(edges.contains(&(triple(1, 10, 2), "rule 0 from 1".into())));
+        assert!(edges.contains(&(triple(1, 10, 3), "rule 1 from 2".into())));
+        assert!(edges.contains(&(triple(1, 11, 2), "stated".into())));
+
+        // Reaching the fixpoint again infers nothing.
+        assert_eq!(
+            reasoner
+                .materialize(&mut db, |_| "again".into())
+                .expect("ok"),
+            0
+        );
+    }
+
+    fn test_rdfs<T: TripleStore<Ulid, (), String>>(mut db: T, overlay: T) {
+        let (cat, mammal, animal, tom) = (1, 2, 3, 4);
+        let (owns, has_pet, alice) = (20, 21, 22);
+        for t in [
+            triple(cat, SUB_CLASS_OF, mammal),
+            triple(mammal, SUB_CLASS_OF, animal),
+            triple(has_pet, SUB_PROPERTY_OF, owns),
+            triple(has_pet, RANGE, cat),
+            triple(owns, DOMAIN, 30),
+            triple(alice, has_pet, tom),
+        ] {
+            db.insert_edge(t, "stated".into()).expect("ok");
+        }
+        let stated = edges(&db);
+
+        let mut overlay = overlay;
+        let reasoner = Reasoner::rdfs(&vocab());
+        let inferred = reasoner
+            .materialize_into(&db, &mut overlay, |_| "inferred".into())
+            .expect("ok");
+
+        // The base store is untouched.
+        assert_eq!(edges(&db), stated);
+        assert_eq!(
+            edges(&overlay)
+                .into_iter()
+                .map(|(t, _)| t)
+                .collect::<HashSet<_>>(),
+            [
+                triple(cat, SUB_CLASS_OF, animal),
+                triple(alice, owns, tom),
+                triple(tom, TYPE, cat),
+                triple(tom, TYPE, mammal),
+                triple(tom, TYPE, animal),
+                triple(alice, TYPE, 30),
+            ]
+            .into()
+        );
+        assert_eq!(inferred, 6);
+
+        // Nothing is inferred twice, even across both stores.
+        assert_eq!(
+            reasoner
+                .materialize_into(&db, &mut overlay, |_| "again".into())
+                .expect("ok"),
+            0
+        );
+    }
+
+    #[test]
+    fn test_rules_mem() {
+        test_rules(MemTripleStore::new(UlidIdGenerator::new()));
+    }
+
+    #[test]
+    fn test_rdfs_mem() {
+        test_rdfs(
+            MemTripleStore::new(UlidIdGenerator::new()),
+            MemTripleStore::new(UlidIdGenerator::new()),
+        );
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_rules_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        test_rules(crate::SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok"));
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_rdfs_sled() {
+        let (_left_tempdir, left) = crate::sled::create_test_db().expect("ok");
+        let (_right_tempdir, right) = crate::sled::create_test_db().expect("ok");
+        test_rdfs(
+            crate::SledTripleStore::new(&left, UlidIdGenerator::new()).expect("ok"),
+            crate::SledTripleStore::new(&right, UlidIdGenerator::new()).expect("ok"),
+        );
+    }
+
+    #[cfg(feature = "rdf")]
+    #[test]
+    fn test_rdfs_entities() {
+        use crate::{
+            rdf::vocab::{rdf, rdfs},
+            Entity, MemRdfTripleStore,
+        };
+
+        let mut db = MemRdfTripleStore::<(), ()>::new_mem();
+        let edge = |sub: &str, pred: &str, obj: &str| Triple {
+            sub: Entity::from(sub),
+            pred: Entity::from(pred),
+            obj: Entity::from(obj),
+        };
+        db.insert_edge(edge("ex:Cat", rdfs::SUB_CLASS_OF, "ex:Animal"), ())
+            .expect("ok");
+        db.insert_edge(edge("ex:tom", rdf::TYPE, "ex:Cat"), ())
+            .expect("ok");
+
+        let inferred = Reasoner::rdfs(&RdfsVocab::rdf())
+            .materialize(&mut db, |_| ())
+            .expect("ok");
+        assert_eq!(inferred, 1);
+        assert!(db
+            .contains_edge(&edge("ex:tom", rdf::TYPE, "ex:Animal"))
+            .expect("ok"));
+    }
+}
@@ -5,17 +5,31 @@ use std::{
 
 use crate::{
     prelude::*,
-    traits::{ConcreteIdType, Property},
+    traits::{ConcreteIdType, IdGeneratorError, Property},
     IdGenerator,
 };
 use serde::{de::DeserializeOwned, Serialize};
 
+mod bulk_load;
+mod count;
 mod extend;
+mod get;
+mod id_generator;
+mod index;
 mod insert;
 mod iter;
 mod merge;
+mod namespace;
+mod paginate;
+mod pattern;
+mod quad;
 mod query;
 mod remove;
+mod stats;
+mod subscribe;
+mod transaction;
+mod verify;
+mod write;
 
 #[derive(Debug)]
 pub enum SledTripleStoreError {
@@ -23,6 +37,12 @@ pub enum SledTripleStoreError {
     SerializationError(bincode::Error),
     KeySizeError,
     MissingPropertyData,
+    IndexNotFound(String),
+    InvalidCursor,
+    InvalidNamespace(String),
+    NamespaceNotFound(String),
+    NamespaceExists(String),
+    IdGeneratorError(IdGeneratorError),
 }
 
 impl From<sled::Error> for SledTripleStoreError {
@@ -31,6 +51,12 @@ impl From<sled::Error> for SledTripleStoreError {
     }
 }
 
+impl From<IdGeneratorError> for SledTripleStoreError {
+    fn from(e: IdGeneratorError) -> Self {
+        SledTripleStoreError::IdGeneratorError(e)
+    }
+}
+
 impl From<bincode::Error> for SledTripleStoreError {
     fn from(e: bincode::Error) -> Self {
         SledTripleStoreError::SerializationError(e)
@@ -137,7 +163,7 @@ impl From<bincode::Error> for SledTripleStoreError {
 pub struct SledTripleStore<
     Id: ConcreteIdType,
     NodeProps: Property + Serialize + DeserializeOwned,
-    EdgeProps: Serialize + DeserializeOwned,
+    EdgeProps: Property + Serialize + DeserializeOwned,
 > {
     _phantom: std::marker::PhantomData<(Id, NodeProps, EdgeProps)>,
     node_props: sled::Tree,
@@ -145,6 +171,18 @@ pub struct SledTripleStore<
     spo_data: sled::Tree,
     pos_data: sled::Tree,
     osp_data: sled::Tree,
+    quad_props: sled::Tree,
+    gspo_data: sled::Tree,
+    gpos_data: sled::Tree,
+    gosp_data: sled::Tree,
+    spog_data: sled::Tree,
+    ospg_data: sled::Tree,
+    statistics: sled::Tree,
+    subscribers: crate::traits::Subscribers<Id, NodeProps, EdgeProps>,
+    node_indexes: BTreeMap<String, index::SledIndex<NodeProps>>,
+    edge_indexes: BTreeMap<String, index::SledIndex<EdgeProps>>,
+    db: sled::Db,
+    tree_prefix: String,
     id_generator: Box<dyn IdGenerator<Id>>,
 }
 
@@ -154,27 +192,77 @@ impl<
         EdgeProps: Property + Serialize + DeserializeOwned,
     > SledTripleStore<Id, NodeProps, EdgeProps>
 {
+    /// Open the store kept in `db`, creating it if it does not exist.
+    ///
+    /// For id types whose generators would repeat ids when the store is reopened, such as `u64`, `id_generator` is
+    /// replaced by one which keeps its state in `db`; see [ConcreteIdType::sled_id_generator].
+    /// Such a generator reserves ids in blocks; see [SledU64IdGenerator]. The first block is reserved here, so a
+    /// read-only or failing database is reported as an error when opening the store.
+    ///
+    /// Opening does not scan the store. A store written before statistics were kept has none until
+    /// [rebuild_statistics][SledTripleStore::rebuild_statistics] is called. The one exception is a store written before
+    /// the edges of named graphs were also kept by vertex, whose tables for that are filled in the first time it is
+    /// opened.
     pub fn new(
         db: &sled::Db,
         id_generator: impl IdGenerator<Id> + 'static,
     ) -> Result<Self, SledTripleStoreError> {
-        let node_data = db
-            .open_tree(b"node_data")
-            .map_err(|e| SledTripleStoreError::SledError(e))?;
-        let edge_data = db.open_tree(b"edge_data")?;
-        let spo_data = db.open_tree(b"spo_data")?;
-        let pos_data = db.open_tree(b"pos_data")?;
-        let osp_data = db.open_tree(b"osp_data")?;
-
-        Ok(Self {
+        Self::open_with_prefix(db, String::new(), id_generator)
+    }
+
+    /// Open the store whose trees are all named `tree_prefix` followed by the usual tree name.
+    fn open_with_prefix(
+        db: &sled::Db,
+        tree_prefix: String,
+        id_generator: impl IdGenerator<Id> + 'static,
+    ) -> Result<Self, SledTripleStoreError> {
+        let open_tree = |name: &str| db.open_tree(format!("{}{}", tree_prefix, name));
+        let node_data = open_tree("node_data").map_err(SledTripleStoreError::SledError)?;
+        let edge_data = open_tree("edge_data")?;
+        let spo_data = open_tree("spo_data")?;
+        let pos_data = open_tree("pos_data")?;
+        let osp_data = open_tree("osp_data")?;
+        let quad_data = open_tree("quad_data")?;
+        let gspo_data = open_tree("gspo_data")?;
+        let gpos_data = open_tree("gpos_data")?;
+        let gosp_data = open_tree("gosp_data")?;
+        let spog_data = open_tree("spog_data")?;
+        let ospg_data = open_tree("ospg_data")?;
+        let statistics = open_tree("statistics")?;
+
+        // Edge properties are keyed by generated ids, so the largest key in use bounds every id generated so far.
+        let mut in_use = None;
+        for tree in [&edge_data, &quad_data] {
+            if let Some((key, _)) = tree.last()? {
+                in_use = in_use.max(Id::try_from_be_bytes(&key));
+            }
+        }
+        let id_generator = Id::sled_id_generator(open_tree("id_counter")?, in_use)?
+            .unwrap_or_else(|| Box::new(id_generator));
+
+        let store = Self {
             node_props: node_data,
             edge_props: edge_data,
             spo_data,
             pos_data,
             osp_data,
-            id_generator: Box::new(id_generator),
+            quad_props: quad_data,
+            gspo_data,
+            gpos_data,
+            gosp_data,
+            spog_data,
+            ospg_data,
+            statistics,
+            subscribers: crate::traits::Subscribers::new(),
+            node_indexes: BTreeMap::new(),
+            edge_indexes: BTreeMap::new(),
+            db: db.clone(),
+            tree_prefix,
+            id_generator,
             _phantom: std::marker::PhantomData,
-        })
+        };
+        store.fill_node_quad_tables()?;
+        Ok(store)
     }
 }
 
@@ -209,7 +297,7 @@ impl<
             f.write_fmt(format_args!(
                 "  {} -> {:?}\n",
                 Id::try_from_be_bytes(&id).ok_or(std::fmt::Error)?,
-                bincode::deserialize(&node_props).map_err(|_| std::fmt::Error)?
+                bincode::deserialize::<NodeProps>(&node_props).map_err(|_| std::fmt::Error)?
             ))?;
         }
 
@@ -478,3 +566,6 @@ mod rdf {
 
 #[cfg(feature = "rdf")]
 pub use rdf::*;
+
+pub use id_generator::SledU64IdGenerator;
+pub use namespace::{drop_namespace, list_namespaces, rename_namespace};
@@ -0,0 +1,168 @@
+use serde::{de::DeserializeOwned, Serialize};
+use sled::Batch;
+
+use crate::{
+    prelude::*,
+    traits::{sort_last_wins, ConcreteIdType, Event, Property},
+    Triple,
+};
+
+use super::{SledTripleStore, SledTripleStoreError};
+
+impl<
+        Id: ConcreteIdType,
+        NodeProps: Property + Serialize + DeserializeOwned,
+        EdgeProps: Property + Serialize + DeserializeOwned,
+    > TripleStoreBulkLoad<Id, NodeProps, EdgeProps> for SledTripleStore<Id, NodeProps, EdgeProps>
+{
+    fn insert_nodes(
+        &mut self,
+        nodes: impl IntoIterator<Item = (Id, NodeProps)>,
+    ) -> Result<(), SledTripleStoreError> {
+        let mut nodes = nodes.into_iter().collect::<Vec<_>>();
+        sort_last_wins(&mut nodes, |(a, _), (b, _)| a.cmp(b));
+
+        let nodes_data = nodes
+            .iter()
+            .map(|(node, props)| Ok((node.to_be_bytes(), bincode::serialize(props)?)))
+            .collect::<Result<Vec<_>, SledTripleStoreError>>()?;
+        self.write_transaction(|trees| {
+            let mut added = 0;
+            for ((node, props), (key, data)) in nodes.iter().zip(&nodes_data) {
+                let old_data = trees.node_props.insert(key.as_ref(), data.as_slice())?;
+                if old_data.is_none() {
+                    added += 1;
+                }
+                let old_props = trees.indexed_node_props(old_data.as_deref())?;
+                trees.reindex_node(node, old_props.as_ref(), Some(props))?;
+            }
+            trees.count_nodes(added)
+        })?;
+
+        if self.subscribers.is_active() {
+            for (node, props) in nodes {
+                self.subscribers
+                    .publish(|| Event::NodeInserted { node, props });
+            }
+        }
+        Ok(())
+    }
+
+    fn bulk_load(
+        &mut self,
+        edges: impl IntoIterator<Item = (Triple<Id>, EdgeProps)>,
+        assume_no_duplicates: bool,
+    ) -> Result<(), SledTripleStoreError> {
+        let mut edges = edges
+            .into_iter()
+            .map(|(triple, props)| (Id::encode_spo_triple(&triple), triple, props))
+            .collect::<Vec<_>>();
+        if assume_no_duplicates {
+            edges.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
+        } else {
+            sort_last_wins(&mut edges, |(a, _, _), (b, _, _)| a.cmp(b));
+        }
+
+        // The new props are only needed again to index them or to publish them.
+        let keep_props = self.subscribers.is_active() || !self.edge_indexes.is_empty();
+        let mut new_props = Vec::new();
+        let mut triples = Vec::with_capacity(edges.len());
+        let mut spo = Vec::with_capacity(edges.len());
+        let mut pos = Vec::with_capacity(edges.len());
+        let mut osp = Vec::with_capacity(edges.len());
+        let mut props = Vec::with_capacity(edges.len());
+        for (spo_triple, triple, edge_props) in edges {
+            triples.push(triple.clone());
+
+            let edge_data_id = self.id_generator.try_fresh()?;
+            props.push((edge_data_id, bincode::serialize(&edge_props)?));
+            pos.push((Id::encode_pos_triple(&triple), edge_data_id));
+            osp.push((Id::encode_osp_triple(&triple), edge_data_id));
+            spo.push((spo_triple, edge_data_id));
+
+            if keep_props {
+                new_props.push(edge_props);
+            }
+        }
+
+        // The SPO keys are already in order. Stable sorts keep duplicates in the order given, so the last one wins.
+        pos.sort_by(|(a, _), (b, _)| a.cmp(b));
+        osp.sort_by(|(a, _), (b, _)| a.cmp(b));
+        props.sort_unstable_by_key(|(a, _)| *a);
+
+        let batch = |entries: Vec<(Id::TripleByteArrayType, Id)>| {
+            let mut batch = Batch::default();
+            for (key, value) in entries {
+                batch.insert(key.as_ref(), value.to_be_bytes().as_ref());
+            }
+            batch
+        };
+        let (spo_batch, pos_batch, osp_batch) = (batch(spo), batch(pos), batch(osp));
+        let mut props_batch = Batch::default();
+        for (key, value) in props {
+            props_batch.insert(key.to_be_bytes().as_ref(), value);
+        }
+
+        self.write_transaction(|trees| {
+            // Count the new edges, and clean up the props of those being replaced.
+            for (i, triple) in triples.iter().enumerate() {
+                let old_edge_data_id = if assume_no_duplicates {
+                    None
+                } else {
+                    trees.spo_data.get(Id::encode_spo_triple(triple).as_ref())?
+                };
+                let old_data = match old_edge_data_id {
+                    Some(old_edge_data_id) => trees.edge_props.remove(old_edge_data_id)?,
+                    None => {
+                        trees.count_edge(triple, true)?;
+                        None
+                    }
+                };
+                if let Some(props) = new_props.get(i) {
+                    let old_props = trees.indexed_edge_props(old_data.as_deref())?;
+                    trees.reindex_edge(triple, old_props.as_ref(), Some(props))?;
+                }
+            }
+
+            trees.edge_props.apply_batch(&props_batch)?;
+            trees.spo_data.apply_batch(&spo_batch)?;
+            trees.pos_data.apply_batch(&pos_batch)?;
+            trees.osp_data.apply_batch(&osp_batch)?;
+            Ok(())
+        })?;
+
+        if self.subscribers.is_active() {
+            for (triple, props) in triples.into_iter().zip(new_props) {
+                self.subscribers
+                    .publish(|| Event::EdgeInserted { triple, props });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{SledTripleStore, UlidIdGenerator};
+
+    #[test]
+    fn test_bulk_load() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::bulk_load::test_bulk_load(sled_db);
+    }
+
+    #[test]
+    fn test_bulk_load_no_duplicates() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::bulk_load::test_bulk_load_no_duplicates(sled_db);
+    }
+
+    #[test]
+    fn test_bulk_load_indexed() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::bulk_load::test_bulk_load_indexed(sled_db);
+    }
+}
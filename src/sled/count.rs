@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    prelude::*,
+    traits::{ConcreteIdType, Property},
+};
+
+use super::{SledTripleStore, SledTripleStoreError};
+
+impl<
+        Id: ConcreteIdType,
+        NodeProps: Property + Serialize + DeserializeOwned,
+        EdgeProps: Property + Serialize + DeserializeOwned,
+    > TripleStoreCount<Id, NodeProps, EdgeProps> for SledTripleStore<Id, NodeProps, EdgeProps>
+{
+    fn len_nodes(&self) -> Result<usize, SledTripleStoreError> {
+        Ok(self.node_props.len())
+    }
+
+    fn len_edges(&self) -> Result<usize, SledTripleStoreError> {
+        Ok(self.spo_data.len())
+    }
+
+    fn count_by_predicate(&self) -> Result<HashMap<Id, usize>, SledTripleStoreError> {
+        Ok(self
+            .statistics()?
+            .predicates
+            .into_iter()
+            .map(|(pred, stats)| (pred, stats.edges as usize))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{SledTripleStore, UlidIdGenerator};
+
+    #[test]
+    fn test_count() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::count::test_count(sled_db);
+    }
+
+    #[test]
+    fn test_count_aggregates() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::count::test_count_aggregates(sled_db);
+    }
+}
@@ -1,7 +1,5 @@
 use serde::{de::DeserializeOwned, Serialize};
 
-use ulid::Ulid;
-
 use crate::{
     prelude::*,
     traits::{ConcreteIdType, Property},
@@ -22,43 +20,16 @@ impl<
     ) -> Result<(), crate::traits::ExtendError<SledTripleStoreError, E>> {
         let (other_nodes, other_edges) = other.into_iter_nodes(crate::EdgeOrder::SPO);
 
+        // Inserting one at a time keeps the statistics and indexes in the same transaction as each write.
         for r in other_nodes {
-            let (id, data) = r.map_err(|e| ExtendError::Right(e))?;
-            let data = bincode::serialize(&data).map_err(|e| ExtendError::Left(e.into()))?;
-            self.node_props
-                .update_and_fetch(&id.to_be_bytes(), |_| Some(data.clone()))
-                .map_err(|e| ExtendError::Left(e.into()))?;
+            let (id, props) = r.map_err(|e| ExtendError::Right(e))?;
+            self.insert_node(id, props).map_err(ExtendError::Left)?;
         }
 
         for r in other_edges {
             let (id, other_edge_props) = r.map_err(|e| ExtendError::Right(e))?;
-
-            // Serialize and insert the new data along with a fresh id for it.
-            let other_edge_props_id = &Ulid::new().0.to_be_bytes();
-            let other_edge_props =
-                bincode::serialize(&other_edge_props).map_err(|e| ExtendError::Left(e.into()))?;
-            self.edge_props
-                .insert(other_edge_props_id.as_slice(), other_edge_props)
-                .map_err(|e| ExtendError::Left(e.into()))?;
-
-            // Update the edge tables
-            let old_edge_props_id = self
-                .spo_data
-                .fetch_and_update(Id::encode_spo_triple(&id), |_| {
-                    Some(other_edge_props_id.as_slice())
-                })
-                .map_err(|e| ExtendError::Left(e.into()))?;
-            self.pos_data
-                .insert(Id::encode_pos_triple(&id), other_edge_props_id.as_slice())
-                .map_err(|e| ExtendError::Left(e.into()))?;
-            self.osp_data
-                .insert(Id::encode_osp_triple(&id), other_edge_props_id)
-                .map_err(|e| ExtendError::Left(e.into()))?;
-
-            old_edge_props_id
-                .map(|old_edge_props_id| self.edge_props.remove(old_edge_props_id))
-                .transpose()
-                .map_err(|e| ExtendError::Left(e.into()))?;
+            self.insert_edge(id, other_edge_props)
+                .map_err(ExtendError::Left)?;
         }
 
         Ok(())
@@ -0,0 +1,54 @@
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    prelude::*,
+    traits::{ConcreteIdType, Property},
+    Triple,
+};
+
+use super::{SledTripleStore, SledTripleStoreError};
+
+impl<
+        Id: ConcreteIdType,
+        NodeProps: Property + Serialize + DeserializeOwned,
+        EdgeProps: Property + Serialize + DeserializeOwned,
+    > TripleStoreGet<Id, NodeProps, EdgeProps> for SledTripleStore<Id, NodeProps, EdgeProps>
+{
+    fn get_node(&self, id: &Id) -> Result<Option<NodeProps>, SledTripleStoreError> {
+        self.get_node_data_by_id(id)
+    }
+
+    fn get_edge(&self, triple: &Triple<Id>) -> Result<Option<EdgeProps>, SledTripleStoreError> {
+        match self.spo_data.get(Id::encode_spo_triple(triple))? {
+            Some(data_id) => self.get_edge_data_internal(&data_id),
+            None => Ok(None),
+        }
+    }
+
+    fn contains_node(&self, id: &Id) -> Result<bool, SledTripleStoreError> {
+        Ok(self.node_props.contains_key(id.to_be_bytes())?)
+    }
+
+    fn contains_edge(&self, triple: &Triple<Id>) -> Result<bool, SledTripleStoreError> {
+        Ok(self.spo_data.contains_key(Id::encode_spo_triple(triple))?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{SledTripleStore, UlidIdGenerator};
+
+    #[test]
+    fn test_get_node() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::get::test_get_node(sled_db);
+    }
+
+    #[test]
+    fn test_get_edge() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::get::test_get_edge(sled_db);
+    }
+}
@@ -0,0 +1,252 @@
+use std::sync::{Arc, Mutex};
+
+use crate::{traits::IdGeneratorError, IdGenerator};
+
+/// How many ids are reserved in the counter tree at a time.
+const BLOCK_SIZE: u64 = 1024;
+
+/// The key in the counter tree which holds the end of the last reserved block.
+const NEXT_BLOCK: &[u8] = b"next_block";
+
+/// A generator for `u64` ids which keeps its state in a sled tree, so that ids are not handed out again when the
+/// database is reopened, even after a crash.
+///
+/// Ids are reserved a block at a time, so restarting skips the unused remainder of the current block. Generators
+/// which share a tree hand out disjoint ids, and clones share their block.
+///
+/// [SledTripleStore][crate::SledTripleStore] uses one automatically for `u64` ids.
+///
+/// Once the current block is used up, [IdGenerator::try_fresh] returns an error if a further block cannot be reserved,
+/// since handing out an unreserved id could collide with an existing one. [IdGenerator::fresh] panics instead. The
+/// first block is reserved by [SledU64IdGenerator::new].
+///
+/// # Example
+/// ```
+/// # use simple_triplestore::{IdGenerator, SledU64IdGenerator};
+/// let temp_dir = tempdir::TempDir::new("sled").unwrap();
+/// let sled_db = sled::open(temp_dir.path()).unwrap();
+///
+/// let mut ids = SledU64IdGenerator::new(sled_db.open_tree("ids")?, 0)?;
+/// let first = ids.fresh();
+///
+/// // A generator opened on the same tree later carries on from a fresh block.
+/// let mut reopened = SledU64IdGenerator::new(sled_db.open_tree("ids")?, 0)?;
+/// assert!(reopened.fresh() > first);
+/// # Ok::<(), sled::Error>(())
+/// ```
+pub struct SledU64IdGenerator {
+    counter: sled::Tree,
+    block: Arc<Mutex<std::ops::Range<u64>>>,
+}
+
+impl SledU64IdGenerator {
+    /// Keep the generator's state in `counter`. Every id it generates is at least `min`.
+    ///
+    /// The first block of ids is reserved straight away, so a database which cannot be written to is reported here.
+    pub fn new(counter: sled::Tree, min: u64) -> Result<Self, sled::Error> {
+        let generator = Self {
+            counter,
+            block: Arc::new(Mutex::new(0..0)),
+        };
+        let block = generator.reserve(min)?;
+        *generator
+            .block
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = block;
+        Ok(generator)
+    }
+
+    /// Reserve the next block of ids, starting no lower than `min`.
+    fn reserve(&self, min: u64) -> Result<std::ops::Range<u64>, sled::Error> {
+        let end = self
+            .counter
+            .update_and_fetch(NEXT_BLOCK, |next_block| {
+                let next_block = next_block.map_or(0, decode).max(min);
+                Some(next_block.saturating_add(BLOCK_SIZE).to_be_bytes().to_vec())
+            })?
+            .map_or(BLOCK_SIZE, |end| decode(&end));
+        Ok(end - BLOCK_SIZE..end)
+    }
+}
+
+fn decode(bytes: &[u8]) -> u64 {
+    bytes.try_into().map(u64::from_be_bytes).unwrap_or_default()
+}
+
+impl IdGenerator<u64> for SledU64IdGenerator {
+    fn clone(&self) -> Box<dyn IdGenerator<u64>> {
+        Box::new(Self {
+            counter: self.counter.clone(),
+            block: self.block.clone(),
+        })
+    }
+
+    /// # Panics
+    /// Panics if the current block is used up and the next one cannot be reserved.
+    fn fresh(&mut self) -> u64 {
+        self.try_fresh().expect("failed to reserve a block of ids")
+    }
+
+    fn try_fresh(&mut self) -> Result<u64, IdGeneratorError> {
+        let mut block = self
+            .block
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if block.is_empty() {
+            // Sled recovers writes in order, so any data written with these ids implies the block was reserved.
+            *block = self.reserve(0)?;
+        }
+        let id = block.start;
+        block.start += 1;
+        Ok(id)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use crate::{
+        id::u64::U64IdGenerator, prelude::*, IdGenerator, SledTripleStore, SledTripleStoreError,
+        Triple,
+    };
+
+    use super::{decode, SledU64IdGenerator, BLOCK_SIZE, NEXT_BLOCK};
+
+    #[test]
+    fn test_fresh_ids_are_unique() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let tree = db.open_tree("ids").expect("ok");
+        let mut first = SledU64IdGenerator::new(tree.clone(), 0).expect("ok");
+        let mut second = SledU64IdGenerator::new(tree.clone(), 0).expect("ok");
+        let mut clone = first.clone();
+
+        let mut ids = HashSet::new();
+        for _ in 0..BLOCK_SIZE * 2 {
+            assert!(ids.insert(first.fresh()));
+            assert!(ids.insert(second.fresh()));
+            assert!(ids.insert(clone.fresh()));
+        }
+    }
+
+    #[test]
+    fn test_first_block_reserved_on_open() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let tree = db.open_tree("ids").expect("ok");
+        let mut ids = SledU64IdGenerator::new(tree.clone(), 0).expect("ok");
+        let next_block =
+            |tree: &sled::Tree| decode(&tree.get(NEXT_BLOCK).expect("ok").expect("ok"));
+        assert_eq!(next_block(&tree), BLOCK_SIZE);
+
+        // Ids from the first block do not need another write.
+        assert_eq!(ids.fresh(), 0);
+        assert_eq!(next_block(&tree), BLOCK_SIZE);
+    }
+
+    #[test]
+    fn test_min() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let tree = db.open_tree("ids").expect("ok");
+        let mut ids = SledU64IdGenerator::new(tree.clone(), 5000).expect("ok");
+        assert_eq!(ids.fresh(), 5000);
+
+        // A lower minimum never moves the counter back.
+        let mut ids = SledU64IdGenerator::new(tree, 0).expect("ok");
+        assert!(ids.fresh() > 5000);
+    }
+
+    #[test]
+    fn test_reopened_store_does_not_reuse_ids() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let edge = |n: u64| Triple {
+            sub: 1,
+            pred: n,
+            obj: 2,
+        };
+
+        // The in-memory generator given to the store starts from 0 every time, which would reuse ids.
+        let mut store =
+            SledTripleStore::<u64, (), u64>::new(&db, U64IdGenerator::new(0)).expect("ok");
+        for n in 0..10 {
+            store.insert_edge(edge(n), n).expect("ok");
+        }
+        drop(store);
+
+        let mut store =
+            SledTripleStore::<u64, (), u64>::new(&db, U64IdGenerator::new(0)).expect("ok");
+        for n in 10..20 {
+            store.insert_edge(edge(n), n).expect("ok");
+        }
+        store
+            .insert_quad(crate::Quad::new(edge(20), 3), 20)
+            .expect("ok");
+        drop(store);
+
+        let store = SledTripleStore::<u64, (), u64>::new(&db, U64IdGenerator::new(0)).expect("ok");
+        for n in 0..20 {
+            assert_eq!(store.get_edge(&edge(n)).expect("ok"), Some(n));
+        }
+        assert_eq!(
+            store.get_quad(&crate::Quad::new(edge(20), 3)).expect("ok"),
+            Some(20)
+        );
+        assert_eq!(store.edge_props.len() + store.quad_props.len(), 21);
+    }
+
+    #[test]
+    fn test_existing_ids_are_skipped() {
+        // Stores written before the counter existed have no record of the ids they used.
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let edge = |n: u64| Triple {
+            sub: 1,
+            pred: n,
+            obj: 2,
+        };
+        let mut store =
+            SledTripleStore::<u64, (), u64>::new(&db, U64IdGenerator::new(0)).expect("ok");
+        store.insert_edge(edge(0), 0).expect("ok");
+        drop(store);
+        db.drop_tree("id_counter").expect("ok");
+
+        let mut store =
+            SledTripleStore::<u64, (), u64>::new(&db, U64IdGenerator::new(0)).expect("ok");
+        store.insert_edge(edge(1), 1).expect("ok");
+        assert_eq!(store.get_edge(&edge(0)).expect("ok"), Some(0));
+        assert_eq!(store.get_edge(&edge(1)).expect("ok"), Some(1));
+    }
+
+    struct FailingIdGenerator;
+
+    impl IdGenerator<u64> for FailingIdGenerator {
+        fn clone(&self) -> Box<dyn IdGenerator<u64>> {
+            Box::new(FailingIdGenerator)
+        }
+
+        fn fresh(&mut self) -> u64 {
+            panic!("stores should call try_fresh")
+        }
+
+        fn try_fresh(&mut self) -> Result<u64, crate::traits::IdGeneratorError> {
+            Err("no ids left".into())
+        }
+    }
+
+    #[test]
+    fn test_failed_reservation_is_an_error() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let mut store =
+            SledTripleStore::<u64, (), u64>::new(&db, U64IdGenerator::new(0)).expect("ok");
+        store.id_generator = Box::new(FailingIdGenerator);
+        let edge = Triple {
+            sub: 1,
+            pred: 2,
+            obj: 3,
+        };
+
+        assert!(matches!(
+            store.insert_edge(edge.clone(), 0),
+            Err(SledTripleStoreError::IdGeneratorError(_))
+        ));
+        assert_eq!(store.get_edge(&edge).expect("ok"), None);
+    }
+}
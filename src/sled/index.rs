@@ -0,0 +1,287 @@
+use std::ops::{Bound, RangeBounds};
+
+use serde::{de::DeserializeOwned, Serialize};
+use sled::{
+    transaction::{ConflictableTransactionError, ConflictableTransactionResult},
+    Batch,
+};
+
+use crate::{
+    prelude::*,
+    traits::{
+        encode_key_range, key_extractor, ConcreteIdType, IndexKey, KeyExtractor, KeyRange, Property,
+    },
+    EdgeOrder, Triple,
+};
+
+use super::{write::WriteTrees, SledTripleStore, SledTripleStoreError};
+
+/// A secondary index stored in its own tree.
+///
+/// Entries are keyed by the encoded key followed by the indexed value (a node id or an SPO triple), and the value is
+/// stored again as the entry's data so that it can be read back without knowing the length of the key.
+pub(super) struct SledIndex<Props> {
+    extract: KeyExtractor<Props>,
+    tree: sled::Tree,
+}
+
+fn entry_key(mut key: Vec<u8>, value: &[u8]) -> Vec<u8> {
+    key.extend_from_slice(value);
+    key
+}
+
+/// The smallest byte string which is greater than every string starting with `prefix`.
+fn prefix_end(prefix: &[u8]) -> Option<Vec<u8>> {
+    let mut end = prefix.to_vec();
+    while let Some(last) = end.pop() {
+        if last < 0xff {
+            end.push(last + 1);
+            return Some(end);
+        }
+    }
+    None
+}
+
+impl<Props> SledIndex<Props> {
+    pub(super) fn tree(&self) -> &sled::Tree {
+        &self.tree
+    }
+
+    /// Add the changes to `batch` which replace the entries for `value` produced by `old` with those produced by `new`.
+    fn update(&self, batch: &mut Batch, value: &[u8], old: Option<&Props>, new: Option<&Props>) {
+        // Later operations on the same key take precedence, so keys shared by old and new are kept.
+        if let Some(old) = old {
+            for key in (self.extract)(old) {
+                batch.remove(entry_key(key, value));
+            }
+        }
+        if let Some(new) = new {
+            for key in (self.extract)(new) {
+                batch.insert(entry_key(key, value), value);
+            }
+        }
+    }
+
+    /// Iterate over the values of all entries with a key in the encoded `range`.
+    fn range(
+        &self,
+        range: Option<KeyRange<Vec<u8>>>,
+    ) -> impl Iterator<Item = Result<sled::IVec, SledTripleStoreError>> {
+        // Widen the key bounds to cover the value suffix of each entry.
+        let range = range.and_then(|(start, end)| {
+            let start = match start {
+                Bound::Included(key) => Bound::Included(key),
+                Bound::Excluded(key) => Bound::Included(prefix_end(&key)?),
+                Bound::Unbounded => Bound::Unbounded,
+            };
+            let end = match end {
+                Bound::Included(key) => match prefix_end(&key) {
+                    Some(key) => Bound::Excluded(key),
+                    None => Bound::Unbounded,
+                },
+                Bound::Excluded(key) => Bound::Excluded(key),
+                Bound::Unbounded => Bound::Unbounded,
+            };
+            Some((start, end))
+        });
+
+        range
+            .map(|range| self.tree.range::<Vec<u8>, _>(range))
+            .into_iter()
+            .flatten()
+            .map(|r| Ok(r?.1))
+    }
+}
+
+impl<NodeProps: DeserializeOwned, EdgeProps: DeserializeOwned>
+    WriteTrees<'_, NodeProps, EdgeProps>
+{
+    /// Decode the props of a node which are being replaced, if they will be needed to update the node indexes.
+    pub(super) fn indexed_node_props(
+        &self,
+        data: Option<&[u8]>,
+    ) -> ConflictableTransactionResult<Option<NodeProps>, SledTripleStoreError> {
+        match data {
+            Some(data) if !self.node_indexes.is_empty() => {
+                Ok(Some(bincode::deserialize(data).map_err(|e| {
+                    ConflictableTransactionError::Abort(SledTripleStoreError::SerializationError(e))
+                })?))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Decode the props of an edge which are being replaced, if they will be needed to update the edge indexes.
+    pub(super) fn indexed_edge_props(
+        &self,
+        data: Option<&[u8]>,
+    ) -> ConflictableTransactionResult<Option<EdgeProps>, SledTripleStoreError> {
+        match data {
+            Some(data) if !self.edge_indexes.is_empty() => {
+                Ok(Some(bincode::deserialize(data).map_err(|e| {
+                    ConflictableTransactionError::Abort(SledTripleStoreError::SerializationError(e))
+                })?))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Bring the node indexes up to date with the props of `node` changing from `old` to `new`.
+    pub(super) fn reindex_node<Id: ConcreteIdType>(
+        &self,
+        node: &Id,
+        old: Option<&NodeProps>,
+        new: Option<&NodeProps>,
+    ) -> ConflictableTransactionResult<(), SledTripleStoreError> {
+        let value = node.to_be_bytes();
+        for (index, tree) in &self.node_indexes {
+            let mut batch = Batch::default();
+            index.update(&mut batch, value.as_ref(), old, new);
+            tree.apply_batch(&batch)?;
+        }
+        Ok(())
+    }
+
+    /// Bring the edge indexes up to date with the props of `triple` changing from `old` to `new`.
+    pub(super) fn reindex_edge<Id: ConcreteIdType>(
+        &self,
+        triple: &Triple<Id>,
+        old: Option<&EdgeProps>,
+        new: Option<&EdgeProps>,
+    ) -> ConflictableTransactionResult<(), SledTripleStoreError> {
+        let value = Id::encode_spo_triple(triple);
+        for (index, tree) in &self.edge_indexes {
+            let mut batch = Batch::default();
+            index.update(&mut batch, value.as_ref(), old, new);
+            tree.apply_batch(&batch)?;
+        }
+        Ok(())
+    }
+}
+
+impl<
+        Id: ConcreteIdType,
+        NodeProps: Property + Serialize + DeserializeOwned,
+        EdgeProps: Property + Serialize + DeserializeOwned,
+    > SledTripleStore<Id, NodeProps, EdgeProps>
+{
+    fn open_index<Props>(
+        &self,
+        tree_name: String,
+        extract: KeyExtractor<Props>,
+    ) -> Result<SledIndex<Props>, SledTripleStoreError> {
+        // Extractors are not persisted, so an existing tree is rebuilt from scratch.
+        let tree = self
+            .db
+            .open_tree(format!("{}{}", self.tree_prefix, tree_name))?;
+        tree.clear()?;
+        Ok(SledIndex { extract, tree })
+    }
+}
+
+impl<
+        Id: ConcreteIdType,
+        NodeProps: Property + Serialize + DeserializeOwned,
+        EdgeProps: Property + Serialize + DeserializeOwned,
+    > TripleStoreIndex<Id, NodeProps, EdgeProps> for SledTripleStore<Id, NodeProps, EdgeProps>
+{
+    fn register_node_index<K: IndexKey>(
+        &mut self,
+        name: &str,
+        extract: impl Fn(&NodeProps) -> Vec<K> + 'static,
+    ) -> Result<(), SledTripleStoreError> {
+        let index = self.open_index(format!("node_index/{}", name), key_extractor(extract))?;
+        let mut batch = Batch::default();
+        for r in self.iter_vertices() {
+            let (node, props) = r?;
+            index.update(&mut batch, node.to_be_bytes().as_ref(), None, Some(&props));
+        }
+        index.tree.apply_batch(batch)?;
+        self.node_indexes.insert(name.to_string(), index);
+        Ok(())
+    }
+
+    fn register_edge_index<K: IndexKey>(
+        &mut self,
+        name: &str,
+        extract: impl Fn(&EdgeProps) -> Vec<K> + 'static,
+    ) -> Result<(), SledTripleStoreError> {
+        let index = self.open_index(format!("edge_index/{}", name), key_extractor(extract))?;
+        let mut batch = Batch::default();
+        for r in self.iter_edges(EdgeOrder::SPO) {
+            let (triple, props) = r?;
+            let value = Id::encode_spo_triple(&triple);
+            index.update(&mut batch, value.as_ref(), None, Some(&props));
+        }
+        index.tree.apply_batch(batch)?;
+        self.edge_indexes.insert(name.to_string(), index);
+        Ok(())
+    }
+
+    fn range_nodes<'a, K: IndexKey>(
+        &'a self,
+        name: &str,
+        range: impl RangeBounds<K>,
+    ) -> impl Iterator<Item = Result<Id, SledTripleStoreError>> + 'a {
+        let nodes: Box<dyn Iterator<Item = Result<Id, SledTripleStoreError>>> =
+            match self.node_indexes.get(name) {
+                Some(index) => Box::new(index.range(encode_key_range(&range)).map(|r| {
+                    Id::try_from_be_bytes(r?.as_ref()).ok_or(SledTripleStoreError::KeySizeError)
+                })),
+                None => Box::new(std::iter::once(Err(SledTripleStoreError::IndexNotFound(
+                    name.to_string(),
+                )))),
+            };
+        nodes
+    }
+
+    fn range_edges<'a, K: IndexKey>(
+        &'a self,
+        name: &str,
+        range: impl RangeBounds<K>,
+    ) -> impl Iterator<Item = Result<Triple<Id>, SledTripleStoreError>> + 'a {
+        let edges: Box<dyn Iterator<Item = Result<Triple<Id>, SledTripleStoreError>>> =
+            match self.edge_indexes.get(name) {
+                Some(index) => Box::new(index.range(encode_key_range(&range)).map(|r| {
+                    Ok(Id::decode_spo_triple(
+                        &r?[..]
+                            .try_into()
+                            .map_err(|_| SledTripleStoreError::KeySizeError)?,
+                    ))
+                })),
+                None => Box::new(std::iter::once(Err(SledTripleStoreError::IndexNotFound(
+                    name.to_string(),
+                )))),
+            };
+        edges
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{SledTripleStore, UlidIdGenerator};
+
+    #[test]
+    fn test_node_index() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::index::test_node_index(sled_db);
+    }
+
+    #[test]
+    fn test_edge_index() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::index::test_edge_index(sled_db);
+    }
+
+    #[test]
+    fn test_index_merge() {
+        let (_left_tempdir, left_db) = crate::sled::create_test_db().expect("ok");
+        let (_right_tempdir, right_db) = crate::sled::create_test_db().expect("ok");
+        crate::conformance::index::test_index_merge(
+            SledTripleStore::new(&left_db, UlidIdGenerator::new()).expect("ok"),
+            SledTripleStore::new(&right_db, UlidIdGenerator::new()).expect("ok"),
+        );
+    }
+}
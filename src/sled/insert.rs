@@ -1,9 +1,8 @@
 use serde::{de::DeserializeOwned, Serialize};
-use sled::Transactional;
 
 use crate::{
     prelude::*,
-    traits::{ConcreteIdType, Property},
+    traits::{ConcreteIdType, Event, Property},
     Triple,
 };
 
@@ -18,7 +17,19 @@ impl<
     fn insert_node(&mut self, node: Id, props: NodeProps) -> Result<(), SledTripleStoreError> {
         let key_bytes = &node.to_be_bytes();
         let data_bytes = bincode::serialize(&props)?;
-        self.node_props.insert(key_bytes, data_bytes)?;
+        self.write_transaction(|trees| {
+            let old_data = trees
+                .node_props
+                .insert(key_bytes.as_ref(), data_bytes.as_slice())?;
+            if old_data.is_none() {
+                trees.count_nodes(1)?;
+            }
+            let old_props = trees.indexed_node_props(old_data.as_deref())?;
+            trees.reindex_node(&node, old_props.as_ref(), Some(&props))
+        })?;
+
+        self.subscribers
+            .publish(|| Event::NodeInserted { node, props });
         Ok(())
     }
 
@@ -27,40 +38,42 @@ impl<
         triple: Triple<Id>,
         props: EdgeProps,
     ) -> Result<(), SledTripleStoreError> {
-        let prop_key = self.id_generator.fresh();
+        let prop_key = self.id_generator.try_fresh()?;
         let prop_key_bytes = prop_key.to_be_bytes();
 
         let data_bytes = bincode::serialize(&props)?;
 
-        (
-            &self.edge_props,
-            &self.spo_data,
-            &self.pos_data,
-            &self.osp_data,
-        )
-            .transaction(move |(edge_props, spo_data, pos_data, osp_data)| {
-                edge_props.insert(prop_key_bytes.as_ref(), data_bytes.as_slice())?;
-                spo_data.insert(
-                    Id::encode_spo_triple(&triple).as_ref(),
-                    prop_key_bytes.as_ref(),
-                )?;
-                pos_data.insert(
-                    Id::encode_pos_triple(&triple).as_ref(),
-                    prop_key_bytes.as_ref(),
-                )?;
-                osp_data.insert(
-                    Id::encode_osp_triple(&triple).as_ref(),
-                    prop_key_bytes.as_ref(),
-                )?;
-                Ok(())
-            })
-            .map_err(|e| match e {
-                sled::transaction::TransactionError::Abort(e) => SledTripleStoreError::SledError(e),
-                sled::transaction::TransactionError::Storage(e) => {
-                    SledTripleStoreError::SledError(e)
+        self.write_transaction(|trees| {
+            trees
+                .edge_props
+                .insert(prop_key_bytes.as_ref(), data_bytes.as_slice())?;
+            let old_prop_key = trees.spo_data.insert(
+                Id::encode_spo_triple(&triple).as_ref(),
+                prop_key_bytes.as_ref(),
+            )?;
+            trees.pos_data.insert(
+                Id::encode_pos_triple(&triple).as_ref(),
+                prop_key_bytes.as_ref(),
+            )?;
+            trees.osp_data.insert(
+                Id::encode_osp_triple(&triple).as_ref(),
+                prop_key_bytes.as_ref(),
+            )?;
+
+            let old_data = match old_prop_key {
+                // Clean up the props of the edge being replaced.
+                Some(old_prop_key) => trees.edge_props.remove(old_prop_key)?,
+                None => {
+                    trees.count_edge(&triple, true)?;
+                    None
                 }
-            })?;
+            };
+            let old_props = trees.indexed_edge_props(old_data.as_deref())?;
+            trees.reindex_edge(&triple, old_props.as_ref(), Some(&props))
+        })?;
 
+        self.subscribers
+            .publish(|| Event::EdgeInserted { triple, props });
         Ok(())
     }
 }
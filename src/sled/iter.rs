@@ -23,26 +23,29 @@ impl<
     ) -> Result<Option<NodeProps>, SledTripleStoreError> {
         self.node_props
             .get(id)
-            .map_err(|e| SledTripleStoreError::SledError(e))?
+            .map_err(SledTripleStoreError::SledError)?
             .map(|data| {
-                bincode::deserialize(&data).map_err(|e| SledTripleStoreError::SerializationError(e))
+                bincode::deserialize(&data).map_err(SledTripleStoreError::SerializationError)
             })
             .transpose()
     }
 
-    fn get_node_data_by_id(&self, id: &Id) -> Result<Option<NodeProps>, SledTripleStoreError> {
+    pub(super) fn get_node_data_by_id(
+        &self,
+        id: &Id,
+    ) -> Result<Option<NodeProps>, SledTripleStoreError> {
         self.get_node_data_internal(&id.to_be_bytes())
     }
 
-    fn get_edge_data_internal(
+    pub(super) fn get_edge_data_internal(
         &self,
         id: &sled::IVec,
     ) -> Result<Option<EdgeProps>, SledTripleStoreError> {
         self.edge_props
             .get(id)
-            .map_err(|e| SledTripleStoreError::SledError(e))?
+            .map_err(SledTripleStoreError::SledError)?
             .map(|data| {
-                bincode::deserialize(&data).map_err(|e| SledTripleStoreError::SerializationError(e))
+                bincode::deserialize(&data).map_err(SledTripleStoreError::SerializationError)
             })
             .transpose()
     }
@@ -96,14 +99,12 @@ impl<
         (self.iter_vertices(), self.iter_edges(order))
     }
 
-    fn iter_vertices<'a>(
-        &'a self,
-    ) -> impl Iterator<Item = Result<(Id, NodeProps), SledTripleStoreError>> {
+    fn iter_vertices(&self) -> impl Iterator<Item = Result<(Id, NodeProps), SledTripleStoreError>> {
         self.node_props.iter().map(|r| match r {
             Ok((k, v)) => {
                 let k = decode_id(k)?;
-                let v = bincode::deserialize(&v)
-                    .map_err(|e| SledTripleStoreError::SerializationError(e))?;
+                let v =
+                    bincode::deserialize(&v).map_err(SledTripleStoreError::SerializationError)?;
                 Ok((k, v))
             }
             Err(e) => Err(SledTripleStoreError::SledError(e)),
@@ -117,7 +118,7 @@ impl<
     {
         let edges: Box<dyn Iterator<Item = _>> = match order {
             EdgeOrder::SPO => Box::new(self.spo_data.iter().map(|r| {
-                r.map_err(|e| SledTripleStoreError::SledError(e))
+                r.map_err(SledTripleStoreError::SledError)
                     .and_then(|(k, v)| {
                         Ok((
                             Id::decode_spo_triple(
@@ -130,7 +131,7 @@ impl<
                     })
             })),
             EdgeOrder::POS => Box::new(self.pos_data.iter().map(|r| {
-                r.map_err(|e| SledTripleStoreError::SledError(e))
+                r.map_err(SledTripleStoreError::SledError)
                     .and_then(|(k, v)| {
                         Ok((
                             Id::decode_pos_triple(
@@ -143,7 +144,7 @@ impl<
                     })
             })),
             EdgeOrder::OSP => Box::new(self.osp_data.iter().map(|r| {
-                r.map_err(|e| SledTripleStoreError::SledError(e))
+                r.map_err(SledTripleStoreError::SledError)
                     .and_then(|(k, v)| {
                         Ok((
                             Id::decode_osp_triple(
@@ -165,7 +166,7 @@ impl<
     ) -> impl Iterator<Item = Result<(Triple<Id>, EdgeProps), SledTripleStoreError>> + 'a {
         let edges: Box<dyn Iterator<Item = _>> = match order {
             EdgeOrder::SPO => Box::new(self.spo_data.iter().map(|r| {
-                r.map_err(|e| SledTripleStoreError::SledError(e))
+                r.map_err(SledTripleStoreError::SledError)
                     .and_then(|(k, v)| {
                         Ok((
                             Id::decode_spo_triple(
@@ -178,7 +179,7 @@ impl<
                     })
             })),
             EdgeOrder::POS => Box::new(self.pos_data.iter().map(|r| {
-                r.map_err(|e| SledTripleStoreError::SledError(e))
+                r.map_err(SledTripleStoreError::SledError)
                     .and_then(|(k, v)| {
                         Ok((
                             Id::decode_pos_triple(
@@ -191,7 +192,7 @@ impl<
                     })
             })),
             EdgeOrder::OSP => Box::new(self.osp_data.iter().map(|r| {
-                r.map_err(|e| SledTripleStoreError::SledError(e))
+                r.map_err(SledTripleStoreError::SledError)
                     .and_then(|(k, v)| {
                         Ok((
                             Id::decode_osp_triple(
@@ -233,8 +234,8 @@ impl<
         let node_iter = self.node_props.into_iter().map(|r| match r {
             Ok((k, v)) => {
                 let k = decode_id(k)?;
-                let v = bincode::deserialize(&v)
-                    .map_err(|e| SledTripleStoreError::SerializationError(e))?;
+                let v =
+                    bincode::deserialize(&v).map_err(SledTripleStoreError::SerializationError)?;
                 Ok((k, v))
             }
             Err(e) => Err(SledTripleStoreError::SledError(e)),
@@ -242,7 +243,7 @@ impl<
 
         let edges: Box<dyn Iterator<Item = _>> = match order {
             EdgeOrder::SPO => Box::new(self.spo_data.iter().map(|r| {
-                r.map_err(|e| SledTripleStoreError::SledError(e))
+                r.map_err(SledTripleStoreError::SledError)
                     .and_then(|(k, v)| {
                         Ok((
                             Id::decode_spo_triple(
@@ -255,7 +256,7 @@ impl<
                     })
             })),
             EdgeOrder::POS => Box::new(self.pos_data.iter().map(|r| {
-                r.map_err(|e| SledTripleStoreError::SledError(e))
+                r.map_err(SledTripleStoreError::SledError)
                     .and_then(|(k, v)| {
                         Ok((
                             Id::decode_pos_triple(
@@ -268,7 +269,7 @@ impl<
                     })
             })),
             EdgeOrder::OSP => Box::new(self.osp_data.iter().map(|r| {
-                r.map_err(|e| SledTripleStoreError::SledError(e))
+                r.map_err(SledTripleStoreError::SledError)
                     .and_then(|(k, v)| {
                         Ok((
                             Id::decode_osp_triple(
@@ -287,10 +288,10 @@ impl<
                 let pred_data = self
                     .edge_props
                     .get(v)
-                    .map_err(|e| SledTripleStoreError::SledError(e))?
+                    .map_err(SledTripleStoreError::SledError)?
                     .map(|data| {
                         bincode::deserialize(&data)
-                            .map_err(|e| SledTripleStoreError::SerializationError(e))
+                            .map_err(SledTripleStoreError::SerializationError)
                     })
                     .transpose();
 
@@ -308,8 +309,8 @@ impl<
         self.node_props.into_iter().map(|r| match r {
             Ok((k, v)) => {
                 let k = decode_id(k)?;
-                let v = bincode::deserialize(&v)
-                    .map_err(|e| SledTripleStoreError::SerializationError(e))?;
+                let v =
+                    bincode::deserialize(&v).map_err(SledTripleStoreError::SerializationError)?;
                 Ok((k, v))
             }
             Err(e) => Err(SledTripleStoreError::SledError(e)),
@@ -322,7 +323,7 @@ impl<
     ) -> impl Iterator<Item = Result<PropsTriple<Id, NodeProps, EdgeProps>, Self::Error>> {
         let edges: Box<dyn Iterator<Item = _>> = match order {
             EdgeOrder::SPO => Box::new(self.spo_data.into_iter().map(|r| {
-                r.map_err(|e| SledTripleStoreError::SledError(e))
+                r.map_err(SledTripleStoreError::SledError)
                     .and_then(|(k, v)| {
                         Ok((
                             Id::decode_spo_triple(
@@ -335,7 +336,7 @@ impl<
                     })
             })),
             EdgeOrder::POS => Box::new(self.pos_data.into_iter().map(|r| {
-                r.map_err(|e| SledTripleStoreError::SledError(e))
+                r.map_err(SledTripleStoreError::SledError)
                     .and_then(|(k, v)| {
                         Ok((
                             Id::decode_pos_triple(
@@ -348,7 +349,7 @@ impl<
                     })
             })),
             EdgeOrder::OSP => Box::new(self.osp_data.into_iter().map(|r| {
-                r.map_err(|e| SledTripleStoreError::SledError(e))
+                r.map_err(SledTripleStoreError::SledError)
                     .and_then(|(k, v)| {
                         Ok((
                             Id::decode_osp_triple(
@@ -370,7 +371,7 @@ impl<
     ) -> impl Iterator<Item = Result<(Triple<Id>, EdgeProps), Self::Error>> {
         let edges: Box<dyn Iterator<Item = _>> = match order {
             EdgeOrder::SPO => Box::new(self.spo_data.iter().map(|r| {
-                r.map_err(|e| SledTripleStoreError::SledError(e))
+                r.map_err(SledTripleStoreError::SledError)
                     .and_then(|(k, v)| {
                         Ok((
                             Id::decode_spo_triple(
@@ -383,7 +384,7 @@ impl<
                     })
             })),
             EdgeOrder::POS => Box::new(self.pos_data.iter().map(|r| {
-                r.map_err(|e| SledTripleStoreError::SledError(e))
+                r.map_err(SledTripleStoreError::SledError)
                     .and_then(|(k, v)| {
                         Ok((
                             Id::decode_pos_triple(
@@ -396,7 +397,7 @@ impl<
                     })
             })),
             EdgeOrder::OSP => Box::new(self.osp_data.iter().map(|r| {
-                r.map_err(|e| SledTripleStoreError::SledError(e))
+                r.map_err(SledTripleStoreError::SledError)
                     .and_then(|(k, v)| {
                         Ok((
                             Id::decode_osp_triple(
@@ -1,13 +1,13 @@
 use serde::{de::DeserializeOwned, Serialize};
-use sled::transaction::{ConflictableTransactionError, Transactional};
+use sled::transaction::ConflictableTransactionError;
 
 use crate::{
     prelude::*,
-    traits::{ConcreteIdType, Mergeable, Property},
+    traits::{ConcreteIdType, Event, Mergeable, MergedProps, Property},
     MergeError, Triple,
 };
 
-use super::{SledTripleStore, SledTripleStoreError};
+use super::{write::WriteTrees, SledTripleStore, SledTripleStoreError};
 
 impl<
         Id: ConcreteIdType,
@@ -38,124 +38,144 @@ impl<
     fn merge_node(&mut self, node: Id, props: NodeProps) -> Result<(), Self::Error> {
         let key_bytes = &node.to_be_bytes();
 
-        (&self.node_props)
-            .transaction(|node_props| {
-                match node_props.get(key_bytes.as_ref())? {
-                    None => {
-                        node_props.insert(
-                            key_bytes.as_ref(),
-                            bincode::serialize(&props).map_err(|e| {
-                                ConflictableTransactionError::Abort(
-                                    SledTripleStoreError::SerializationError(e),
-                                )
-                            })?,
-                        )?;
-                    }
-
-                    Some(existing_value) => {
-                        let mut old_props: NodeProps = bincode::deserialize(&existing_value)
-                            .map_err(|e| {
-                                ConflictableTransactionError::Abort(
-                                    SledTripleStoreError::SerializationError(e),
-                                )
-                            })?;
-                        old_props.merge(props.clone());
-                        node_props.insert(
-                            key_bytes.as_ref(),
-                            bincode::serialize(&old_props).map_err(|e| {
-                                ConflictableTransactionError::Abort(
-                                    SledTripleStoreError::SerializationError(e),
-                                )
-                            })?,
-                        )?;
-                    }
+        let (added, merged_props) = self.write_transaction(|trees| {
+            let node_props = trees.node_props;
+            let old_value = node_props.get(key_bytes.as_ref())?;
+            let added = old_value.is_none();
+            let old_props = trees.indexed_node_props(old_value.as_deref())?;
+            let merged_props = match old_value {
+                None => {
+                    node_props.insert(
+                        key_bytes.as_ref(),
+                        bincode::serialize(&props).map_err(|e| {
+                            ConflictableTransactionError::Abort(
+                                SledTripleStoreError::SerializationError(e),
+                            )
+                        })?,
+                    )?;
+                    props.clone()
                 }
 
-                Ok(())
-            })
-            .map_err(|e| match e {
-                sled::transaction::TransactionError::Abort(e) => e,
-                sled::transaction::TransactionError::Storage(e) => {
-                    SledTripleStoreError::SledError(e)
+                Some(existing_value) => {
+                    let mut merged: NodeProps =
+                        bincode::deserialize(&existing_value).map_err(|e| {
+                            ConflictableTransactionError::Abort(
+                                SledTripleStoreError::SerializationError(e),
+                            )
+                        })?;
+                    merged.merge(props.clone());
+                    node_props.insert(
+                        key_bytes.as_ref(),
+                        bincode::serialize(&merged).map_err(|e| {
+                            ConflictableTransactionError::Abort(
+                                SledTripleStoreError::SerializationError(e),
+                            )
+                        })?,
+                    )?;
+                    merged
                 }
-            })?;
+            };
+
+            if added {
+                trees.count_nodes(1)?;
+            }
+            trees.reindex_node(&node, old_props.as_ref(), Some(&merged_props))?;
+            Ok((added, merged_props))
+        })?;
+
+        self.subscribers.publish(|| {
+            if added {
+                Event::NodeInserted {
+                    node,
+                    props: merged_props,
+                }
+            } else {
+                Event::PropsMerged(MergedProps::Node {
+                    node,
+                    props: merged_props,
+                })
+            }
+        });
         Ok(())
     }
 
     fn merge_edge(&mut self, triple: Triple<Id>, props: EdgeProps) -> Result<(), Self::Error> {
-        let new_edge_props_id = self.id_generator.fresh().to_be_bytes();
+        let new_edge_props_id = self.id_generator.try_fresh()?.to_be_bytes();
         let spo_triple = Id::encode_spo_triple(&triple);
         let pos_triple = Id::encode_pos_triple(&triple);
         let osp_triple = Id::encode_osp_triple(&triple);
 
-        (
-            &self.edge_props,
-            &self.spo_data,
-            &self.pos_data,
-            &self.osp_data,
-        )
-            .transaction(|(edge_props, spo_data, pos_data, osp_data)| {
-                let old_edge_props_id =
-                    spo_data.insert(spo_triple.as_ref(), new_edge_props_id.as_ref())?;
-                pos_data.insert(pos_triple.as_ref(), new_edge_props_id.as_ref())?;
-                osp_data.insert(osp_triple.as_ref(), new_edge_props_id.as_ref())?;
-
-                match old_edge_props_id {
-                    None => {
-                        edge_props.insert(
-                            new_edge_props_id.as_ref(),
-                            bincode::serialize(&props).map_err(|e| {
-                                ConflictableTransactionError::Abort(
-                                    SledTripleStoreError::SerializationError(e),
-                                )
-                            })?,
-                        )?;
-                    }
-
-                    Some(old_edge_props_id) => {
-                        let old_value = edge_props.remove(old_edge_props_id)?;
-                        match old_value {
-                            None => {
-                                edge_props.insert(
-                                    new_edge_props_id.as_ref(),
-                                    bincode::serialize(&props).map_err(|e| {
-                                        ConflictableTransactionError::Abort(
-                                            SledTripleStoreError::SerializationError(e),
-                                        )
-                                    })?,
-                                )?;
-                            }
-
-                            Some(old_value) => {
-                                let mut old_props: EdgeProps = bincode::deserialize(&old_value)
-                                    .map_err(|e| {
-                                        ConflictableTransactionError::Abort(
-                                            SledTripleStoreError::SerializationError(e),
-                                        )
-                                    })?;
-                                old_props.merge(props.clone());
-                                edge_props.insert(
-                                    new_edge_props_id.as_ref(),
-                                    bincode::serialize(&old_props).map_err(|e| {
-                                        ConflictableTransactionError::Abort(
-                                            SledTripleStoreError::SerializationError(e),
-                                        )
-                                    })?,
-                                )?;
-                            }
-                        }
-                    }
+        let (added, merged_props) = self.write_transaction(|trees| {
+            let WriteTrees {
+                edge_props,
+                spo_data,
+                pos_data,
+                osp_data,
+                ..
+            } = trees;
+            let old_edge_props_id =
+                spo_data.insert(spo_triple.as_ref(), new_edge_props_id.as_ref())?;
+            let added = old_edge_props_id.is_none();
+            pos_data.insert(pos_triple.as_ref(), new_edge_props_id.as_ref())?;
+            osp_data.insert(osp_triple.as_ref(), new_edge_props_id.as_ref())?;
+
+            let old_value = match old_edge_props_id {
+                Some(old_edge_props_id) => edge_props.remove(old_edge_props_id)?,
+                None => None,
+            };
+            let old_props = trees.indexed_edge_props(old_value.as_deref())?;
+            let merged_props = match old_value {
+                None => {
+                    edge_props.insert(
+                        new_edge_props_id.as_ref(),
+                        bincode::serialize(&props).map_err(|e| {
+                            ConflictableTransactionError::Abort(
+                                SledTripleStoreError::SerializationError(e),
+                            )
+                        })?,
+                    )?;
+                    props.clone()
                 }
 
-                Ok(())
-            })
-            .map_err(|e| match e {
-                sled::transaction::TransactionError::Abort(e) => e,
-                sled::transaction::TransactionError::Storage(e) => {
-                    SledTripleStoreError::SledError(e)
+                Some(old_value) => {
+                    let mut merged: EdgeProps = bincode::deserialize(&old_value).map_err(|e| {
+                        ConflictableTransactionError::Abort(
+                            SledTripleStoreError::SerializationError(e),
+                        )
+                    })?;
+                    merged.merge(props.clone());
+                    edge_props.insert(
+                        new_edge_props_id.as_ref(),
+                        bincode::serialize(&merged).map_err(|e| {
+                            ConflictableTransactionError::Abort(
+                                SledTripleStoreError::SerializationError(e),
+                            )
+                        })?,
+                    )?;
+                    merged
                 }
-            })?;
-
+            };
+
+            if added {
+                trees.count_edge(&triple, true)?;
+            }
+            trees.reindex_edge(&triple, old_props.as_ref(), Some(&merged_props))?;
+            Ok((added, merged_props))
+        })?;
+
+        self.subscribers.publish(|| {
+            if added {
+                Event::EdgeInserted {
+                    triple,
+                    props: merged_props,
+                }
+            } else {
+                Event::PropsMerged(MergedProps::Edge {
+                    triple,
+                    props: merged_props,
+                })
+            }
+        });
         Ok(())
     }
 }
@@ -0,0 +1,279 @@
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    traits::{ConcreteIdType, Property},
+    IdGenerator,
+};
+
+use super::{SledTripleStore, SledTripleStoreError};
+
+/// The tree which records every namespace which has been opened.
+const NAMESPACES_TREE: &str = "namespaces";
+
+fn tree_prefix(namespace: &str) -> String {
+    format!("namespace/{}/", namespace)
+}
+
+fn check_namespace(namespace: &str) -> Result<(), SledTripleStoreError> {
+    if namespace.is_empty() || namespace.contains('/') {
+        return Err(SledTripleStoreError::InvalidNamespace(
+            namespace.to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// The names of every tree which belongs to `namespace`.
+fn namespace_trees(db: &sled::Db, namespace: &str) -> impl Iterator<Item = sled::IVec> + 'static {
+    let prefix = tree_prefix(namespace);
+    db.tree_names()
+        .into_iter()
+        .filter(move |name| name.starts_with(prefix.as_bytes()))
+}
+
+impl<
+        Id: ConcreteIdType,
+        NodeProps: Property + Serialize + DeserializeOwned,
+        EdgeProps: Property + Serialize + DeserializeOwned,
+    > SledTripleStore<Id, NodeProps, EdgeProps>
+{
+    /// Open the store named `namespace` within `db`, creating it if it does not exist.
+    ///
+    /// Each namespace keeps its data in its own trees, apart from the store opened by [SledTripleStore::new] and from
+    /// every other namespace, so several independent stores can share one database.
+    ///
+    /// Namespaces may not be empty or contain '/'.
+    ///
+    /// # Example
+    /// ```
+    /// # use ulid::Ulid;
+    /// # use simple_triplestore::{prelude::*, sled::list_namespaces, SledTripleStore, UlidIdGenerator};
+    /// let temp_dir = tempdir::TempDir::new("sled").unwrap();
+    /// let sled_db = sled::open(temp_dir.path()).unwrap();
+    ///
+    /// let mut tenant_a =
+    ///     SledTripleStore::<Ulid, String, ()>::open_namespaced(&sled_db, "tenant-a", UlidIdGenerator::new())?;
+    /// let tenant_b =
+    ///     SledTripleStore::<Ulid, String, ()>::open_namespaced(&sled_db, "tenant-b", UlidIdGenerator::new())?;
+    ///
+    /// tenant_a.insert_node(Ulid(1), "only in a".to_string())?;
+    /// assert_eq!(tenant_b.get_node(&Ulid(1))?, None);
+    /// assert_eq!(list_namespaces(&sled_db)?, ["tenant-a", "tenant-b"]);
+    /// # Ok::<(), simple_triplestore::sled::SledTripleStoreError>(())
+    /// ```
+    pub fn open_namespaced(
+        db: &sled::Db,
+        namespace: &str,
+        id_generator: impl IdGenerator<Id> + 'static,
+    ) -> Result<Self, SledTripleStoreError> {
+        check_namespace(namespace)?;
+        db.open_tree(NAMESPACES_TREE)?
+            .insert(namespace.as_bytes(), &[])?;
+        Self::open_with_prefix(db, tree_prefix(namespace), id_generator)
+    }
+}
+
+/// List the namespaces in `db` which were created by [SledTripleStore::open_namespaced], in name order.
+pub fn list_namespaces(db: &sled::Db) -> Result<Vec<String>, SledTripleStoreError> {
+    db.open_tree(NAMESPACES_TREE)?
+        .iter()
+        .keys()
+        .map(|r| Ok(String::from_utf8_lossy(&r?).into_owned()))
+        .collect()
+}
+
+/// Rename the namespace `from` to `to`, moving all of its data.
+///
+/// Any store opened on `from` must not be used afterwards. The data is copied a tree at a time, so the rename is not
+/// atomic.
+pub fn rename_namespace(db: &sled::Db, from: &str, to: &str) -> Result<(), SledTripleStoreError> {
+    check_namespace(from)?;
+    check_namespace(to)?;
+    let namespaces = db.open_tree(NAMESPACES_TREE)?;
+    if !namespaces.contains_key(from)? {
+        return Err(SledTripleStoreError::NamespaceNotFound(from.to_string()));
+    }
+    if namespaces.contains_key(to)? {
+        return Err(SledTripleStoreError::NamespaceExists(to.to_string()));
+    }
+
+    let (from_prefix, to_prefix) = (tree_prefix(from), tree_prefix(to));
+    for name in namespace_trees(db, from) {
+        let source = db.open_tree(&name)?;
+        let target = db.open_tree([to_prefix.as_bytes(), &name[from_prefix.len()..]].concat())?;
+        let mut batch = sled::Batch::default();
+        for r in source.iter() {
+            let (key, value) = r?;
+            batch.insert(key, value);
+        }
+        target.apply_batch(batch)?;
+        db.drop_tree(&name)?;
+    }
+
+    namespaces.insert(to.as_bytes(), &[])?;
+    namespaces.remove(from.as_bytes())?;
+    Ok(())
+}
+
+/// Drop the namespace `namespace` and all of its data.
+///
+/// Any store opened on `namespace` must not be used afterwards.
+pub fn drop_namespace(db: &sled::Db, namespace: &str) -> Result<(), SledTripleStoreError> {
+    check_namespace(namespace)?;
+    let namespaces = db.open_tree(NAMESPACES_TREE)?;
+    if !namespaces.contains_key(namespace)? {
+        return Err(SledTripleStoreError::NamespaceNotFound(
+            namespace.to_string(),
+        ));
+    }
+
+    for name in namespace_trees(db, namespace) {
+        db.drop_tree(&name)?;
+    }
+    namespaces.remove(namespace.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use ulid::Ulid;
+
+    use crate::{
+        prelude::*, Query, SledTripleStore, SledTripleStoreError, Triple, UlidIdGenerator,
+    };
+
+    use super::{drop_namespace, list_namespaces, rename_namespace};
+
+    fn open(db: &sled::Db, namespace: &str) -> SledTripleStore<Ulid, String, String> {
+        SledTripleStore::open_namespaced(db, namespace, UlidIdGenerator::new()).expect("ok")
+    }
+
+    fn edge(n: u128) -> Triple<Ulid> {
+        Triple {
+            sub: Ulid(1),
+            pred: Ulid(n),
+            obj: Ulid(2),
+        }
+    }
+
+    #[test]
+    fn test_namespaces_are_independent() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let mut default =
+            SledTripleStore::<Ulid, String, String>::new(&db, UlidIdGenerator::new()).expect("ok");
+        let mut a = open(&db, "a");
+        let mut b = open(&db, "b");
+
+        default
+            .insert_node(Ulid(1), "default".to_string())
+            .expect("ok");
+        a.insert_node(Ulid(1), "a".to_string()).expect("ok");
+        a.insert_edge(edge(10), "a".to_string()).expect("ok");
+        b.insert_edge(edge(11), "b".to_string()).expect("ok");
+
+        assert_eq!(
+            default.get_node(&Ulid(1)).expect("ok"),
+            Some("default".to_string())
+        );
+        assert_eq!(a.get_node(&Ulid(1)).expect("ok"), Some("a".to_string()));
+        assert_eq!(b.get_node(&Ulid(1)).expect("ok"), None);
+        assert_eq!(default.iter_edges(Default::default()).count(), 0);
+        assert_eq!(a.get_edge(&edge(11)).expect("ok"), None);
+        assert_eq!(b.get_edge(&edge(11)).expect("ok"), Some("b".to_string()));
+
+        // Indexes are kept per namespace too.
+        a.register_node_index("name", |props: &String| vec![props.clone()])
+            .expect("ok");
+        b.register_node_index("name", |props: &String| vec![props.clone()])
+            .expect("ok");
+        assert_eq!(
+            a.range_nodes::<String>("name", ..)
+                .collect::<Result<Vec<_>, _>>()
+                .expect("ok"),
+            [Ulid(1)]
+        );
+        assert_eq!(b.range_nodes::<String>("name", ..).count(), 0);
+
+        // Reopening a namespace finds its data again.
+        drop(a);
+        assert_eq!(
+            open(&db, "a").get_edge(&edge(10)).expect("ok"),
+            Some("a".to_string())
+        );
+        assert_eq!(list_namespaces(&db).expect("ok"), ["a", "b"]);
+    }
+
+    #[test]
+    fn test_rename_namespace() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let mut a = open(&db, "a");
+        a.insert_node(Ulid(1), "a".to_string()).expect("ok");
+        a.insert_edge(edge(10), "a".to_string()).expect("ok");
+        drop(a);
+        open(&db, "b");
+
+        assert!(matches!(
+            rename_namespace(&db, "a", "b"),
+            Err(SledTripleStoreError::NamespaceExists(_))
+        ));
+        assert!(matches!(
+            rename_namespace(&db, "missing", "c"),
+            Err(SledTripleStoreError::NamespaceNotFound(_))
+        ));
+
+        rename_namespace(&db, "a", "c").expect("ok");
+        assert_eq!(list_namespaces(&db).expect("ok"), ["b", "c"]);
+
+        let c = open(&db, "c");
+        assert_eq!(c.get_node(&Ulid(1)).expect("ok"), Some("a".to_string()));
+        assert_eq!(c.get_edge(&edge(10)).expect("ok"), Some("a".to_string()));
+        assert_eq!(c.iter_query(Query::P([Ulid(10)].into())).1.count(), 1);
+        assert_eq!(open(&db, "a").get_node(&Ulid(1)).expect("ok"), None);
+    }
+
+    #[test]
+    fn test_drop_namespace() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let mut default =
+            SledTripleStore::<Ulid, String, String>::new(&db, UlidIdGenerator::new()).expect("ok");
+        default
+            .insert_node(Ulid(1), "default".to_string())
+            .expect("ok");
+        let mut a = open(&db, "a");
+        a.insert_node(Ulid(1), "a".to_string()).expect("ok");
+        drop(a);
+
+        drop_namespace(&db, "a").expect("ok");
+        assert_eq!(list_namespaces(&db).expect("ok"), Vec::<String>::new());
+        assert!(matches!(
+            drop_namespace(&db, "a"),
+            Err(SledTripleStoreError::NamespaceNotFound(_))
+        ));
+        assert!(!db
+            .tree_names()
+            .iter()
+            .any(|name| name.starts_with(b"namespace/a/")));
+
+        // Other stores are untouched, and the namespace starts out empty if opened again.
+        assert_eq!(
+            default.get_node(&Ulid(1)).expect("ok"),
+            Some("default".to_string())
+        );
+        assert_eq!(open(&db, "a").get_node(&Ulid(1)).expect("ok"), None);
+    }
+
+    #[test]
+    fn test_invalid_namespace() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        for namespace in ["", "a/b"] {
+            assert!(matches!(
+                SledTripleStore::<Ulid, (), ()>::open_namespaced(
+                    &db,
+                    namespace,
+                    UlidIdGenerator::new()
+                ),
+                Err(SledTripleStoreError::InvalidNamespace(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use std::ops::Bound;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    prelude::*,
+    traits::{
+        collect_page, resume, ConcreteIdType, Cursor, DecodeTriple, KeyRange, Page, Property,
+        QueryPages, QueryScan,
+    },
+    EdgeOrder, Query, Triple,
+};
+
+use super::{SledTripleStore, SledTripleStoreError};
+
+impl<
+        Id: ConcreteIdType,
+        NodeProps: Property + Serialize + DeserializeOwned,
+        EdgeProps: Property + Serialize + DeserializeOwned,
+    > SledTripleStore<Id, NodeProps, EdgeProps>
+{
+    /// Page through the given ranges of the edge table for `order`.
+    fn page_table(
+        &self,
+        order: EdgeOrder,
+        ranges: Vec<KeyRange<Id::TripleByteArrayType>>,
+        after: Option<&Cursor>,
+        limit: usize,
+    ) -> Result<Page<(Triple<Id>, EdgeProps)>, SledTripleStoreError> {
+        let after = after
+            .map(|cursor| {
+                cursor
+                    .key(Some(&order))
+                    .ok_or(SledTripleStoreError::InvalidCursor)
+            })
+            .transpose()?;
+        let (table, decode): (&sled::Tree, DecodeTriple<Id>) = match order {
+            EdgeOrder::SPO => (&self.spo_data, Id::decode_spo_triple),
+            EdgeOrder::POS => (&self.pos_data, Id::decode_pos_triple),
+            EdgeOrder::OSP => (&self.osp_data, Id::decode_osp_triple),
+        };
+
+        let rows = ranges
+            .into_iter()
+            .filter_map(|bounds| resume(bounds, after.as_ref()))
+            .flat_map(|bounds| table.range(bounds))
+            .map(|r| {
+                let (key, data_id) = r?;
+                let triple = decode(
+                    &key[..]
+                        .try_into()
+                        .map_err(|_| SledTripleStoreError::KeySizeError)?,
+                );
+                let props = self
+                    .get_edge_data_internal(&data_id)?
+                    .ok_or(SledTripleStoreError::MissingPropertyData)?;
+                Ok((Cursor::new(Some(&order), &key), (triple, props)))
+            });
+        collect_page(rows, limit)
+    }
+
+    /// Decode a cursor into the node table.
+    fn cursor_node(cursor: Option<&Cursor>) -> Result<Option<Id>, SledTripleStoreError> {
+        cursor
+            .map(|cursor| {
+                cursor
+                    .key::<Id::ByteArrayType>(None)
+                    .map(|bytes| Id::from_be_bytes(&bytes))
+                    .ok_or(SledTripleStoreError::InvalidCursor)
+            })
+            .transpose()
+    }
+}
+
+impl<
+        Id: ConcreteIdType,
+        NodeProps: Property + Serialize + DeserializeOwned,
+        EdgeProps: Property + Serialize + DeserializeOwned,
+    > TripleStorePaginate<Id, NodeProps, EdgeProps> for SledTripleStore<Id, NodeProps, EdgeProps>
+{
+    fn page_vertices(
+        &self,
+        after: Option<&Cursor>,
+        limit: usize,
+    ) -> Result<Page<(Id, NodeProps)>, SledTripleStoreError> {
+        let start = match Self::cursor_node(after)? {
+            Some(after) => Bound::Excluded(after.to_be_bytes()),
+            None => Bound::Unbounded,
+        };
+        let rows = self.node_props.range((start, Bound::Unbounded)).map(|r| {
+            let (key, data) = r?;
+            let id = Id::try_from_be_bytes(&key).ok_or(SledTripleStoreError::KeySizeError)?;
+            Ok((Cursor::new(None, &key), (id, bincode::deserialize(&data)?)))
+        });
+        collect_page(rows, limit)
+    }
+
+    fn page_edges(
+        &self,
+        order: EdgeOrder,
+        after: Option<&Cursor>,
+        limit: usize,
+    ) -> Result<Page<(Triple<Id>, EdgeProps)>, SledTripleStoreError> {
+        self.page_table(
+            order,
+            vec![(Bound::Unbounded, Bound::Unbounded)],
+            after,
+            limit,
+        )
+    }
+
+    fn page_query(
+        &self,
+        query: Query<Id>,
+        after: Option<&Cursor>,
+        limit: usize,
+    ) -> Result<QueryPages<Id, NodeProps, EdgeProps>, SledTripleStoreError> {
+        match QueryScan::new(query) {
+            QueryScan::Nodes(nodes) => {
+                let after = Self::cursor_node(after)?;
+                let rows = nodes
+                    .into_iter()
+                    .filter(|node| after.is_none_or(|after| *node > after))
+                    .filter_map(|node| match self.get_node_data_by_id(&node) {
+                        Ok(Some(props)) => Some(Ok((
+                            Cursor::new(None, node.to_be_bytes().as_ref()),
+                            (node, props),
+                        ))),
+                        Ok(None) => None,
+                        Err(e) => Some(Err(e)),
+                    });
+                Ok((collect_page(rows, limit)?, Page::default()))
+            }
+            QueryScan::Edges(order, ranges) => Ok((
+                Page::default(),
+                self.page_table(order, ranges, after, limit)?,
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{SledTripleStore, UlidIdGenerator};
+
+    #[test]
+    fn test_page_vertices() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::paginate::test_page_vertices(sled_db);
+    }
+
+    #[test]
+    fn test_page_edges() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::paginate::test_page_edges(sled_db);
+    }
+
+    #[test]
+    fn test_page_query() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::paginate::test_page_query(sled_db);
+    }
+}
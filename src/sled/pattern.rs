@@ -0,0 +1,112 @@
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    prelude::*,
+    traits::{ConcreteIdType, Property},
+    Triple,
+};
+
+use super::{SledTripleStore, SledTripleStoreError};
+
+impl<
+        Id: ConcreteIdType,
+        NodeProps: Property + Serialize + DeserializeOwned,
+        EdgeProps: Property + Serialize + DeserializeOwned,
+    > TripleStorePattern<Id, NodeProps, EdgeProps> for SledTripleStore<Id, NodeProps, EdgeProps>
+{
+    fn match_triples<'a>(
+        &'a self,
+        sub: Option<Id>,
+        pred: Option<Id>,
+        obj: Option<Id>,
+    ) -> impl Iterator<Item = Result<Triple<Id>, SledTripleStoreError>> + 'a {
+        fn decode<Id: ConcreteIdType>(
+            r: Result<(sled::IVec, sled::IVec), sled::Error>,
+            decode: fn(&Id::TripleByteArrayType) -> Triple<Id>,
+        ) -> Result<Triple<Id>, SledTripleStoreError> {
+            let (key, _) = r?;
+            Ok(decode(
+                &key[..]
+                    .try_into()
+                    .map_err(|_| SledTripleStoreError::KeySizeError)?,
+            ))
+        }
+
+        let triples: Box<dyn Iterator<Item = Result<Triple<Id>, SledTripleStoreError>>> =
+            match (sub, pred, obj) {
+                (Some(sub), Some(pred), Some(obj)) => {
+                    let triple = Triple { sub, pred, obj };
+                    Box::new(
+                        self.spo_data
+                            .contains_key(Id::encode_spo_triple(&triple))
+                            .map_err(SledTripleStoreError::SledError)
+                            .map(|found| found.then_some(triple))
+                            .transpose()
+                            .into_iter(),
+                    )
+                }
+                (Some(sub), Some(pred), None) => Box::new(
+                    self.spo_data
+                        .range(Id::key_bounds_2(sub, pred))
+                        .map(|r| decode(r, Id::decode_spo_triple)),
+                ),
+                (Some(sub), None, Some(obj)) => Box::new(
+                    self.osp_data
+                        .range(Id::key_bounds_2(obj, sub))
+                        .map(|r| decode(r, Id::decode_osp_triple)),
+                ),
+                (Some(sub), None, None) => Box::new(
+                    self.spo_data
+                        .range(Id::key_bounds_1(sub))
+                        .map(|r| decode(r, Id::decode_spo_triple)),
+                ),
+                (None, Some(pred), Some(obj)) => Box::new(
+                    self.pos_data
+                        .range(Id::key_bounds_2(pred, obj))
+                        .map(|r| decode(r, Id::decode_pos_triple)),
+                ),
+                (None, Some(pred), None) => Box::new(
+                    self.pos_data
+                        .range(Id::key_bounds_1(pred))
+                        .map(|r| decode(r, Id::decode_pos_triple)),
+                ),
+                (None, None, Some(obj)) => Box::new(
+                    self.osp_data
+                        .range(Id::key_bounds_1(obj))
+                        .map(|r| decode(r, Id::decode_osp_triple)),
+                ),
+                (None, None, None) => Box::new(
+                    self.spo_data
+                        .iter()
+                        .map(|r| decode(r, Id::decode_spo_triple)),
+                ),
+            };
+        triples
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{SledTripleStore, UlidIdGenerator};
+
+    #[test]
+    fn test_match_triples() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::pattern::test_match_triples(sled_db);
+    }
+
+    #[test]
+    fn test_run_pattern() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::pattern::test_run_pattern(sled_db);
+    }
+
+    #[test]
+    fn test_run_path() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::path::test_run_path(sled_db);
+    }
+}
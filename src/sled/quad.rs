@@ -0,0 +1,336 @@
+use std::ops::Bound;
+
+use serde::{de::DeserializeOwned, Serialize};
+use sled::{transaction::ConflictableTransactionResult, Batch, Transactional};
+
+use crate::{
+    prelude::*,
+    traits::{quad_ranges, ConcreteIdType, Event, Property},
+    triple::NodeQuadOrder,
+    Quad, QuadOrder, Query,
+};
+
+use super::{write::WriteTrees, SledTripleStore, SledTripleStoreError};
+
+impl<NodeProps, EdgeProps> WriteTrees<'_, NodeProps, EdgeProps> {
+    /// Write `quad` to every table of the named graphs, with its props stored under `props_id`.
+    pub(super) fn write_quad<Id: ConcreteIdType>(
+        &self,
+        quad: &Quad<Id>,
+        props_id: &[u8],
+        data: &[u8],
+    ) -> ConflictableTransactionResult<(), SledTripleStoreError> {
+        self.quad_props.insert(props_id, data)?;
+        let old_props_id = self
+            .gspo_data
+            .insert(quad.encode(&QuadOrder::GSPO).as_ref(), props_id)?;
+        self.gpos_data
+            .insert(quad.encode(&QuadOrder::GPOS).as_ref(), props_id)?;
+        self.gosp_data
+            .insert(quad.encode(&QuadOrder::GOSP).as_ref(), props_id)?;
+        self.spog_data
+            .insert(quad.encode_node(NodeQuadOrder::SPOG).as_ref(), props_id)?;
+        self.ospg_data
+            .insert(quad.encode_node(NodeQuadOrder::OSPG).as_ref(), props_id)?;
+        if let Some(old_props_id) = old_props_id {
+            self.quad_props.remove(old_props_id)?;
+        }
+        Ok(())
+    }
+
+    /// Delete `quad` and its props from every table of the named graphs, returning whether it was there.
+    pub(super) fn delete_quad<Id: ConcreteIdType>(
+        &self,
+        quad: &Quad<Id>,
+    ) -> ConflictableTransactionResult<bool, SledTripleStoreError> {
+        let Some(props_id) = self
+            .gspo_data
+            .remove(quad.encode(&QuadOrder::GSPO).as_ref())?
+        else {
+            return Ok(false);
+        };
+        self.gpos_data
+            .remove(quad.encode(&QuadOrder::GPOS).as_ref())?;
+        self.gosp_data
+            .remove(quad.encode(&QuadOrder::GOSP).as_ref())?;
+        self.spog_data
+            .remove(quad.encode_node(NodeQuadOrder::SPOG).as_ref())?;
+        self.ospg_data
+            .remove(quad.encode_node(NodeQuadOrder::OSPG).as_ref())?;
+        self.quad_props.remove(props_id)?;
+        Ok(true)
+    }
+}
+
+impl<
+        Id: ConcreteIdType,
+        NodeProps: Property + Serialize + DeserializeOwned,
+        EdgeProps: Property + Serialize + DeserializeOwned,
+    > SledTripleStore<Id, NodeProps, EdgeProps>
+{
+    fn quad_table(&self, order: &QuadOrder) -> &sled::Tree {
+        match order {
+            QuadOrder::GSPO => &self.gspo_data,
+            QuadOrder::GPOS => &self.gpos_data,
+            QuadOrder::GOSP => &self.gosp_data,
+        }
+    }
+
+    fn decode_quad_key(order: &QuadOrder, key: &[u8]) -> Result<Quad<Id>, SledTripleStoreError> {
+        let key =
+            Id::QuadByteArrayType::try_from(key).map_err(|_| SledTripleStoreError::KeySizeError)?;
+        Ok(Quad::decode(order, &key))
+    }
+
+    fn quad_props_for(&self, props_id: &[u8]) -> Result<EdgeProps, SledTripleStoreError> {
+        let data = self
+            .quad_props
+            .get(props_id)?
+            .ok_or(SledTripleStoreError::MissingPropertyData)?;
+        Ok(bincode::deserialize(&data)?)
+    }
+
+    fn scan_quads(
+        &self,
+        order: QuadOrder,
+        bounds: (Bound<Id::QuadByteArrayType>, Bound<Id::QuadByteArrayType>),
+    ) -> impl Iterator<Item = Result<(Quad<Id>, EdgeProps), SledTripleStoreError>> + '_ {
+        self.quad_table(&order).range(bounds).map(move |r| {
+            let (key, props_id) = r?;
+            Ok((
+                Self::decode_quad_key(&order, &key)?,
+                self.quad_props_for(&props_id)?,
+            ))
+        })
+    }
+
+    fn node_quad_table(&self, order: NodeQuadOrder) -> &sled::Tree {
+        match order {
+            NodeQuadOrder::SPOG => &self.spog_data,
+            NodeQuadOrder::OSPG => &self.ospg_data,
+        }
+    }
+
+    /// The edges of every named graph which start or end at `node`. Self loops are only listed once.
+    pub(super) fn node_quads(&self, node: &Id) -> Result<Vec<Quad<Id>>, SledTripleStoreError> {
+        let mut quads = Vec::new();
+        for order in [NodeQuadOrder::SPOG, NodeQuadOrder::OSPG] {
+            for r in self
+                .node_quad_table(order)
+                .range(Quad::node_key_bounds(*node))
+            {
+                let (key, _) = r?;
+                let key = Id::QuadByteArrayType::try_from(&key[..])
+                    .map_err(|_| SledTripleStoreError::KeySizeError)?;
+                let quad = Quad::decode_node(order, &key);
+                if order == NodeQuadOrder::SPOG || quad.sub != *node {
+                    quads.push(quad);
+                }
+            }
+        }
+        Ok(quads)
+    }
+
+    /// Fill the node-leading tables from the GSPO table, if the store was written before they were kept.
+    pub(super) fn fill_node_quad_tables(&self) -> Result<(), SledTripleStoreError> {
+        if !self.spog_data.is_empty() || !self.ospg_data.is_empty() || self.gspo_data.is_empty() {
+            return Ok(());
+        }
+        let (mut spog, mut ospg) = (Batch::default(), Batch::default());
+        for r in self.gspo_data.iter() {
+            let (key, props_id) = r?;
+            let quad = Self::decode_quad_key(&QuadOrder::GSPO, &key)?;
+            spog.insert(
+                quad.encode_node(NodeQuadOrder::SPOG).as_ref(),
+                props_id.clone(),
+            );
+            ospg.insert(quad.encode_node(NodeQuadOrder::OSPG).as_ref(), props_id);
+        }
+        (&self.spog_data, &self.ospg_data)
+            .transaction(|(spog_data, ospg_data)| {
+                spog_data.apply_batch(&spog)?;
+                ospg_data.apply_batch(&ospg)?;
+                Ok(())
+            })
+            .map_err(
+                |e: sled::transaction::TransactionError<SledTripleStoreError>| match e {
+                    sled::transaction::TransactionError::Abort(e) => e,
+                    sled::transaction::TransactionError::Storage(e) => {
+                        SledTripleStoreError::SledError(e)
+                    }
+                },
+            )
+    }
+}
+
+impl<
+        Id: ConcreteIdType,
+        NodeProps: Property + Serialize + DeserializeOwned,
+        EdgeProps: Property + Serialize + DeserializeOwned,
+    > QuadStore<Id, NodeProps, EdgeProps> for SledTripleStore<Id, NodeProps, EdgeProps>
+{
+    fn insert_quad(&mut self, quad: Quad<Id>, props: EdgeProps) -> Result<(), Self::Error> {
+        let props_id = self.id_generator.try_fresh()?.to_be_bytes();
+        let data_bytes = bincode::serialize(&props)?;
+
+        self.write_transaction(|trees| trees.write_quad(&quad, props_id.as_ref(), &data_bytes))?;
+
+        self.subscribers
+            .publish(|| Event::QuadInserted { quad, props });
+        Ok(())
+    }
+
+    fn get_quad(&self, quad: &Quad<Id>) -> Result<Option<EdgeProps>, Self::Error> {
+        self.gspo_data
+            .get(quad.encode(&QuadOrder::GSPO))?
+            .map(|props_id| self.quad_props_for(&props_id))
+            .transpose()
+    }
+
+    fn remove_quad(&mut self, quad: Quad<Id>) -> Result<(), Self::Error> {
+        if self.write_transaction(|trees| trees.delete_quad(&quad))? {
+            self.subscribers.publish(|| Event::QuadRemoved { quad });
+        }
+        Ok(())
+    }
+
+    fn iter_graphs<'a>(&'a self) -> impl Iterator<Item = Result<Id, Self::Error>> + 'a {
+        // Skip from each graph to the first key after it.
+        let mut lower = Some(Bound::Unbounded);
+        std::iter::from_fn(move || {
+            let key = match self
+                .gspo_data
+                .range::<Id::QuadByteArrayType, _>((lower.take()?, Bound::Unbounded))
+                .next()?
+            {
+                Ok((key, _)) => key,
+                Err(e) => return Some(Err(e.into())),
+            };
+            let graph = match Self::decode_quad_key(&QuadOrder::GSPO, &key) {
+                Ok(quad) => quad.graph,
+                Err(e) => return Some(Err(e)),
+            };
+            lower = match Id::graph_key_bounds(graph).1 {
+                Bound::Included(key) => Some(Bound::Excluded(key)),
+                bound => Some(bound),
+            };
+            Some(Ok(graph))
+        })
+    }
+
+    fn iter_quads<'a>(
+        &'a self,
+        order: QuadOrder,
+    ) -> impl Iterator<Item = Result<(Quad<Id>, EdgeProps), Self::Error>> + 'a {
+        self.scan_quads(order, (Bound::Unbounded, Bound::Unbounded))
+    }
+
+    fn iter_query_graphs<'a>(
+        &'a self,
+        graphs: impl IntoIterator<Item = Id>,
+        query: Query<Id>,
+    ) -> impl Iterator<Item = Result<(Quad<Id>, EdgeProps), Self::Error>> + 'a {
+        quad_ranges(graphs, &query)
+            .into_iter()
+            .flat_map(move |(order, bounds)| self.scan_quads(order, bounds))
+    }
+
+    fn drop_graph(&mut self, graph: &Id) -> Result<(), Self::Error> {
+        // The edges are scanned beforehand, so start again if any of them was written in between.
+        let removed = loop {
+            let watch = self.watch_graph(graph);
+            let quads = self
+                .gspo_data
+                .range(Id::graph_key_bounds(*graph))
+                .map(|r| Self::decode_quad_key(&QuadOrder::GSPO, &r?.0))
+                .collect::<Result<Vec<_>, _>>()?;
+            let removed = self.write_transaction(|trees| {
+                if watch.changed() {
+                    return Ok(None);
+                }
+                let mut removed = Vec::new();
+                for quad in &quads {
+                    if trees.delete_quad(quad)? {
+                        removed.push(quad.clone());
+                    }
+                }
+                Ok(Some(removed))
+            })?;
+            if let Some(removed) = removed {
+                break removed;
+            }
+        };
+
+        for quad in removed {
+            self.subscribers.publish(|| Event::QuadRemoved { quad });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ulid::Ulid;
+
+    use crate::{prelude::*, Quad, SledTripleStore, Triple, UlidIdGenerator};
+
+    #[test]
+    fn test_quads() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::quad::test_quads(sled_db);
+    }
+
+    #[test]
+    fn test_query_graphs() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::quad::test_query_graphs(sled_db);
+    }
+
+    #[test]
+    fn test_drop_graph() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::quad::test_drop_graph(sled_db);
+    }
+
+    #[test]
+    fn test_fill_node_quad_tables() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let mut sled_db =
+            SledTripleStore::<Ulid, (), String>::new(&db, UlidIdGenerator::new()).expect("ok");
+        let quad = |sub: u128, obj: u128| {
+            Quad::new(
+                Triple {
+                    sub: Ulid(sub),
+                    pred: Ulid(10),
+                    obj: Ulid(obj),
+                },
+                Ulid(20),
+            )
+        };
+        for (sub, obj) in [(1, 2), (2, 3), (3, 3)] {
+            sled_db
+                .insert_quad(quad(sub, obj), format!("{sub}->{obj}"))
+                .expect("ok");
+        }
+
+        // As if the store had been written before the node-leading tables were kept.
+        sled_db.spog_data.clear().expect("ok");
+        sled_db.ospg_data.clear().expect("ok");
+        drop(sled_db);
+
+        let sled_db =
+            SledTripleStore::<Ulid, (), String>::new(&db, UlidIdGenerator::new()).expect("ok");
+        assert!(sled_db.verify().expect("ok").is_consistent());
+        assert_eq!(
+            sled_db.node_quads(&Ulid(2)).expect("ok"),
+            [quad(2, 3), quad(1, 2)]
+        );
+        assert_eq!(
+            sled_db.node_quads(&Ulid(3)).expect("ok"),
+            [quad(3, 3), quad(2, 3)]
+        );
+    }
+}
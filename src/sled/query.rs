@@ -2,11 +2,56 @@ use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
     prelude::*,
-    traits::{ConcreteIdType, Property},
-    MemTripleStore, Query, QueryError, Triple,
+    traits::{BoxedRows, ConcreteIdType, Property},
+    MemTripleStore, PropsFilter, Query, QueryError, RunError, Triple,
 };
 
-use super::SledTripleStore;
+use super::{SledTripleStore, SledTripleStoreError};
+
+impl<
+        Id: ConcreteIdType,
+        NodeProps: Property + Serialize + DeserializeOwned,
+        EdgeProps: Property + Serialize + DeserializeOwned,
+    > SledTripleStore<Id, NodeProps, EdgeProps>
+{
+    /// Fetch the properties for an edge found in one of the triple tables, skipping edges without properties and
+    /// edges rejected by the filter.
+    fn query_edge(
+        &self,
+        triple: Triple<Id>,
+        data_id: &[u8],
+        filter: PropsFilter<Id, NodeProps, EdgeProps>,
+    ) -> Result<Option<(Triple<Id>, EdgeProps)>, SledTripleStoreError> {
+        match self.edge_props.get(data_id)? {
+            Some(data) => {
+                let data = bincode::deserialize(&data)?;
+                Ok(filter.keep_edge(&triple, &data).then_some((triple, data)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Iterate over the edges in a range of one of the triple tables.
+    fn query_range<'a>(
+        &'a self,
+        tree: &'a sled::Tree,
+        range: impl std::ops::RangeBounds<Id::TripleByteArrayType>,
+        decode: fn(&Id::TripleByteArrayType) -> Triple<Id>,
+        filter: PropsFilter<'a, Id, NodeProps, EdgeProps>,
+    ) -> impl Iterator<Item = Result<(Triple<Id>, EdgeProps), SledTripleStoreError>> + 'a {
+        tree.range(range).filter_map(move |r| {
+            let (key, data_id) = match r {
+                Ok(kv) => kv,
+                Err(e) => return Some(Err(e.into())),
+            };
+            let triple = match key[..].try_into() {
+                Ok(key) => decode(&key),
+                Err(_) => return Some(Err(SledTripleStoreError::KeySizeError)),
+            };
+            self.query_edge(triple, &data_id, filter).transpose()
+        })
+    }
+}
 
 impl<
         Id: ConcreteIdType,
@@ -16,241 +61,144 @@ impl<
 {
     type QueryResult = MemTripleStore<Id, NodeProps, EdgeProps>;
 
-    fn run(
-        &self,
+    fn iter_query_filtered<'a>(
+        &'a self,
         query: Query<Id>,
-    ) -> Result<Self::QueryResult, QueryError<Self::Error, <<Self as TripleStoreQuery<Id, NodeProps, EdgeProps>>::QueryResult as TripleStoreError>::Error>>{
-        Ok(match query {
-            Query::NodeProps(nodes) => {
-                let mut result =
-                    MemTripleStore::new_from_boxed_id_generator(self.id_generator.clone());
-                for node in nodes {
-                    if let Some(data) = self
-                        .node_props
-                        .get(&node.to_be_bytes())
-                        .map_err(|e| QueryError::Left(super::SledTripleStoreError::SledError(e)))?
-                    {
-                        result
-                            .insert_node(
-                                node,
-                                bincode::deserialize(&data).map_err(|e| {
-                                    QueryError::Left(
-                                        super::SledTripleStoreError::SerializationError(e),
-                                    )
-                                })?,
-                            )
-                            .map_err(|e| QueryError::Right(e))?;
-                    }
-                }
-                result
-            }
-
-            Query::SPO(triples) => {
-                let mut result =
-                    MemTripleStore::new_from_boxed_id_generator(self.id_generator.clone());
-                for (sub, pred, obj) in triples.into_iter() {
-                    let triple = Triple { sub, pred, obj };
-                    if let Some(data_id) = self
-                        .spo_data
-                        .get(&Id::encode_spo_triple(&triple))
-                        .map_err(|e| QueryError::Left(super::SledTripleStoreError::SledError(e)))?
-                    {
-                        if let Some(data) = self.edge_props.get(&data_id).map_err(|e| {
-                            QueryError::Left(super::SledTripleStoreError::SledError(e))
-                        })? {
-                            result
-                                .insert_edge(
-                                    triple,
-                                    bincode::deserialize(&data).map_err(|e| {
-                                        QueryError::Left(
-                                            super::SledTripleStoreError::SerializationError(e),
-                                        )
-                                    })?,
-                                )
-                                .map_err(|e| QueryError::Right(e))?;
+        filter: PropsFilter<'a, Id, NodeProps, EdgeProps>,
+    ) -> (
+        impl Iterator<Item = Result<(Id, NodeProps), SledTripleStoreError>> + 'a,
+        impl Iterator<Item = Result<(Triple<Id>, EdgeProps), SledTripleStoreError>> + 'a,
+    ) {
+        let (nodes, edges): BoxedRows<'_, Id, NodeProps, EdgeProps, SledTripleStoreError> =
+            match query {
+                Query::NodeProps(nodes) => (
+                    Box::new(nodes.into_iter().filter_map(move |node| {
+                        match self.node_props.get(node.to_be_bytes()) {
+                            Ok(Some(data)) => match bincode::deserialize(&data) {
+                                Ok(data) => {
+                                    filter.keep_node(&node, &data).then_some(Ok((node, data)))
+                                }
+                                Err(e) => Some(Err(SledTripleStoreError::SerializationError(e))),
+                            },
+                            Ok(None) => None,
+                            Err(e) => Some(Err(SledTripleStoreError::SledError(e))),
                         }
-                    }
-                }
-                result
-            }
+                    })),
+                    Box::new(std::iter::empty()),
+                ),
 
-            Query::S(items) => {
-                let mut result =
-                    MemTripleStore::new_from_boxed_id_generator(self.id_generator.clone());
-                for sub in items {
-                    for r in self.spo_data.range(Id::key_bounds_1(sub)) {
-                        let (key, data_id) = r.map_err(|e| {
-                            QueryError::Left(super::SledTripleStoreError::SledError(e))
-                        })?;
-                        if let Some(data) = self.edge_props.get(&data_id).map_err(|e| {
-                            QueryError::Left(super::SledTripleStoreError::SledError(e))
-                        })? {
-                            result
-                                .insert_edge(
-                                    Id::decode_spo_triple(&key[..].try_into().map_err(|_| {
-                                        QueryError::Left(super::SledTripleStoreError::KeySizeError)
-                                    })?),
-                                    bincode::deserialize(&data).map_err(|e| {
-                                        QueryError::Left(
-                                            super::SledTripleStoreError::SerializationError(e),
-                                        )
-                                    })?,
-                                )
-                                .map_err(|e| QueryError::Right(e))?;
+                Query::SPO(triples) => (
+                    Box::new(std::iter::empty()),
+                    Box::new(triples.into_iter().filter_map(move |(sub, pred, obj)| {
+                        let triple = Triple { sub, pred, obj };
+                        match self.spo_data.get(Id::encode_spo_triple(&triple)) {
+                            Ok(Some(data_id)) => {
+                                self.query_edge(triple, &data_id, filter).transpose()
+                            }
+                            Ok(None) => None,
+                            Err(e) => Some(Err(SledTripleStoreError::SledError(e))),
                         }
-                    }
-                }
-                result
-            }
+                    })),
+                ),
 
-            Query::SP(items) => {
-                let mut result =
-                    MemTripleStore::new_from_boxed_id_generator(self.id_generator.clone());
-                for (sub, pred) in items {
-                    for r in self.spo_data.range(Id::key_bounds_2(sub, pred)) {
-                        let (key, data_id) = r.map_err(|e| {
-                            QueryError::Left(super::SledTripleStoreError::SledError(e))
-                        })?;
-                        if let Some(data) = self.edge_props.get(&data_id).map_err(|e| {
-                            QueryError::Left(super::SledTripleStoreError::SledError(e))
-                        })? {
-                            result
-                                .insert_edge(
-                                    Id::decode_spo_triple(&key[..].try_into().map_err(|_| {
-                                        QueryError::Left(super::SledTripleStoreError::KeySizeError)
-                                    })?),
-                                    bincode::deserialize(&data).map_err(|e| {
-                                        QueryError::Left(
-                                            super::SledTripleStoreError::SerializationError(e),
-                                        )
-                                    })?,
-                                )
-                                .map_err(|e| QueryError::Right(e))?;
-                        }
-                    }
-                }
-                result
-            }
+                Query::S(items) => (
+                    Box::new(std::iter::empty()),
+                    Box::new(items.into_iter().flat_map(move |sub| {
+                        self.query_range(
+                            &self.spo_data,
+                            Id::key_bounds_1(sub),
+                            Id::decode_spo_triple,
+                            filter,
+                        )
+                    })),
+                ),
 
-            Query::SO(items) => {
-                let mut result =
-                    MemTripleStore::new_from_boxed_id_generator(self.id_generator.clone());
-                for (sub, obj) in items {
-                    for r in self.osp_data.range(Id::key_bounds_2(obj, sub)) {
-                        let (key, data_id) = r.map_err(|e| {
-                            QueryError::Left(super::SledTripleStoreError::SledError(e))
-                        })?;
-                        if let Some(data) = self.edge_props.get(&data_id).map_err(|e| {
-                            QueryError::Left(super::SledTripleStoreError::SledError(e))
-                        })? {
-                            result
-                                .insert_edge(
-                                    Id::decode_osp_triple(&key[..].try_into().map_err(|_| {
-                                        QueryError::Left(super::SledTripleStoreError::KeySizeError)
-                                    })?),
-                                    bincode::deserialize(&data).map_err(|e| {
-                                        QueryError::Left(
-                                            super::SledTripleStoreError::SerializationError(e),
-                                        )
-                                    })?,
-                                )
-                                .map_err(|e| QueryError::Right(e))?;
-                        }
-                    }
-                }
-                result
-            }
+                Query::SP(items) => (
+                    Box::new(std::iter::empty()),
+                    Box::new(items.into_iter().flat_map(move |(sub, pred)| {
+                        self.query_range(
+                            &self.spo_data,
+                            Id::key_bounds_2(sub, pred),
+                            Id::decode_spo_triple,
+                            filter,
+                        )
+                    })),
+                ),
 
-            Query::P(items) => {
-                let mut result =
-                    MemTripleStore::new_from_boxed_id_generator(self.id_generator.clone());
-                for pred in items {
-                    for r in self.pos_data.range(Id::key_bounds_1(pred)) {
-                        let (key, data_id) = r.map_err(|e| {
-                            QueryError::Left(super::SledTripleStoreError::SledError(e))
-                        })?;
-                        if let Some(data) = self.edge_props.get(&data_id).map_err(|e| {
-                            QueryError::Left(super::SledTripleStoreError::SledError(e))
-                        })? {
-                            result
-                                .insert_edge(
-                                    Id::decode_pos_triple(&key[..].try_into().map_err(|_| {
-                                        QueryError::Left(super::SledTripleStoreError::KeySizeError)
-                                    })?),
-                                    bincode::deserialize(&data).map_err(|e| {
-                                        QueryError::Left(
-                                            super::SledTripleStoreError::SerializationError(e),
-                                        )
-                                    })?,
-                                )
-                                .map_err(|e| QueryError::Right(e))?;
-                        }
-                    }
-                }
-                result
-            }
+                Query::SO(items) => (
+                    Box::new(std::iter::empty()),
+                    Box::new(items.into_iter().flat_map(move |(sub, obj)| {
+                        self.query_range(
+                            &self.osp_data,
+                            Id::key_bounds_2(obj, sub),
+                            Id::decode_osp_triple,
+                            filter,
+                        )
+                    })),
+                ),
 
-            Query::PO(items) => {
-                let mut result =
-                    MemTripleStore::new_from_boxed_id_generator(self.id_generator.clone());
-                for (pred, obj) in items {
-                    for r in self.pos_data.range(Id::key_bounds_2(pred, obj)) {
-                        let (key, data_id) = r.map_err(|e| {
-                            QueryError::Left(super::SledTripleStoreError::SledError(e))
-                        })?;
-                        if let Some(data) = self.edge_props.get(&data_id).map_err(|e| {
-                            QueryError::Left(super::SledTripleStoreError::SledError(e))
-                        })? {
-                            result
-                                .insert_edge(
-                                    Id::decode_pos_triple(&key[..].try_into().map_err(|_| {
-                                        QueryError::Left(super::SledTripleStoreError::KeySizeError)
-                                    })?),
-                                    bincode::deserialize(&data).map_err(|e| {
-                                        QueryError::Left(
-                                            super::SledTripleStoreError::SerializationError(e),
-                                        )
-                                    })?,
-                                )
-                                .map_err(|e| QueryError::Right(e))?;
-                        }
-                    }
-                }
-                result
-            }
+                Query::P(items) => (
+                    Box::new(std::iter::empty()),
+                    Box::new(items.into_iter().flat_map(move |pred| {
+                        self.query_range(
+                            &self.pos_data,
+                            Id::key_bounds_1(pred),
+                            Id::decode_pos_triple,
+                            filter,
+                        )
+                    })),
+                ),
 
-            Query::O(items) => {
-                let mut result =
-                    MemTripleStore::new_from_boxed_id_generator(self.id_generator.clone());
-                for obj in items {
-                    for r in self.osp_data.range(Id::key_bounds_1(obj)) {
-                        let (key, data_id) = r.map_err(|e| {
-                            QueryError::Left(super::SledTripleStoreError::SledError(e))
-                        })?;
-                        if let Some(data) = self.edge_props.get(&data_id).map_err(|e| {
-                            QueryError::Left(super::SledTripleStoreError::SledError(e))
-                        })? {
-                            result
-                                .insert_edge(
-                                    Id::decode_osp_triple(&key[..].try_into().map_err(|_| {
-                                        QueryError::Left(super::SledTripleStoreError::KeySizeError)
-                                    })?),
-                                    bincode::deserialize(&data).map_err(|e| {
-                                        QueryError::Left(
-                                            super::SledTripleStoreError::SerializationError(e),
-                                        )
-                                    })?,
-                                )
-                                .map_err(|e| QueryError::Right(e))?;
-                        }
-                    }
-                }
-                result
-            }
-        })
+                Query::PO(items) => (
+                    Box::new(std::iter::empty()),
+                    Box::new(items.into_iter().flat_map(move |(pred, obj)| {
+                        self.query_range(
+                            &self.pos_data,
+                            Id::key_bounds_2(pred, obj),
+                            Id::decode_pos_triple,
+                            filter,
+                        )
+                    })),
+                ),
+
+                Query::O(items) => (
+                    Box::new(std::iter::empty()),
+                    Box::new(items.into_iter().flat_map(move |obj| {
+                        self.query_range(
+                            &self.osp_data,
+                            Id::key_bounds_1(obj),
+                            Id::decode_osp_triple,
+                            filter,
+                        )
+                    })),
+                ),
+            };
+
+        (nodes, edges)
     }
-}
 
+    fn run_filtered(
+        &self,
+        query: Query<Id>,
+        filter: PropsFilter<Id, NodeProps, EdgeProps>,
+    ) -> Result<Self::QueryResult, RunError<Self, Id, NodeProps, EdgeProps>> {
+        let mut result = MemTripleStore::new_from_boxed_id_generator(self.id_generator.clone());
+
+        let (nodes, edges) = self.iter_query_filtered(query, filter);
+        for r in nodes {
+            let (node, data) = r.map_err(QueryError::Left)?;
+            result.insert_node(node, data).map_err(QueryError::Right)?;
+        }
+        for r in edges {
+            let (triple, data) = r.map_err(QueryError::Left)?;
+            result
+                .insert_edge(triple, data)
+                .map_err(QueryError::Right)?;
+        }
+
+        Ok(result)
+    }
+}
 #[cfg(test)]
 mod test {
     use crate::{SledTripleStore, UlidIdGenerator};
@@ -310,4 +258,18 @@ mod test {
         let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
         crate::conformance::query::test_query_os(sled_db);
     }
+
+    #[test]
+    fn test_iter_query() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::query::test_iter_query(sled_db);
+    }
+
+    #[test]
+    fn test_query_filtered() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::query::test_query_filtered(sled_db);
+    }
 }
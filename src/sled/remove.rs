@@ -1,11 +1,13 @@
 use std::borrow::Borrow;
 
 use serde::{de::DeserializeOwned, Serialize};
-use sled::Batch;
-use sled::Transactional;
 
 use crate::Triple;
-use crate::{prelude::*, sled::SledTripleStoreError, traits::ConcreteIdType, traits::Property};
+use crate::{
+    prelude::*,
+    sled::SledTripleStoreError,
+    traits::{ConcreteIdType, Event, Property},
+};
 
 use super::SledTripleStore;
 
@@ -16,108 +18,70 @@ impl<
     > TripleStoreRemove<Id, NodeProps, EdgeProps> for SledTripleStore<Id, NodeProps, EdgeProps>
 {
     fn remove_node(&mut self, node: impl Borrow<Id>) -> Result<(), Self::Error> {
-        // Collect forward edges from this node as subject.
-        let (spo_forward_batch, pos_forward_batch, osp_forward_batch, edge_props_forward_batch) =
-            self.spo_data
-                .range(Id::key_bounds_1(node.borrow().clone()))
-                .try_fold(
-                    (
-                        Batch::default(),
-                        Batch::default(),
-                        Batch::default(),
-                        Batch::default(),
-                    ),
-                    |(mut spo_batch, mut pos_batch, mut osp_batch, mut edge_data_ids), r| {
-                        let (spo_triple, edge_data_id) =
-                            r.map_err(|e| SledTripleStoreError::SledError(e))?;
-
-                        let triple = Id::decode_spo_triple(
-                            &spo_triple[..]
-                                .try_into()
-                                .map_err(|_| super::SledTripleStoreError::KeySizeError)?,
-                        );
-
-                        spo_batch.remove(&spo_triple);
-                        pos_batch.remove(Id::encode_pos_triple(&triple).as_ref());
-                        osp_batch.remove(Id::encode_osp_triple(&triple).as_ref());
-                        edge_data_ids.remove(edge_data_id.as_ref());
-
-                        Ok::<(Batch, Batch, Batch, Batch), SledTripleStoreError>((
-                            spo_batch,
-                            pos_batch,
-                            osp_batch,
-                            edge_data_ids,
-                        ))
-                    },
-                )?;
-
-        // Collect backward edges from this node as object.
-        let (spo_backward_batch, pos_backward_batch, osp_backward_batch, edge_props_backward_batch) =
-            self.osp_data
-                .range(Id::key_bounds_1(node.borrow().clone()))
-                .try_fold(
-                    (
-                        Batch::default(),
-                        Batch::default(),
-                        Batch::default(),
-                        Batch::default(),
-                    ),
-                    |(mut spo_batch, mut pos_batch, mut osp_batch, mut edge_data_ids), r| {
-                        let (osp_triple, edge_data_id) =
-                            r.map_err(|e| SledTripleStoreError::SledError(e))?;
-
-                        let triple = Id::decode_osp_triple(
-                            &osp_triple[..]
-                                .try_into()
-                                .map_err(|_| super::SledTripleStoreError::KeySizeError)?,
-                        );
-
-                        osp_batch.remove(&osp_triple);
-                        pos_batch.remove(Id::encode_pos_triple(&triple).as_ref());
-                        spo_batch.remove(Id::encode_spo_triple(&triple).as_ref());
-                        edge_data_ids.remove(edge_data_id.as_ref());
-
-                        Ok::<(Batch, Batch, Batch, Batch), SledTripleStoreError>((
-                            spo_batch,
-                            pos_batch,
-                            osp_batch,
-                            edge_data_ids,
-                        ))
-                    },
-                )?;
-
-        // Remove the NodeProps, EdgeProps, and edges in one transaction.
-        (
-            &self.node_props,
-            &self.edge_props,
-            &self.spo_data,
-            &self.pos_data,
-            &self.osp_data,
-        )
-            .transaction(|(node_props, edge_props, spo_data, pos_data, osp_data)| {
-                node_props.remove(node.borrow().to_be_bytes().as_ref())?;
-
-                edge_props.apply_batch(&edge_props_forward_batch)?;
-                edge_props.apply_batch(&edge_props_backward_batch)?;
-
-                spo_data.apply_batch(&spo_forward_batch)?;
-                spo_data.apply_batch(&spo_backward_batch)?;
-
-                pos_data.apply_batch(&pos_forward_batch)?;
-                pos_data.apply_batch(&pos_backward_batch)?;
-
-                osp_data.apply_batch(&osp_forward_batch)?;
-                osp_data.apply_batch(&osp_backward_batch)?;
+        // Remove the NodeProps, EdgeProps, and edges, including those in named graphs, in one transaction. The edges
+        // are scanned beforehand, so start again if any of them was written in between.
+        let (removed_node, removed_edges, removed_quads) = loop {
+            let watch = self.watch_nodes([node.borrow()]);
+            let edges = self.node_edges(node.borrow())?;
+            let quads = self.node_quads(node.borrow())?;
+            let removed = self.write_transaction(|trees| {
+                if watch.changed() {
+                    return Ok(None);
+                }
+                let old_data = trees
+                    .node_props
+                    .remove(node.borrow().to_be_bytes().as_ref())?;
+                let removed_node = old_data.is_some();
+                if removed_node {
+                    trees.count_nodes(-1)?;
+                }
+                let old_props = trees.indexed_node_props(old_data.as_deref())?;
+                trees.reindex_node(node.borrow(), old_props.as_ref(), None)?;
+
+                let mut removed_edges = Vec::new();
+                for triple in &edges {
+                    trees
+                        .pos_data
+                        .remove(Id::encode_pos_triple(triple).as_ref())?;
+                    trees
+                        .osp_data
+                        .remove(Id::encode_osp_triple(triple).as_ref())?;
+                    if let Some(edge_props_id) = trees
+                        .spo_data
+                        .remove(Id::encode_spo_triple(triple).as_ref())?
+                    {
+                        let old_data = trees.edge_props.remove(edge_props_id)?;
+                        trees.count_edge(triple, false)?;
+                        let old_props = trees.indexed_edge_props(old_data.as_deref())?;
+                        trees.reindex_edge(triple, old_props.as_ref(), None)?;
+                        removed_edges.push(triple.clone());
+                    }
+                }
 
-                Ok(())
-            })
-            .map_err(|e| match e {
-                sled::transaction::TransactionError::Abort(e) => e,
-                sled::transaction::TransactionError::Storage(e) => {
-                    SledTripleStoreError::SledError(e)
+                let mut removed_quads = Vec::new();
+                for quad in &quads {
+                    if trees.delete_quad(quad)? {
+                        removed_quads.push(quad.clone());
+                    }
                 }
+                Ok(Some((removed_node, removed_edges, removed_quads)))
             })?;
+            if let Some(removed) = removed {
+                break removed;
+            }
+        };
 
+        for triple in removed_edges {
+            self.subscribers.publish(|| Event::EdgeRemoved { triple });
+        }
+        for quad in removed_quads {
+            self.subscribers.publish(|| Event::QuadRemoved { quad });
+        }
+        if removed_node {
+            self.subscribers.publish(|| Event::NodeRemoved {
+                node: *node.borrow(),
+            });
+        }
         Ok(())
     }
 
@@ -126,45 +90,40 @@ impl<
         let pos_triple = Id::encode_pos_triple(&triple);
         let osp_triple = Id::encode_osp_triple(&triple);
 
-        (
-            &self.spo_data,
-            &self.pos_data,
-            &self.osp_data,
-            &self.edge_props,
-        )
-            .transaction(|(spo_data, pos_data, osp_data, edge_props)| {
-                let edge_props_id = spo_data.remove(spo_triple.as_ref())?;
-                pos_data.remove(pos_triple.as_ref())?;
-                osp_data.remove(osp_triple.as_ref())?;
-                if let Some(edge_props_id) = edge_props_id {
-                    edge_props.remove(edge_props_id)?;
-                }
-                Ok(())
-            })
-            .map_err(|e| match e {
-                sled::transaction::TransactionError::Abort(e) => e,
-                sled::transaction::TransactionError::Storage(e) => {
-                    SledTripleStoreError::SledError(e)
-                }
-            })?;
+        let removed = self.write_transaction(|trees| {
+            let edge_props_id = trees.spo_data.remove(spo_triple.as_ref())?;
+            trees.pos_data.remove(pos_triple.as_ref())?;
+            trees.osp_data.remove(osp_triple.as_ref())?;
+            let removed = edge_props_id.is_some();
+            if let Some(edge_props_id) = edge_props_id {
+                let old_data = trees.edge_props.remove(edge_props_id)?;
+                trees.count_edge(&triple, false)?;
+                let old_props = trees.indexed_edge_props(old_data.as_deref())?;
+                trees.reindex_edge(&triple, old_props.as_ref(), None)?;
+            }
+            Ok(removed)
+        })?;
 
         let edge_data_id = self
             .spo_data
             .remove(Id::encode_spo_triple(&triple))
-            .map_err(|e| SledTripleStoreError::SledError(e))?;
+            .map_err(SledTripleStoreError::SledError)?;
         self.pos_data
             .remove(Id::encode_pos_triple(&triple))
-            .map_err(|e| SledTripleStoreError::SledError(e))?;
+            .map_err(SledTripleStoreError::SledError)?;
         self.osp_data
             .remove(Id::encode_osp_triple(&triple))
-            .map_err(|e| SledTripleStoreError::SledError(e))?;
+            .map_err(SledTripleStoreError::SledError)?;
 
         if let Some(edge_data_id) = edge_data_id {
             self.edge_props
                 .remove(edge_data_id)
-                .map_err(|e| SledTripleStoreError::SledError(e))?;
+                .map_err(SledTripleStoreError::SledError)?;
         }
 
+        if removed {
+            self.subscribers.publish(|| Event::EdgeRemoved { triple });
+        }
         Ok(())
     }
 }
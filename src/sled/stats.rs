@@ -0,0 +1,327 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{de::DeserializeOwned, Serialize};
+use sled::transaction::{ConflictableTransactionError, ConflictableTransactionResult};
+
+use crate::{
+    prelude::*,
+    traits::{ConcreteIdType, PredicateStats, Property, Statistics, StatsDelta},
+    Triple,
+};
+
+use super::{write::WriteTrees, SledTripleStore, SledTripleStoreError};
+
+/// The key of the node count in the statistics tree.
+const NODES_KEY: &[u8] = b"n";
+
+/// The prefix of the keys of predicate statistics, which are followed by the predicate id.
+const PREDICATE_PREFIX: &[u8] = b"p";
+
+/// The prefix of the keys which count the edges from a subject with a predicate, followed by the predicate and
+/// subject ids.
+const SUBJECT_PREFIX: &[u8] = b"s";
+
+/// The prefix of the keys which count the edges to an object with a predicate, followed by the predicate and object
+/// ids.
+const OBJECT_PREFIX: &[u8] = b"o";
+
+fn predicate_key<Id: ConcreteIdType>(pred: Id) -> Vec<u8> {
+    let mut key = PREDICATE_PREFIX.to_vec();
+    key.extend_from_slice(pred.to_be_bytes().as_ref());
+    key
+}
+
+fn pair_key<Id: ConcreteIdType>(prefix: &[u8], pred: Id, node: Id) -> Vec<u8> {
+    let mut key = prefix.to_vec();
+    key.extend_from_slice(pred.to_be_bytes().as_ref());
+    key.extend_from_slice(node.to_be_bytes().as_ref());
+    key
+}
+
+fn decode_count(bytes: &[u8]) -> u64 {
+    bytes.try_into().map(u64::from_be_bytes).unwrap_or_default()
+}
+
+impl<NodeProps, EdgeProps> WriteTrees<'_, NodeProps, EdgeProps> {
+    /// Adjust the node count by `delta`.
+    pub(super) fn count_nodes(
+        &self,
+        delta: i64,
+    ) -> ConflictableTransactionResult<(), SledTripleStoreError> {
+        if delta != 0 {
+            self.count(NODES_KEY.to_vec(), delta)?;
+        }
+        Ok(())
+    }
+
+    /// Update the statistics for `triple`, which has just been added (or removed, when `added` is false).
+    ///
+    /// The edges from each subject and to each object of a predicate are counted alongside the statistics, so whether
+    /// the subject or object is new to the predicate, or gone from it, is known without scanning the edges. Sled
+    /// transactions cannot scan, and a scan made before the transaction could miss a concurrent write, so this costs
+    /// three statistics writes per edge written.
+    pub(super) fn count_edge<Id: ConcreteIdType>(
+        &self,
+        triple: &Triple<Id>,
+        added: bool,
+    ) -> ConflictableTransactionResult<(), SledTripleStoreError> {
+        let sign = if added { 1 } else { -1 };
+        let delta = StatsDelta {
+            edges: sign,
+            subjects: sign
+                * self.count(pair_key(SUBJECT_PREFIX, triple.pred, triple.sub), sign)? as i64,
+            objects: sign
+                * self.count(pair_key(OBJECT_PREFIX, triple.pred, triple.obj), sign)? as i64,
+        };
+
+        let key = predicate_key(triple.pred);
+        let mut stats = match self.statistics.get(&key)? {
+            Some(data) => bincode::deserialize(&data).map_err(|e| {
+                ConflictableTransactionError::Abort(SledTripleStoreError::SerializationError(e))
+            })?,
+            None => PredicateStats::default(),
+        };
+        delta.apply(&mut stats);
+
+        if stats.edges == 0 {
+            self.statistics.remove(key)?;
+        } else {
+            self.statistics.insert(
+                key,
+                bincode::serialize(&stats).map_err(|e| {
+                    ConflictableTransactionError::Abort(SledTripleStoreError::SerializationError(e))
+                })?,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Adjust the count under `key` by `delta`, returning whether it went up from zero or down to zero.
+    fn count(
+        &self,
+        key: Vec<u8>,
+        delta: i64,
+    ) -> ConflictableTransactionResult<bool, SledTripleStoreError> {
+        let old = self
+            .statistics
+            .get(&key)?
+            .map_or(0, |count| decode_count(&count));
+        let new = old.saturating_add_signed(delta);
+        if new == 0 {
+            self.statistics.remove(key)?;
+        } else {
+            self.statistics.insert(key, new.to_be_bytes().as_ref())?;
+        }
+        Ok((old == 0) != (new == 0))
+    }
+}
+
+impl<
+        Id: ConcreteIdType,
+        NodeProps: Property + Serialize + DeserializeOwned,
+        EdgeProps: Property + Serialize + DeserializeOwned,
+    > SledTripleStore<Id, NodeProps, EdgeProps>
+{
+    /// The contents of the statistics tree, recounted from the nodes and edges.
+    pub(super) fn recount_statistics(
+        &self,
+    ) -> Result<BTreeMap<Vec<u8>, Vec<u8>>, SledTripleStoreError> {
+        let mut subjects: HashMap<(Id, Id), u64> = HashMap::new();
+        let mut objects: HashMap<(Id, Id), u64> = HashMap::new();
+        for r in self.match_triples(None, None, None) {
+            let triple = r?;
+            *subjects.entry((triple.pred, triple.sub)).or_default() += 1;
+            *objects.entry((triple.pred, triple.obj)).or_default() += 1;
+        }
+
+        let mut entries = BTreeMap::new();
+        let mut predicates: HashMap<Id, PredicateStats> = HashMap::new();
+        for ((pred, sub), count) in subjects {
+            let stats = predicates.entry(pred).or_default();
+            stats.edges += count;
+            stats.subjects += 1;
+            entries.insert(
+                pair_key(SUBJECT_PREFIX, pred, sub),
+                count.to_be_bytes().to_vec(),
+            );
+        }
+        for ((pred, obj), count) in objects {
+            predicates.entry(pred).or_default().objects += 1;
+            entries.insert(
+                pair_key(OBJECT_PREFIX, pred, obj),
+                count.to_be_bytes().to_vec(),
+            );
+        }
+        for (pred, stats) in predicates {
+            entries.insert(predicate_key(pred), bincode::serialize(&stats)?);
+        }
+
+        let nodes = self.node_props.len() as u64;
+        if nodes > 0 {
+            entries.insert(NODES_KEY.to_vec(), nodes.to_be_bytes().to_vec());
+        }
+        Ok(entries)
+    }
+
+    /// Recount the statistics from scratch, in one pass over every edge.
+    ///
+    /// Statistics are updated in the same transaction as the writes they count, so this is only needed to repair a
+    /// store which was changed behind its back, or one written before statistics were kept, which has none. Such
+    /// stores are reported by [verify][crate::traits::TripleStoreVerify::verify].
+    pub fn rebuild_statistics(&self) -> Result<(), SledTripleStoreError> {
+        let entries = self.recount_statistics()?;
+
+        let mut batch = sled::Batch::default();
+        for r in self.statistics.iter() {
+            let (key, _) = r?;
+            batch.remove(key);
+        }
+        for (key, value) in entries {
+            batch.insert(key, value);
+        }
+        Ok(self.statistics.apply_batch(batch)?)
+    }
+}
+
+impl<
+        Id: ConcreteIdType,
+        NodeProps: Property + Serialize + DeserializeOwned,
+        EdgeProps: Property + Serialize + DeserializeOwned,
+    > TripleStoreStats<Id, NodeProps, EdgeProps> for SledTripleStore<Id, NodeProps, EdgeProps>
+{
+    fn statistics(&self) -> Result<Statistics<Id>, SledTripleStoreError> {
+        let nodes = self
+            .statistics
+            .get(NODES_KEY)?
+            .map(|data| {
+                data.as_ref()
+                    .try_into()
+                    .map(u64::from_be_bytes)
+                    .map_err(|_| SledTripleStoreError::KeySizeError)
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        let mut predicates = HashMap::new();
+        for r in self.statistics.scan_prefix(PREDICATE_PREFIX) {
+            let (key, data) = r?;
+            let pred = Id::try_from_be_bytes(&key[PREDICATE_PREFIX.len()..])
+                .ok_or(SledTripleStoreError::KeySizeError)?;
+            predicates.insert(pred, bincode::deserialize::<PredicateStats>(&data)?);
+        }
+
+        Ok(Statistics {
+            nodes,
+            edges: predicates.values().map(|stats| stats.edges).sum(),
+            predicates,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{prelude::*, traits::Statistics, SledTripleStore, Triple, UlidIdGenerator};
+
+    #[test]
+    fn test_statistics() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::stats::test_statistics(sled_db);
+    }
+
+    #[test]
+    fn test_statistics_bulk() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::stats::test_statistics_bulk(sled_db);
+    }
+
+    #[test]
+    fn test_explain() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::stats::test_explain(sled_db);
+    }
+
+    #[test]
+    fn test_statistics_match_recount() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let mut sled_db: SledTripleStore<ulid::Ulid, (), ()> =
+            SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        let triple = |sub: u128, pred: u128, obj: u128| Triple {
+            sub: ulid::Ulid(sub),
+            pred: ulid::Ulid(pred),
+            obj: ulid::Ulid(obj),
+        };
+        let stale = |sled_db: &SledTripleStore<ulid::Ulid, (), ()>| {
+            sled_db.verify().expect("ok").stale_statistics
+        };
+
+        sled_db.insert_node(ulid::Ulid(1), ()).expect("ok");
+        sled_db.insert_edge(triple(1, 10, 2), ()).expect("ok");
+        sled_db.insert_edge(triple(1, 10, 3), ()).expect("ok");
+        sled_db.insert_edge(triple(1, 10, 3), ()).expect("ok");
+        sled_db.merge_edge(triple(2, 10, 3), ()).expect("ok");
+        sled_db.merge_node(ulid::Ulid(2), ()).expect("ok");
+        assert!(!stale(&sled_db));
+
+        sled_db
+            .bulk_load([(triple(3, 11, 1), ()), (triple(1, 10, 2), ())], false)
+            .expect("ok");
+        sled_db
+            .insert_nodes([(ulid::Ulid(2), ()), (ulid::Ulid(3), ())])
+            .expect("ok");
+        assert!(!stale(&sled_db));
+
+        sled_db
+            .transaction(|tx| {
+                tx.insert_edge(triple(3, 10, 3), ())?;
+                tx.remove_edge(triple(1, 10, 2))?;
+                tx.remove_node(ulid::Ulid(2))
+            })
+            .expect("ok");
+        assert!(!stale(&sled_db));
+
+        sled_db.remove_node(ulid::Ulid(3)).expect("ok");
+        sled_db.remove_edge(triple(1, 10, 3)).expect("ok");
+        sled_db.remove_node(ulid::Ulid(1)).expect("ok");
+        assert!(!stale(&sled_db));
+        assert_eq!(sled_db.statistics().expect("ok"), Statistics::default());
+    }
+
+    #[test]
+    fn test_statistics_persist() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let mut sled_db: SledTripleStore<ulid::Ulid, (), ()> =
+            SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        let (a, b, knows) = (ulid::Ulid(1), ulid::Ulid(2), ulid::Ulid(10));
+        sled_db.insert_node(a, ()).expect("ok");
+        sled_db
+            .insert_edge(
+                Triple {
+                    sub: a,
+                    pred: knows,
+                    obj: b,
+                },
+                (),
+            )
+            .expect("ok");
+        let expected = sled_db.statistics().expect("ok");
+        assert_eq!(expected.nodes, 1);
+        assert_eq!(expected.edges, 1);
+
+        // Reopening reads the persisted statistics.
+        let reopened: SledTripleStore<ulid::Ulid, (), ()> =
+            SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        assert_eq!(reopened.statistics().expect("ok"), expected);
+
+        // Statistics which are missing are only rebuilt when asked, since that scans every edge.
+        db.drop_tree(b"statistics").expect("ok");
+        let rebuilt: SledTripleStore<ulid::Ulid, (), ()> =
+            SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        assert_eq!(rebuilt.statistics().expect("ok"), Statistics::default());
+        assert!(rebuilt.verify().expect("ok").stale_statistics);
+        rebuilt.rebuild_statistics().expect("ok");
+        assert_eq!(rebuilt.statistics().expect("ok"), expected);
+    }
+}
@@ -0,0 +1,68 @@
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    prelude::*,
+    traits::{ConcreteIdType, EventFilter, Property, Subscription},
+};
+
+use super::SledTripleStore;
+
+impl<
+        Id: ConcreteIdType,
+        NodeProps: Property + Serialize + DeserializeOwned,
+        EdgeProps: Property + Serialize + DeserializeOwned,
+    > TripleStoreSubscribe<Id, NodeProps, EdgeProps> for SledTripleStore<Id, NodeProps, EdgeProps>
+{
+    fn subscribe(&self, filter: EventFilter<Id>) -> Subscription<Id, NodeProps, EdgeProps> {
+        self.subscribers.subscribe(filter)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{SledTripleStore, UlidIdGenerator};
+
+    #[test]
+    fn test_subscribe() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::subscribe::test_subscribe(sled_db);
+    }
+
+    #[test]
+    fn test_subscribe_merge() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::subscribe::test_subscribe_merge(sled_db);
+    }
+
+    #[test]
+    fn test_subscribe_bulk() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::subscribe::test_subscribe_bulk(sled_db);
+    }
+
+    #[test]
+    fn test_subscribe_extend() {
+        let (_left_tempdir, left_db) = crate::sled::create_test_db().expect("ok");
+        let (_right_tempdir, right_db) = crate::sled::create_test_db().expect("ok");
+        let left = SledTripleStore::new(&left_db, UlidIdGenerator::new()).expect("ok");
+        let right = SledTripleStore::new(&right_db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::subscribe::test_subscribe_extend(left, right);
+    }
+
+    #[test]
+    fn test_subscribe_filter() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::subscribe::test_subscribe_filter(sled_db);
+    }
+
+    #[test]
+    fn test_subscribe_quads() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::subscribe::test_subscribe_quads(sled_db);
+    }
+}
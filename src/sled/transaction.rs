@@ -0,0 +1,394 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use sled::transaction::{ConflictableTransactionError, ConflictableTransactionResult};
+
+use crate::{
+    prelude::*,
+    traits::{ConcreteIdType, Event, MergedProps, Operation, Property, Transaction},
+    Quad, Triple,
+};
+
+use super::{write::WriteTrees, SledTripleStore, SledTripleStoreError};
+
+/// What a transaction changed, beyond the props it wrote.
+struct Applied<Id: ConcreteIdType> {
+    /// Nodes whose stored props were replaced by merged ones.
+    merged_nodes: HashSet<Id>,
+    /// Edges whose stored props were replaced by merged ones.
+    merged_edges: HashSet<Triple<Id>>,
+    removed_nodes: Vec<Id>,
+    removed_edges: Vec<Triple<Id>>,
+    removed_quads: Vec<Quad<Id>>,
+}
+
+impl<Id: ConcreteIdType> Default for Applied<Id> {
+    fn default() -> Self {
+        Self {
+            merged_nodes: HashSet::new(),
+            merged_edges: HashSet::new(),
+            removed_nodes: Vec::new(),
+            removed_edges: Vec::new(),
+            removed_quads: Vec::new(),
+        }
+    }
+}
+
+impl<NodeProps: DeserializeOwned, EdgeProps: DeserializeOwned>
+    WriteTrees<'_, NodeProps, EdgeProps>
+{
+    /// The props of `node` as stored, as seen from inside the transaction.
+    fn stored_node<Id: ConcreteIdType>(
+        &self,
+        node: &Id,
+    ) -> ConflictableTransactionResult<Option<NodeProps>, SledTripleStoreError> {
+        match self.node_props.get(node.to_be_bytes().as_ref())? {
+            Some(data) => Ok(Some(bincode::deserialize(&data).map_err(serialization)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// The props of `triple` as stored, as seen from inside the transaction.
+    fn stored_edge<Id: ConcreteIdType>(
+        &self,
+        triple: &Triple<Id>,
+    ) -> ConflictableTransactionResult<Option<EdgeProps>, SledTripleStoreError> {
+        let Some(prop_key) = self.spo_data.get(Id::encode_spo_triple(triple).as_ref())? else {
+            return Ok(None);
+        };
+        match self.edge_props.get(prop_key)? {
+            Some(data) => Ok(Some(bincode::deserialize(&data).map_err(serialization)?)),
+            None => Err(ConflictableTransactionError::Abort(
+                SledTripleStoreError::MissingPropertyData,
+            )),
+        }
+    }
+}
+
+fn serialization(e: bincode::Error) -> ConflictableTransactionError<SledTripleStoreError> {
+    ConflictableTransactionError::Abort(SledTripleStoreError::SerializationError(e))
+}
+
+impl<
+        Id: ConcreteIdType,
+        NodeProps: Property + Serialize + DeserializeOwned,
+        EdgeProps: Property + Serialize + DeserializeOwned,
+    > TripleStoreTransaction<Id, NodeProps, EdgeProps>
+    for SledTripleStore<Id, NodeProps, EdgeProps>
+{
+    fn transaction<R>(
+        &mut self,
+        f: impl FnOnce(
+            &mut Transaction<Id, NodeProps, EdgeProps, SledTripleStoreError>,
+        ) -> Result<R, SledTripleStoreError>,
+    ) -> Result<R, SledTripleStoreError> {
+        let mut tx = Transaction::new();
+        let result = f(&mut tx)?;
+        let operations = tx.into_operations();
+
+        // Allocate prop keys for the edges which end up written up front, since the transaction closure may be
+        // retried. Which edges those are does not depend on what is stored.
+        let mut written = HashMap::new();
+        for operation in &operations {
+            match operation {
+                Operation::InsertEdge(triple, _) | Operation::MergeEdge(triple, _, _) => {
+                    written.insert(triple.clone(), true);
+                }
+                Operation::RemoveEdge(triple) => {
+                    written.insert(triple.clone(), false);
+                }
+                Operation::RemoveNode(node) => {
+                    for (triple, written) in written.iter_mut() {
+                        if triple.sub == *node || triple.obj == *node {
+                            *written = false;
+                        }
+                    }
+                }
+                Operation::InsertNode(..) | Operation::MergeNode(..) => {}
+            }
+        }
+        let prop_keys = written
+            .into_iter()
+            .filter(|(_, written)| *written)
+            .map(|(triple, _)| Ok((triple, self.id_generator.try_fresh()?.to_be_bytes())))
+            .collect::<Result<HashMap<_, _>, SledTripleStoreError>>()?;
+
+        let removed_nodes = operations
+            .iter()
+            .filter_map(|operation| match operation {
+                Operation::RemoveNode(node) => Some(*node),
+                _ => None,
+            })
+            .collect::<HashSet<_>>();
+
+        let (nodes, edges, applied) = loop {
+            // The edges of removed nodes are scanned beforehand, so start again if any of them was written in between.
+            let watch = self.watch_nodes(&removed_nodes);
+            let node_edges = removed_nodes
+                .iter()
+                .map(|node| Ok((*node, self.node_edges(node)?)))
+                .collect::<Result<HashMap<_, _>, SledTripleStoreError>>()?;
+            let node_quads = removed_nodes
+                .iter()
+                .map(|node| self.node_quads(node))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let outcome = self.write_transaction(|trees| {
+                if watch.changed() {
+                    return Ok(None);
+                }
+
+                // Reduce the operations to the final props of every node and edge they touch, where `None` is a
+                // removal. Merges read the props they build on from inside the transaction.
+                let mut nodes: HashMap<Id, Option<NodeProps>> = HashMap::new();
+                let mut edges: HashMap<Triple<Id>, Option<EdgeProps>> = HashMap::new();
+                // The nodes and edges whose final props were merged into earlier ones.
+                let mut merged_nodes = HashSet::new();
+                let mut merged_edges = HashSet::new();
+                for operation in &operations {
+                    match operation {
+                        Operation::InsertNode(node, props) => {
+                            merged_nodes.remove(node);
+                            nodes.insert(*node, Some(props.clone()));
+                        }
+                        Operation::InsertEdge(triple, props) => {
+                            merged_edges.remove(triple);
+                            edges.insert(triple.clone(), Some(props.clone()));
+                        }
+                        Operation::MergeNode(node, props, merge) => {
+                            let old = match nodes.get(node) {
+                                Some(planned) => planned.clone(),
+                                None => trees.stored_node(node)?,
+                            };
+                            let props = match old {
+                                Some(mut old) => {
+                                    merge(&mut old, props.clone());
+                                    merged_nodes.insert(*node);
+                                    old
+                                }
+                                None => {
+                                    merged_nodes.remove(node);
+                                    props.clone()
+                                }
+                            };
+                            nodes.insert(*node, Some(props));
+                        }
+                        Operation::MergeEdge(triple, props, merge) => {
+                            let old = match edges.get(triple) {
+                                Some(planned) => planned.clone(),
+                                None => trees.stored_edge(triple)?,
+                            };
+                            let props = match old {
+                                Some(mut old) => {
+                                    merge(&mut old, props.clone());
+                                    merged_edges.insert(triple.clone());
+                                    old
+                                }
+                                None => {
+                                    merged_edges.remove(triple);
+                                    props.clone()
+                                }
+                            };
+                            edges.insert(triple.clone(), Some(props));
+                        }
+                        Operation::RemoveNode(node) => {
+                            merged_nodes.remove(node);
+                            nodes.insert(*node, None);
+
+                            // Edges to or from the node, both in the store and planned by earlier operations.
+                            let mut removed = node_edges.get(node).cloned().unwrap_or_default();
+                            removed.extend(
+                                edges
+                                    .keys()
+                                    .filter(|triple| triple.sub == *node || triple.obj == *node)
+                                    .cloned(),
+                            );
+                            for triple in removed {
+                                merged_edges.remove(&triple);
+                                edges.insert(triple, None);
+                            }
+                        }
+                        Operation::RemoveEdge(triple) => {
+                            merged_edges.remove(triple);
+                            edges.insert(triple.clone(), None);
+                        }
+                    }
+                }
+
+                let WriteTrees {
+                    node_props,
+                    edge_props,
+                    spo_data,
+                    pos_data,
+                    osp_data,
+                    ..
+                } = trees;
+
+                // Track what was actually replaced or removed, to publish afterwards.
+                let mut node_delta = 0;
+                let mut applied = Applied::default();
+
+                for (node, props) in &nodes {
+                    let key_bytes = node.to_be_bytes();
+                    let old_data = match props {
+                        Some(props) => {
+                            let data_bytes = bincode::serialize(props).map_err(serialization)?;
+                            let old_data = node_props.insert(key_bytes.as_ref(), data_bytes)?;
+                            match old_data {
+                                Some(_) if merged_nodes.contains(node) => {
+                                    applied.merged_nodes.insert(*node);
+                                }
+                                Some(_) => {}
+                                None => node_delta += 1,
+                            }
+                            old_data
+                        }
+                        None => {
+                            let old_data = node_props.remove(key_bytes.as_ref())?;
+                            if old_data.is_some() {
+                                node_delta -= 1;
+                                applied.removed_nodes.push(*node);
+                            }
+                            old_data
+                        }
+                    };
+                    let old_props = trees.indexed_node_props(old_data.as_deref())?;
+                    trees.reindex_node(node, old_props.as_ref(), props.as_ref())?;
+                }
+
+                for (triple, props) in &edges {
+                    let spo_triple = Id::encode_spo_triple(triple);
+                    let pos_triple = Id::encode_pos_triple(triple);
+                    let osp_triple = Id::encode_osp_triple(triple);
+
+                    let old_data = match (props, prop_keys.get(triple)) {
+                        (Some(props), Some(prop_key_bytes)) => {
+                            let data_bytes = bincode::serialize(props).map_err(serialization)?;
+                            edge_props.insert(prop_key_bytes.as_ref(), data_bytes)?;
+                            let old_prop_key =
+                                spo_data.insert(spo_triple.as_ref(), prop_key_bytes.as_ref())?;
+                            pos_data.insert(pos_triple.as_ref(), prop_key_bytes.as_ref())?;
+                            osp_data.insert(osp_triple.as_ref(), prop_key_bytes.as_ref())?;
+                            match old_prop_key {
+                                Some(old_prop_key) => {
+                                    if merged_edges.contains(triple) {
+                                        applied.merged_edges.insert(triple.clone());
+                                    }
+                                    edge_props.remove(old_prop_key)?
+                                }
+                                None => {
+                                    trees.count_edge(triple, true)?;
+                                    None
+                                }
+                            }
+                        }
+                        (Some(_), None) => {
+                            unreachable!("a prop key is allocated for every edge which is written")
+                        }
+                        (None, _) => {
+                            let old_prop_key = spo_data.remove(spo_triple.as_ref())?;
+                            pos_data.remove(pos_triple.as_ref())?;
+                            osp_data.remove(osp_triple.as_ref())?;
+                            match old_prop_key {
+                                Some(old_prop_key) => {
+                                    trees.count_edge(triple, false)?;
+                                    applied.removed_edges.push(triple.clone());
+                                    edge_props.remove(old_prop_key)?
+                                }
+                                None => None,
+                            }
+                        }
+                    };
+                    let old_props = trees.indexed_edge_props(old_data.as_deref())?;
+                    trees.reindex_edge(triple, old_props.as_ref(), props.as_ref())?;
+                }
+
+                // Removed nodes also lose their edges in the named graphs, which no operation writes.
+                for quad in node_quads.iter().flatten() {
+                    if trees.delete_quad(quad)? {
+                        applied.removed_quads.push(quad.clone());
+                    }
+                }
+
+                trees.count_nodes(node_delta)?;
+                Ok(Some((nodes, edges, applied)))
+            })?;
+            if let Some(outcome) = outcome {
+                break outcome;
+            }
+        };
+
+        // Publish the net changes, removing edges before the nodes they hang off. Merges into props which were
+        // already stored are reported as such, and anything else written as an insert.
+        for (node, props) in nodes {
+            if let Some(props) = props {
+                self.subscribers.publish(|| {
+                    if applied.merged_nodes.contains(&node) {
+                        Event::PropsMerged(MergedProps::Node { node, props })
+                    } else {
+                        Event::NodeInserted { node, props }
+                    }
+                });
+            }
+        }
+        for (triple, props) in edges {
+            if let Some(props) = props {
+                self.subscribers.publish(|| {
+                    if applied.merged_edges.contains(&triple) {
+                        Event::PropsMerged(MergedProps::Edge { triple, props })
+                    } else {
+                        Event::EdgeInserted { triple, props }
+                    }
+                });
+            }
+        }
+        for triple in applied.removed_edges {
+            self.subscribers.publish(|| Event::EdgeRemoved { triple });
+        }
+        for quad in applied.removed_quads {
+            self.subscribers.publish(|| Event::QuadRemoved { quad });
+        }
+        for node in applied.removed_nodes {
+            self.subscribers.publish(|| Event::NodeRemoved { node });
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{SledTripleStore, UlidIdGenerator};
+
+    #[test]
+    fn test_transaction_commit() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::transaction::test_transaction_commit(sled_db);
+    }
+
+    #[test]
+    fn test_transaction_rollback() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::transaction::test_transaction_rollback(
+            sled_db,
+            crate::sled::SledTripleStoreError::MissingPropertyData,
+        );
+    }
+
+    #[test]
+    fn test_transaction_merge() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::transaction::test_transaction_merge(sled_db);
+    }
+
+    #[test]
+    fn test_transaction_remove_node_quads() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::transaction::test_transaction_remove_node_quads(sled_db);
+    }
+}
@@ -0,0 +1,323 @@
+use std::collections::BTreeMap;
+
+use serde::{de::DeserializeOwned, Serialize};
+use sled::{Batch, Transactional};
+
+use crate::{
+    prelude::*,
+    traits::{check_quad_tables, check_tables, ConcreteIdType, IntegrityReport, Property},
+    triple::NodeQuadOrder,
+    Quad, QuadOrder,
+};
+
+use super::{SledTripleStore, SledTripleStoreError};
+
+/// Decode the entries of one of the edge tables to their triples or quads and the ids of their props.
+fn decode_entries<Id: ConcreteIdType, Key: for<'a> TryFrom<&'a [u8]>, Row>(
+    tree: &sled::Tree,
+    decode: impl Fn(&Key) -> Row,
+) -> impl Iterator<Item = Result<(Row, Id), SledTripleStoreError>> {
+    tree.iter().map(move |r| {
+        let (key, props_id) = r?;
+        let key = key[..]
+            .try_into()
+            .map_err(|_| SledTripleStoreError::KeySizeError)?;
+        let props_id =
+            Id::try_from_be_bytes(&props_id).ok_or(SledTripleStoreError::KeySizeError)?;
+        Ok((decode(&key), props_id))
+    })
+}
+
+impl<
+        Id: ConcreteIdType,
+        NodeProps: Property + Serialize + DeserializeOwned,
+        EdgeProps: Property + Serialize + DeserializeOwned,
+    > TripleStoreVerify<Id, NodeProps, EdgeProps> for SledTripleStore<Id, NodeProps, EdgeProps>
+{
+    fn verify(&self) -> Result<IntegrityReport<Id>, SledTripleStoreError> {
+        let mut report = check_tables(
+            decode_entries(&self.spo_data, Id::decode_spo_triple),
+            decode_entries(&self.pos_data, Id::decode_pos_triple),
+            decode_entries(&self.osp_data, Id::decode_osp_triple),
+            self.edge_props
+                .iter()
+                .keys()
+                .map(|r| Id::try_from_be_bytes(&r?).ok_or(SledTripleStoreError::KeySizeError)),
+        )?;
+        check_quad_tables(
+            &mut report,
+            decode_entries(&self.gspo_data, |key| Quad::decode(&QuadOrder::GSPO, key)),
+            decode_entries(&self.gpos_data, |key| Quad::decode(&QuadOrder::GPOS, key)),
+            decode_entries(&self.gosp_data, |key| Quad::decode(&QuadOrder::GOSP, key)),
+            decode_entries(&self.spog_data, |key| {
+                Quad::decode_node(NodeQuadOrder::SPOG, key)
+            }),
+            decode_entries(&self.ospg_data, |key| {
+                Quad::decode_node(NodeQuadOrder::OSPG, key)
+            }),
+            self.quad_props
+                .iter()
+                .keys()
+                .map(|r| Id::try_from_be_bytes(&r?).ok_or(SledTripleStoreError::KeySizeError)),
+        )?;
+
+        // Compare the whole tree, including the edge counts which the predicate statistics are updated from.
+        let stored = self
+            .statistics
+            .iter()
+            .map(|r| r.map(|(key, value)| (key.to_vec(), value.to_vec())))
+            .collect::<Result<BTreeMap<_, _>, _>>()?;
+        report.stale_statistics = stored != self.recount_statistics()?;
+        Ok(report)
+    }
+
+    fn repair(&mut self) -> Result<IntegrityReport<Id>, SledTripleStoreError> {
+        let report = self.verify()?;
+
+        let mut spo = Batch::default();
+        for triple in &report.missing_props {
+            spo.remove(Id::encode_spo_triple(triple).as_ref());
+        }
+
+        // Clear the POS and OSP tables, then write back an entry for every edge which is left in the SPO table.
+        let (mut pos, mut osp) = (Batch::default(), Batch::default());
+        for r in self.pos_data.iter().keys() {
+            pos.remove(r?);
+        }
+        for r in self.osp_data.iter().keys() {
+            osp.remove(r?);
+        }
+        for r in decode_entries::<Id, _, _>(&self.spo_data, Id::decode_spo_triple) {
+            let (triple, props_id) = r?;
+            if report.missing_props.contains(&triple) {
+                continue;
+            }
+            pos.insert(
+                Id::encode_pos_triple(&triple).as_ref(),
+                props_id.to_be_bytes().as_ref(),
+            );
+            osp.insert(
+                Id::encode_osp_triple(&triple).as_ref(),
+                props_id.to_be_bytes().as_ref(),
+            );
+        }
+
+        let mut edge_props = Batch::default();
+        for props_id in &report.orphaned_props {
+            edge_props.remove(props_id.to_be_bytes().as_ref());
+        }
+
+        // The named graphs are repaired the same way from the GSPO table.
+        let mut gspo = Batch::default();
+        for quad in &report.missing_quad_props {
+            gspo.remove(quad.encode(&QuadOrder::GSPO).as_ref());
+        }
+        let (mut gpos, mut gosp, mut spog, mut ospg) = (
+            Batch::default(),
+            Batch::default(),
+            Batch::default(),
+            Batch::default(),
+        );
+        for (tree, batch) in [
+            (&self.gpos_data, &mut gpos),
+            (&self.gosp_data, &mut gosp),
+            (&self.spog_data, &mut spog),
+            (&self.ospg_data, &mut ospg),
+        ] {
+            for r in tree.iter().keys() {
+                batch.remove(r?);
+            }
+        }
+        for r in
+            decode_entries::<Id, _, _>(&self.gspo_data, |key| Quad::decode(&QuadOrder::GSPO, key))
+        {
+            let (quad, props_id) = r?;
+            if report.missing_quad_props.contains(&quad) {
+                continue;
+            }
+            gpos.insert(
+                quad.encode(&QuadOrder::GPOS).as_ref(),
+                props_id.to_be_bytes().as_ref(),
+            );
+            gosp.insert(
+                quad.encode(&QuadOrder::GOSP).as_ref(),
+                props_id.to_be_bytes().as_ref(),
+            );
+            spog.insert(
+                quad.encode_node(NodeQuadOrder::SPOG).as_ref(),
+                props_id.to_be_bytes().as_ref(),
+            );
+            ospg.insert(
+                quad.encode_node(NodeQuadOrder::OSPG).as_ref(),
+                props_id.to_be_bytes().as_ref(),
+            );
+        }
+
+        let mut quad_props = Batch::default();
+        for props_id in &report.orphaned_quad_props {
+            quad_props.remove(props_id.to_be_bytes().as_ref());
+        }
+
+        (
+            &self.edge_props,
+            &self.spo_data,
+            &self.pos_data,
+            &self.osp_data,
+            &self.quad_props,
+            &self.gspo_data,
+            &self.gpos_data,
+            &self.gosp_data,
+            &self.spog_data,
+            &self.ospg_data,
+        )
+            .transaction(
+                |(
+                    edge_props_tree,
+                    spo_data,
+                    pos_data,
+                    osp_data,
+                    quad_props_tree,
+                    gspo_data,
+                    gpos_data,
+                    gosp_data,
+                    spog_data,
+                    ospg_data,
+                )| {
+                    edge_props_tree.apply_batch(&edge_props)?;
+                    spo_data.apply_batch(&spo)?;
+                    pos_data.apply_batch(&pos)?;
+                    osp_data.apply_batch(&osp)?;
+                    quad_props_tree.apply_batch(&quad_props)?;
+                    gspo_data.apply_batch(&gspo)?;
+                    gpos_data.apply_batch(&gpos)?;
+                    gosp_data.apply_batch(&gosp)?;
+                    spog_data.apply_batch(&spog)?;
+                    ospg_data.apply_batch(&ospg)?;
+                    Ok(())
+                },
+            )
+            .map_err(|e| match e {
+                sled::transaction::TransactionError::Abort(e) => e,
+                sled::transaction::TransactionError::Storage(e) => {
+                    SledTripleStoreError::SledError(e)
+                }
+            })?;
+        self.rebuild_statistics()?;
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ulid::Ulid;
+
+    use crate::{
+        prelude::*, traits::ConcreteIdType, triple::NodeQuadOrder, Quad, QuadOrder,
+        SledTripleStore, Triple, UlidIdGenerator,
+    };
+
+    #[test]
+    fn test_verify_consistent() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let sled_db = SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok");
+        crate::conformance::verify::test_verify_consistent(sled_db);
+    }
+
+    #[test]
+    fn test_repair() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        let mut sled_db =
+            SledTripleStore::<Ulid, (), String>::new(&db, UlidIdGenerator::new()).expect("ok");
+        let triple = |n: u128| Triple {
+            sub: Ulid(1),
+            pred: Ulid(10),
+            obj: Ulid(n),
+        };
+        let quad = |n: u128| Quad::new(triple(n), Ulid(20));
+        for n in 2..6 {
+            sled_db.insert_edge(triple(n), n.to_string()).expect("ok");
+            sled_db.insert_quad(quad(n), n.to_string()).expect("ok");
+        }
+
+        // As if a removal had stopped part way: the edge is gone from SPO but its other entries and props remain.
+        let spo_key = Ulid::encode_spo_triple(&triple(5));
+        let dropped_props = sled_db.spo_data.remove(spo_key).expect("ok").expect("edge");
+
+        // Lose an edge from POS, an edge's props, and point an OSP entry elsewhere.
+        let pos_key = Ulid::encode_pos_triple(&triple(2));
+        sled_db.pos_data.remove(pos_key).expect("ok");
+        let spo_key = Ulid::encode_spo_triple(&triple(3));
+        let lost_props = sled_db.spo_data.get(spo_key).expect("ok").expect("edge");
+        sled_db.edge_props.remove(lost_props).expect("ok");
+        let osp_key = Ulid::encode_osp_triple(&triple(4));
+        sled_db
+            .osp_data
+            .insert(osp_key, Ulid(99).to_be_bytes().as_ref())
+            .expect("ok");
+
+        // Likewise in the named graph: lose a quad from GOSP, another from SPOG and another's props.
+        sled_db
+            .gosp_data
+            .remove(quad(2).encode(&QuadOrder::GOSP))
+            .expect("ok");
+        sled_db
+            .spog_data
+            .remove(quad(4).encode_node(NodeQuadOrder::SPOG))
+            .expect("ok");
+        let lost_props = sled_db
+            .gspo_data
+            .get(quad(3).encode(&QuadOrder::GSPO))
+            .expect("ok")
+            .expect("quad");
+        sled_db.quad_props.remove(lost_props).expect("ok");
+
+        let report = sled_db.verify().expect("ok");
+        assert!(!report.is_consistent());
+        assert_eq!(report.edges, 3);
+        assert_eq!(report.pos_mismatches, [triple(2), triple(5)]);
+        assert_eq!(report.osp_mismatches, [triple(4), triple(5)]);
+        assert_eq!(report.missing_props, [triple(3)]);
+        assert_eq!(
+            report.orphaned_props,
+            [Ulid::try_from_be_bytes(&dropped_props).expect("id")]
+        );
+        assert_eq!(report.quads, 4);
+        assert!(report.gpos_mismatches.is_empty());
+        assert_eq!(report.gosp_mismatches, [quad(2)]);
+        assert_eq!(report.spog_mismatches, [quad(4)]);
+        assert!(report.ospg_mismatches.is_empty());
+        assert_eq!(report.missing_quad_props, [quad(3)]);
+        assert!(report.orphaned_quad_props.is_empty());
+        assert!(report.stale_statistics);
+
+        assert_eq!(sled_db.repair().expect("ok"), report);
+        assert!(sled_db.verify().expect("ok").is_consistent());
+        assert_eq!(
+            sled_db.get_edge(&triple(2)).expect("ok"),
+            Some("2".to_string())
+        );
+        assert_eq!(sled_db.get_edge(&triple(3)).expect("ok"), None);
+        assert_eq!(
+            sled_db.get_edge(&triple(4)).expect("ok"),
+            Some("4".to_string())
+        );
+        assert_eq!(
+            sled_db
+                .count(crate::query! { ? -[Ulid(10)]-> ? })
+                .expect("ok"),
+            2
+        );
+        assert_eq!(sled_db.statistics().expect("ok").edges, 2);
+        assert_eq!(sled_db.edge_props.len(), 2);
+        assert_eq!(sled_db.get_quad(&quad(3)).expect("ok"), None);
+        assert_eq!(
+            sled_db
+                .iter_quads(QuadOrder::GOSP)
+                .map(|r| r.expect("ok").0)
+                .collect::<Vec<_>>(),
+            [quad(2), quad(4), quad(5)]
+        );
+        assert_eq!(sled_db.node_quads(&Ulid(4)).expect("ok"), [quad(4)]);
+    }
+}
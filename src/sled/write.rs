@@ -0,0 +1,172 @@
+use std::{cell::RefCell, collections::HashSet, time::Duration};
+
+use serde::{de::DeserializeOwned, Serialize};
+use sled::transaction::{ConflictableTransactionResult, Transactional, TransactionalTree};
+
+use crate::{
+    prelude::*,
+    traits::{ConcreteIdType, Property},
+    Triple,
+};
+
+use super::{index::SledIndex, SledTripleStore, SledTripleStoreError};
+
+/// The trees of a [SledTripleStore] as seen from inside a write transaction.
+pub(super) struct WriteTrees<'a, NodeProps, EdgeProps> {
+    pub(super) node_props: &'a TransactionalTree,
+    pub(super) edge_props: &'a TransactionalTree,
+    pub(super) spo_data: &'a TransactionalTree,
+    pub(super) pos_data: &'a TransactionalTree,
+    pub(super) osp_data: &'a TransactionalTree,
+    pub(super) statistics: &'a TransactionalTree,
+    pub(super) quad_props: &'a TransactionalTree,
+    pub(super) gspo_data: &'a TransactionalTree,
+    pub(super) gpos_data: &'a TransactionalTree,
+    pub(super) gosp_data: &'a TransactionalTree,
+    pub(super) spog_data: &'a TransactionalTree,
+    pub(super) ospg_data: &'a TransactionalTree,
+    pub(super) node_indexes: Vec<(&'a SledIndex<NodeProps>, &'a TransactionalTree)>,
+    pub(super) edge_indexes: Vec<(&'a SledIndex<EdgeProps>, &'a TransactionalTree)>,
+}
+
+/// A watch on the keys read by a scan, for write transactions which rely on that scan.
+///
+/// Sled transactions cannot scan, so the scan is made beforehand and a write through another handle could change the
+/// keys in between. Sled holds off every other write while a transaction runs, so checking the watch from inside the
+/// transaction tells whether the scan still holds.
+pub(super) struct ScanWatch(RefCell<Vec<sled::Subscriber>>);
+
+impl ScanWatch {
+    /// Whether any of the watched keys has been written since the watch was started.
+    pub(super) fn changed(&self) -> bool {
+        self.0
+            .borrow_mut()
+            .iter_mut()
+            .any(|subscriber| subscriber.next_timeout(Duration::ZERO).is_ok())
+    }
+}
+
+impl<
+        Id: ConcreteIdType,
+        NodeProps: Property + Serialize + DeserializeOwned,
+        EdgeProps: Property + Serialize + DeserializeOwned,
+    > SledTripleStore<Id, NodeProps, EdgeProps>
+{
+    /// Start watching the edges to and from `nodes`, in the default graph and the named graphs. Start the watch before
+    /// scanning the edges.
+    pub(super) fn watch_nodes<'n>(&self, nodes: impl IntoIterator<Item = &'n Id>) -> ScanWatch
+    where
+        Id: 'n,
+    {
+        ScanWatch(RefCell::new(
+            nodes
+                .into_iter()
+                .flat_map(|node| {
+                    let prefix = node.to_be_bytes();
+                    [
+                        self.spo_data.watch_prefix(prefix.as_ref()),
+                        self.osp_data.watch_prefix(prefix.as_ref()),
+                        self.spog_data.watch_prefix(prefix.as_ref()),
+                        self.ospg_data.watch_prefix(prefix.as_ref()),
+                    ]
+                })
+                .collect(),
+        ))
+    }
+
+    /// Start watching the edges of `graph`. Start the watch before scanning the edges.
+    pub(super) fn watch_graph(&self, graph: &Id) -> ScanWatch {
+        ScanWatch(RefCell::new(vec![self
+            .gspo_data
+            .watch_prefix(graph.to_be_bytes().as_ref())]))
+    }
+
+    /// The edges to or from `node`. Self loops are only listed once.
+    pub(super) fn node_edges(&self, node: &Id) -> Result<Vec<Triple<Id>>, SledTripleStoreError> {
+        let mut seen = HashSet::new();
+        Ok(self
+            .match_triples(Some(*node), None, None)
+            .chain(self.match_triples(None, None, Some(*node)))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .filter(|triple| seen.insert(triple.clone()))
+            .collect())
+    }
+
+    /// Run `f` in one transaction over the node and edge trees, the statistics, the trees of the named graphs and the
+    /// secondary indexes, so that a write commits together with the statistics and index entries it implies. The
+    /// statistics and indexes only cover the default graph, so writes to the named graphs leave them as they are.
+    ///
+    /// `f` is run again if the transaction conflicts with another one, so it must not have side effects of its own.
+    pub(super) fn write_transaction<R>(
+        &self,
+        f: impl Fn(
+            &WriteTrees<NodeProps, EdgeProps>,
+        ) -> ConflictableTransactionResult<R, SledTripleStoreError>,
+    ) -> Result<R, SledTripleStoreError> {
+        // The number of indexes varies, so the trees are passed as a slice rather than a tuple.
+        let trees = [
+            &self.node_props,
+            &self.edge_props,
+            &self.spo_data,
+            &self.pos_data,
+            &self.osp_data,
+            &self.statistics,
+            &self.quad_props,
+            &self.gspo_data,
+            &self.gpos_data,
+            &self.gosp_data,
+            &self.spog_data,
+            &self.ospg_data,
+        ]
+        .into_iter()
+        .chain(self.node_indexes.values().map(SledIndex::tree))
+        .chain(self.edge_indexes.values().map(SledIndex::tree))
+        .collect::<Vec<_>>();
+
+        trees[..]
+            .transaction(|trees| {
+                let [
+                    node_props,
+                    edge_props,
+                    spo_data,
+                    pos_data,
+                    osp_data,
+                    statistics,
+                    quad_props,
+                    gspo_data,
+                    gpos_data,
+                    gosp_data,
+                    spog_data,
+                    ospg_data,
+                    indexes @ ..,
+                ] = trees.as_slice()
+                else {
+                    unreachable!("every tree is passed to the transaction");
+                };
+                let (node_indexes, edge_indexes) = indexes.split_at(self.node_indexes.len());
+                f(&WriteTrees {
+                    node_props,
+                    edge_props,
+                    spo_data,
+                    pos_data,
+                    osp_data,
+                    statistics,
+                    quad_props,
+                    gspo_data,
+                    gpos_data,
+                    gosp_data,
+                    spog_data,
+                    ospg_data,
+                    node_indexes: self.node_indexes.values().zip(node_indexes).collect(),
+                    edge_indexes: self.edge_indexes.values().zip(edge_indexes).collect(),
+                })
+            })
+            .map_err(|e| match e {
+                sled::transaction::TransactionError::Abort(e) => e,
+                sled::transaction::TransactionError::Storage(e) => {
+                    SledTripleStoreError::SledError(e)
+                }
+            })
+    }
+}
@@ -1,9 +1,13 @@
 #[cfg(feature = "rdf")]
 mod bidir_index;
+mod bulk_load;
+mod count;
 mod error;
 mod extend;
+mod get;
 mod id_generator;
 mod id_type;
+mod index;
 #[cfg(feature = "rdf")]
 mod index_type;
 mod insert;
@@ -11,18 +15,29 @@ mod into_iter;
 mod iter;
 mod merge;
 mod mergeable;
+mod paginate;
+mod pattern;
 mod property;
+mod quad;
 mod query;
 mod remove;
 mod set;
+mod stats;
+mod subscribe;
+mod transaction;
 mod triplestore;
+mod verify;
 
 #[cfg(feature = "rdf")]
 pub use bidir_index::*;
+pub use bulk_load::*;
+pub use count::*;
 pub use error::*;
 pub use extend::*;
+pub use get::*;
 pub use id_generator::*;
 pub use id_type::*;
+pub use index::*;
 #[cfg(feature = "rdf")]
 pub use index_type::*;
 pub use insert::*;
@@ -30,8 +45,15 @@ pub use into_iter::*;
 pub use iter::*;
 pub use merge::*;
 pub use mergeable::*;
+pub use paginate::*;
+pub use pattern::*;
 pub use property::*;
+pub use quad::*;
 pub use query::*;
 pub use remove::*;
 pub use set::*;
+pub use stats::*;
+pub use subscribe::*;
+pub use transaction::*;
 pub use triplestore::*;
+pub use verify::*;
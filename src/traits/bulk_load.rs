@@ -0,0 +1,71 @@
+use crate::{
+    prelude::*,
+    traits::{IdType, Property},
+    Triple,
+};
+
+/// A trait for inserting many nodes and edges at once.
+///
+/// The result is the same as calling [insert_node][TripleStoreInsert::insert_node] or
+/// [insert_edge][TripleStoreInsert::insert_edge] for each item in order, but keys are sorted for each table and written
+/// together, rather than one edge at a time.
+///
+/// # Example
+/// ```
+/// # use ulid::Ulid;
+/// # use simple_triplestore::{prelude::*, MemTripleStore, Triple, UlidIdGenerator};
+/// let mut db = MemTripleStore::<Ulid, (), u32>::new(UlidIdGenerator::new());
+/// let knows = Ulid(0);
+///
+/// db.insert_edges((1..=100).map(|n| (Triple { sub: Ulid(n), pred: knows, obj: Ulid(n + 1) }, n as u32)))?;
+/// assert_eq!(db.iter_edges(simple_triplestore::EdgeOrder::SPO).count(), 100);
+///
+/// // The last props given for an edge win.
+/// db.insert_edges([
+///     (Triple { sub: Ulid(1), pred: knows, obj: Ulid(2) }, 10),
+///     (Triple { sub: Ulid(1), pred: knows, obj: Ulid(2) }, 20),
+/// ])?;
+/// assert_eq!(db.get_edge(&Triple { sub: Ulid(1), pred: knows, obj: Ulid(2) })?, Some(20));
+/// # Ok::<(), ()>(())
+/// ```
+pub trait TripleStoreBulkLoad<Id: IdType, NodeProps: Property, EdgeProps: Property>:
+    TripleStoreInsert<Id, NodeProps, EdgeProps>
+{
+    /// Insert every node in `nodes`, replacing the props of existing nodes.
+    fn insert_nodes(
+        &mut self,
+        nodes: impl IntoIterator<Item = (Id, NodeProps)>,
+    ) -> Result<(), Self::Error>;
+
+    /// Insert every edge in `edges`, replacing the props of existing edges.
+    fn insert_edges(
+        &mut self,
+        edges: impl IntoIterator<Item = (Triple<Id>, EdgeProps)>,
+    ) -> Result<(), Self::Error> {
+        self.bulk_load(edges, false)
+    }
+
+    /// Insert every edge in `edges`.
+    ///
+    /// With `assume_no_duplicates`, the caller promises that no edge appears twice in `edges` or is already in the
+    /// store, which skips looking up and cleaning up existing edges. If the promise is broken the store is left with
+    /// unreachable edge props and inaccurate [statistics][TripleStoreStats].
+    fn bulk_load(
+        &mut self,
+        edges: impl IntoIterator<Item = (Triple<Id>, EdgeProps)>,
+        assume_no_duplicates: bool,
+    ) -> Result<(), Self::Error>;
+}
+
+/// Sort `items` with `compare`, keeping only the last of the items which compare equal.
+pub(crate) fn sort_last_wins<T>(
+    items: &mut Vec<T>,
+    compare: impl Fn(&T, &T) -> std::cmp::Ordering,
+) {
+    // A stable sort keeps equal items in their original order, so after reversing the first of each run is the last
+    // one given.
+    items.sort_by(&compare);
+    items.reverse();
+    items.dedup_by(|a, b| compare(a, b).is_eq());
+    items.reverse();
+}
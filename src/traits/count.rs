@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use crate::{
+    prelude::*,
+    traits::{IdType, Property},
+    Query,
+};
+
+/// A trait for counting nodes and edges without reading their properties.
+///
+/// Counts are computed with range scans over the SPO, POS and OSP tables, so unlike
+/// [run][TripleStoreQuery::run] nothing is copied or deserialised.
+///
+/// # Example
+/// ```
+/// # use ulid::Ulid;
+/// # use simple_triplestore::{prelude::*, MemTripleStore, Triple, UlidIdGenerator, query};
+/// let mut db = MemTripleStore::<Ulid, (), ()>::new(UlidIdGenerator::new());
+/// let (alice, bob, carol, knows, likes) = (Ulid(1), Ulid(2), Ulid(3), Ulid(10), Ulid(11));
+///
+/// db.insert_edge(Triple { sub: alice, pred: knows, obj: bob }, ())?;
+/// db.insert_edge(Triple { sub: alice, pred: knows, obj: carol }, ())?;
+/// db.insert_edge(Triple { sub: bob, pred: likes, obj: carol }, ())?;
+///
+/// assert_eq!(db.count(query! { ? -[knows]-> ? })?, 2);
+/// assert_eq!(db.out_degree(&alice)?, 2);
+/// assert_eq!(db.in_degree(&carol)?, 2);
+/// assert_eq!(db.count_by_object(Some(knows))?, [(bob, 1), (carol, 1)].into());
+/// # Ok::<(), ()>(())
+/// ```
+pub trait TripleStoreCount<Id: IdType, NodeProps: Property, EdgeProps: Property>:
+    TripleStoreGet<Id, NodeProps, EdgeProps> + TripleStorePattern<Id, NodeProps, EdgeProps>
+{
+    /// The number of vertices with props.
+    fn len_nodes(&self) -> Result<usize, Self::Error>;
+
+    /// The number of edges.
+    fn len_edges(&self) -> Result<usize, Self::Error> {
+        count_matches(self, None, None, None)
+    }
+
+    /// The number of rows [run][TripleStoreQuery::run] would return for `query`: vertices for [Query::NodeProps],
+    /// and edges for every other query.
+    fn count(&self, query: Query<Id>) -> Result<usize, Self::Error> {
+        let mut count = 0;
+        match query {
+            Query::NodeProps(nodes) => {
+                for node in nodes {
+                    if self.contains_node(&node)? {
+                        count += 1;
+                    }
+                }
+            }
+            Query::SPO(triples) => {
+                for (sub, pred, obj) in triples {
+                    count += count_matches(self, Some(sub), Some(pred), Some(obj))?;
+                }
+            }
+            Query::S(subs) => {
+                for sub in subs {
+                    count += count_matches(self, Some(sub), None, None)?;
+                }
+            }
+            Query::P(preds) => {
+                for pred in preds {
+                    count += count_matches(self, None, Some(pred), None)?;
+                }
+            }
+            Query::O(objs) => {
+                for obj in objs {
+                    count += count_matches(self, None, None, Some(obj))?;
+                }
+            }
+            Query::SP(pairs) => {
+                for (sub, pred) in pairs {
+                    count += count_matches(self, Some(sub), Some(pred), None)?;
+                }
+            }
+            Query::SO(pairs) => {
+                for (sub, obj) in pairs {
+                    count += count_matches(self, Some(sub), None, Some(obj))?;
+                }
+            }
+            Query::PO(pairs) => {
+                for (pred, obj) in pairs {
+                    count += count_matches(self, None, Some(pred), Some(obj))?;
+                }
+            }
+        }
+        Ok(count)
+    }
+
+    /// The number of edges starting at `node`.
+    fn out_degree(&self, node: &Id) -> Result<usize, Self::Error> {
+        count_matches(self, Some(node.clone()), None, None)
+    }
+
+    /// The number of edges pointing to `node`.
+    fn in_degree(&self, node: &Id) -> Result<usize, Self::Error> {
+        count_matches(self, None, None, Some(node.clone()))
+    }
+
+    /// The number of edges with each predicate. Predicates without edges are left out.
+    fn count_by_predicate(&self) -> Result<HashMap<Id, usize>, Self::Error> {
+        let mut counts = HashMap::new();
+        for r in self.match_triples(None, None, None) {
+            *counts.entry(r?.pred).or_default() += 1;
+        }
+        Ok(counts)
+    }
+
+    /// The number of edges pointing to each object, optionally only counting edges with predicate `pred`. Objects
+    /// without edges are left out.
+    fn count_by_object(&self, pred: Option<Id>) -> Result<HashMap<Id, usize>, Self::Error> {
+        let mut counts = HashMap::new();
+        for r in self.match_triples(None, pred, None) {
+            *counts.entry(r?.obj).or_default() += 1;
+        }
+        Ok(counts)
+    }
+}
+
+fn count_matches<Id: IdType, NodeProps: Property, EdgeProps: Property, T>(
+    db: &T,
+    sub: Option<Id>,
+    pred: Option<Id>,
+    obj: Option<Id>,
+) -> Result<usize, T::Error>
+where
+    T: TripleStorePattern<Id, NodeProps, EdgeProps> + ?Sized,
+{
+    let mut count = 0;
+    for r in db.match_triples(sub, pred, obj) {
+        r?;
+        count += 1;
+    }
+    Ok(count)
+}
@@ -0,0 +1,26 @@
+use crate::{
+    prelude::*,
+    traits::{IdType, Property},
+    Triple,
+};
+
+/// A trait for point lookups of single nodes and edges in a [TripleStore].
+pub trait TripleStoreGet<Id: IdType, NodeProps: Property, EdgeProps: Property>:
+    TripleStoreError
+{
+    /// Get the properties of a vertex, or `None` if it is not present.
+    fn get_node(&self, id: &Id) -> Result<Option<NodeProps>, Self::Error>;
+
+    /// Get the properties of an edge, or `None` if it is not present.
+    fn get_edge(&self, triple: &Triple<Id>) -> Result<Option<EdgeProps>, Self::Error>;
+
+    /// Check whether a vertex is present.
+    fn contains_node(&self, id: &Id) -> Result<bool, Self::Error> {
+        Ok(self.get_node(id)?.is_some())
+    }
+
+    /// Check whether an edge is present.
+    fn contains_edge(&self, triple: &Triple<Id>) -> Result<bool, Self::Error> {
+        Ok(self.get_edge(triple)?.is_some())
+    }
+}
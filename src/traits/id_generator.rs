@@ -1,6 +1,17 @@
 use super::ConcreteIdType;
 
+/// The error returned by an [IdGenerator] which could not generate an id, e.g. because its storage failed.
+pub type IdGeneratorError = Box<dyn std::error::Error + Send + Sync>;
+
 pub trait IdGenerator<Id: ConcreteIdType> {
     fn clone(&self) -> Box<dyn IdGenerator<Id>>;
     fn fresh(&mut self) -> Id;
+
+    /// Generate an id, returning an error rather than panicking if the generator cannot.
+    ///
+    /// Stores which can report errors generate ids with this. Generators which cannot fail can rely on the default,
+    /// which calls [fresh][IdGenerator::fresh].
+    fn try_fresh(&mut self) -> Result<Id, IdGeneratorError> {
+        Ok(self.fresh())
+    }
 }
@@ -22,6 +22,15 @@ pub trait ConcreteIdType:
         + Ord
         + AsRef<[u8]>
         + for<'a> TryFrom<&'a [u8]>;
+    type QuadByteArrayType: std::hash::Hash
+        + PartialEq
+        + Ord
+        + AsRef<[u8]>
+        + for<'a> TryFrom<&'a [u8]>;
+
+    /// A name for the id type which does not change between versions. [Dumps][crate::dump] record it, so that they are
+    /// only read back with the same type, even if another has the same size.
+    const NAME: &'static str;
 
     fn to_be_bytes(self) -> Self::ByteArrayType;
     fn from_be_bytes(bytes: &Self::ByteArrayType) -> Self;
@@ -49,4 +58,56 @@ pub trait ConcreteIdType:
         std::ops::Bound<Self::TripleByteArrayType>,
         std::ops::Bound<Self::TripleByteArrayType>,
     );
+
+    /// Prefix an encoded triple (in any ordering) with its graph, as a key in the graph-leading tables.
+    fn encode_quad(graph: Self, triple: &Self::TripleByteArrayType) -> Self::QuadByteArrayType;
+
+    /// Split a key from the graph-leading tables into its graph and encoded triple.
+    fn decode_quad(data: &Self::QuadByteArrayType) -> (Self, Self::TripleByteArrayType);
+
+    /// The bounds of every key in `graph`.
+    fn graph_key_bounds(
+        graph: Self,
+    ) -> (
+        std::ops::Bound<Self::QuadByteArrayType>,
+        std::ops::Bound<Self::QuadByteArrayType>,
+    );
+
+    /// Scope the bounds from [key_bounds_1][ConcreteIdType::key_bounds_1] or
+    /// [key_bounds_2][ConcreteIdType::key_bounds_2] to `graph`.
+    fn quad_key_bounds(
+        graph: Self,
+        bounds: (
+            std::ops::Bound<Self::TripleByteArrayType>,
+            std::ops::Bound<Self::TripleByteArrayType>,
+        ),
+    ) -> (
+        std::ops::Bound<Self::QuadByteArrayType>,
+        std::ops::Bound<Self::QuadByteArrayType>,
+    ) {
+        let (graph_lower, graph_upper) = Self::graph_key_bounds(graph);
+        let scope = |bound, unbounded| match bound {
+            std::ops::Bound::Included(key) => {
+                std::ops::Bound::Included(Self::encode_quad(graph, &key))
+            }
+            std::ops::Bound::Excluded(key) => {
+                std::ops::Bound::Excluded(Self::encode_quad(graph, &key))
+            }
+            std::ops::Bound::Unbounded => unbounded,
+        };
+        (scope(bounds.0, graph_lower), scope(bounds.1, graph_upper))
+    }
+
+    /// A generator which keeps its state in `counter`, for id types whose generators would otherwise hand out the same
+    /// ids again when a [SledTripleStore][crate::SledTripleStore] is reopened. Every id it generates is greater than
+    /// `in_use`, the largest id which the store already holds.
+    ///
+    /// Returns `None` for id types which never repeat, in which case the store uses the generator it was given.
+    #[cfg(feature = "sled")]
+    fn sled_id_generator(
+        _counter: sled::Tree,
+        _in_use: Option<Self>,
+    ) -> Result<Option<Box<dyn crate::IdGenerator<Self>>>, sled::Error> {
+        Ok(None)
+    }
 }
@@ -0,0 +1,236 @@
+use std::ops::{Bound, RangeBounds};
+
+use crate::{
+    prelude::*,
+    traits::{IdType, Property},
+    Triple,
+};
+
+/// A value which can be used as the key of a secondary index.
+///
+/// Keys are stored in their encoded form, so the encoding must agree with [Ord] when the bytes are compared
+/// lexicographically, and no encoding may be a prefix of another.
+pub trait IndexKey: Ord {
+    /// Append the encoded key to `buf`.
+    fn encode_index_key(&self, buf: &mut Vec<u8>);
+}
+
+/// Escape zero bytes and terminate with `0x00 0x00` so variable-length keys keep their order and are prefix-free.
+fn encode_bytes(bytes: &[u8], buf: &mut Vec<u8>) {
+    for b in bytes {
+        buf.push(*b);
+        if *b == 0x00 {
+            buf.push(0xff);
+        }
+    }
+    buf.extend_from_slice(&[0x00, 0x00]);
+}
+
+impl IndexKey for String {
+    fn encode_index_key(&self, buf: &mut Vec<u8>) {
+        encode_bytes(self.as_bytes(), buf);
+    }
+}
+
+impl IndexKey for Vec<u8> {
+    fn encode_index_key(&self, buf: &mut Vec<u8>) {
+        encode_bytes(self, buf);
+    }
+}
+
+impl IndexKey for bool {
+    fn encode_index_key(&self, buf: &mut Vec<u8>) {
+        buf.push(*self as u8);
+    }
+}
+
+impl IndexKey for ulid::Ulid {
+    fn encode_index_key(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.0.to_be_bytes());
+    }
+}
+
+macro_rules! unsigned_index_key {
+    ($($t:ty),*) => {
+        $(
+            impl IndexKey for $t {
+                fn encode_index_key(&self, buf: &mut Vec<u8>) {
+                    buf.extend_from_slice(&self.to_be_bytes());
+                }
+            }
+        )*
+    };
+}
+
+// Flipping the sign bit makes two's complement sort correctly as unsigned big-endian bytes.
+macro_rules! signed_index_key {
+    ($($t:ty => $u:ty),*) => {
+        $(
+            impl IndexKey for $t {
+                fn encode_index_key(&self, buf: &mut Vec<u8>) {
+                    buf.extend_from_slice(&((*self as $u) ^ (1 << (<$u>::BITS - 1))).to_be_bytes());
+                }
+            }
+        )*
+    };
+}
+
+unsigned_index_key!(u8, u16, u32, u64, u128);
+signed_index_key!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128);
+
+impl<A: IndexKey, B: IndexKey> IndexKey for (A, B) {
+    fn encode_index_key(&self, buf: &mut Vec<u8>) {
+        self.0.encode_index_key(buf);
+        self.1.encode_index_key(buf);
+    }
+}
+
+impl<A: IndexKey, B: IndexKey, C: IndexKey> IndexKey for (A, B, C) {
+    fn encode_index_key(&self, buf: &mut Vec<u8>) {
+        self.0.encode_index_key(buf);
+        self.1.encode_index_key(buf);
+        self.2.encode_index_key(buf);
+    }
+}
+
+/// A type-erased key extractor which produces encoded keys.
+pub(crate) type KeyExtractor<Props> = Box<dyn Fn(&Props) -> Vec<Vec<u8>>>;
+
+pub(crate) fn encode_key<K: IndexKey>(key: &K) -> Vec<u8> {
+    let mut buf = Vec::new();
+    key.encode_index_key(&mut buf);
+    buf
+}
+
+pub(crate) fn key_extractor<Props, K: IndexKey>(
+    extract: impl Fn(&Props) -> Vec<K> + 'static,
+) -> KeyExtractor<Props> {
+    Box::new(move |props| extract(props).iter().map(encode_key).collect())
+}
+
+/// The start and end bounds of a range of keys.
+pub(crate) type KeyRange<K> = (Bound<K>, Bound<K>);
+
+/// Encode the bounds of a key range, returning `None` if the range is empty.
+pub(crate) fn encode_key_range<K: IndexKey>(
+    range: &impl RangeBounds<K>,
+) -> Option<KeyRange<Vec<u8>>> {
+    let empty = match (range.start_bound(), range.end_bound()) {
+        (Bound::Included(start), Bound::Included(end)) => start > end,
+        (Bound::Included(start), Bound::Excluded(end))
+        | (Bound::Excluded(start), Bound::Included(end))
+        | (Bound::Excluded(start), Bound::Excluded(end)) => start >= end,
+        _ => false,
+    };
+    if empty {
+        return None;
+    }
+
+    let encode = |bound: Bound<&K>| match bound {
+        Bound::Included(key) => Bound::Included(encode_key(key)),
+        Bound::Excluded(key) => Bound::Excluded(encode_key(key)),
+        Bound::Unbounded => Bound::Unbounded,
+    };
+    Some((encode(range.start_bound()), encode(range.end_bound())))
+}
+
+/// A trait for secondary indexes over node and edge properties in a [TripleStore].
+///
+/// Each index is registered under a name with a key extractor, which may produce any number of keys for a set of
+/// properties. Indexes are built from the existing contents when registered and then kept up to date as nodes and
+/// edges are inserted, removed, merged and extended.
+pub trait TripleStoreIndex<Id: IdType, NodeProps: Property, EdgeProps: Property>:
+    TripleStoreError
+{
+    /// Register an index over node properties, replacing any existing index with the same name.
+    fn register_node_index<K: IndexKey>(
+        &mut self,
+        name: &str,
+        extract: impl Fn(&NodeProps) -> Vec<K> + 'static,
+    ) -> Result<(), Self::Error>;
+
+    /// Register an index over edge properties, replacing any existing index with the same name.
+    fn register_edge_index<K: IndexKey>(
+        &mut self,
+        name: &str,
+        extract: impl Fn(&EdgeProps) -> Vec<K> + 'static,
+    ) -> Result<(), Self::Error>;
+
+    /// Iterate over the vertices with a key in `range`, in key order.
+    ///
+    /// Produces an error if no node index is registered under `name`.
+    fn range_nodes<'a, K: IndexKey>(
+        &'a self,
+        name: &str,
+        range: impl RangeBounds<K>,
+    ) -> impl Iterator<Item = Result<Id, Self::Error>> + 'a;
+
+    /// Iterate over the edges with a key in `range`, in key order.
+    ///
+    /// Produces an error if no edge index is registered under `name`.
+    fn range_edges<'a, K: IndexKey>(
+        &'a self,
+        name: &str,
+        range: impl RangeBounds<K>,
+    ) -> impl Iterator<Item = Result<Triple<Id>, Self::Error>> + 'a;
+
+    /// Iterate over the vertices with the given key.
+    fn lookup_nodes<'a, K: IndexKey>(
+        &'a self,
+        name: &str,
+        key: &K,
+    ) -> impl Iterator<Item = Result<Id, Self::Error>> + 'a {
+        self.range_nodes::<K>(name, (Bound::Included(key), Bound::Included(key)))
+    }
+
+    /// Iterate over the edges with the given key.
+    fn lookup_edges<'a, K: IndexKey>(
+        &'a self,
+        name: &str,
+        key: &K,
+    ) -> impl Iterator<Item = Result<Triple<Id>, Self::Error>> + 'a {
+        self.range_edges::<K>(name, (Bound::Included(key), Bound::Included(key)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{encode_key, IndexKey};
+
+    fn assert_ordered<K: IndexKey + std::fmt::Debug>(keys: &[K]) {
+        for pair in keys.windows(2) {
+            assert!(pair[0] < pair[1], "{:?}", pair);
+            assert!(encode_key(&pair[0]) < encode_key(&pair[1]), "{:?}", pair);
+        }
+    }
+
+    #[test]
+    fn test_encoding_order() {
+        assert_ordered(&[i64::MIN, -100, -1, 0, 1, 100, i64::MAX]);
+        assert_ordered(&[0u32, 1, 255, 256, u32::MAX]);
+        assert_ordered(&[
+            "".to_string(),
+            "\0".to_string(),
+            "\0\0".to_string(),
+            "a".to_string(),
+            "a\0".to_string(),
+            "ab".to_string(),
+            "b".to_string(),
+        ]);
+        assert_ordered(&[
+            ("a".to_string(), 2u8),
+            ("a".to_string(), 3u8),
+            ("ab".to_string(), 0u8),
+        ]);
+    }
+
+    #[test]
+    fn test_encoding_prefix_free() {
+        let keys = ["", "a", "a\0", "ab", "\0"].map(|k| encode_key(&k.to_string()));
+        for a in keys.iter() {
+            for b in keys.iter() {
+                assert!(a == b || !b.starts_with(a), "{:?} {:?}", a, b);
+            }
+        }
+    }
+}
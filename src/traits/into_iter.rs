@@ -8,6 +8,10 @@ pub trait TripleStoreIntoIter<Id: IdType, NodeProps: Property, EdgeProps: Proper
     TripleStoreError
 {
     // Return two iterators: one for vertices, and one for edges.
+    #[allow(
+        clippy::type_complexity,
+        reason = "a type alias cannot name the `impl Iterator` pair"
+    )]
     fn into_iter_nodes(
         self,
         order: EdgeOrder,
@@ -12,6 +12,10 @@ pub trait TripleStoreIter<Id: IdType, NodeProps: Property, EdgeProps: Property>:
     fn vertices(&self) -> Result<impl Iterator<Item = Id>, Self::Error>;
 
     // Return two iterators: one for vertices, and one for edges.
+    #[allow(
+        clippy::type_complexity,
+        reason = "a type alias cannot name the `impl Iterator` pair"
+    )]
     fn iter_nodes(
         &self,
         order: EdgeOrder,
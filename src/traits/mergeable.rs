@@ -2,7 +2,7 @@
 ///
 /// Intended to be used for hashtables or json objects.
 pub trait Mergeable {
-    ///
+    /// Merge `other` into `self`.
     fn merge(&mut self, other: Self);
 }
 
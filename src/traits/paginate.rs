@@ -0,0 +1,247 @@
+use std::ops::Bound;
+
+use crate::{
+    prelude::*,
+    traits::{ConcreteIdType, IdType, KeyRange, Property},
+    EdgeOrder, Query, Triple,
+};
+
+/// An opaque position in a paged iteration, as returned in [Page::next].
+///
+/// A cursor holds the encoded key of the last row returned, so resuming from it is unaffected by rows inserted or
+/// removed elsewhere in the meantime. It can be stored as bytes with [as_bytes][Cursor::as_bytes] and
+/// [from_bytes][Cursor::from_bytes], or with serde when the `sled` feature is enabled.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "sled", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cursor(Vec<u8>);
+
+/// The first byte of a cursor, which says which table its key is from so that it cannot be used to resume another.
+fn table_tag(order: Option<&EdgeOrder>) -> u8 {
+    match order {
+        None => 0,
+        Some(EdgeOrder::SPO) => 1,
+        Some(EdgeOrder::POS) => 2,
+        Some(EdgeOrder::OSP) => 3,
+    }
+}
+
+impl Cursor {
+    /// The encoded cursor.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Restore a cursor from [as_bytes][Cursor::as_bytes].
+    pub fn from_bytes(bytes: impl Into<Vec<u8>>) -> Self {
+        Self(bytes.into())
+    }
+
+    /// A cursor at `key` in the node table (`order` is `None`) or in the edge table for `order`.
+    pub(crate) fn new(order: Option<&EdgeOrder>, key: &[u8]) -> Self {
+        let mut bytes = Vec::with_capacity(key.len() + 1);
+        bytes.push(table_tag(order));
+        bytes.extend_from_slice(key);
+        Self(bytes)
+    }
+
+    /// Decode the key, or `None` if the cursor does not point into the given table.
+    pub(crate) fn key<K: for<'a> TryFrom<&'a [u8]>>(&self, order: Option<&EdgeOrder>) -> Option<K> {
+        match self.0.split_first() {
+            Some((tag, key)) if *tag == table_tag(order) => K::try_from(key).ok(),
+            _ => None,
+        }
+    }
+}
+
+/// One page of rows, and the cursor to pass to get the next page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page<T> {
+    /// The rows, in key order.
+    pub items: Vec<T>,
+
+    /// The position of the last row, or `None` if there are no more rows.
+    pub next: Option<Cursor>,
+}
+
+impl<T> Default for Page<T> {
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            next: None,
+        }
+    }
+}
+
+/// A trait for iterating a [TripleStore] one page at a time, resuming from a [Cursor].
+///
+/// Vertices are paged in id order and edges in the order of the table they are read from. Each page holds at most
+/// `limit` rows, so a `limit` of zero gives an empty page with no cursor.
+///
+/// # Example
+/// ```
+/// # use ulid::Ulid;
+/// # use simple_triplestore::{prelude::*, Cursor, EdgeOrder, MemTripleStore, Triple, UlidIdGenerator};
+/// let mut db = MemTripleStore::<Ulid, (), ()>::new(UlidIdGenerator::new());
+/// for n in 1..=5 {
+///     db.insert_edge(Triple { sub: Ulid(1), pred: Ulid(10), obj: Ulid(n) }, ())?;
+/// }
+///
+/// let first = db.page_edges(EdgeOrder::SPO, None, 2)?;
+/// assert_eq!(first.items.len(), 2);
+///
+/// // Cursors can be stored and handed back later, e.g. by a client.
+/// let cursor = Cursor::from_bytes(first.next.expect("more rows").as_bytes());
+/// let rest = db.page_edges(EdgeOrder::SPO, Some(&cursor), 10)?;
+/// assert_eq!(rest.items.len(), 3);
+/// assert_eq!(rest.next, None);
+/// # Ok::<(), ()>(())
+/// ```
+pub trait TripleStorePaginate<Id: IdType, NodeProps: Property, EdgeProps: Property>:
+    TripleStoreError
+{
+    /// Fetch up to `limit` vertices with ids after `after`.
+    fn page_vertices(
+        &self,
+        after: Option<&Cursor>,
+        limit: usize,
+    ) -> Result<Page<(Id, NodeProps)>, Self::Error>;
+
+    /// Fetch up to `limit` edges in `order`, after `after`.
+    fn page_edges(
+        &self,
+        order: EdgeOrder,
+        after: Option<&Cursor>,
+        limit: usize,
+    ) -> Result<Page<(Triple<Id>, EdgeProps)>, Self::Error>;
+
+    /// Fetch up to `limit` rows of `query` after `after`.
+    ///
+    /// Like [iter_query][TripleStoreQuery::iter_query], returns a page of vertices for [Query::NodeProps] and a page
+    /// of edges for every other query; the other page is empty. The cursor is only valid for the same query.
+    fn page_query(
+        &self,
+        query: Query<Id>,
+        after: Option<&Cursor>,
+        limit: usize,
+    ) -> Result<QueryPages<Id, NodeProps, EdgeProps>, Self::Error>;
+}
+
+/// A page of vertices and a page of edges, as returned by [TripleStorePaginate::page_query].
+pub type QueryPages<Id, NodeProps, EdgeProps> =
+    (Page<(Id, NodeProps)>, Page<(Triple<Id>, EdgeProps)>);
+
+/// Decodes a key of one of the edge tables back to its triple.
+pub(crate) type DecodeTriple<Id> = fn(&<Id as ConcreteIdType>::TripleByteArrayType) -> Triple<Id>;
+
+/// The key ranges to scan for a [Query], in key order.
+pub(crate) enum QueryScan<Id: ConcreteIdType> {
+    /// Look up each vertex, in id order.
+    Nodes(Vec<Id>),
+
+    /// Scan each range of the table for the [EdgeOrder].
+    Edges(EdgeOrder, Vec<KeyRange<Id::TripleByteArrayType>>),
+}
+
+impl<Id: ConcreteIdType> QueryScan<Id> {
+    pub(crate) fn new(query: Query<Id>) -> Self {
+        fn sorted<T: Ord>(items: impl IntoIterator<Item = T>) -> Vec<T> {
+            let mut items = items.into_iter().collect::<Vec<_>>();
+            items.sort_unstable();
+            items
+        }
+
+        match query {
+            Query::NodeProps(nodes) => QueryScan::Nodes(sorted(nodes)),
+            Query::SPO(triples) => QueryScan::Edges(
+                EdgeOrder::SPO,
+                sorted(triples)
+                    .into_iter()
+                    .map(|(sub, pred, obj)| {
+                        let triple = Triple { sub, pred, obj };
+                        (
+                            Bound::Included(Id::encode_spo_triple(&triple)),
+                            Bound::Included(Id::encode_spo_triple(&triple)),
+                        )
+                    })
+                    .collect(),
+            ),
+            Query::S(subs) => QueryScan::Edges(
+                EdgeOrder::SPO,
+                sorted(subs).into_iter().map(Id::key_bounds_1).collect(),
+            ),
+            Query::SP(pairs) => QueryScan::Edges(
+                EdgeOrder::SPO,
+                sorted(pairs)
+                    .into_iter()
+                    .map(|(sub, pred)| Id::key_bounds_2(sub, pred))
+                    .collect(),
+            ),
+            Query::P(preds) => QueryScan::Edges(
+                EdgeOrder::POS,
+                sorted(preds).into_iter().map(Id::key_bounds_1).collect(),
+            ),
+            Query::PO(pairs) => QueryScan::Edges(
+                EdgeOrder::POS,
+                sorted(pairs)
+                    .into_iter()
+                    .map(|(pred, obj)| Id::key_bounds_2(pred, obj))
+                    .collect(),
+            ),
+            Query::O(objs) => QueryScan::Edges(
+                EdgeOrder::OSP,
+                sorted(objs).into_iter().map(Id::key_bounds_1).collect(),
+            ),
+            Query::SO(pairs) => QueryScan::Edges(
+                EdgeOrder::OSP,
+                sorted(pairs.into_iter().map(|(sub, obj)| (obj, sub)))
+                    .into_iter()
+                    .map(|(obj, sub)| Id::key_bounds_2(obj, sub))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Narrow a range of keys to those after `after`, or `None` if there are none.
+pub(crate) fn resume<K: Ord + AsRef<[u8]> + for<'a> TryFrom<&'a [u8]>>(
+    (start, end): (Bound<K>, Bound<K>),
+    after: Option<&K>,
+) -> Option<(Bound<K>, Bound<K>)> {
+    let Some(after) = after else {
+        return Some((start, end));
+    };
+    if let Bound::Included(e) | Bound::Excluded(e) = &end {
+        if after >= e {
+            return None;
+        }
+    }
+    if let Bound::Included(s) | Bound::Excluded(s) = &start {
+        if after < s {
+            return Some((start, end));
+        }
+    }
+    // Keys are not Clone, but always round trip through their bytes.
+    let after = K::try_from(after.as_ref()).ok()?;
+    Some((Bound::Excluded(after), end))
+}
+
+/// Collect up to `limit` rows into a [Page], where each row comes with the cursor pointing at it.
+pub(crate) fn collect_page<T, E>(
+    rows: impl Iterator<Item = Result<(Cursor, T), E>>,
+    limit: usize,
+) -> Result<Page<T>, E> {
+    let mut page = Page::default();
+    if limit == 0 {
+        return Ok(page);
+    }
+    for r in rows {
+        let (cursor, item) = r?;
+        if page.items.len() == limit {
+            return Ok(page);
+        }
+        page.items.push(item);
+        page.next = Some(cursor);
+    }
+    page.next = None;
+    Ok(page)
+}
@@ -0,0 +1,73 @@
+use std::collections::HashSet;
+
+use crate::{
+    path::PropertyPath,
+    pattern::{Bindings, Pattern, Plan},
+    prelude::*,
+    traits::{IdType, Property},
+    Triple,
+};
+
+/// A trait for matching [Pattern]s with variables against a [TripleStore].
+///
+/// Patterns are planned using the store's [statistics][TripleStoreStats].
+pub trait TripleStorePattern<Id: IdType, NodeProps: Property, EdgeProps: Property>:
+    TripleStoreStats<Id, NodeProps, EdgeProps>
+{
+    /// Iterate over the edges matching a single triple pattern, where `None` matches anything.
+    ///
+    /// Implementations should use whichever of the SPO, POS or OSP tables turns the bound terms into a range query.
+    fn match_triples<'a>(
+        &'a self,
+        sub: Option<Id>,
+        pred: Option<Id>,
+        obj: Option<Id>,
+    ) -> impl Iterator<Item = Result<Triple<Id>, Self::Error>> + 'a;
+
+    /// The [Plan] which [run_pattern][TripleStorePattern::run_pattern] would use for `pattern`.
+    fn explain(&self, pattern: &Pattern<Id>) -> Result<Plan<Id>, Self::Error> {
+        Ok(Plan::new(pattern, &self.statistics()?))
+    }
+
+    /// Evaluate a basic graph pattern, returning the bindings for every match.
+    ///
+    /// Triple patterns are joined with an index nested loop in the order chosen by
+    /// [explain][TripleStorePattern::explain]: each partial solution is substituted into the next pattern, which is
+    /// then looked up with [match_triples][TripleStorePattern::match_triples].
+    fn run_pattern(&self, pattern: &Pattern<Id>) -> Result<Vec<Bindings<Id>>, Self::Error> {
+        let mut solutions = vec![Bindings::new()];
+
+        for step in self.explain(pattern)?.steps {
+            let triple_pattern = &step.pattern;
+            let mut next = Vec::new();
+            for bindings in solutions.iter() {
+                let (sub, pred, obj) = triple_pattern.resolve(bindings);
+                for triple in self.match_triples(sub, pred, obj) {
+                    if let Some(extended) = triple_pattern.bind(triple?, bindings) {
+                        next.push(extended);
+                    }
+                }
+            }
+
+            if next.is_empty() {
+                return Ok(next);
+            }
+            solutions = next;
+        }
+
+        Ok(solutions)
+    }
+
+    /// Evaluate a property path, returning every `(start, end)` pair it connects.
+    ///
+    /// Binding `sub` or `obj` restricts the start or end of the path, and lets it be evaluated from that end with
+    /// range scans rather than over every edge with a matching predicate.
+    fn run_path(
+        &self,
+        sub: Option<Id>,
+        path: &PropertyPath<Id>,
+        obj: Option<Id>,
+    ) -> Result<HashSet<(Id, Id)>, Self::Error> {
+        crate::path::evaluate(self, sub, path, obj)
+    }
+}
@@ -0,0 +1,148 @@
+use std::ops::Bound;
+
+use crate::{
+    prelude::*,
+    traits::{ConcreteIdType, IdType, Property},
+    Quad, QuadOrder, Query, Triple,
+};
+
+/// A trait for stores which also hold edges in named graphs, so that each fact can be tracked by where it came from.
+///
+/// The [TripleStore] methods act on the default graph, and the methods here act on the named graphs. Vertices are
+/// shared by every graph, so an edge in a named graph refers to the same vertices as one in the default graph, and
+/// removing a vertex removes its edges from every graph.
+///
+/// # Example
+/// ```
+/// # use ulid::Ulid;
+/// # use simple_triplestore::{prelude::*, MemTripleStore, Quad, QuadOrder, Triple, UlidIdGenerator};
+/// let mut db = MemTripleStore::<Ulid, (), &str>::new(UlidIdGenerator::new());
+/// let (alice, bob, knows) = (Ulid(1), Ulid(2), Ulid(10));
+/// let (doc_1, doc_2) = (Ulid(100), Ulid(200));
+///
+/// let edge = Triple { sub: alice, pred: knows, obj: bob };
+/// db.insert_quad(Quad::new(edge.clone(), doc_1), "from doc 1")?;
+/// db.insert_quad(Quad::new(edge.clone(), doc_2), "from doc 2")?;
+///
+/// // Each graph holds its own copy of the edge, apart from the default graph.
+/// assert_eq!(db.get_edge(&edge)?, None);
+/// assert_eq!(db.iter_graphs().collect::<Result<Vec<_>, _>>()?, [doc_1, doc_2]);
+///
+/// // Dropping a graph removes everything which came from it.
+/// db.drop_graph(&doc_1)?;
+/// assert_eq!(
+///     db.iter_quads(QuadOrder::GSPO).collect::<Result<Vec<_>, _>>()?,
+///     [(Quad::new(edge, doc_2), "from doc 2")]
+/// );
+/// # Ok::<(), ()>(())
+/// ```
+pub trait QuadStore<Id: IdType, NodeProps: Property, EdgeProps: Property>:
+    TripleStore<Id, NodeProps, EdgeProps>
+{
+    /// Insert an edge into a named graph, replacing its props if it is already there.
+    fn insert_quad(&mut self, quad: Quad<Id>, props: EdgeProps) -> Result<(), Self::Error>;
+
+    /// Fetch the props of an edge in a named graph.
+    fn get_quad(&self, quad: &Quad<Id>) -> Result<Option<EdgeProps>, Self::Error>;
+
+    /// Remove an edge from a named graph. Removing a missing edge does nothing.
+    fn remove_quad(&mut self, quad: Quad<Id>) -> Result<(), Self::Error>;
+
+    /// Iterate over the named graphs which hold at least one edge.
+    fn iter_graphs<'a>(&'a self) -> impl Iterator<Item = Result<Id, Self::Error>> + 'a;
+
+    /// Iterate over the edges of every named graph.
+    fn iter_quads<'a>(
+        &'a self,
+        order: QuadOrder,
+    ) -> impl Iterator<Item = Result<(Quad<Id>, EdgeProps), Self::Error>> + 'a;
+
+    /// Execute a query against the edges of the given named graphs.
+    ///
+    /// Vertices are shared by every graph, so [Query::NodeProps] matches nothing here.
+    fn iter_query_graphs<'a>(
+        &'a self,
+        graphs: impl IntoIterator<Item = Id>,
+        query: Query<Id>,
+    ) -> impl Iterator<Item = Result<(Quad<Id>, EdgeProps), Self::Error>> + 'a;
+
+    /// Remove every edge in `graph`.
+    fn drop_graph(&mut self, graph: &Id) -> Result<(), Self::Error>;
+}
+
+/// The key range of one table to scan when running a query against a set of graphs.
+pub(crate) type QuadRange<Id> = (
+    QuadOrder,
+    (
+        Bound<<Id as ConcreteIdType>::QuadByteArrayType>,
+        Bound<<Id as ConcreteIdType>::QuadByteArrayType>,
+    ),
+);
+
+/// Plan the ranges to scan for `query` within each of `graphs`, using the table which leads with the fixed positions.
+pub(crate) fn quad_ranges<Id: ConcreteIdType>(
+    graphs: impl IntoIterator<Item = Id>,
+    query: &Query<Id>,
+) -> Vec<QuadRange<Id>> {
+    let mut graphs = graphs.into_iter().collect::<Vec<_>>();
+    graphs.sort();
+    graphs.dedup();
+
+    let exact = |graph: Id, triple: Triple<Id>| {
+        let quad = Quad::new(triple, graph);
+        (
+            QuadOrder::GSPO,
+            (
+                Bound::Included(quad.encode(&QuadOrder::GSPO)),
+                Bound::Included(quad.encode(&QuadOrder::GSPO)),
+            ),
+        )
+    };
+
+    let mut ranges = Vec::new();
+    for graph in graphs {
+        let scoped = |order, bounds| (order, Id::quad_key_bounds(graph, bounds));
+        match query {
+            Query::NodeProps(_) => {}
+            Query::SPO(triples) => ranges.extend(triples.iter().map(|(sub, pred, obj)| {
+                exact(
+                    graph,
+                    Triple {
+                        sub: *sub,
+                        pred: *pred,
+                        obj: *obj,
+                    },
+                )
+            })),
+            Query::S(subs) => ranges.extend(
+                subs.iter()
+                    .map(|sub| scoped(QuadOrder::GSPO, Id::key_bounds_1(*sub))),
+            ),
+            Query::P(preds) => ranges.extend(
+                preds
+                    .iter()
+                    .map(|pred| scoped(QuadOrder::GPOS, Id::key_bounds_1(*pred))),
+            ),
+            Query::O(objs) => ranges.extend(
+                objs.iter()
+                    .map(|obj| scoped(QuadOrder::GOSP, Id::key_bounds_1(*obj))),
+            ),
+            Query::SP(pairs) => ranges.extend(
+                pairs
+                    .iter()
+                    .map(|(sub, pred)| scoped(QuadOrder::GSPO, Id::key_bounds_2(*sub, *pred))),
+            ),
+            Query::PO(pairs) => ranges.extend(
+                pairs
+                    .iter()
+                    .map(|(pred, obj)| scoped(QuadOrder::GPOS, Id::key_bounds_2(*pred, *obj))),
+            ),
+            Query::SO(pairs) => ranges.extend(
+                pairs
+                    .iter()
+                    .map(|(sub, obj)| scoped(QuadOrder::GOSP, Id::key_bounds_2(*obj, *sub))),
+            ),
+        }
+    }
+    ranges
+}
@@ -1,15 +1,118 @@
 use crate::{
     prelude::*,
     traits::{IdType, Property},
-    Query,
+    Query, Triple,
 };
 
+/// A boxed iterator over fallible rows, for building query results whose shape depends on the query.
+pub(crate) type BoxedIter<'a, T, E> = Box<dyn Iterator<Item = Result<T, E>> + 'a>;
+
+/// The vertex and edge iterators of a query result, boxed.
+pub(crate) type BoxedRows<'a, Id, NodeProps, EdgeProps, E> = (
+    BoxedIter<'a, (Id, NodeProps), E>,
+    BoxedIter<'a, (Triple<Id>, EdgeProps), E>,
+);
+
+/// The error from [run][TripleStoreQuery::run] on a `T`: either `T` failed, or the store the results went into did.
+pub type RunError<T, Id, NodeProps, EdgeProps> = QueryError<
+    <T as TripleStoreError>::Error,
+    <<T as TripleStoreQuery<Id, NodeProps, EdgeProps>>::QueryResult as TripleStoreError>::Error,
+>;
+
 #[derive(Debug)]
 pub enum QueryError<SourceError: std::fmt::Debug, ResultError: std::fmt::Debug> {
     Left(SourceError),
     Right(ResultError),
 }
 
+/// Predicates on node and edge properties which are applied to the rows of a [Query] while it is being scanned.
+///
+/// Rows which are rejected are skipped before their properties are copied into the result. An unset predicate keeps
+/// every row.
+///
+/// # Example
+/// ```
+/// # use ulid::Ulid;
+/// # use simple_triplestore::{prelude::*, MemTripleStore, PropsFilter, Triple, UlidIdGenerator, query};
+/// let mut db = MemTripleStore::new(UlidIdGenerator::new());
+/// let (a, b, c, weight) = (Ulid(1), Ulid(2), Ulid(3), Ulid(4));
+/// db.insert_edge(Triple { sub: a, pred: weight, obj: b }, 3)?;
+/// db.insert_edge(Triple { sub: a, pred: weight, obj: c }, 8)?;
+///
+/// let heavy = |_: &Triple<Ulid>, w: &i32| *w > 5;
+/// let result = db
+///     .run_filtered(query! { ? -[weight]-> ? }, PropsFilter::<_, (), _>::new().edges(&heavy))
+///     .expect("ok");
+/// assert_eq!(
+///     result.iter_edges(Default::default()).map(|r| r.expect("ok")).collect::<Vec<_>>(),
+///     [(Triple { sub: a, pred: weight, obj: c }, 8)]
+/// );
+/// # Ok::<(), ()>(())
+/// ```
+pub struct PropsFilter<'f, Id: IdType, NodeProps, EdgeProps> {
+    nodes: Option<NodePredicate<'f, Id, NodeProps>>,
+    edges: Option<EdgePredicate<'f, Id, EdgeProps>>,
+}
+
+type NodePredicate<'f, Id, NodeProps> = &'f dyn Fn(&Id, &NodeProps) -> bool;
+type EdgePredicate<'f, Id, EdgeProps> = &'f dyn Fn(&Triple<Id>, &EdgeProps) -> bool;
+
+impl<'f, Id: IdType, NodeProps, EdgeProps> PropsFilter<'f, Id, NodeProps, EdgeProps> {
+    /// A filter which keeps every row.
+    pub fn new() -> Self {
+        Self {
+            nodes: None,
+            edges: None,
+        }
+    }
+
+    /// Only keep vertices for which `f` returns true.
+    pub fn nodes(mut self, f: &'f dyn Fn(&Id, &NodeProps) -> bool) -> Self {
+        self.nodes = Some(f);
+        self
+    }
+
+    /// Only keep edges for which `f` returns true.
+    pub fn edges(mut self, f: &'f dyn Fn(&Triple<Id>, &EdgeProps) -> bool) -> Self {
+        self.edges = Some(f);
+        self
+    }
+
+    /// Whether a node predicate has been set.
+    pub fn has_nodes(&self) -> bool {
+        self.nodes.is_some()
+    }
+
+    /// Whether an edge predicate has been set.
+    pub fn has_edges(&self) -> bool {
+        self.edges.is_some()
+    }
+
+    /// Check a vertex against the node predicate.
+    pub fn keep_node(&self, id: &Id, props: &NodeProps) -> bool {
+        self.nodes.is_none_or(|f| f(id, props))
+    }
+
+    /// Check an edge against the edge predicate.
+    pub fn keep_edge(&self, triple: &Triple<Id>, props: &EdgeProps) -> bool {
+        self.edges.is_none_or(|f| f(triple, props))
+    }
+}
+
+impl<Id: IdType, NodeProps, EdgeProps> Default for PropsFilter<'_, Id, NodeProps, EdgeProps> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Id: IdType, NodeProps, EdgeProps> Clone for PropsFilter<'_, Id, NodeProps, EdgeProps> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Id: IdType, NodeProps, EdgeProps> Copy for PropsFilter<'_, Id, NodeProps, EdgeProps> {}
+
 /// A trait for querying operations in a [TripleStore].
 ///
 /// Supports arbitrary source, predicate, and object queries, as well as lookups for properties of nodes and edges.
@@ -19,9 +122,55 @@ pub trait TripleStoreQuery<Id: IdType, NodeProps: Property, EdgeProps: Property>
     /// The result type of a query.
     type QueryResult: TripleStore<Id, NodeProps, EdgeProps>;
 
-    /// Execute a query and return the result.
+    /// Execute a query lazily, borrowing from the store rather than copying the results.
+    ///
+    /// Returns two iterators: one for vertices (from [Query::NodeProps]) and one for edges (from every other query).
+    /// The iterator which does not apply to the query is empty.
+    #[allow(
+        clippy::type_complexity,
+        reason = "a type alias cannot name the `impl Iterator` pair"
+    )]
+    fn iter_query<'a>(
+        &'a self,
+        query: Query<Id>,
+    ) -> (
+        impl Iterator<Item = Result<(Id, NodeProps), Self::Error>> + 'a,
+        impl Iterator<Item = Result<(Triple<Id>, EdgeProps), Self::Error>> + 'a,
+    )
+    where
+        Id: 'a,
+        NodeProps: 'a,
+        EdgeProps: 'a,
+    {
+        self.iter_query_filtered(query, PropsFilter::new())
+    }
+
+    /// Like [iter_query][TripleStoreQuery::iter_query], skipping rows rejected by `filter`.
+    #[allow(
+        clippy::type_complexity,
+        reason = "a type alias cannot name the `impl Iterator` pair"
+    )]
+    fn iter_query_filtered<'a>(
+        &'a self,
+        query: Query<Id>,
+        filter: PropsFilter<'a, Id, NodeProps, EdgeProps>,
+    ) -> (
+        impl Iterator<Item = Result<(Id, NodeProps), Self::Error>> + 'a,
+        impl Iterator<Item = Result<(Triple<Id>, EdgeProps), Self::Error>> + 'a,
+    );
+
+    /// Execute a query and return the result as a new [TripleStore].
     fn run(
         &self,
         query: Query<Id>,
-    ) -> Result<Self::QueryResult, QueryError<Self::Error, <<Self as TripleStoreQuery<Id, NodeProps, EdgeProps>>::QueryResult as TripleStoreError>::Error>>;
+    ) -> Result<Self::QueryResult, RunError<Self, Id, NodeProps, EdgeProps>> {
+        self.run_filtered(query, PropsFilter::new())
+    }
+
+    /// Like [run][TripleStoreQuery::run], leaving out rows rejected by `filter`.
+    fn run_filtered(
+        &self,
+        query: Query<Id>,
+        filter: PropsFilter<Id, NodeProps, EdgeProps>,
+    ) -> Result<Self::QueryResult, RunError<Self, Id, NodeProps, EdgeProps>>;
 }
@@ -0,0 +1,174 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    prelude::*,
+    traits::{ConcreteIdType, IdType, Property},
+    Triple,
+};
+
+/// Cardinality statistics for the edges with one predicate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "sled", derive(serde::Serialize, serde::Deserialize))]
+pub struct PredicateStats {
+    /// The number of edges with the predicate.
+    pub edges: u64,
+
+    /// The number of distinct subjects of edges with the predicate.
+    pub subjects: u64,
+
+    /// The number of distinct objects of edges with the predicate.
+    pub objects: u64,
+}
+
+/// Cardinality statistics for a whole [TripleStore], used to plan queries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Statistics<Id: IdType> {
+    /// The number of nodes with props.
+    pub nodes: u64,
+
+    /// The number of edges.
+    pub edges: u64,
+
+    /// The statistics for each predicate which is used by at least one edge.
+    pub predicates: HashMap<Id, PredicateStats>,
+}
+
+impl<Id: IdType> Default for Statistics<Id> {
+    fn default() -> Self {
+        Self {
+            nodes: 0,
+            edges: 0,
+            predicates: HashMap::new(),
+        }
+    }
+}
+
+impl<Id: IdType> Statistics<Id> {
+    /// The statistics for `pred`, which are all zero if no edge uses it.
+    pub fn predicate(&self, pred: &Id) -> PredicateStats {
+        self.predicates.get(pred).copied().unwrap_or_default()
+    }
+}
+
+/// A trait for reading the cardinality statistics which a [TripleStore] maintains as it is written to.
+///
+/// # Example
+/// ```
+/// # use ulid::Ulid;
+/// # use simple_triplestore::{prelude::*, traits::PredicateStats, MemTripleStore, Triple, UlidIdGenerator};
+/// let mut db = MemTripleStore::<Ulid, (), ()>::new(UlidIdGenerator::new());
+/// let (alice, bob, carol, knows) = (Ulid(1), Ulid(2), Ulid(3), Ulid(10));
+///
+/// db.insert_edge(Triple { sub: alice, pred: knows, obj: bob }, ())?;
+/// db.insert_edge(Triple { sub: alice, pred: knows, obj: carol }, ())?;
+///
+/// let stats = db.statistics()?;
+/// assert_eq!(stats.edges, 2);
+/// assert_eq!(stats.predicate(&knows), PredicateStats { edges: 2, subjects: 1, objects: 2 });
+/// # Ok::<(), ()>(())
+/// ```
+pub trait TripleStoreStats<Id: IdType, NodeProps: Property, EdgeProps: Property>:
+    TripleStoreError
+{
+    /// The current statistics.
+    fn statistics(&self) -> Result<Statistics<Id>, Self::Error>;
+}
+
+/// A change to the [PredicateStats] of one predicate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct StatsDelta {
+    pub(crate) edges: i64,
+    pub(crate) subjects: i64,
+    pub(crate) objects: i64,
+}
+
+impl StatsDelta {
+    pub(crate) fn apply(&self, stats: &mut PredicateStats) {
+        stats.edges = stats.edges.saturating_add_signed(self.edges);
+        stats.subjects = stats.subjects.saturating_add_signed(self.subjects);
+        stats.objects = stats.objects.saturating_add_signed(self.objects);
+    }
+}
+
+/// Work out how the statistics of each predicate change when the distinct `triples` have just been added to `db` (or
+/// removed from it, when `added` is false).
+///
+/// A subject is new to a predicate if every edge from it with the predicate is one of those added, and is gone if no
+/// such edge remains; objects are treated the same way.
+pub(crate) fn stats_delta<Id: IdType, NodeProps: Property, EdgeProps: Property, T>(
+    db: &T,
+    triples: &[Triple<Id>],
+    added: bool,
+) -> Result<HashMap<Id, StatsDelta>, T::Error>
+where
+    T: TripleStorePattern<Id, NodeProps, EdgeProps>,
+{
+    let mut deltas: HashMap<Id, StatsDelta> = HashMap::new();
+    let mut subjects: HashMap<(&Id, &Id), usize> = HashMap::new();
+    let mut objects: HashMap<(&Id, &Id), usize> = HashMap::new();
+    for triple in triples {
+        deltas.entry(triple.pred.clone()).or_default().edges += 1;
+        *subjects.entry((&triple.sub, &triple.pred)).or_default() += 1;
+        *objects.entry((&triple.pred, &triple.obj)).or_default() += 1;
+    }
+
+    let sign = if added { 1 } else { -1 };
+    let changed = |remaining: usize, count: usize| {
+        if added {
+            remaining <= count
+        } else {
+            remaining == 0
+        }
+    };
+
+    for ((sub, pred), count) in subjects {
+        let remaining = db
+            .match_triples(Some(sub.clone()), Some(pred.clone()), None)
+            .take(count + 1)
+            .collect::<Result<Vec<_>, _>>()?
+            .len();
+        if changed(remaining, count) {
+            deltas.entry(pred.clone()).or_default().subjects += 1;
+        }
+    }
+    for ((pred, obj), count) in objects {
+        let remaining = db
+            .match_triples(None, Some(pred.clone()), Some(obj.clone()))
+            .take(count + 1)
+            .collect::<Result<Vec<_>, _>>()?
+            .len();
+        if changed(remaining, count) {
+            deltas.entry(pred.clone()).or_default().objects += 1;
+        }
+    }
+
+    for delta in deltas.values_mut() {
+        delta.edges *= sign;
+        delta.subjects *= sign;
+        delta.objects *= sign;
+    }
+    Ok(deltas)
+}
+
+/// Count the statistics of the edges produced by `triples`, which must be in SPO order.
+pub(crate) fn count_predicates<Id: ConcreteIdType, E>(
+    triples: impl Iterator<Item = Result<Triple<Id>, E>>,
+) -> Result<HashMap<Id, PredicateStats>, E> {
+    let mut predicates: HashMap<Id, PredicateStats> = HashMap::new();
+    let mut objects: HashMap<Id, HashSet<Id>> = HashMap::new();
+    let mut last_sub: HashMap<Id, Id> = HashMap::new();
+    for r in triples {
+        let triple = r?;
+        let stats = predicates.entry(triple.pred).or_default();
+        stats.edges += 1;
+        // In SPO order all edges from a subject are adjacent, so a subject is new when it differs from the last one.
+        if last_sub.get(&triple.pred) != Some(&triple.sub) {
+            stats.subjects += 1;
+            last_sub.insert(triple.pred, triple.sub);
+        }
+        if objects.entry(triple.pred).or_default().insert(triple.obj) {
+            stats.objects += 1;
+        }
+    }
+    Ok(predicates)
+}
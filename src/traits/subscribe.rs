@@ -0,0 +1,228 @@
+use std::{
+    cell::RefCell,
+    collections::HashSet,
+    sync::mpsc::{Receiver, Sender},
+};
+
+use crate::{
+    prelude::*,
+    traits::{IdType, Property},
+    Quad, Triple,
+};
+
+/// A change made to a [TripleStore], as delivered to a [Subscription].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event<Id: IdType, NodeProps: Property, EdgeProps: Property> {
+    /// A vertex was inserted, or its props were replaced.
+    NodeInserted { node: Id, props: NodeProps },
+
+    /// A vertex was removed. Its edges are removed first, each with an [Event::EdgeRemoved], and likewise its edges in
+    /// named graphs, each with an [Event::QuadRemoved].
+    NodeRemoved { node: Id },
+
+    /// An edge was inserted, or its props were replaced.
+    EdgeInserted {
+        triple: Triple<Id>,
+        props: EdgeProps,
+    },
+
+    /// An edge was removed.
+    EdgeRemoved { triple: Triple<Id> },
+
+    /// An edge was inserted into a named graph, or its props there were replaced.
+    QuadInserted { quad: Quad<Id>, props: EdgeProps },
+
+    /// An edge was removed from a named graph. Dropping a graph publishes one for each of its edges.
+    QuadRemoved { quad: Quad<Id> },
+
+    /// New props were merged into those of an existing vertex or edge.
+    ///
+    /// Merging into a vertex or edge which does not exist yet inserts it instead.
+    PropsMerged(MergedProps<Id, NodeProps, EdgeProps>),
+}
+
+/// The result of merging props, in an [Event::PropsMerged].
+#[derive(Debug, Clone, PartialEq)]
+pub enum MergedProps<Id: IdType, NodeProps: Property, EdgeProps: Property> {
+    /// The props of a vertex, after merging.
+    Node { node: Id, props: NodeProps },
+
+    /// The props of an edge, after merging.
+    Edge {
+        triple: Triple<Id>,
+        props: EdgeProps,
+    },
+}
+
+/// Selects which [Event]s a [Subscription] receives. An empty filter receives every event.
+///
+/// A subject filter keeps edge events whose subject is one of the given ids, and vertex events for those ids. A
+/// predicate filter keeps edge events with one of the given predicates, and drops all vertex events. When both are set
+/// an event must pass both.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventFilter<Id: IdType> {
+    subjects: Option<HashSet<Id>>,
+    predicates: Option<HashSet<Id>>,
+}
+
+impl<Id: IdType> Default for EventFilter<Id> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Id: IdType> EventFilter<Id> {
+    /// A filter which keeps every event.
+    pub fn new() -> Self {
+        Self {
+            subjects: None,
+            predicates: None,
+        }
+    }
+
+    /// Also keep events for `subject`, and drop events for subjects which are never given.
+    pub fn subject(mut self, subject: Id) -> Self {
+        self.subjects
+            .get_or_insert_with(HashSet::new)
+            .insert(subject);
+        self
+    }
+
+    /// Also keep edge events with `predicate`, and drop events for predicates which are never given.
+    pub fn predicate(mut self, predicate: Id) -> Self {
+        self.predicates
+            .get_or_insert_with(HashSet::new)
+            .insert(predicate);
+        self
+    }
+
+    /// Check an event against the filter.
+    pub fn matches<NodeProps: Property, EdgeProps: Property>(
+        &self,
+        event: &Event<Id, NodeProps, EdgeProps>,
+    ) -> bool {
+        let (subject, predicate) = match event {
+            Event::NodeInserted { node, .. }
+            | Event::NodeRemoved { node }
+            | Event::PropsMerged(MergedProps::Node { node, .. }) => (node, None),
+            Event::EdgeInserted { triple, .. }
+            | Event::EdgeRemoved { triple }
+            | Event::PropsMerged(MergedProps::Edge { triple, .. }) => {
+                (&triple.sub, Some(&triple.pred))
+            }
+            Event::QuadInserted { quad, .. } | Event::QuadRemoved { quad } => {
+                (&quad.sub, Some(&quad.pred))
+            }
+        };
+        self.subjects
+            .as_ref()
+            .is_none_or(|subjects| subjects.contains(subject))
+            && self.predicates.as_ref().is_none_or(|predicates| {
+                predicate.is_some_and(|predicate| predicates.contains(predicate))
+            })
+    }
+}
+
+/// The receiving end of [TripleStoreSubscribe::subscribe].
+///
+/// Iterating blocks until the next event, and ends once the store is dropped. Dropping the subscription unsubscribes.
+pub struct Subscription<Id: IdType, NodeProps: Property, EdgeProps: Property> {
+    receiver: Receiver<Event<Id, NodeProps, EdgeProps>>,
+}
+
+impl<Id: IdType, NodeProps: Property, EdgeProps: Property> Subscription<Id, NodeProps, EdgeProps> {
+    /// The events which have already been published, without blocking.
+    pub fn try_iter(&self) -> impl Iterator<Item = Event<Id, NodeProps, EdgeProps>> + '_ {
+        self.receiver.try_iter()
+    }
+}
+
+impl<Id: IdType, NodeProps: Property, EdgeProps: Property> Iterator
+    for Subscription<Id, NodeProps, EdgeProps>
+{
+    type Item = Event<Id, NodeProps, EdgeProps>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.receiver.recv().ok()
+    }
+}
+
+/// A trait for subscribing to the changes made to a [TripleStore].
+///
+/// Events are published once a change has been applied, from every path which changes the store: inserts, removals,
+/// merges, extends, bulk loads, transactions and writes to named graphs. Transactions publish their net changes when
+/// they commit. Only changes made through the same store value are seen.
+///
+/// # Example
+/// ```
+/// # use ulid::Ulid;
+/// # use simple_triplestore::{prelude::*, traits::{Event, EventFilter}, MemTripleStore, Triple, UlidIdGenerator};
+/// let mut db = MemTripleStore::<Ulid, (), String>::new(UlidIdGenerator::new());
+/// let (alice, bob, knows, likes) = (Ulid(1), Ulid(2), Ulid(10), Ulid(11));
+///
+/// let subscription = db.subscribe(EventFilter::new().predicate(knows));
+///
+/// db.insert_edge(Triple { sub: alice, pred: knows, obj: bob }, "since 2020".to_string())?;
+/// db.insert_edge(Triple { sub: alice, pred: likes, obj: bob }, "".to_string())?;
+/// db.remove_edge(Triple { sub: alice, pred: knows, obj: bob })?;
+///
+/// assert_eq!(
+///     subscription.try_iter().collect::<Vec<_>>(),
+///     [
+///         Event::EdgeInserted {
+///             triple: Triple { sub: alice, pred: knows, obj: bob },
+///             props: "since 2020".to_string()
+///         },
+///         Event::EdgeRemoved { triple: Triple { sub: alice, pred: knows, obj: bob } },
+///     ]
+/// );
+/// # Ok::<(), ()>(())
+/// ```
+pub trait TripleStoreSubscribe<Id: IdType, NodeProps: Property, EdgeProps: Property>:
+    TripleStoreError
+{
+    /// Receive the events which pass `filter` from now on.
+    fn subscribe(&self, filter: EventFilter<Id>) -> Subscription<Id, NodeProps, EdgeProps>;
+}
+
+/// The subscriptions to one store.
+pub(crate) struct Subscribers<Id: IdType, NodeProps: Property, EdgeProps: Property> {
+    senders: RefCell<Vec<Subscriber<Id, NodeProps, EdgeProps>>>,
+}
+
+/// One subscription's filter, and the channel its events are sent on.
+type Subscriber<Id, NodeProps, EdgeProps> =
+    (EventFilter<Id>, Sender<Event<Id, NodeProps, EdgeProps>>);
+
+impl<Id: IdType, NodeProps: Property, EdgeProps: Property> Subscribers<Id, NodeProps, EdgeProps> {
+    pub(crate) fn new() -> Self {
+        Self {
+            senders: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub(crate) fn subscribe(
+        &self,
+        filter: EventFilter<Id>,
+    ) -> Subscription<Id, NodeProps, EdgeProps> {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        self.senders.borrow_mut().push((filter, sender));
+        Subscription { receiver }
+    }
+
+    /// Whether anyone is subscribed, so that events are worth building.
+    pub(crate) fn is_active(&self) -> bool {
+        !self.senders.borrow().is_empty()
+    }
+
+    /// Send the event built by `event` to every matching subscription, dropping those which have been dropped.
+    pub(crate) fn publish(&self, event: impl FnOnce() -> Event<Id, NodeProps, EdgeProps>) {
+        if !self.is_active() {
+            return;
+        }
+        let event = event();
+        self.senders.borrow_mut().retain(|(filter, sender)| {
+            !filter.matches(&event) || sender.send(event.clone()).is_ok()
+        });
+    }
+}
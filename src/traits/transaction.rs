@@ -0,0 +1,177 @@
+use std::borrow::Borrow;
+
+use crate::{
+    prelude::*,
+    traits::{IdType, Mergeable, Property},
+    EdgeOrder, MergeError, Triple,
+};
+
+/// A single write recorded by a [Transaction].
+///
+/// Merges carry the [Mergeable::merge] function for their props, so that stores can apply them without requiring
+/// `Mergeable` props themselves.
+pub(crate) enum Operation<Id: IdType, NodeProps, EdgeProps> {
+    InsertNode(Id, NodeProps),
+    InsertEdge(Triple<Id>, EdgeProps),
+    MergeNode(Id, NodeProps, fn(&mut NodeProps, NodeProps)),
+    MergeEdge(Triple<Id>, EdgeProps, fn(&mut EdgeProps, EdgeProps)),
+    RemoveNode(Id),
+    RemoveEdge(Triple<Id>),
+}
+
+/// A batch of inserts, removals and merges which is applied to a store all at once by
+/// [TripleStoreTransaction::transaction].
+///
+/// Operations are recorded in order and are not visible in the store until the transaction commits.
+pub struct Transaction<Id: IdType, NodeProps, EdgeProps, Error> {
+    operations: Vec<Operation<Id, NodeProps, EdgeProps>>,
+    _phantom: std::marker::PhantomData<fn() -> Error>,
+}
+
+impl<Id: IdType, NodeProps, EdgeProps, Error> Default
+    for Transaction<Id, NodeProps, EdgeProps, Error>
+{
+    fn default() -> Self {
+        Self {
+            operations: Vec::new(),
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<Id: IdType, NodeProps, EdgeProps, Error> Transaction<Id, NodeProps, EdgeProps, Error> {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of operations recorded so far.
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    /// Whether no operations have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    pub(crate) fn into_operations(self) -> Vec<Operation<Id, NodeProps, EdgeProps>> {
+        self.operations
+    }
+}
+
+impl<Id: IdType, NodeProps, EdgeProps, Error: std::fmt::Debug> TripleStoreError
+    for Transaction<Id, NodeProps, EdgeProps, Error>
+{
+    type Error = Error;
+}
+
+impl<Id: IdType, NodeProps: Property, EdgeProps: Property, Error: std::fmt::Debug>
+    TripleStoreInsert<Id, NodeProps, EdgeProps> for Transaction<Id, NodeProps, EdgeProps, Error>
+{
+    fn insert_node(&mut self, node: Id, props: NodeProps) -> Result<(), Error> {
+        self.operations.push(Operation::InsertNode(node, props));
+        Ok(())
+    }
+
+    fn insert_edge(&mut self, triple: Triple<Id>, props: EdgeProps) -> Result<(), Error> {
+        self.operations.push(Operation::InsertEdge(triple, props));
+        Ok(())
+    }
+}
+
+impl<Id: IdType, NodeProps: Property, EdgeProps: Property, Error: std::fmt::Debug>
+    TripleStoreRemove<Id, NodeProps, EdgeProps> for Transaction<Id, NodeProps, EdgeProps, Error>
+{
+    fn remove_node(&mut self, node: impl Borrow<Id>) -> Result<(), Error> {
+        self.operations
+            .push(Operation::RemoveNode(node.borrow().clone()));
+        Ok(())
+    }
+
+    fn remove_edge(&mut self, triple: Triple<Id>) -> Result<(), Error> {
+        self.operations.push(Operation::RemoveEdge(triple));
+        Ok(())
+    }
+}
+
+impl<
+        Id: IdType,
+        NodeProps: Property + Mergeable,
+        EdgeProps: Property + Mergeable,
+        Error: std::fmt::Debug,
+    > TripleStoreMerge<Id, NodeProps, EdgeProps> for Transaction<Id, NodeProps, EdgeProps, Error>
+{
+    fn merge<E: std::fmt::Debug>(
+        &mut self,
+        other: impl TripleStore<Id, NodeProps, EdgeProps, Error = E>,
+    ) -> Result<(), MergeError<Error, E>> {
+        let (other_nodes, other_edges) = other.into_iter_nodes(EdgeOrder::SPO);
+
+        for r in other_nodes {
+            let (id, props) = r.map_err(MergeError::Right)?;
+            self.merge_node(id, props).map_err(MergeError::Left)?;
+        }
+
+        for r in other_edges {
+            let (triple, props) = r.map_err(MergeError::Right)?;
+            self.merge_edge(triple, props).map_err(MergeError::Left)?;
+        }
+
+        Ok(())
+    }
+
+    fn merge_node(&mut self, node: Id, props: NodeProps) -> Result<(), Error> {
+        self.operations
+            .push(Operation::MergeNode(node, props, NodeProps::merge));
+        Ok(())
+    }
+
+    fn merge_edge(&mut self, triple: Triple<Id>, props: EdgeProps) -> Result<(), Error> {
+        self.operations
+            .push(Operation::MergeEdge(triple, props, EdgeProps::merge));
+        Ok(())
+    }
+}
+
+/// A trait for applying several writes to a [TripleStore] atomically.
+///
+/// The closure passed to [transaction][TripleStoreTransaction::transaction] records inserts, removals and merges on a
+/// [Transaction], which supports the same [insert][TripleStoreInsert], [remove][TripleStoreRemove] and
+/// [merge][TripleStoreMerge] operations as the store. If the closure succeeds every operation is applied in order,
+/// all or nothing; if it returns an error none of them are.
+///
+/// # Example
+/// ```
+/// # use ulid::Ulid;
+/// # use simple_triplestore::{prelude::*, MemTripleStore, Triple, UlidIdGenerator};
+/// let mut db = MemTripleStore::<Ulid, &str, ()>::new(UlidIdGenerator::new());
+/// let (a, b, edge) = (Ulid(1), Ulid(2), Ulid(3));
+///
+/// db.transaction(|tx| {
+///     tx.insert_node(a, "a")?;
+///     tx.insert_node(b, "b")?;
+///     tx.insert_edge(Triple { sub: a, pred: edge, obj: b }, ())
+/// })?;
+/// assert_eq!(db.iter_vertices().count(), 2);
+///
+/// // Returning an error discards the whole transaction.
+/// assert!(db
+///     .transaction(|tx| {
+///         tx.remove_node(a)?;
+///         Err::<(), ()>(())
+///     })
+///     .is_err());
+/// assert_eq!(db.iter_vertices().count(), 2);
+/// # Ok::<(), ()>(())
+/// ```
+pub trait TripleStoreTransaction<Id: IdType, NodeProps: Property, EdgeProps: Property>:
+    TripleStoreError
+{
+    /// Record operations with `f` and then apply them atomically, returning the result of `f`.
+    fn transaction<R>(
+        &mut self,
+        f: impl FnOnce(
+            &mut Transaction<Id, NodeProps, EdgeProps, Self::Error>,
+        ) -> Result<R, Self::Error>,
+    ) -> Result<R, Self::Error>;
+}
@@ -19,25 +19,40 @@ pub enum TryEqError<LeftError: std::fmt::Debug, RightError: std::fmt::Debug> {
 ///
 /// By default includes:
 ///   * [Insert][TripleStoreInsert]
+///   * [Get][TripleStoreGet]
 ///   * [Remove][TripleStoreRemove]
 ///   * [Iter][TripleStoreIter]
 ///   * [IntoIter][TripleStoreIntoIter]
 ///   * [Query][TripleStoreQuery]
+///   * [Pattern][TripleStorePattern]
+///   * [Statistics][TripleStoreStats]
+///   * [Counting][TripleStoreCount]
 ///   * [Extend][TripleStoreExtend]
 ///
 /// Some implementations may also support:
+///   * [Bulk Loading][TripleStoreBulkLoad]
 ///   * [Merge][TripleStoreMerge]
+///   * [Named Graphs][QuadStore]
+///   * [Pagination][TripleStorePaginate]
 ///   * [Set Operations][TripleStoreSetOps]
+///   * [Secondary Indexes][TripleStoreIndex]
+///   * [Subscriptions][TripleStoreSubscribe]
+///   * [Transactions][TripleStoreTransaction]
+///   * [Verification and Repair][TripleStoreVerify]
 ///
 /// # Example
 ///
 /// See [MemTripleStore] or [SledTripleStore] for usage.
 pub trait TripleStore<Id: IdType, NodeProps: Property, EdgeProps: Property>:
     TripleStoreInsert<Id, NodeProps, EdgeProps>
+    + TripleStoreGet<Id, NodeProps, EdgeProps>
     + TripleStoreRemove<Id, NodeProps, EdgeProps>
     + TripleStoreIter<Id, NodeProps, EdgeProps>
     + TripleStoreIntoIter<Id, NodeProps, EdgeProps>
     + TripleStoreQuery<Id, NodeProps, EdgeProps>
+    + TripleStoreStats<Id, NodeProps, EdgeProps>
+    + TripleStoreCount<Id, NodeProps, EdgeProps>
+    + TripleStorePattern<Id, NodeProps, EdgeProps>
     + TripleStoreExtend<Id, NodeProps, EdgeProps>
 {
     fn try_eq<OError: std::fmt::Debug>(
@@ -0,0 +1,227 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use crate::{
+    traits::{ConcreteIdType, IdType, Property, TripleStoreError},
+    Quad, QuadOrder, Triple,
+};
+
+/// The inconsistencies between the tables of a [TripleStore][crate::prelude::TripleStore], as found by
+/// [TripleStoreVerify::verify] or fixed by [TripleStoreVerify::repair].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntegrityReport<Id: IdType> {
+    /// The number of edges in the SPO table.
+    pub edges: u64,
+
+    /// Edges which are missing from the POS table, point at different props there, or are only in the POS table.
+    pub pos_mismatches: Vec<Triple<Id>>,
+
+    /// Edges which are missing from the OSP table, point at different props there, or are only in the OSP table.
+    pub osp_mismatches: Vec<Triple<Id>>,
+
+    /// Edges in the SPO table whose props record is missing.
+    pub missing_props: Vec<Triple<Id>>,
+
+    /// The ids of edge props records which no edge in the SPO table points at.
+    pub orphaned_props: Vec<Id>,
+
+    /// The number of edges in the GSPO table of the named graphs.
+    pub quads: u64,
+
+    /// Named graph edges which are missing from the GPOS table, point at different props there, or are only in the
+    /// GPOS table.
+    pub gpos_mismatches: Vec<Quad<Id>>,
+
+    /// Named graph edges which are missing from the GOSP table, point at different props there, or are only in the
+    /// GOSP table.
+    pub gosp_mismatches: Vec<Quad<Id>>,
+
+    /// Named graph edges which are missing from the SPOG table, point at different props there, or are only in the
+    /// SPOG table.
+    pub spog_mismatches: Vec<Quad<Id>>,
+
+    /// Named graph edges which are missing from the OSPG table, point at different props there, or are only in the
+    /// OSPG table.
+    pub ospg_mismatches: Vec<Quad<Id>>,
+
+    /// Named graph edges in the GSPO table whose props record is missing.
+    pub missing_quad_props: Vec<Quad<Id>>,
+
+    /// The ids of named graph props records which no edge in the GSPO table points at.
+    pub orphaned_quad_props: Vec<Id>,
+
+    /// Whether the stored [Statistics][crate::traits::Statistics] differ from a recount of the nodes and edges.
+    pub stale_statistics: bool,
+}
+
+impl<Id: IdType> IntegrityReport<Id> {
+    /// Whether no inconsistencies were found.
+    pub fn is_consistent(&self) -> bool {
+        self.pos_mismatches.is_empty()
+            && self.osp_mismatches.is_empty()
+            && self.missing_props.is_empty()
+            && self.orphaned_props.is_empty()
+            && self.gpos_mismatches.is_empty()
+            && self.gosp_mismatches.is_empty()
+            && self.spog_mismatches.is_empty()
+            && self.ospg_mismatches.is_empty()
+            && self.missing_quad_props.is_empty()
+            && self.orphaned_quad_props.is_empty()
+            && !self.stale_statistics
+    }
+}
+
+/// A trait for checking that the tables of a [TripleStore][crate::prelude::TripleStore] agree with each other, and
+/// for repairing them when they do not.
+///
+/// Each edge is stored under its SPO, POS and OSP keys, all pointing at a props record of its own. A crash part way
+/// through a write, or a bug, can leave these out of step. The SPO table is taken to be the source of truth. Edges in
+/// named graphs are checked the same way, with the GSPO table as the source of truth for the GPOS, GOSP, SPOG and OSPG
+/// tables.
+///
+/// # Example
+/// ```
+/// # use ulid::Ulid;
+/// # use simple_triplestore::{prelude::*, MemTripleStore, Triple, UlidIdGenerator};
+/// let mut db = MemTripleStore::<Ulid, (), ()>::new(UlidIdGenerator::new());
+/// db.insert_edge(Triple { sub: Ulid(1), pred: Ulid(10), obj: Ulid(2) }, ())?;
+///
+/// let report = db.verify()?;
+/// assert!(report.is_consistent());
+/// assert_eq!(report.edges, 1);
+/// # Ok::<(), ()>(())
+/// ```
+pub trait TripleStoreVerify<Id: IdType, NodeProps: Property, EdgeProps: Property>:
+    TripleStoreError
+{
+    /// Cross-check the SPO, POS and OSP tables against each other and against the edge props, likewise the tables of
+    /// the named graphs, and the statistics against the nodes and edges, without changing anything.
+    fn verify(&self) -> Result<IntegrityReport<Id>, Self::Error>;
+
+    /// Make the tables consistent again, returning the report of what was fixed.
+    ///
+    /// Edges whose props are missing are removed, the POS and OSP tables are rebuilt from the SPO table, orphaned
+    /// props records are deleted and the statistics are recounted. The tables of the named graphs are repaired in the
+    /// same way from the GSPO table.
+    fn repair(&mut self) -> Result<IntegrityReport<Id>, Self::Error>;
+}
+
+/// Cross-check the entries of the SPO, POS and OSP tables, decoded to triples with the id of their props, against each
+/// other and against the ids of the edge props records. The named graphs and statistics are not checked.
+pub(crate) fn check_tables<Id: ConcreteIdType, E>(
+    spo: impl Iterator<Item = Result<(Triple<Id>, Id), E>>,
+    pos: impl Iterator<Item = Result<(Triple<Id>, Id), E>>,
+    osp: impl Iterator<Item = Result<(Triple<Id>, Id), E>>,
+    edge_props: impl Iterator<Item = Result<Id, E>>,
+) -> Result<IntegrityReport<Id>, E> {
+    let edges = check(spo, pos, osp, edge_props, Id::encode_spo_triple)?;
+    Ok(IntegrityReport {
+        edges: edges.primary.len() as u64,
+        pos_mismatches: edges.second_mismatches,
+        osp_mismatches: edges.third_mismatches,
+        missing_props: edges.missing_props,
+        orphaned_props: edges.orphaned_props,
+        quads: 0,
+        gpos_mismatches: Vec::new(),
+        gosp_mismatches: Vec::new(),
+        spog_mismatches: Vec::new(),
+        ospg_mismatches: Vec::new(),
+        missing_quad_props: Vec::new(),
+        orphaned_quad_props: Vec::new(),
+        stale_statistics: false,
+    })
+}
+
+/// Cross-check the entries of the GSPO, GPOS, GOSP, SPOG and OSPG tables, decoded to quads with the id of their props,
+/// against each other and against the ids of the named graph props records, adding what was found to `report`.
+pub(crate) fn check_quad_tables<Id: ConcreteIdType, E>(
+    report: &mut IntegrityReport<Id>,
+    gspo: impl Iterator<Item = Result<(Quad<Id>, Id), E>>,
+    gpos: impl Iterator<Item = Result<(Quad<Id>, Id), E>>,
+    gosp: impl Iterator<Item = Result<(Quad<Id>, Id), E>>,
+    spog: impl Iterator<Item = Result<(Quad<Id>, Id), E>>,
+    ospg: impl Iterator<Item = Result<(Quad<Id>, Id), E>>,
+    quad_props: impl Iterator<Item = Result<Id, E>>,
+) -> Result<(), E> {
+    let sort_key = |quad: &Quad<Id>| quad.encode(&QuadOrder::GSPO);
+    let quads = check(gspo, gpos, gosp, quad_props, sort_key)?;
+    report.quads = quads.primary.len() as u64;
+    report.gpos_mismatches = quads.second_mismatches;
+    report.gosp_mismatches = quads.third_mismatches;
+    report.spog_mismatches = mismatches(&quads.primary, spog, sort_key)?;
+    report.ospg_mismatches = mismatches(&quads.primary, ospg, sort_key)?;
+    report.missing_quad_props = quads.missing_props;
+    report.orphaned_quad_props = quads.orphaned_props;
+    Ok(())
+}
+
+/// What was found by cross-checking one set of tables, whose rows are identified by `K`.
+struct TableReport<K, Id> {
+    /// The rows of the primary table, with the ids of their props.
+    primary: HashMap<K, Id>,
+    second_mismatches: Vec<K>,
+    third_mismatches: Vec<K>,
+    missing_props: Vec<K>,
+    orphaned_props: Vec<Id>,
+}
+
+/// Cross-check the entries of a primary table and two other orderings of it against each other and against the ids
+/// of the props records. Rows are returned in the order given by `sort_key`.
+fn check<K: Clone + Eq + Hash, Id: ConcreteIdType, S: Ord, E>(
+    primary: impl Iterator<Item = Result<(K, Id), E>>,
+    second: impl Iterator<Item = Result<(K, Id), E>>,
+    third: impl Iterator<Item = Result<(K, Id), E>>,
+    props: impl Iterator<Item = Result<Id, E>>,
+    sort_key: impl Fn(&K) -> S + Copy,
+) -> Result<TableReport<K, Id>, E> {
+    let primary = primary.collect::<Result<HashMap<_, _>, _>>()?;
+
+    let second_mismatches = mismatches(&primary, second, sort_key)?;
+    let third_mismatches = mismatches(&primary, third, sort_key)?;
+
+    let props = props.collect::<Result<HashSet<_>, _>>()?;
+    let referenced = primary.values().collect::<HashSet<_>>();
+    let mut missing_props = primary
+        .iter()
+        .filter(|(_, props_id)| !props.contains(*props_id))
+        .map(|(row, _)| row.clone())
+        .collect::<Vec<_>>();
+    missing_props.sort_by_key(sort_key);
+    let mut orphaned_props = props
+        .into_iter()
+        .filter(|props_id| !referenced.contains(props_id))
+        .collect::<Vec<_>>();
+    orphaned_props.sort();
+
+    Ok(TableReport {
+        primary,
+        second_mismatches,
+        third_mismatches,
+        missing_props,
+        orphaned_props,
+    })
+}
+
+/// The rows whose entries in another ordering do not match the primary table: those missing from it, pointing at
+/// different props, or only found in it. Returned in the order given by `sort_key`.
+fn mismatches<K: Clone + Eq + Hash, Id: ConcreteIdType, S: Ord, E>(
+    primary: &HashMap<K, Id>,
+    entries: impl Iterator<Item = Result<(K, Id), E>>,
+    sort_key: impl Fn(&K) -> S,
+) -> Result<Vec<K>, E> {
+    let mut seen = HashSet::new();
+    let mut mismatches = Vec::new();
+    for r in entries {
+        let (row, props_id) = r?;
+        if primary.get(&row) != Some(&props_id) {
+            mismatches.push(row.clone());
+        }
+        seen.insert(row);
+    }
+    mismatches.extend(primary.keys().filter(|row| !seen.contains(*row)).cloned());
+    mismatches.sort_by_key(sort_key);
+    mismatches.dedup();
+    Ok(mismatches)
+}
@@ -0,0 +1,668 @@
+//! Graph traversals over any [TripleStoreQuery]: breadth-first and depth-first walks, k-hop neighbourhoods and
+//! shortest paths.
+//!
+//! Outbound edges are found with the SPO table and inbound edges with the OSP table, so each step of a traversal is a
+//! range query rather than a scan.
+//!
+//! # Example
+//! ```
+//! # use ulid::Ulid;
+//! # use simple_triplestore::{prelude::*, traversal::{Direction, Traversal}, MemTripleStore, Triple, UlidIdGenerator};
+//! let mut db = MemTripleStore::<Ulid, (), u32>::new(UlidIdGenerator::new());
+//!
+//! let (a, b, c, d) = (Ulid(1), Ulid(2), Ulid(3), Ulid(4));
+//! let road = Ulid(10);
+//!
+//! db.insert_edge(Triple { sub: a, pred: road, obj: b }, 5)?;
+//! db.insert_edge(Triple { sub: b, pred: road, obj: c }, 5)?;
+//! db.insert_edge(Triple { sub: a, pred: road, obj: d }, 1)?;
+//! db.insert_edge(Triple { sub: d, pred: road, obj: c }, 1)?;
+//!
+//! // Everything within one hop of c, following edges backwards.
+//! let reached = Traversal::new()
+//!     .direction(Direction::In)
+//!     .max_depth(1)
+//!     .bfs(&db, c)
+//!     .map(|r| r.map(|step| step.node))
+//!     .collect::<Result<Vec<_>, _>>()?;
+//! assert_eq!(reached.len(), 3);
+//!
+//! // The cheapest route from a to c.
+//! let (cost, path) = Traversal::new()
+//!     .dijkstra(&db, a, c, |_, weight| *weight)?
+//!     .expect("reachable");
+//! assert_eq!(cost, 2);
+//! assert_eq!(path, [Triple { sub: a, pred: road, obj: d }, Triple { sub: d, pred: road, obj: c }]);
+//! # Ok::<(), ()>(())
+//! ```
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    ops::Add,
+};
+
+use crate::{
+    prelude::*,
+    traits::{IdType, Property},
+    Query, QueryError, RunError, Triple,
+};
+
+/// Which edges a traversal follows from each node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Direction {
+    /// Follow edges from subject to object.
+    #[default]
+    Out,
+
+    /// Follow edges from object to subject.
+    In,
+
+    /// Follow edges in either direction.
+    Both,
+}
+
+/// A node reached by a walk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step<Id: IdType> {
+    /// The node which was reached.
+    pub node: Id,
+
+    /// The number of edges between the start and this node.
+    pub depth: usize,
+
+    /// The edge which was followed to reach this node, or `None` for the start node.
+    pub edge: Option<Triple<Id>>,
+}
+
+/// The settings shared by every kind of traversal.
+///
+/// By default a traversal follows outbound edges with any predicate to any depth.
+#[derive(Debug, Clone)]
+pub struct Traversal<Id: IdType> {
+    direction: Direction,
+    predicates: Option<HashSet<Id>>,
+    max_depth: Option<usize>,
+}
+
+/// An edge followed from a node, the node at its other end, and the edge's props.
+type Neighbour<Id, EdgeProps> = (Triple<Id>, Id, EdgeProps);
+
+/// The total weight of a path, and the edges along it.
+type WeightedPath<Id, W> = (W, Vec<Triple<Id>>);
+
+impl<Id: IdType> Default for Traversal<Id> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Id: IdType> Traversal<Id> {
+    pub fn new() -> Self {
+        Self {
+            direction: Direction::Out,
+            predicates: None,
+            max_depth: None,
+        }
+    }
+
+    /// Set which edges are followed from each node.
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Only follow edges with one of the given predicates.
+    pub fn predicates(mut self, predicates: impl IntoIterator<Item = Id>) -> Self {
+        self.predicates = Some(predicates.into_iter().collect());
+        self
+    }
+
+    /// Do not follow edges from nodes which are `max_depth` edges away from the start.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    fn expands(&self, depth: usize) -> bool {
+        self.max_depth.is_none_or(|max_depth| depth < max_depth)
+    }
+
+    /// The edges this traversal follows from `node`, along with the node at the other end of each.
+    fn neighbours<NodeProps: Property, EdgeProps: Property, T>(
+        &self,
+        db: &T,
+        node: &Id,
+    ) -> Result<Vec<Neighbour<Id, EdgeProps>>, T::Error>
+    where
+        T: TripleStoreQuery<Id, NodeProps, EdgeProps>,
+    {
+        let mut neighbours = Vec::new();
+
+        if matches!(self.direction, Direction::Out | Direction::Both) {
+            let query = match &self.predicates {
+                Some(predicates) => Query::SP(
+                    predicates
+                        .iter()
+                        .map(|pred| (node.clone(), pred.clone()))
+                        .collect(),
+                ),
+                None => Query::S([node.clone()].into()),
+            };
+            for r in db.iter_query(query).1 {
+                let (triple, props) = r?;
+                let other = triple.obj.clone();
+                neighbours.push((triple, other, props));
+            }
+        }
+
+        if matches!(self.direction, Direction::In | Direction::Both) {
+            for r in db.iter_query(Query::O([node.clone()].into())).1 {
+                let (triple, props) = r?;
+                if self
+                    .predicates
+                    .as_ref()
+                    .is_none_or(|predicates| predicates.contains(&triple.pred))
+                {
+                    let other = triple.sub.clone();
+                    neighbours.push((triple, other, props));
+                }
+            }
+        }
+
+        Ok(neighbours)
+    }
+
+    /// Walk the graph breadth-first from `start`, visiting each reachable node once.
+    pub fn bfs<'a, NodeProps: Property, EdgeProps: Property, T>(
+        &'a self,
+        db: &'a T,
+        start: Id,
+    ) -> impl Iterator<Item = Result<Step<Id>, T::Error>> + 'a
+    where
+        T: TripleStoreQuery<Id, NodeProps, EdgeProps>,
+    {
+        let mut visited = HashSet::new();
+        visited.insert(start.clone());
+        let mut queue = VecDeque::from([Step {
+            node: start,
+            depth: 0,
+            edge: None,
+        }]);
+
+        std::iter::from_fn(move || {
+            let step = queue.pop_front()?;
+            if self.expands(step.depth) {
+                match self.neighbours(db, &step.node) {
+                    Ok(neighbours) => {
+                        for (triple, node, _) in neighbours {
+                            if visited.insert(node.clone()) {
+                                queue.push_back(Step {
+                                    node,
+                                    depth: step.depth + 1,
+                                    edge: Some(triple),
+                                });
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        queue.clear();
+                        return Some(Err(e));
+                    }
+                }
+            }
+            Some(Ok(step))
+        })
+    }
+
+    /// Walk the graph depth-first from `start`, visiting each reachable node once.
+    ///
+    /// Neighbours are visited in the order the store returns them.
+    pub fn dfs<'a, NodeProps: Property, EdgeProps: Property, T>(
+        &'a self,
+        db: &'a T,
+        start: Id,
+    ) -> impl Iterator<Item = Result<Step<Id>, T::Error>> + 'a
+    where
+        T: TripleStoreQuery<Id, NodeProps, EdgeProps>,
+    {
+        let mut visited = HashSet::new();
+        let mut stack = vec![Step {
+            node: start,
+            depth: 0,
+            edge: None,
+        }];
+
+        std::iter::from_fn(move || loop {
+            let step = stack.pop()?;
+            if !visited.insert(step.node.clone()) {
+                continue;
+            }
+            if self.expands(step.depth) {
+                match self.neighbours(db, &step.node) {
+                    Ok(neighbours) => {
+                        // Reversed so that the first neighbour is on top of the stack.
+                        for (triple, node, _) in neighbours.into_iter().rev() {
+                            if !visited.contains(&node) {
+                                stack.push(Step {
+                                    node,
+                                    depth: step.depth + 1,
+                                    edge: Some(triple),
+                                });
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        stack.clear();
+                        return Some(Err(e));
+                    }
+                }
+            }
+            return Some(Ok(step));
+        })
+    }
+
+    /// Extract the subgraph within `k` hops of `start` as a new [TripleStore].
+    ///
+    /// The result contains the props of every node reached, and every edge followed from a node less than `k` hops
+    /// away. This overrides [max_depth][Traversal::max_depth].
+    pub fn k_hop<NodeProps: Property, EdgeProps: Property, T>(
+        &self,
+        db: &T,
+        start: Id,
+        k: usize,
+    ) -> Result<T::QueryResult, RunError<T, Id, NodeProps, EdgeProps>>
+    where
+        T: TripleStoreQuery<Id, NodeProps, EdgeProps>,
+    {
+        let mut nodes = HashSet::from([start.clone()]);
+        let mut edges = HashSet::new();
+        let mut frontier = vec![start];
+
+        for _ in 0..k {
+            let mut next = Vec::new();
+            for node in frontier {
+                for (triple, other, _) in self.neighbours(db, &node).map_err(QueryError::Left)? {
+                    edges.insert((triple.sub, triple.pred, triple.obj));
+                    if nodes.insert(other.clone()) {
+                        next.push(other);
+                    }
+                }
+            }
+            frontier = next;
+        }
+
+        let mut result = db.run(Query::SPO(edges))?;
+        for r in db.iter_query(Query::NodeProps(nodes)).0 {
+            let (node, props) = r.map_err(QueryError::Left)?;
+            result.insert_node(node, props).map_err(QueryError::Right)?;
+        }
+        Ok(result)
+    }
+
+    /// Find a path from `from` to `to` with the fewest edges, or `None` if there is no such path.
+    ///
+    /// Edges are returned as stored, so when following inbound edges they point back along the path.
+    pub fn shortest_path<NodeProps: Property, EdgeProps: Property, T>(
+        &self,
+        db: &T,
+        from: Id,
+        to: Id,
+    ) -> Result<Option<Vec<Triple<Id>>>, T::Error>
+    where
+        T: TripleStoreQuery<Id, NodeProps, EdgeProps>,
+    {
+        let mut previous: HashMap<Id, (Id, Triple<Id>)> = HashMap::new();
+
+        for r in self.bfs(db, from.clone()) {
+            let step = r?;
+            if let Some(edge) = step.edge {
+                let parent = if edge.obj == step.node && edge.sub != step.node {
+                    edge.sub.clone()
+                } else {
+                    edge.obj.clone()
+                };
+                previous.insert(step.node.clone(), (parent, edge));
+            }
+            if step.node == to {
+                return Ok(Some(unwind(&previous, &from, to)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Find the path from `from` to `to` with the lowest total weight, or `None` if there is no such path.
+    ///
+    /// Weights must not be negative.
+    pub fn dijkstra<NodeProps: Property, EdgeProps: Property, T, W>(
+        &self,
+        db: &T,
+        from: Id,
+        to: Id,
+        weight: impl Fn(&Triple<Id>, &EdgeProps) -> W,
+    ) -> Result<Option<WeightedPath<Id, W>>, T::Error>
+    where
+        T: TripleStoreQuery<Id, NodeProps, EdgeProps>,
+        W: Copy + Ord + Add<Output = W> + Default,
+    {
+        // The heap holds indexes into `nodes` so that ids do not need to be ordered.
+        let mut nodes = vec![(from.clone(), 0usize)];
+        let mut heap = BinaryHeap::from([(Reverse(W::default()), 0usize)]);
+        let mut distances = HashMap::from([(from.clone(), W::default())]);
+        let mut previous: HashMap<Id, (Id, Triple<Id>)> = HashMap::new();
+        let mut done = HashSet::new();
+
+        while let Some((Reverse(distance), index)) = heap.pop() {
+            let (node, depth) = nodes[index].clone();
+            if !done.insert(node.clone()) {
+                continue;
+            }
+            if node == to {
+                return Ok(Some((distance, unwind(&previous, &from, to))));
+            }
+            if !self.expands(depth) {
+                continue;
+            }
+
+            for (triple, other, props) in self.neighbours(db, &node)? {
+                if done.contains(&other) {
+                    continue;
+                }
+                let candidate = distance + weight(&triple, &props);
+                if distances
+                    .get(&other)
+                    .is_none_or(|existing| candidate < *existing)
+                {
+                    distances.insert(other.clone(), candidate);
+                    previous.insert(other.clone(), (node.clone(), triple));
+                    nodes.push((other, depth + 1));
+                    heap.push((Reverse(candidate), nodes.len() - 1));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// Follow `previous` back from `to` to `from`, returning the edges in path order.
+fn unwind<Id: IdType>(
+    previous: &HashMap<Id, (Id, Triple<Id>)>,
+    from: &Id,
+    to: Id,
+) -> Vec<Triple<Id>> {
+    let mut path = Vec::new();
+    let mut node = to;
+    while &node != from {
+        let (parent, edge) = previous[&node].clone();
+        path.push(edge);
+        node = parent;
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use ulid::Ulid;
+
+    use super::{Direction, Traversal};
+    use crate::{prelude::*, traits::IdType, EdgeOrder, MemTripleStore, Triple, UlidIdGenerator};
+
+    struct Graph {
+        nodes: [Ulid; 6],
+        road: Ulid,
+        rail: Ulid,
+    }
+
+    //   0 -road(4)-> 1 -road(4)-> 2 -road(1)-> 3
+    //   0 -rail(1)-> 4 -rail(1)-> 2
+    //   5 -road(1)-> 0
+    fn build_graph<T: TripleStore<Ulid, String, u32>>(db: &mut T) -> Graph {
+        let graph = Graph {
+            nodes: [Ulid(1), Ulid(2), Ulid(3), Ulid(4), Ulid(5), Ulid(6)],
+            road: Ulid(10),
+            rail: Ulid(11),
+        };
+        let n = graph.nodes;
+
+        for (i, node) in n.iter().enumerate() {
+            db.insert_node(*node, format!("node {}", i)).expect("ok");
+        }
+        for (sub, pred, obj, weight) in [
+            (n[0], graph.road, n[1], 4),
+            (n[1], graph.road, n[2], 4),
+            (n[2], graph.road, n[3], 1),
+            (n[0], graph.rail, n[4], 1),
+            (n[4], graph.rail, n[2], 1),
+            (n[5], graph.road, n[0], 1),
+        ] {
+            db.insert_edge(Triple { sub, pred, obj }, weight)
+                .expect("ok");
+        }
+        graph
+    }
+
+    fn depths<Id: IdType, E: std::fmt::Debug>(
+        steps: impl Iterator<Item = Result<super::Step<Id>, E>>,
+    ) -> HashSet<(Id, usize)> {
+        steps
+            .map(|r| r.map(|step| (step.node, step.depth)).expect("ok"))
+            .collect()
+    }
+
+    fn test_walks<T: TripleStore<Ulid, String, u32>>(mut db: T) {
+        let graph = build_graph(&mut db);
+        let n = graph.nodes;
+
+        let all = Traversal::new();
+        assert_eq!(
+            depths(all.bfs(&db, n[0])),
+            [(n[0], 0), (n[1], 1), (n[4], 1), (n[2], 2), (n[3], 3)].into()
+        );
+
+        // Depth first visits every reachable node once, following each branch to the end.
+        let order = all
+            .dfs(&db, n[0])
+            .map(|r| r.expect("ok").node)
+            .collect::<Vec<_>>();
+        assert_eq!(order.len(), 5);
+        assert_eq!(order[0], n[0]);
+        let position = |node| order.iter().position(|n| *n == node).expect("visited");
+        assert_eq!(position(n[3]), position(n[2]) + 1);
+
+        // Depth limits.
+        assert_eq!(
+            depths(Traversal::new().max_depth(1).bfs(&db, n[0])),
+            [(n[0], 0), (n[1], 1), (n[4], 1)].into()
+        );
+        assert_eq!(
+            depths(Traversal::new().max_depth(0).dfs(&db, n[0])),
+            [(n[0], 0)].into()
+        );
+
+        // Predicate allow-lists.
+        assert_eq!(
+            depths(Traversal::new().predicates([graph.rail]).bfs(&db, n[0])),
+            [(n[0], 0), (n[4], 1), (n[2], 2)].into()
+        );
+
+        // Direction.
+        assert_eq!(
+            depths(Traversal::new().direction(Direction::In).bfs(&db, n[2])),
+            [(n[2], 0), (n[1], 1), (n[4], 1), (n[0], 2), (n[5], 3)].into()
+        );
+        assert_eq!(
+            depths(
+                Traversal::new()
+                    .direction(Direction::Both)
+                    .max_depth(1)
+                    .bfs(&db, n[0])
+            ),
+            [(n[0], 0), (n[1], 1), (n[4], 1), (n[5], 1)].into()
+        );
+        assert_eq!(
+            depths(
+                Traversal::new()
+                    .direction(Direction::In)
+                    .predicates([graph.rail])
+                    .dfs(&db, n[2])
+            ),
+            [(n[2], 0), (n[4], 1), (n[0], 2)].into()
+        );
+    }
+
+    fn test_k_hop<T: TripleStore<Ulid, String, u32>>(mut db: T) {
+        let graph = build_graph(&mut db);
+        let n = graph.nodes;
+
+        let result = Traversal::new()
+            .direction(Direction::Both)
+            .k_hop(&db, n[0], 1)
+            .expect("ok");
+
+        assert_eq!(
+            result
+                .iter_vertices()
+                .map(|r| r.expect("ok").0)
+                .collect::<HashSet<_>>(),
+            [n[0], n[1], n[4], n[5]].into()
+        );
+        assert_eq!(
+            result
+                .iter_edges(EdgeOrder::SPO)
+                .map(|r| r.expect("ok"))
+                .collect::<HashSet<_>>(),
+            [
+                (
+                    Triple {
+                        sub: n[0],
+                        pred: graph.road,
+                        obj: n[1]
+                    },
+                    4
+                ),
+                (
+                    Triple {
+                        sub: n[0],
+                        pred: graph.rail,
+                        obj: n[4]
+                    },
+                    1
+                ),
+                (
+                    Triple {
+                        sub: n[5],
+                        pred: graph.road,
+                        obj: n[0]
+                    },
+                    1
+                ),
+            ]
+            .into()
+        );
+
+        let result = Traversal::new().k_hop(&db, n[0], 0).expect("ok");
+        assert_eq!(result.iter_vertices().count(), 1);
+        assert_eq!(result.iter_edges(EdgeOrder::SPO).count(), 0);
+    }
+
+    fn test_paths<T: TripleStore<Ulid, String, u32>>(mut db: T) {
+        let graph = build_graph(&mut db);
+        let n = graph.nodes;
+        let edge = |sub, pred, obj| Triple { sub, pred, obj };
+
+        let all = Traversal::new();
+
+        assert_eq!(
+            all.shortest_path(&db, n[0], n[0]).expect("ok"),
+            Some(vec![])
+        );
+        assert_eq!(all.shortest_path(&db, n[3], n[0]).expect("ok"), None);
+
+        let path = all
+            .shortest_path(&db, n[0], n[3])
+            .expect("ok")
+            .expect("path");
+        assert_eq!(path.len(), 3);
+        assert_eq!(path[0].sub, n[0]);
+        assert_eq!(path[2], edge(n[2], graph.road, n[3]));
+
+        // Inbound paths are returned as stored.
+        assert_eq!(
+            Traversal::new()
+                .direction(Direction::In)
+                .shortest_path(&db, n[1], n[5])
+                .expect("ok"),
+            Some(vec![
+                edge(n[0], graph.road, n[1]),
+                edge(n[5], graph.road, n[0])
+            ])
+        );
+
+        // The cheapest path takes the rail detour.
+        assert_eq!(
+            all.dijkstra(&db, n[5], n[3], |_, w| *w).expect("ok"),
+            Some((
+                4,
+                vec![
+                    edge(n[5], graph.road, n[0]),
+                    edge(n[0], graph.rail, n[4]),
+                    edge(n[4], graph.rail, n[2]),
+                    edge(n[2], graph.road, n[3]),
+                ]
+            ))
+        );
+
+        // Unless the rail is excluded.
+        assert_eq!(
+            Traversal::new()
+                .predicates([graph.road])
+                .dijkstra(&db, n[5], n[3], |_, w| *w)
+                .expect("ok")
+                .map(|(cost, _)| cost),
+            Some(10)
+        );
+
+        assert_eq!(all.dijkstra(&db, n[3], n[5], |_, w| *w).expect("ok"), None);
+    }
+
+    #[test]
+    fn test_walks_mem() {
+        test_walks(MemTripleStore::new(UlidIdGenerator::new()));
+    }
+
+    #[test]
+    fn test_k_hop_mem() {
+        test_k_hop(MemTripleStore::new(UlidIdGenerator::new()));
+    }
+
+    #[test]
+    fn test_paths_mem() {
+        test_paths(MemTripleStore::new(UlidIdGenerator::new()));
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_walks_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        test_walks(crate::SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok"));
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_k_hop_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        test_k_hop(crate::SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok"));
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_paths_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        test_paths(crate::SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok"));
+    }
+}
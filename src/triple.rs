@@ -1,6 +1,9 @@
 mod key_bounds;
 
-use crate::traits::{ConcreteIdType, IdType, Property};
+use crate::{
+    traits::{ConcreteIdType, IdType, Property},
+    QuadOrder,
+};
 
 /// The three components of an edge (subject, predicate, object)
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -52,6 +55,138 @@ impl<Id: ConcreteIdType> PartialOrd for Triple<Id> {
     }
 }
 
+/// The four components of an edge in a named graph (subject, predicate, object, graph).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Quad<Id: IdType> {
+    pub sub: Id,
+    pub pred: Id,
+    pub obj: Id,
+    pub graph: Id,
+}
+
+impl<Id: IdType> Quad<Id> {
+    /// Place `triple` in `graph`.
+    pub fn new(triple: Triple<Id>, graph: Id) -> Self {
+        Self {
+            sub: triple.sub,
+            pred: triple.pred,
+            obj: triple.obj,
+            graph,
+        }
+    }
+
+    /// The edge, without its graph.
+    pub fn triple(&self) -> Triple<Id> {
+        Triple {
+            sub: self.sub.clone(),
+            pred: self.pred.clone(),
+            obj: self.obj.clone(),
+        }
+    }
+
+    pub fn map<O: IdType>(self, mut f: impl FnMut(Id) -> O) -> Quad<O> {
+        Quad {
+            sub: f(self.sub),
+            pred: f(self.pred),
+            obj: f(self.obj),
+            graph: f(self.graph),
+        }
+    }
+
+    pub fn try_map<E, O: IdType>(
+        self,
+        mut f: impl FnMut(Id) -> Result<O, E>,
+    ) -> Result<Quad<O>, E> {
+        Ok(Quad {
+            sub: f(self.sub)?,
+            pred: f(self.pred)?,
+            obj: f(self.obj)?,
+            graph: f(self.graph)?,
+        })
+    }
+}
+
+impl<Id: ConcreteIdType> Quad<Id> {
+    /// Encode as a key in the table for `order`.
+    pub(crate) fn encode(&self, order: &QuadOrder) -> Id::QuadByteArrayType {
+        let triple = self.triple();
+        let key = match order {
+            QuadOrder::GSPO => Id::encode_spo_triple(&triple),
+            QuadOrder::GPOS => Id::encode_pos_triple(&triple),
+            QuadOrder::GOSP => Id::encode_osp_triple(&triple),
+        };
+        Id::encode_quad(self.graph, &key)
+    }
+
+    /// Decode a key from the table for `order`.
+    pub(crate) fn decode(order: &QuadOrder, data: &Id::QuadByteArrayType) -> Self {
+        let (graph, key) = Id::decode_quad(data);
+        let triple = match order {
+            QuadOrder::GSPO => Id::decode_spo_triple(&key),
+            QuadOrder::GPOS => Id::decode_pos_triple(&key),
+            QuadOrder::GOSP => Id::decode_osp_triple(&key),
+        };
+        Self::new(triple, graph)
+    }
+}
+
+/// The orderings of the node-leading tables, which find the edges of a vertex in every named graph with one scan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(
+    clippy::upper_case_acronyms,
+    reason = "named like the variants of QuadOrder"
+)]
+pub(crate) enum NodeQuadOrder {
+    /// Subject, Predicate, Object, Graph
+    SPOG,
+
+    /// Object, Subject, Predicate, Graph
+    OSPG,
+}
+
+impl<Id: ConcreteIdType> Quad<Id> {
+    /// Encode as a key in the node-leading table for `order`. The keys have the same layout as the graph-leading ones,
+    /// with the vertex in place of the graph.
+    pub(crate) fn encode_node(&self, order: NodeQuadOrder) -> Id::QuadByteArrayType {
+        let (node, rest) = match order {
+            NodeQuadOrder::SPOG => (self.sub, [self.pred, self.obj, self.graph]),
+            NodeQuadOrder::OSPG => (self.obj, [self.sub, self.pred, self.graph]),
+        };
+        let [sub, pred, obj] = rest;
+        Id::encode_quad(node, &Id::encode_spo_triple(&Triple { sub, pred, obj }))
+    }
+
+    /// Decode a key from the node-leading table for `order`.
+    pub(crate) fn decode_node(order: NodeQuadOrder, data: &Id::QuadByteArrayType) -> Self {
+        let (node, rest) = Id::decode_quad(data);
+        let rest = Id::decode_spo_triple(&rest);
+        match order {
+            NodeQuadOrder::SPOG => Self {
+                sub: node,
+                pred: rest.sub,
+                obj: rest.pred,
+                graph: rest.obj,
+            },
+            NodeQuadOrder::OSPG => Self {
+                sub: rest.sub,
+                pred: rest.pred,
+                obj: node,
+                graph: rest.obj,
+            },
+        }
+    }
+
+    /// The bounds of every key led by `node` in the node-leading tables.
+    pub(crate) fn node_key_bounds(
+        node: Id,
+    ) -> (
+        std::ops::Bound<Id::QuadByteArrayType>,
+        std::ops::Bound<Id::QuadByteArrayType>,
+    ) {
+        Id::graph_key_bounds(node)
+    }
+}
+
 /// A triple along with the associated NodeProps and EdgeProps.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct PropsTriple<Id: IdType, NodeProps: Property, EdgeProps: Property> {
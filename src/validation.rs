@@ -0,0 +1,779 @@
+//! Shape constraints on graphs, in the spirit of [SHACL](https://www.w3.org/TR/shacl/).
+//!
+//! A [Schema] is a set of [Shape]s, each of which applies to the nodes of one class. A node belongs to a class when it
+//! has an edge to the class with the schema's type predicate (e.g. `rdf:type`). Shapes constrain a node's props, and
+//! through [PropertyShape]s the number of edges with a predicate, the classes of their objects and their props.
+//!
+//! [Schema::validate] checks a whole store and returns a [ValidationReport] listing every [Violation]. A [Guarded]
+//! store checks each insert before it is applied and rejects inserts which would break the schema.
+//!
+//! # Example
+//! ```
+//! # use ulid::Ulid;
+//! # use simple_triplestore::{prelude::*, MemTripleStore, Triple, UlidIdGenerator};
+//! # use simple_triplestore::validation::{PropertyShape, Schema, Shape, ViolationKind};
+//! let mut db = MemTripleStore::<Ulid, String, u32>::new(UlidIdGenerator::new());
+//!
+//! let (is_a, person, company) = (Ulid(1), Ulid(2), Ulid(3));
+//! let works_at = Ulid(10);
+//! let (alice, bob, acme) = (Ulid(20), Ulid(21), Ulid(22));
+//!
+//! // Every person works at exactly one company.
+//! let schema = Schema::new(is_a).with_shape(
+//!     Shape::new("person", person)
+//!         .node_props(|name: &String| !name.is_empty())
+//!         .property(PropertyShape::new(works_at).min(1).max(1).classes([company])),
+//! );
+//!
+//! for (node, class) in [(alice, person), (bob, person), (acme, company)] {
+//!     db.insert_node(node, "name".into())?;
+//!     db.insert_edge(Triple { sub: node, pred: is_a, obj: class }, 0)?;
+//! }
+//! db.insert_edge(Triple { sub: alice, pred: works_at, obj: acme }, 0)?;
+//!
+//! let report = schema.validate(&db)?;
+//! assert!(!report.conforms());
+//! assert_eq!(report.violations.len(), 1);
+//! assert_eq!(report.violations[0].node, bob);
+//! assert!(matches!(report.violations[0].kind, ViolationKind::MinCount { actual: 0, .. }));
+//! # Ok::<(), ()>(())
+//! ```
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    prelude::*,
+    traits::{IdType, Property},
+    EdgeOrder, Query, Triple,
+};
+
+/// Constraints on the edges with one predicate from the nodes of a [Shape].
+pub struct PropertyShape<Id: IdType, EdgeProps> {
+    predicate: Id,
+    min: usize,
+    max: Option<usize>,
+    classes: Option<HashSet<Id>>,
+    edge_props: Option<PropsCheck<EdgeProps>>,
+}
+
+/// A predicate on the properties of a node or edge.
+type PropsCheck<Props> = Box<dyn Fn(&Props) -> bool>;
+
+impl<Id: IdType, EdgeProps> PropertyShape<Id, EdgeProps> {
+    /// Constrain the edges with `predicate`. With no further constraints any number of edges is allowed.
+    pub fn new(predicate: Id) -> Self {
+        Self {
+            predicate,
+            min: 0,
+            max: None,
+            classes: None,
+            edge_props: None,
+        }
+    }
+
+    /// Require at least `min` edges.
+    pub fn min(mut self, min: usize) -> Self {
+        self.min = min;
+        self
+    }
+
+    /// Allow at most `max` edges.
+    pub fn max(mut self, max: usize) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Require the object of each edge to belong to one of `classes`.
+    pub fn classes(mut self, classes: impl IntoIterator<Item = Id>) -> Self {
+        self.classes = Some(classes.into_iter().collect());
+        self
+    }
+
+    /// Require the props of each edge to satisfy `check`.
+    pub fn edge_props(mut self, check: impl Fn(&EdgeProps) -> bool + 'static) -> Self {
+        self.edge_props = Some(Box::new(check));
+        self
+    }
+}
+
+/// Constraints on the nodes of one class.
+pub struct Shape<Id: IdType, NodeProps, EdgeProps> {
+    name: String,
+    class: Id,
+    node_props: Option<PropsCheck<NodeProps>>,
+    properties: Vec<PropertyShape<Id, EdgeProps>>,
+}
+
+impl<Id: IdType, NodeProps, EdgeProps> Shape<Id, NodeProps, EdgeProps> {
+    /// Constrain the nodes of `class`. `name` identifies the shape in [Violation]s.
+    pub fn new(name: impl Into<String>, class: Id) -> Self {
+        Self {
+            name: name.into(),
+            class,
+            node_props: None,
+            properties: Vec::new(),
+        }
+    }
+
+    /// Require each node to have props which satisfy `check`.
+    pub fn node_props(mut self, check: impl Fn(&NodeProps) -> bool + 'static) -> Self {
+        self.node_props = Some(Box::new(check));
+        self
+    }
+
+    /// Add constraints on the edges with one predicate.
+    pub fn property(mut self, property: PropertyShape<Id, EdgeProps>) -> Self {
+        self.properties.push(property);
+        self
+    }
+}
+
+/// The way in which a node failed to satisfy a [Shape].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ViolationKind<Id: IdType> {
+    /// The node's props were rejected, or the node has no props.
+    NodeProps,
+
+    /// The node has fewer than `min` edges with `predicate`.
+    MinCount {
+        predicate: Id,
+        min: usize,
+        actual: usize,
+    },
+
+    /// The node has more than `max` edges with `predicate`.
+    MaxCount {
+        predicate: Id,
+        max: usize,
+        actual: usize,
+    },
+
+    /// The object of `edge` does not belong to any of the allowed classes.
+    ObjectClass { edge: Triple<Id> },
+
+    /// The props of `edge` were rejected.
+    EdgeProps { edge: Triple<Id> },
+}
+
+/// A node which failed to satisfy a [Shape].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation<Id: IdType> {
+    /// The node which failed.
+    pub node: Id,
+
+    /// The name of the shape.
+    pub shape: String,
+
+    /// How the node failed.
+    pub kind: ViolationKind<Id>,
+}
+
+/// The result of [Schema::validate].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationReport<Id: IdType> {
+    pub violations: Vec<Violation<Id>>,
+}
+
+impl<Id: IdType> ValidationReport<Id> {
+    /// Whether the store satisfied every shape.
+    pub fn conforms(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// A set of [Shape]s, along with the predicate which assigns nodes to classes.
+pub struct Schema<Id: IdType, NodeProps, EdgeProps> {
+    type_predicate: Id,
+    shapes: Vec<Shape<Id, NodeProps, EdgeProps>>,
+}
+
+impl<Id: IdType, NodeProps: Property, EdgeProps: Property> Schema<Id, NodeProps, EdgeProps> {
+    /// Construct a schema with no shapes, where `node -[type_predicate]-> class` makes `node` a member of `class`.
+    pub fn new(type_predicate: Id) -> Self {
+        Self {
+            type_predicate,
+            shapes: Vec::new(),
+        }
+    }
+
+    /// Add a shape.
+    pub fn with_shape(mut self, shape: Shape<Id, NodeProps, EdgeProps>) -> Self {
+        self.shapes.push(shape);
+        self
+    }
+
+    fn shapes_for<'a>(
+        &'a self,
+        classes: &'a HashSet<Id>,
+    ) -> impl Iterator<Item = &'a Shape<Id, NodeProps, EdgeProps>> + 'a {
+        self.shapes
+            .iter()
+            .filter(move |shape| classes.contains(&shape.class))
+    }
+
+    /// Check every node in `db` against the shapes for its classes.
+    ///
+    /// This makes two passes over the edges: one to find the classes of every node and one to check the edges.
+    pub fn validate<T>(&self, db: &T) -> Result<ValidationReport<Id>, T::Error>
+    where
+        T: TripleStoreIter<Id, NodeProps, EdgeProps>,
+    {
+        let mut classes: HashMap<Id, HashSet<Id>> = HashMap::new();
+        for r in db.iter_edges(EdgeOrder::POS) {
+            let (triple, _) = r?;
+            if triple.pred == self.type_predicate {
+                classes.entry(triple.sub).or_default().insert(triple.obj);
+            }
+        }
+
+        let mut violations = Vec::new();
+        let no_classes = HashSet::new();
+
+        // Count the edges for each (node, shape, property) while checking them individually.
+        let mut counts: HashMap<(Id, usize, usize), usize> = HashMap::new();
+        for r in db.iter_edges(EdgeOrder::SPO) {
+            let (triple, props) = r?;
+            let Some(sub_classes) = classes.get(&triple.sub) else {
+                continue;
+            };
+            for (shape_index, shape) in self.shapes.iter().enumerate() {
+                if !sub_classes.contains(&shape.class) {
+                    continue;
+                }
+                for (property_index, property) in shape.properties.iter().enumerate() {
+                    if property.predicate != triple.pred {
+                        continue;
+                    }
+                    *counts
+                        .entry((triple.sub.clone(), shape_index, property_index))
+                        .or_default() += 1;
+                    let obj_classes = classes.get(&triple.obj).unwrap_or(&no_classes);
+                    violations.extend(check_edge(property, &triple, &props, obj_classes).map(
+                        |kind| Violation {
+                            node: triple.sub.clone(),
+                            shape: shape.name.clone(),
+                            kind,
+                        },
+                    ));
+                }
+            }
+        }
+
+        for (node, node_classes) in classes.iter() {
+            for (shape_index, shape) in self.shapes.iter().enumerate() {
+                if !node_classes.contains(&shape.class) {
+                    continue;
+                }
+                for (property_index, property) in shape.properties.iter().enumerate() {
+                    let actual = counts
+                        .get(&(node.clone(), shape_index, property_index))
+                        .copied()
+                        .unwrap_or(0);
+                    violations.extend(check_count(property, actual, true).map(|kind| Violation {
+                        node: node.clone(),
+                        shape: shape.name.clone(),
+                        kind,
+                    }));
+                }
+            }
+        }
+
+        // Nodes which need props but have none are found by elimination.
+        let mut unchecked = classes
+            .iter()
+            .flat_map(|(node, node_classes)| {
+                self.shapes_for(node_classes)
+                    .filter(|shape| shape.node_props.is_some())
+                    .map(move |shape| (node.clone(), shape.name.clone()))
+            })
+            .collect::<HashSet<_>>();
+        if !unchecked.is_empty() {
+            for r in db.iter_vertices() {
+                let (node, props) = r?;
+                let Some(node_classes) = classes.get(&node) else {
+                    continue;
+                };
+                for shape in self.shapes_for(node_classes) {
+                    if let Some(check) = &shape.node_props {
+                        unchecked.remove(&(node.clone(), shape.name.clone()));
+                        if !check(&props) {
+                            violations.push(Violation {
+                                node: node.clone(),
+                                shape: shape.name.clone(),
+                                kind: ViolationKind::NodeProps,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        violations.extend(unchecked.into_iter().map(|(node, shape)| Violation {
+            node,
+            shape,
+            kind: ViolationKind::NodeProps,
+        }));
+
+        Ok(ValidationReport { violations })
+    }
+
+    fn classes_of<T>(&self, db: &T, node: &Id) -> Result<HashSet<Id>, T::Error>
+    where
+        T: TripleStoreQuery<Id, NodeProps, EdgeProps>,
+    {
+        db.iter_query(Query::SP(
+            [(node.clone(), self.type_predicate.clone())].into(),
+        ))
+        .1
+        .map(|r| r.map(|(triple, _)| triple.obj))
+        .collect()
+    }
+
+    /// Check the constraints which inserting a node with `props` could break.
+    ///
+    /// Returns the violations the insert would cause, without modifying `db`.
+    pub fn check_insert_node<T>(
+        &self,
+        db: &T,
+        node: &Id,
+        props: &NodeProps,
+    ) -> Result<Vec<Violation<Id>>, T::Error>
+    where
+        T: TripleStoreQuery<Id, NodeProps, EdgeProps>,
+    {
+        let classes = self.classes_of(db, node)?;
+        Ok(self
+            .shapes_for(&classes)
+            .filter(|shape| shape.node_props.as_ref().is_some_and(|check| !check(props)))
+            .map(|shape| Violation {
+                node: node.clone(),
+                shape: shape.name.clone(),
+                kind: ViolationKind::NodeProps,
+            })
+            .collect())
+    }
+
+    /// Check the constraints which inserting an edge with `props` could break.
+    ///
+    /// These are the props, object class and maximum count of the edge. If the edge gives its subject a new class, the
+    /// subject's existing props and edges are also checked against the shapes for that class. Minimum counts can only
+    /// be satisfied after the fact and are left to [validate][Schema::validate].
+    ///
+    /// Returns the violations the insert would cause, without modifying `db`.
+    pub fn check_insert_edge<T>(
+        &self,
+        db: &T,
+        triple: &Triple<Id>,
+        props: &EdgeProps,
+    ) -> Result<Vec<Violation<Id>>, T::Error>
+    where
+        T: TripleStoreQuery<Id, NodeProps, EdgeProps> + TripleStoreGet<Id, NodeProps, EdgeProps>,
+    {
+        let mut violations = Vec::new();
+        let mut violation = |shape: &Shape<Id, NodeProps, EdgeProps>, kind| {
+            violations.push(Violation {
+                node: triple.sub.clone(),
+                shape: shape.name.clone(),
+                kind,
+            })
+        };
+
+        let classes = self.classes_of(db, &triple.sub)?;
+        let exists = db.contains_edge(triple)?;
+
+        for shape in self.shapes_for(&classes) {
+            for property in shape.properties.iter() {
+                if property.predicate != triple.pred {
+                    continue;
+                }
+                let obj_classes = self.classes_of(db, &triple.obj)?;
+                for kind in check_edge(property, triple, props, &obj_classes) {
+                    violation(shape, kind);
+                }
+                let existing = db
+                    .iter_query(Query::SP(
+                        [(triple.sub.clone(), triple.pred.clone())].into(),
+                    ))
+                    .1
+                    .count();
+                let actual = if exists { existing } else { existing + 1 };
+                if let Some(kind) = check_count(property, actual, false) {
+                    violation(shape, kind);
+                }
+            }
+        }
+
+        // A new class brings in new shapes, which the subject must already satisfy.
+        if triple.pred == self.type_predicate && !classes.contains(&triple.obj) {
+            let new_classes = HashSet::from([triple.obj.clone()]);
+            let shapes = self.shapes_for(&new_classes).collect::<Vec<_>>();
+            if !shapes.is_empty() {
+                let node_props = db.get_node(&triple.sub)?;
+                let mut edges: HashMap<Id, Vec<(Triple<Id>, EdgeProps)>> = HashMap::new();
+                for r in db.iter_query(Query::S([triple.sub.clone()].into())).1 {
+                    let (edge, edge_props) = r?;
+                    edges
+                        .entry(edge.pred.clone())
+                        .or_default()
+                        .push((edge, edge_props));
+                }
+
+                for shape in shapes {
+                    if let (Some(check), Some(node_props)) = (&shape.node_props, &node_props) {
+                        if !check(node_props) {
+                            violation(shape, ViolationKind::NodeProps);
+                        }
+                    }
+                    for property in shape.properties.iter() {
+                        let edges = edges
+                            .get(&property.predicate)
+                            .map(Vec::as_slice)
+                            .unwrap_or_default();
+                        for (edge, edge_props) in edges {
+                            let obj_classes = self.classes_of(db, &edge.obj)?;
+                            for kind in check_edge(property, edge, edge_props, &obj_classes) {
+                                violation(shape, kind);
+                            }
+                        }
+                        if let Some(kind) = check_count(property, edges.len(), false) {
+                            violation(shape, kind);
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(violations)
+    }
+}
+
+/// Check a single edge against a property shape.
+fn check_edge<Id: IdType, EdgeProps>(
+    property: &PropertyShape<Id, EdgeProps>,
+    triple: &Triple<Id>,
+    props: &EdgeProps,
+    obj_classes: &HashSet<Id>,
+) -> impl Iterator<Item = ViolationKind<Id>> {
+    let wrong_class = property
+        .classes
+        .as_ref()
+        .is_some_and(|allowed| allowed.is_disjoint(obj_classes));
+    let wrong_props = property
+        .edge_props
+        .as_ref()
+        .is_some_and(|check| !check(props));
+
+    wrong_class
+        .then(|| ViolationKind::ObjectClass {
+            edge: triple.clone(),
+        })
+        .into_iter()
+        .chain(wrong_props.then(|| ViolationKind::EdgeProps {
+            edge: triple.clone(),
+        }))
+}
+
+/// Check the number of edges against a property shape, optionally including the minimum.
+fn check_count<Id: IdType, EdgeProps>(
+    property: &PropertyShape<Id, EdgeProps>,
+    actual: usize,
+    check_min: bool,
+) -> Option<ViolationKind<Id>> {
+    if check_min && actual < property.min {
+        return Some(ViolationKind::MinCount {
+            predicate: property.predicate.clone(),
+            min: property.min,
+            actual,
+        });
+    }
+    match property.max {
+        Some(max) if actual > max => Some(ViolationKind::MaxCount {
+            predicate: property.predicate.clone(),
+            max,
+            actual,
+        }),
+        _ => None,
+    }
+}
+
+/// Errors from a [Guarded] store.
+#[derive(Debug)]
+pub enum GuardError<Id: IdType, StoreError: std::fmt::Debug> {
+    /// The insert was rejected because it would violate the schema.
+    Invalid(Vec<Violation<Id>>),
+
+    /// Error from the underlying store.
+    Store(StoreError),
+}
+
+/// A store which checks each insert against a [Schema] before applying it.
+///
+/// Inserts which would break a shape are rejected with [GuardError::Invalid] and leave the store unchanged. See
+/// [Schema::check_insert_edge] for which constraints can be checked on insert.
+pub struct Guarded<Id: IdType, NodeProps, EdgeProps, T> {
+    schema: Schema<Id, NodeProps, EdgeProps>,
+    store: T,
+}
+
+impl<Id: IdType, NodeProps: Property, EdgeProps: Property, T> Guarded<Id, NodeProps, EdgeProps, T>
+where
+    T: TripleStore<Id, NodeProps, EdgeProps>,
+{
+    pub fn new(schema: Schema<Id, NodeProps, EdgeProps>, store: T) -> Self {
+        Self { schema, store }
+    }
+
+    pub fn schema(&self) -> &Schema<Id, NodeProps, EdgeProps> {
+        &self.schema
+    }
+
+    /// The underlying store, for reads.
+    pub fn store(&self) -> &T {
+        &self.store
+    }
+
+    /// Return the underlying store, removing the guard.
+    pub fn into_inner(self) -> T {
+        self.store
+    }
+
+    /// Check the whole store against the schema.
+    pub fn validate(&self) -> Result<ValidationReport<Id>, T::Error> {
+        self.schema.validate(&self.store)
+    }
+}
+
+impl<Id: IdType, NodeProps: Property, EdgeProps: Property, T> TripleStoreError
+    for Guarded<Id, NodeProps, EdgeProps, T>
+where
+    T: TripleStore<Id, NodeProps, EdgeProps>,
+{
+    type Error = GuardError<Id, T::Error>;
+}
+
+impl<Id: IdType, NodeProps: Property, EdgeProps: Property, T>
+    TripleStoreInsert<Id, NodeProps, EdgeProps> for Guarded<Id, NodeProps, EdgeProps, T>
+where
+    T: TripleStore<Id, NodeProps, EdgeProps>,
+{
+    fn insert_node(&mut self, id: Id, props: NodeProps) -> Result<(), Self::Error> {
+        let violations = self
+            .schema
+            .check_insert_node(&self.store, &id, &props)
+            .map_err(GuardError::Store)?;
+        if !violations.is_empty() {
+            return Err(GuardError::Invalid(violations));
+        }
+        self.store.insert_node(id, props).map_err(GuardError::Store)
+    }
+
+    fn insert_edge(&mut self, triple: Triple<Id>, props: EdgeProps) -> Result<(), Self::Error> {
+        let violations = self
+            .schema
+            .check_insert_edge(&self.store, &triple, &props)
+            .map_err(GuardError::Store)?;
+        if !violations.is_empty() {
+            return Err(GuardError::Invalid(violations));
+        }
+        self.store
+            .insert_edge(triple, props)
+            .map_err(GuardError::Store)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ulid::Ulid;
+
+    use super::{GuardError, Guarded, PropertyShape, Schema, Shape, Violation, ViolationKind};
+    use crate::{prelude::*, MemTripleStore, Triple, UlidIdGenerator};
+
+    const IS_A: Ulid = Ulid(1);
+    const PERSON: Ulid = Ulid(2);
+    const COMPANY: Ulid = Ulid(3);
+    const WORKS_AT: Ulid = Ulid(10);
+    const EMAIL: Ulid = Ulid(11);
+
+    fn edge(sub: Ulid, pred: Ulid, obj: Ulid) -> Triple<Ulid> {
+        Triple { sub, pred, obj }
+    }
+
+    // People have a non-empty name, work at one company with a positive salary, and have at most two emails.
+    fn schema() -> Schema<Ulid, String, u32> {
+        Schema::new(IS_A).with_shape(
+            Shape::new("person", PERSON)
+                .node_props(|name: &String| !name.is_empty())
+                .property(
+                    PropertyShape::new(WORKS_AT)
+                        .min(1)
+                        .max(1)
+                        .classes([COMPANY])
+                        .edge_props(|salary: &u32| *salary > 0),
+                )
+                .property(PropertyShape::new(EMAIL).max(2)),
+        )
+    }
+
+    fn violation(node: Ulid, kind: ViolationKind<Ulid>) -> Violation<Ulid> {
+        Violation {
+            node,
+            shape: "person".into(),
+            kind,
+        }
+    }
+
+    fn test_validate<T: TripleStore<Ulid, String, u32>>(mut db: T) {
+        let (alice, bob, carol, dave, acme) = (Ulid(20), Ulid(21), Ulid(22), Ulid(23), Ulid(24));
+
+        db.insert_node(acme, "acme".into()).expect("ok");
+        db.insert_edge(edge(acme, IS_A, COMPANY), 0).expect("ok");
+
+        // Alice conforms.
+        db.insert_node(alice, "alice".into()).expect("ok");
+        db.insert_edge(edge(alice, IS_A, PERSON), 0).expect("ok");
+        db.insert_edge(edge(alice, WORKS_AT, acme), 100)
+            .expect("ok");
+
+        // Bob has an empty name, works at a person for nothing, and has too many emails.
+        db.insert_node(bob, "".into()).expect("ok");
+        db.insert_edge(edge(bob, IS_A, PERSON), 0).expect("ok");
+        db.insert_edge(edge(bob, WORKS_AT, alice), 0).expect("ok");
+        for email in [30, 31, 32] {
+            db.insert_edge(edge(bob, EMAIL, Ulid(email)), 0)
+                .expect("ok");
+        }
+
+        // Carol has no props and no job.
+        db.insert_edge(edge(carol, IS_A, PERSON), 0).expect("ok");
+
+        // Dave is not a person, so is not checked.
+        db.insert_node(dave, "".into()).expect("ok");
+        db.insert_edge(edge(dave, WORKS_AT, alice), 0).expect("ok");
+
+        let report = schema().validate(&db).expect("ok");
+        let mut expected = vec![
+            violation(bob, ViolationKind::NodeProps),
+            violation(
+                bob,
+                ViolationKind::ObjectClass {
+                    edge: edge(bob, WORKS_AT, alice),
+                },
+            ),
+            violation(
+                bob,
+                ViolationKind::EdgeProps {
+                    edge: edge(bob, WORKS_AT, alice),
+                },
+            ),
+            violation(
+                bob,
+                ViolationKind::MaxCount {
+                    predicate: EMAIL,
+                    max: 2,
+                    actual: 3,
+                },
+            ),
+            violation(carol, ViolationKind::NodeProps),
+            violation(
+                carol,
+                ViolationKind::MinCount {
+                    predicate: WORKS_AT,
+                    min: 1,
+                    actual: 0,
+                },
+            ),
+        ];
+        let mut actual = report.violations;
+        let key = |v: &Violation<Ulid>| format!("{:?}", v);
+        actual.sort_by_key(key);
+        expected.sort_by_key(key);
+        assert_eq!(actual, expected);
+    }
+
+    fn test_guard<T: TripleStore<Ulid, String, u32>>(db: T) {
+        let (alice, bob, acme) = (Ulid(20), Ulid(21), Ulid(22));
+        let mut db = Guarded::new(schema(), db);
+
+        let invalid = |r: Result<(), GuardError<Ulid, T::Error>>| match r {
+            Err(GuardError::Invalid(violations)) => violations,
+            r => panic!("expected violations, got {:?}", r),
+        };
+
+        db.insert_edge(edge(acme, IS_A, COMPANY), 0).expect("ok");
+        db.insert_edge(edge(alice, IS_A, PERSON), 0).expect("ok");
+
+        // Props, object classes and maximum counts are checked on insert.
+        assert_eq!(
+            invalid(db.insert_node(alice, "".into())),
+            [violation(alice, ViolationKind::NodeProps)]
+        );
+        db.insert_node(alice, "alice".into()).expect("ok");
+
+        assert_eq!(
+            invalid(db.insert_edge(edge(alice, WORKS_AT, bob), 100)),
+            [violation(
+                alice,
+                ViolationKind::ObjectClass {
+                    edge: edge(alice, WORKS_AT, bob)
+                }
+            )]
+        );
+        db.insert_edge(edge(alice, WORKS_AT, acme), 100)
+            .expect("ok");
+        // Replacing an edge does not count twice.
+        db.insert_edge(edge(alice, WORKS_AT, acme), 200)
+            .expect("ok");
+
+        db.insert_edge(edge(bob, IS_A, COMPANY), 0).expect("ok");
+        assert_eq!(
+            invalid(db.insert_edge(edge(alice, WORKS_AT, bob), 100)),
+            [violation(
+                alice,
+                ViolationKind::MaxCount {
+                    predicate: WORKS_AT,
+                    max: 1,
+                    actual: 2
+                }
+            )]
+        );
+
+        // Becoming a person checks the existing node.
+        db.insert_node(bob, "".into()).expect("ok");
+        assert_eq!(
+            invalid(db.insert_edge(edge(bob, IS_A, PERSON), 0)),
+            [violation(bob, ViolationKind::NodeProps)]
+        );
+
+        // Rejected inserts were not applied.
+        assert!(db.validate().expect("ok").conforms());
+        assert_eq!(
+            db.store()
+                .get_edge(&edge(alice, WORKS_AT, acme))
+                .expect("ok"),
+            Some(200)
+        );
+    }
+
+    #[test]
+    fn test_validate_mem() {
+        test_validate(MemTripleStore::new(UlidIdGenerator::new()));
+    }
+
+    #[test]
+    fn test_guard_mem() {
+        test_guard(MemTripleStore::new(UlidIdGenerator::new()));
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_validate_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        test_validate(crate::SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok"));
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_guard_sled() {
+        let (_tempdir, db) = crate::sled::create_test_db().expect("ok");
+        test_guard(crate::SledTripleStore::new(&db, UlidIdGenerator::new()).expect("ok"));
+    }
+}